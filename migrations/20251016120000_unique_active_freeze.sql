@@ -0,0 +1,22 @@
+-- Enforce at most one active freeze per (installation, repository, branch)
+-- The overlap check in FreezeRecord::create is a read-then-write race, so the
+-- database has the final say. A NULL branch (whole repository) is normalised
+-- to '' so that two repository-wide freezes also collide.
+
+-- End duplicate active freezes left behind by earlier races, keeping the newest
+UPDATE freeze_records
+SET status = 'ended', ended_at = datetime('now'), ended_by = 'frezze'
+WHERE status = 'active'
+AND EXISTS (
+    SELECT 1 FROM freeze_records newer
+    WHERE newer.status = 'active'
+    AND newer.installation_id = freeze_records.installation_id
+    AND newer.repository = freeze_records.repository
+    AND COALESCE(newer.branch, '') = COALESCE(freeze_records.branch, '')
+    AND (newer.created_at > freeze_records.created_at
+        OR (newer.created_at = freeze_records.created_at AND newer.id > freeze_records.id))
+);
+
+CREATE UNIQUE INDEX idx_freeze_records_one_active
+ON freeze_records(installation_id, repository, COALESCE(branch, ''))
+WHERE status = 'active';
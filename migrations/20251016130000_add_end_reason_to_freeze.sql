@@ -0,0 +1,4 @@
+-- Add end_reason column to freeze_records table
+-- Stores the justification given when a freeze is ended (e.g. via /unfreeze-all --reason)
+-- NULL means no reason was provided
+ALTER TABLE freeze_records ADD COLUMN end_reason TEXT;
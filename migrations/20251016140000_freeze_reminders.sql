@@ -0,0 +1,8 @@
+-- Track where a freeze was requested and when its initiator was last reminded about it
+-- Used by the worker to escalate long-running freezes without spamming the issue
+CREATE TABLE freeze_reminders (
+    freeze_id TEXT PRIMARY KEY NOT NULL,
+    issue_number INTEGER NOT NULL,
+    last_reminded_at DATETIME,
+    FOREIGN KEY (freeze_id) REFERENCES freeze_records(id) ON DELETE CASCADE
+);
@@ -0,0 +1,22 @@
+-- GitHub repository names are case-insensitive, so "owner/Repo" and "owner/repo"
+-- must share the single active freeze slot per (installation, repository, branch).
+
+-- End duplicate active freezes that differ only in repository case, keeping the newest
+UPDATE freeze_records
+SET status = 'ended', ended_at = datetime('now'), ended_by = 'frezze'
+WHERE status = 'active'
+AND EXISTS (
+    SELECT 1 FROM freeze_records newer
+    WHERE newer.status = 'active'
+    AND newer.installation_id = freeze_records.installation_id
+    AND newer.repository = freeze_records.repository COLLATE NOCASE
+    AND COALESCE(newer.branch, '') = COALESCE(freeze_records.branch, '')
+    AND (newer.created_at > freeze_records.created_at
+        OR (newer.created_at = freeze_records.created_at AND newer.id > freeze_records.id))
+);
+
+DROP INDEX idx_freeze_records_one_active;
+
+CREATE UNIQUE INDEX idx_freeze_records_one_active
+ON freeze_records(installation_id, repository COLLATE NOCASE, COALESCE(branch, ''))
+WHERE status = 'active';
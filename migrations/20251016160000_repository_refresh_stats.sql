@@ -0,0 +1,10 @@
+-- Track when each repository's PR check runs were last refreshed and how many
+-- retries the refreshes needed, to help debug stuck checks
+CREATE TABLE repository_refresh_stats (
+    installation_id INTEGER NOT NULL,
+    repository TEXT NOT NULL COLLATE NOCASE,
+    last_refreshed_at DATETIME NOT NULL,
+    last_retries INTEGER NOT NULL DEFAULT 0,
+    total_retries INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (installation_id, repository)
+);
@@ -0,0 +1,16 @@
+-- Add environment column to freeze_records table
+-- An environment-scoped freeze blocks deployments to that GitHub Environment
+-- instead of failing pull request checks. NULL means a regular freeze.
+ALTER TABLE freeze_records ADD COLUMN environment TEXT;
+
+-- Environment freezes get their own active slot next to the regular freeze
+DROP INDEX idx_freeze_records_one_active;
+
+CREATE UNIQUE INDEX idx_freeze_records_one_active
+ON freeze_records(
+    installation_id,
+    repository COLLATE NOCASE,
+    COALESCE(branch, ''),
+    COALESCE(environment, '') COLLATE NOCASE
+)
+WHERE status = 'active';
@@ -0,0 +1,11 @@
+-- Org-wide commands waiting for their initiator to confirm them with a token
+-- Rows are short-lived: they are removed when confirmed or once they expire
+CREATE TABLE pending_confirmations (
+    token TEXT PRIMARY KEY NOT NULL,
+    installation_id INTEGER NOT NULL,
+    repository TEXT NOT NULL COLLATE NOCASE,
+    requested_by TEXT NOT NULL,
+    command_line TEXT NOT NULL,
+    created_at DATETIME NOT NULL,
+    expires_at DATETIME NOT NULL
+);
@@ -0,0 +1,4 @@
+-- Add soft column to freeze_records table
+-- A soft freeze marks blocked PRs as requiring action instead of failing
+-- their check, so a freeze does not look like broken CI.
+ALTER TABLE freeze_records ADD COLUMN soft BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,5 @@
+-- Remember the repository of the issue or PR a freeze was requested from.
+-- Organization-wide freezes are recorded for "owner/*", which has no issues to
+-- post reminders to or to reply in. NULL for reminders tracked before, which
+-- fall back to the repository of the freeze.
+ALTER TABLE freeze_reminders ADD COLUMN repository TEXT COLLATE NOCASE;
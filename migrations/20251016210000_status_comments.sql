@@ -0,0 +1,10 @@
+-- Remember the comment `/status --pinned` keeps up to date in each issue, so
+-- later runs edit it in place instead of posting a new comment
+CREATE TABLE status_comments (
+    installation_id INTEGER NOT NULL,
+    repository TEXT NOT NULL COLLATE NOCASE,
+    issue_number INTEGER NOT NULL,
+    comment_id INTEGER NOT NULL,
+    updated_at DATETIME NOT NULL,
+    PRIMARY KEY (installation_id, repository, issue_number)
+);
@@ -0,0 +1,12 @@
+-- Unfreezes waiting for one of the installation's required approvers
+-- At most one request is pending per repository; a new `/unfreeze` replaces it
+CREATE TABLE pending_unfreezes (
+    installation_id INTEGER NOT NULL,
+    repository TEXT NOT NULL COLLATE NOCASE,
+    requested_by TEXT NOT NULL,
+    command_line TEXT NOT NULL,
+    approvers TEXT NOT NULL,
+    created_at DATETIME NOT NULL,
+    expires_at DATETIME NOT NULL,
+    PRIMARY KEY (installation_id, repository)
+);
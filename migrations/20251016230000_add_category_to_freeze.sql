@@ -0,0 +1,4 @@
+-- Add category column to freeze_records table
+-- An optional structured reason (deploy, incident, maintenance, compliance)
+-- so freezes can be grouped by why they happened.
+ALTER TABLE freeze_records ADD COLUMN category TEXT;
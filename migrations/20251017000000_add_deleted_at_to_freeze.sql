@@ -0,0 +1,4 @@
+-- Add deleted_at column to freeze_records table
+-- Finished freezes past the retention period are soft-deleted: hidden from
+-- the history but kept for exports until they are purged.
+ALTER TABLE freeze_records ADD COLUMN deleted_at DATETIME;
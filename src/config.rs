@@ -3,6 +3,9 @@
 //! This module provides functionality to load and manage user permissions from
 //! YAML configuration files. This allows administrators to define which users
 //! have access to which commands without modifying the database directly.
+//!
+//! Application-wide settings that are not tied to a specific installation are
+//! read from environment variables into [`AppConfig`].
 
 use std::{collections::HashMap, path::Path};
 
@@ -10,13 +13,270 @@ use anyhow::{Result, anyhow};
 use serde::{Deserialize, Serialize};
 use tracing::info;
 
-use crate::database::models::Role;
+use crate::{
+    database::models::Role,
+    freezer::{
+        business_hours::BusinessHours, commands::Command, messages::MessageTheme,
+        pr_refresh::FrozenConclusion,
+    },
+    notifications::quiet_hours::QuietHours,
+    repository::DEFAULT_GITHUB_URL,
+};
+
+/// Default minimum duration of a freeze, in seconds.
+pub const DEFAULT_MIN_FREEZE_DURATION_SECS: i64 = 60;
+
+/// Default age after which the initiator of a freeze is reminded about it, in seconds.
+pub const DEFAULT_FREEZE_REMINDER_AFTER_SECS: i64 = 24 * 60 * 60;
+
+/// Default interval between two reminders for the same freeze, in seconds.
+pub const DEFAULT_FREEZE_REMINDER_INTERVAL_SECS: i64 = 24 * 60 * 60;
+
+/// Default maximum number of repositories a single `--repo` list may name.
+pub const DEFAULT_MAX_REPOS_PER_COMMAND: usize = 25;
+
+/// Commands allowed when no permissions file is loaded (`NO_CONFIG_MODE`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NoConfigMode {
+    /// Deny every command
+    DenyAll,
+    /// Only allow `/status` (default)
+    #[default]
+    StatusOnly,
+    /// Allow every command to every user, for trying the bot out
+    AllowAll,
+}
+
+impl NoConfigMode {
+    /// Returns whether a command may run without a permissions file
+    pub fn allows(&self, command: &Command) -> bool {
+        match self {
+            NoConfigMode::DenyAll => false,
+            NoConfigMode::StatusOnly => matches!(command, Command::Status(_)),
+            NoConfigMode::AllowAll => true,
+        }
+    }
+}
+
+impl std::str::FromStr for NoConfigMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "deny-all" => Ok(NoConfigMode::DenyAll),
+            "status-only" => Ok(NoConfigMode::StatusOnly),
+            "allow-all" => Ok(NoConfigMode::AllowAll),
+            other => Err(anyhow!(
+                "Invalid no config mode '{}'. Expected 'deny-all', 'status-only' or 'allow-all'",
+                other
+            )),
+        }
+    }
+}
+
+/// Application-wide settings loaded from environment variables.
+#[derive(Debug, Clone)]
+pub struct AppConfig {
+    /// Theme used when rendering bot messages (`MESSAGE_THEME`: `emoji` or `plain`)
+    pub message_theme: MessageTheme,
+    /// Shortest freeze that can be requested (`MIN_FREEZE_DURATION_SECS`)
+    pub min_freeze_duration: chrono::Duration,
+    /// Endpoint receiving JSON freeze events (`FREEZE_EVENT_WEBHOOK_URL`)
+    pub freeze_event_webhook_url: Option<String>,
+    /// Secret used to sign freeze event payloads (`FREEZE_EVENT_WEBHOOK_SECRET`)
+    pub freeze_event_webhook_secret: Option<String>,
+    /// Print freeze events to stdout as prefixed NDJSON lines (`EMIT_EVENTS_STDOUT`)
+    pub emit_events_stdout: bool,
+    /// Fall back to commit statuses when check runs are forbidden (`COMMIT_STATUS_FALLBACK`)
+    pub commit_status_fallback: bool,
+    /// Check run conclusion used for frozen PRs (`FROZEN_CONCLUSION`)
+    pub frozen_conclusion: FrozenConclusion,
+    /// Check run text templates keyed by installation and lowercase repository, taken from the permissions file
+    pub check_run_text_templates: HashMap<(i64, String), String>,
+    /// Users notified on freeze state changes keyed by installation and lowercase repository, taken from the permissions file
+    pub repository_watchers: HashMap<(i64, String), Vec<String>>,
+    /// Users mentioned on partially failed multi-repository commands keyed by installation, taken from the permissions file
+    pub ops_contacts: HashMap<i64, Vec<String>>,
+    /// Windows during which freeze events are not sent keyed by installation, taken from the permissions file
+    pub quiet_hours: HashMap<i64, QuietHours>,
+    /// Age after which forgotten freezes are escalated (`FREEZE_REMINDER_AFTER_SECS`, 0 disables)
+    pub freeze_reminder_after: Option<chrono::Duration>,
+    /// Minimum time between reminders for the same freeze (`FREEZE_REMINDER_INTERVAL_SECS`)
+    pub freeze_reminder_interval: chrono::Duration,
+    /// Age after which active freezes are treated as expired (`MAX_FREEZE_AGE_SECS`, 0 disables)
+    pub max_freeze_age: Option<chrono::Duration>,
+    /// Age after which finished freezes are soft-deleted (`FREEZE_RETENTION_SECS`, 0 disables)
+    pub freeze_retention: Option<chrono::Duration>,
+    /// Time soft-deleted freezes are kept before being purged (`FREEZE_RETENTION_GRACE_SECS`, 0 keeps them)
+    pub freeze_retention_grace: Option<chrono::Duration>,
+    /// Issue label that freezes the repository while applied (`FREEZE_LABEL`, unset disables)
+    pub freeze_label: Option<String>,
+    /// `repository_dispatch` event type that freezes the repository (`FREEZE_DISPATCH_EVENT_TYPE`, unset disables)
+    pub freeze_dispatch_event_type: Option<String>,
+    /// Comment on PRs opened against a frozen branch (`COMMENT_ON_NEW_PR_DURING_FREEZE`)
+    pub comment_on_new_pr_during_freeze: bool,
+    /// Alert the freeze owner and watchers when a PR is unlocked (`ALERT_ON_UNLOCK_PR`)
+    pub alert_on_unlock_pr: bool,
+    /// Commands allowed when no permissions file is loaded (`NO_CONFIG_MODE`)
+    pub no_config_mode: NoConfigMode,
+    /// Web address of the GitHub instance used in links (`GITHUB_URL`)
+    pub github_url: String,
+    /// Delete the rows of an installation once it is deleted (`PRUNE_DELETED_INSTALLATIONS`)
+    pub prune_deleted_installations: bool,
+    /// Working window for `bh` durations (`BUSINESS_DAYS`, `BUSINESS_HOURS`, `BUSINESS_HOURS_UTC_OFFSET`)
+    pub business_hours: BusinessHours,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            message_theme: MessageTheme::default(),
+            min_freeze_duration: chrono::Duration::seconds(DEFAULT_MIN_FREEZE_DURATION_SECS),
+            freeze_event_webhook_url: None,
+            freeze_event_webhook_secret: None,
+            emit_events_stdout: false,
+            commit_status_fallback: false,
+            frozen_conclusion: FrozenConclusion::default(),
+            check_run_text_templates: HashMap::new(),
+            repository_watchers: HashMap::new(),
+            ops_contacts: HashMap::new(),
+            quiet_hours: HashMap::new(),
+            freeze_reminder_after: Some(chrono::Duration::seconds(
+                DEFAULT_FREEZE_REMINDER_AFTER_SECS,
+            )),
+            freeze_reminder_interval: chrono::Duration::seconds(
+                DEFAULT_FREEZE_REMINDER_INTERVAL_SECS,
+            ),
+            max_freeze_age: None,
+            freeze_retention: None,
+            freeze_retention_grace: None,
+            freeze_label: None,
+            freeze_dispatch_event_type: None,
+            comment_on_new_pr_during_freeze: false,
+            alert_on_unlock_pr: false,
+            no_config_mode: NoConfigMode::default(),
+            github_url: DEFAULT_GITHUB_URL.to_string(),
+            prune_deleted_installations: false,
+            business_hours: BusinessHours::default(),
+        }
+    }
+}
+
+/// Reads a boolean environment variable, defaulting to `false` when unset
+fn parse_bool_var(name: &str) -> Result<bool> {
+    match std::env::var(name) {
+        Ok(value) => match value.trim().to_lowercase().as_str() {
+            "1" | "true" | "yes" | "on" => Ok(true),
+            "" | "0" | "false" | "no" | "off" => Ok(false),
+            other => Err(anyhow!("Invalid boolean value '{}' for {}", other, name)),
+        },
+        Err(_) => Ok(false),
+    }
+}
+
+/// Reads a non-negative number of seconds from an environment variable
+fn parse_secs_var(name: &str, default_secs: i64) -> Result<chrono::Duration> {
+    match std::env::var(name) {
+        Ok(value) => {
+            let secs: i64 = value
+                .trim()
+                .parse()
+                .map_err(|e| anyhow!("Invalid {} '{}': {}", name, value, e))?;
+            if secs < 0 {
+                return Err(anyhow!("{} must not be negative", name));
+            }
+            Ok(chrono::Duration::seconds(secs))
+        }
+        Err(_) => Ok(chrono::Duration::seconds(default_secs)),
+    }
+}
+
+impl AppConfig {
+    /// Loads the application settings from environment variables.
+    ///
+    /// Unset variables fall back to their defaults; invalid values are reported
+    /// as errors so misconfigurations surface at startup.
+    pub fn from_env() -> Result<Self> {
+        let non_empty_var = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+
+        let message_theme = match std::env::var("MESSAGE_THEME") {
+            Ok(value) => value.parse()?,
+            Err(_) => MessageTheme::default(),
+        };
+        // An empty value, as left by templated deployments, keeps the default
+        let frozen_conclusion = match non_empty_var("FROZEN_CONCLUSION") {
+            Some(value) => value.parse()?,
+            None => FrozenConclusion::default(),
+        };
+        let no_config_mode = match std::env::var("NO_CONFIG_MODE") {
+            Ok(value) => value.parse()?,
+            Err(_) => NoConfigMode::default(),
+        };
+
+        let min_freeze_duration =
+            parse_secs_var("MIN_FREEZE_DURATION_SECS", DEFAULT_MIN_FREEZE_DURATION_SECS)?;
+        let freeze_reminder_after =
+            parse_secs_var("FREEZE_REMINDER_AFTER_SECS", DEFAULT_FREEZE_REMINDER_AFTER_SECS)?;
+        let freeze_reminder_interval = parse_secs_var(
+            "FREEZE_REMINDER_INTERVAL_SECS",
+            DEFAULT_FREEZE_REMINDER_INTERVAL_SECS,
+        )?;
+        let max_freeze_age = parse_secs_var("MAX_FREEZE_AGE_SECS", 0)?;
+        let freeze_retention = parse_secs_var("FREEZE_RETENTION_SECS", 0)?;
+        let freeze_retention_grace = parse_secs_var("FREEZE_RETENTION_GRACE_SECS", 0)?;
+
+        let business_hours = BusinessHours::parse(
+            non_empty_var("BUSINESS_DAYS").as_deref(),
+            non_empty_var("BUSINESS_HOURS").as_deref(),
+            non_empty_var("BUSINESS_HOURS_UTC_OFFSET").as_deref(),
+        )?;
+
+        Ok(AppConfig {
+            message_theme,
+            min_freeze_duration,
+            freeze_event_webhook_url: non_empty_var("FREEZE_EVENT_WEBHOOK_URL"),
+            freeze_event_webhook_secret: non_empty_var("FREEZE_EVENT_WEBHOOK_SECRET"),
+            emit_events_stdout: parse_bool_var("EMIT_EVENTS_STDOUT")?,
+            commit_status_fallback: parse_bool_var("COMMIT_STATUS_FALLBACK")?,
+            frozen_conclusion,
+            check_run_text_templates: HashMap::new(),
+            repository_watchers: HashMap::new(),
+            ops_contacts: HashMap::new(),
+            quiet_hours: HashMap::new(),
+            freeze_reminder_after: (!freeze_reminder_after.is_zero())
+                .then_some(freeze_reminder_after),
+            freeze_reminder_interval,
+            max_freeze_age: (!max_freeze_age.is_zero()).then_some(max_freeze_age),
+            freeze_retention: (!freeze_retention.is_zero()).then_some(freeze_retention),
+            freeze_retention_grace: (!freeze_retention_grace.is_zero())
+                .then_some(freeze_retention_grace),
+            freeze_label: non_empty_var("FREEZE_LABEL"),
+            freeze_dispatch_event_type: non_empty_var("FREEZE_DISPATCH_EVENT_TYPE"),
+            comment_on_new_pr_during_freeze: parse_bool_var("COMMENT_ON_NEW_PR_DURING_FREEZE")?,
+            alert_on_unlock_pr: parse_bool_var("ALERT_ON_UNLOCK_PR")?,
+            no_config_mode,
+            github_url: non_empty_var("GITHUB_URL")
+                .unwrap_or_else(|| DEFAULT_GITHUB_URL.to_string()),
+            prune_deleted_installations: parse_bool_var("PRUNE_DELETED_INSTALLATIONS")?,
+            business_hours,
+        })
+    }
+}
 
 /// Configuration for user permissions loaded from YAML file.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserPermissionsConfig {
     /// Map of installation ID to installation-specific permissions
     pub installations: HashMap<String, InstallationConfig>,
+    /// Whether a `/freeze` without `--branch` freezes only the repository's
+    /// default branch, unless an installation or repository says otherwise
+    #[serde(default)]
+    pub default_branch_only: bool,
 }
 
 /// Configuration for a specific GitHub App installation.
@@ -33,6 +293,138 @@ pub struct InstallationConfig {
     /// Global users that apply to all repositories in this installation
     #[serde(default)]
     pub global_users: HashMap<String, UserPermissions>,
+    /// Commands enabled for this installation (e.g. "freeze", "unfreeze").
+    /// All commands are enabled when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub enabled_commands: Vec<String>,
+    /// Whether a `/freeze` without `--branch` freezes only the repository's default branch.
+    /// Falls back to the top-level setting when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_branch_only: Option<bool>,
+    /// Word every command must start with (e.g. "frezze" for `/frezze freeze`).
+    /// Commands are bare verbs like `/freeze` when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command_prefix: Option<String>,
+    /// Hours to freeze a repository for after one of its releases is published.
+    /// Releases do not freeze anything when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub release_freeze_hours: Option<u32>,
+    /// Whether `/freeze-all` must be confirmed with a token before it runs
+    #[serde(default)]
+    pub confirm_freeze_all: bool,
+    /// Whether a `/freeze` commented on a pull request unlocks that pull request
+    #[serde(default)]
+    pub auto_unlock_initiating_pr: bool,
+    /// Whether freezes must be given a reason
+    #[serde(default)]
+    pub require_reason: bool,
+    /// Minimum number of characters of a required reason.
+    /// Only applies when `require_reason` is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_reason_length: Option<u32>,
+    /// Maximum number of repositories a single `--repo` list may name.
+    /// Defaults to [`DEFAULT_MAX_REPOS_PER_COMMAND`] when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_repos_per_command: Option<usize>,
+    /// Users one of whom must approve a `/unfreeze` with `/approve-unfreeze`.
+    /// Unfreezes run right away when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unfreeze_approvers: Vec<String>,
+    /// Users mentioned when `/freeze-all` or `/unfreeze-all` fails for some
+    /// repositories, so someone looks into them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ops_contacts: Vec<String>,
+    /// Daily window during which freeze events are not sent to the
+    /// notification endpoints. GitHub comments are posted as usual.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quiet_hours: Option<QuietHoursConfig>,
+}
+
+/// Quiet hours of an installation, as written in the permissions file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuietHoursConfig {
+    /// Local time at which the quiet hours start (e.g. "22:00")
+    pub start: String,
+    /// Local time at which the quiet hours end (e.g. "07:00")
+    pub end: String,
+    /// IANA timezone of the window (e.g. "Europe/Rome"), UTC when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+}
+
+impl QuietHoursConfig {
+    /// Parses the window into [`QuietHours`].
+    pub fn parse(&self) -> Result<QuietHours> {
+        QuietHours::parse(&self.start, &self.end, self.timezone.as_deref())
+    }
+}
+
+impl InstallationConfig {
+    /// Returns the repository entries matching a repository, most specific first.
+    ///
+    /// Repository keys may be glob patterns such as `owner/service-*`. An exact
+    /// key always comes first, followed by the matching patterns ordered by how
+    /// many literal characters they contain.
+    fn matching_repositories(&self, repository: &str) -> Vec<&RepositoryConfig> {
+        let mut matching: Vec<(&String, &RepositoryConfig)> = self
+            .repositories
+            .iter()
+            .filter(|(key, _)| glob_matches(key, repository))
+            .collect();
+        matching.sort_by(|(a, _), (b, _)| {
+            (*b == repository)
+                .cmp(&(*a == repository))
+                .then_with(|| glob_specificity(b).cmp(&glob_specificity(a)))
+                .then_with(|| a.cmp(b))
+        });
+        matching.into_iter().map(|(_, config)| config).collect()
+    }
+}
+
+/// Checks whether a repository name matches a repository key.
+///
+/// `*` matches any run of characters other than `/` and `?` matches a single
+/// one, so `owner/*` covers every repository of `owner`. Keys without
+/// wildcards only match themselves.
+fn glob_matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` and the name position it was tried at
+    let mut backtrack: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some('?') if name[n] != '/' => {
+                p += 1;
+                n += 1;
+            }
+            Some(&c) if c != '?' && c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` swallow one more character
+                Some((star, from)) if name[from] != '/' => {
+                    backtrack = Some((star, from + 1));
+                    p = star + 1;
+                    n = from + 1;
+                }
+                _ => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Number of literal characters in a repository key, used to rank patterns.
+fn glob_specificity(pattern: &str) -> usize {
+    pattern.chars().filter(|c| !matches!(c, '*' | '?')).count()
 }
 
 /// Configuration for a specific repository.
@@ -42,6 +434,17 @@ pub struct RepositoryConfig {
     pub repository: String,
     /// Users with specific permissions for this repository
     pub users: HashMap<String, UserPermissions>,
+    /// Custom text for the frozen check run. Supports the `{reason}`, `{end}`,
+    /// `{author}`, `{start}`, `{branch}`, `{repository}` and `{repository_url}` placeholders.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_run_text_template: Option<String>,
+    /// GitHub users mentioned whenever this repository is frozen or unfrozen
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub watchers: Vec<String>,
+    /// Whether a `/freeze` without `--branch` freezes only this repository's
+    /// default branch. Falls back to the installation setting when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_branch_only: Option<bool>,
 }
 
 /// User permissions configuration.
@@ -143,6 +546,8 @@ impl UserPermissionsConfig {
     ///             role: maintainer
     ///             can_freeze: true
     ///             can_unfreeze: true
+    ///         check_run_text_template: "Frozen by {author} until {end}: {reason}"
+    ///         watchers: ["release-manager"]
     /// ```
     pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
         let content = std::fs::read_to_string(path)?;
@@ -179,6 +584,59 @@ impl UserPermissionsConfig {
                 user_perms.to_role()?;
             }
 
+            for command in &installation.enabled_commands {
+                let name = command.trim_start_matches('/');
+                if !Command::NAMES.contains(&name) {
+                    return Err(anyhow!(
+                        "Unknown command '{}' in enabled_commands of installation '{}'",
+                        command,
+                        install_key
+                    ));
+                }
+            }
+
+            if let Some(ref prefix) = installation.command_prefix {
+                let prefix = prefix.trim_start_matches('/');
+                if prefix.is_empty() || prefix.contains(char::is_whitespace) {
+                    return Err(anyhow!(
+                        "Invalid command_prefix '{}' of installation '{}': expected a single word",
+                        prefix,
+                        install_key
+                    ));
+                }
+            }
+
+            if installation.min_reason_length == Some(0) {
+                return Err(anyhow!(
+                    "Invalid min_reason_length of installation '{}': must be greater than 0",
+                    install_key
+                ));
+            }
+
+            if installation.release_freeze_hours == Some(0) {
+                return Err(anyhow!(
+                    "Invalid release_freeze_hours of installation '{}': must be greater than 0",
+                    install_key
+                ));
+            }
+
+            if let Some(ref quiet_hours) = installation.quiet_hours
+                && let Err(e) = quiet_hours.parse()
+            {
+                return Err(anyhow!(
+                    "Invalid quiet_hours of installation '{}': {}",
+                    install_key,
+                    e
+                ));
+            }
+
+            if installation.max_repos_per_command == Some(0) {
+                return Err(anyhow!(
+                    "Invalid max_repos_per_command of installation '{}': must be greater than 0",
+                    install_key
+                ));
+            }
+
             for (repo_key, repo_config) in &installation.repositories {
                 if repo_key != &repo_config.repository {
                     return Err(anyhow!(
@@ -217,9 +675,11 @@ impl UserPermissionsConfig {
         let installation_key = installation_id.to_string();
         let installation = self.installations.get(&installation_key)?;
 
-        // Check repository-specific permissions first
-        if let Some(repo_config) = installation.repositories.get(repository)
-            && let Some(user_perms) = repo_config.users.get(user_login)
+        // Check repository-specific permissions first, most specific entry first
+        if let Some(user_perms) = installation
+            .matching_repositories(repository)
+            .into_iter()
+            .find_map(|repo_config| repo_config.users.get(user_login))
         {
             return Some(user_perms.clone());
         }
@@ -232,6 +692,257 @@ impl UserPermissionsConfig {
         // Fall back to default permissions
         installation.default_permissions.clone()
     }
+
+    /// Checks whether a command is enabled for an installation.
+    ///
+    /// # Arguments
+    ///
+    /// * `installation_id` - GitHub App installation ID
+    /// * `command` - Command name without the leading slash (e.g. "freeze-all")
+    ///
+    /// # Returns
+    ///
+    /// `true` unless the installation restricts `enabled_commands` and the
+    /// command is not listed.
+    pub fn is_command_enabled(&self, installation_id: i64, command: &str) -> bool {
+        match self.installations.get(&installation_id.to_string()) {
+            Some(installation) if !installation.enabled_commands.is_empty() => installation
+                .enabled_commands
+                .iter()
+                .any(|enabled| enabled.trim_start_matches('/') == command),
+            _ => true,
+        }
+    }
+
+    /// Checks whether a bare `/freeze` should only freeze the default branch.
+    ///
+    /// The most specific `default_branch_only` wins: the repository entry,
+    /// then the installation, then the top-level setting.
+    ///
+    /// # Arguments
+    ///
+    /// * `installation_id` - GitHub App installation ID
+    /// * `repository` - Repository name in "owner/repo" format
+    ///
+    /// # Returns
+    ///
+    /// `true` if `default_branch_only` is enabled for the repository.
+    pub fn is_default_branch_only(&self, installation_id: i64, repository: &str) -> bool {
+        let Some(installation) = self.installations.get(&installation_id.to_string()) else {
+            return self.default_branch_only;
+        };
+
+        installation
+            .matching_repositories(repository)
+            .into_iter()
+            .find_map(|repo_config| repo_config.default_branch_only)
+            .or(installation.default_branch_only)
+            .unwrap_or(self.default_branch_only)
+    }
+
+    /// Checks whether `/freeze-all` has to be confirmed before it runs.
+    ///
+    /// # Arguments
+    ///
+    /// * `installation_id` - GitHub App installation ID
+    ///
+    /// # Returns
+    ///
+    /// `true` if the installation enables `confirm_freeze_all`.
+    pub fn requires_freeze_all_confirmation(&self, installation_id: i64) -> bool {
+        self.installations
+            .get(&installation_id.to_string())
+            .is_some_and(|installation| installation.confirm_freeze_all)
+    }
+
+    /// Returns the users one of whom must approve an unfreeze.
+    ///
+    /// # Arguments
+    ///
+    /// * `installation_id` - GitHub App installation ID
+    ///
+    /// # Returns
+    ///
+    /// The installation's `unfreeze_approvers` without leading `@`s, empty if
+    /// unfreezes need no approval.
+    pub fn unfreeze_approvers(&self, installation_id: i64) -> Vec<String> {
+        self.installations
+            .get(&installation_id.to_string())
+            .map(|installation| {
+                installation
+                    .unfreeze_approvers
+                    .iter()
+                    .map(|approver| approver.trim().trim_start_matches('@').to_string())
+                    .filter(|approver| !approver.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Checks whether a `/freeze` should unlock the pull request it was commented on.
+    ///
+    /// # Arguments
+    ///
+    /// * `installation_id` - GitHub App installation ID
+    ///
+    /// # Returns
+    ///
+    /// `true` if the installation enables `auto_unlock_initiating_pr`.
+    pub fn auto_unlocks_initiating_pr(&self, installation_id: i64) -> bool {
+        self.installations
+            .get(&installation_id.to_string())
+            .is_some_and(|installation| installation.auto_unlock_initiating_pr)
+    }
+
+    /// Checks a freeze reason against the installation's reason requirements.
+    ///
+    /// # Arguments
+    ///
+    /// * `installation_id` - GitHub App installation ID
+    /// * `reason` - Reason given to the freeze, if any
+    ///
+    /// # Returns
+    ///
+    /// Why the reason is rejected, or `None` if it is acceptable.
+    pub fn reason_problem(&self, installation_id: i64, reason: Option<&str>) -> Option<String> {
+        let installation = self.installations.get(&installation_id.to_string())?;
+        if !installation.require_reason {
+            return None;
+        }
+
+        let reason = reason.map(str::trim).unwrap_or_default();
+        if reason.is_empty() {
+            return Some("A reason is required: add `--reason \"...\"` to the command".to_string());
+        }
+        match installation.min_reason_length {
+            Some(min) if reason.chars().count() < min as usize => Some(format!(
+                "The reason must be at least {} characters long, got {}",
+                min,
+                reason.chars().count()
+            )),
+            _ => None,
+        }
+    }
+
+    /// Returns the command prefix of an installation, if it configures one.
+    ///
+    /// # Arguments
+    ///
+    /// * `installation_id` - GitHub App installation ID
+    pub fn command_prefix(&self, installation_id: i64) -> Option<&str> {
+        self.installations
+            .get(&installation_id.to_string())
+            .and_then(|installation| installation.command_prefix.as_deref())
+    }
+
+    /// Returns how long to freeze a repository after a release is published.
+    ///
+    /// # Arguments
+    ///
+    /// * `installation_id` - GitHub App installation ID
+    ///
+    /// # Returns
+    ///
+    /// `None` unless the installation enables `release_freeze_hours`.
+    pub fn release_freeze_duration(&self, installation_id: i64) -> Option<chrono::Duration> {
+        self.installations
+            .get(&installation_id.to_string())
+            .and_then(|installation| installation.release_freeze_hours)
+            .map(|hours| chrono::Duration::hours(hours.into()))
+    }
+
+    /// Returns how many repositories a single `--repo` list may name.
+    ///
+    /// # Arguments
+    ///
+    /// * `installation_id` - GitHub App installation ID
+    ///
+    /// # Returns
+    ///
+    /// The installation's `max_repos_per_command`, or
+    /// [`DEFAULT_MAX_REPOS_PER_COMMAND`] if it does not set one.
+    pub fn max_repos_per_command(&self, installation_id: i64) -> usize {
+        self.installations
+            .get(&installation_id.to_string())
+            .and_then(|installation| installation.max_repos_per_command)
+            .unwrap_or(DEFAULT_MAX_REPOS_PER_COMMAND)
+    }
+
+    /// Lists every repository configuration together with its installation ID.
+    fn repository_configs(&self) -> impl Iterator<Item = (i64, &RepositoryConfig)> {
+        self.installations.values().flat_map(|installation| {
+            let installation_id = installation.installation_id.parse().ok();
+            installation
+                .repositories
+                .values()
+                .filter_map(move |repo_config| Some((installation_id?, repo_config)))
+        })
+    }
+
+    /// Collects the check run text templates configured across all installations.
+    ///
+    /// # Returns
+    ///
+    /// A map of installation ID and lowercase repository name ("owner/repo")
+    /// to its check run text template.
+    pub fn check_run_text_templates(&self) -> HashMap<(i64, String), String> {
+        self.repository_configs()
+            .filter_map(|(installation_id, repo_config)| {
+                let template = repo_config.check_run_text_template.clone()?;
+                Some(((installation_id, repo_config.repository.to_lowercase()), template))
+            })
+            .collect()
+    }
+
+    /// Collects the watchers configured across all installations.
+    ///
+    /// # Returns
+    ///
+    /// A map of installation ID and lowercase repository name ("owner/repo")
+    /// to the users watching it.
+    pub fn repository_watchers(&self) -> HashMap<(i64, String), Vec<String>> {
+        self.repository_configs()
+            .filter(|(_, repo_config)| !repo_config.watchers.is_empty())
+            .map(|(installation_id, repo_config)| {
+                let key = (installation_id, repo_config.repository.to_lowercase());
+                (key, repo_config.watchers.clone())
+            })
+            .collect()
+    }
+
+    /// Collects the ops contacts configured across all installations.
+    ///
+    /// # Returns
+    ///
+    /// A map of installation ID to the users mentioned when a multi-repository
+    /// freeze or unfreeze partially fails.
+    pub fn ops_contacts(&self) -> HashMap<i64, Vec<String>> {
+        self.installations
+            .values()
+            .filter(|installation| !installation.ops_contacts.is_empty())
+            .filter_map(|installation| {
+                let installation_id = installation.installation_id.parse().ok()?;
+                Some((installation_id, installation.ops_contacts.clone()))
+            })
+            .collect()
+    }
+
+    /// Collects the quiet hours configured across all installations.
+    ///
+    /// # Returns
+    ///
+    /// A map of installation ID to the window during which freeze events are
+    /// not sent.
+    pub fn quiet_hours(&self) -> HashMap<i64, QuietHours> {
+        self.installations
+            .values()
+            .filter_map(|installation| {
+                let installation_id = installation.installation_id.parse().ok()?;
+                let quiet_hours = installation.quiet_hours.as_ref()?.parse().ok()?;
+                Some((installation_id, quiet_hours))
+            })
+            .collect()
+    }
 }
 
 /// Creates an example configuration file.
@@ -252,6 +963,9 @@ pub fn create_example_config<P: AsRef<Path>>(path: P) -> Result<()> {
                 );
                 users
             },
+            check_run_text_template: None,
+            watchers: Vec::new(),
+            default_branch_only: None,
         },
     );
 
@@ -265,10 +979,25 @@ pub fn create_example_config<P: AsRef<Path>>(path: P) -> Result<()> {
             default_permissions: Some(UserPermissions::contributor()),
             repositories,
             global_users,
+            enabled_commands: Vec::new(),
+            default_branch_only: None,
+            command_prefix: None,
+            release_freeze_hours: None,
+            confirm_freeze_all: false,
+            auto_unlock_initiating_pr: false,
+            require_reason: false,
+            min_reason_length: None,
+            max_repos_per_command: None,
+            unfreeze_approvers: Vec::new(),
+            ops_contacts: Vec::new(),
+            quiet_hours: None,
         },
     );
 
-    let config = UserPermissionsConfig { installations };
+    let config = UserPermissionsConfig {
+        installations,
+        default_branch_only: false,
+    };
 
     let yaml_content = serde_yaml::to_string(&config)?;
     std::fs::write(path, yaml_content)?;
@@ -356,4 +1085,534 @@ mod tests {
                 .is_none()
         );
     }
+
+    #[test]
+    fn test_glob_matches() {
+        assert!(glob_matches("owner/repo", "owner/repo"));
+        assert!(!glob_matches("owner/repo", "owner/repo2"));
+        assert!(glob_matches("owner/service-*", "owner/service-api"));
+        assert!(glob_matches("owner/service-*", "owner/service-"));
+        assert!(!glob_matches("owner/service-*", "owner/web"));
+        assert!(glob_matches("owner/*", "owner/anything"));
+        assert!(glob_matches("*/docs", "other/docs"));
+        assert!(glob_matches("owner/*-api-*", "owner/billing-api-v2"));
+        assert!(glob_matches("owner/repo-?", "owner/repo-1"));
+        assert!(!glob_matches("owner/repo-?", "owner/repo-10"));
+
+        // Wildcards never cross the owner/repo separator
+        assert!(!glob_matches("owner*", "owner/repo"));
+        assert!(!glob_matches("*", "owner/repo"));
+        assert!(!glob_matches("owner?repo", "owner/repo"));
+    }
+
+    #[test]
+    fn test_get_user_permissions_with_patterns() {
+        let yaml = r#"
+installations:
+  "12345":
+    installation_id: "12345"
+    default_permissions:
+      role: contributor
+      can_freeze: false
+      can_unfreeze: false
+    repositories:
+      "owner/*":
+        repository: "owner/*"
+        users:
+          alice:
+            role: maintainer
+            can_freeze: false
+            can_unfreeze: true
+          carol:
+            role: maintainer
+            can_freeze: true
+            can_unfreeze: false
+      "owner/service-*":
+        repository: "owner/service-*"
+        users:
+          alice:
+            role: maintainer
+            can_freeze: true
+            can_unfreeze: true
+      "owner/service-billing":
+        repository: "owner/service-billing"
+        users:
+          alice:
+            role: contributor
+            can_freeze: false
+            can_unfreeze: false
+"#;
+        let config: UserPermissionsConfig = serde_yaml::from_str(yaml).unwrap();
+        config.validate().unwrap();
+
+        // The most specific pattern wins over the broader one
+        let perms = config
+            .get_user_permissions(12345, "owner/service-api", "alice")
+            .unwrap();
+        assert!(perms.can_freeze);
+
+        // An exact key beats every pattern
+        let perms = config
+            .get_user_permissions(12345, "owner/service-billing", "alice")
+            .unwrap();
+        assert_eq!(perms.role, "contributor");
+
+        // Only the broad pattern matches
+        let perms = config
+            .get_user_permissions(12345, "owner/web", "alice")
+            .unwrap();
+        assert!(!perms.can_freeze);
+        assert!(perms.can_unfreeze);
+
+        // Users missing from the more specific matches fall through to broader ones
+        let perms = config
+            .get_user_permissions(12345, "owner/service-billing", "carol")
+            .unwrap();
+        assert_eq!(perms.role, "maintainer");
+        assert!(perms.can_freeze);
+
+        // Other owners are not covered
+        let perms = config
+            .get_user_permissions(12345, "other/service-api", "alice")
+            .unwrap();
+        assert_eq!(perms.role, "contributor");
+    }
+
+    #[test]
+    fn test_check_run_text_templates() {
+        let yaml = r#"
+installations:
+  "12345":
+    installation_id: "12345"
+    repositories:
+      "owner/repo":
+        repository: "owner/repo"
+        users: {}
+        check_run_text_template: "See https://runbooks.example.com/freeze ({reason})"
+      "owner/other":
+        repository: "owner/other"
+        users: {}
+  "67890":
+    installation_id: "67890"
+    repositories:
+      "Owner/Repo":
+        repository: "Owner/Repo"
+        users: {}
+        check_run_text_template: "Ask #releases ({reason})"
+"#;
+        let config: UserPermissionsConfig = serde_yaml::from_str(yaml).unwrap();
+        config.validate().unwrap();
+
+        // The same repository name stays separate per installation
+        let templates = config.check_run_text_templates();
+        assert_eq!(templates.len(), 2);
+        assert_eq!(
+            templates[&(12345, "owner/repo".to_string())],
+            "See https://runbooks.example.com/freeze ({reason})"
+        );
+        assert_eq!(
+            templates[&(67890, "owner/repo".to_string())],
+            "Ask #releases ({reason})"
+        );
+    }
+
+    #[test]
+    fn test_repository_watchers() {
+        let yaml = r#"
+installations:
+  "12345":
+    installation_id: "12345"
+    repositories:
+      "owner/repo":
+        repository: "owner/repo"
+        users: {}
+        watchers: ["release-manager", "oncall"]
+      "owner/other":
+        repository: "owner/other"
+        users: {}
+  "67890":
+    installation_id: "67890"
+    repositories:
+      "owner/repo":
+        repository: "owner/repo"
+        users: {}
+        watchers: ["someone-else"]
+"#;
+        let config: UserPermissionsConfig = serde_yaml::from_str(yaml).unwrap();
+        config.validate().unwrap();
+
+        let watchers = config.repository_watchers();
+        assert_eq!(watchers.len(), 2);
+        assert_eq!(
+            watchers[&(12345, "owner/repo".to_string())],
+            vec!["release-manager", "oncall"]
+        );
+        assert_eq!(watchers[&(67890, "owner/repo".to_string())], vec!["someone-else"]);
+    }
+
+    #[test]
+    fn test_ops_contacts() {
+        let yaml = r#"
+installations:
+  "12345":
+    installation_id: "12345"
+    ops_contacts: ["oncall", "@platform-lead"]
+  "67890":
+    installation_id: "67890"
+"#;
+        let config: UserPermissionsConfig = serde_yaml::from_str(yaml).unwrap();
+        config.validate().unwrap();
+
+        let contacts = config.ops_contacts();
+        assert_eq!(contacts.len(), 1);
+        assert_eq!(contacts[&12345], vec!["oncall", "@platform-lead"]);
+    }
+
+    #[test]
+    fn test_quiet_hours() {
+        let yaml = r#"
+installations:
+  "12345":
+    installation_id: "12345"
+    quiet_hours:
+      start: "22:00"
+      end: "07:00"
+      timezone: Europe/Rome
+  "67890":
+    installation_id: "67890"
+"#;
+        let config: UserPermissionsConfig = serde_yaml::from_str(yaml).unwrap();
+        config.validate().unwrap();
+
+        let quiet_hours = config.quiet_hours();
+        assert_eq!(quiet_hours.len(), 1);
+        assert_eq!(
+            quiet_hours[&12345],
+            QuietHours::parse("22:00", "07:00", Some("Europe/Rome")).unwrap()
+        );
+
+        let yaml = r#"
+installations:
+  "12345":
+    installation_id: "12345"
+    quiet_hours:
+      start: "22:00"
+      end: "7am"
+"#;
+        let config: UserPermissionsConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_enabled_commands() {
+        let yaml = r#"
+installations:
+  "12345":
+    installation_id: "12345"
+    enabled_commands: ["freeze", "/unfreeze"]
+"#;
+        let config: UserPermissionsConfig = serde_yaml::from_str(yaml).unwrap();
+        config.validate().unwrap();
+
+        assert!(config.is_command_enabled(12345, "freeze"));
+        assert!(config.is_command_enabled(12345, "unfreeze"));
+        assert!(!config.is_command_enabled(12345, "schedule-freeze"));
+        assert!(!config.is_command_enabled(12345, "unlock-pr"));
+        // Installations without a list allow everything
+        assert!(config.is_command_enabled(99999, "unlock-pr"));
+
+        let invalid: UserPermissionsConfig =
+            serde_yaml::from_str(&yaml.replace("\"/unfreeze\"", "\"unfrezee\"")).unwrap();
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_default_branch_only() {
+        let yaml = r#"
+installations:
+  "12345":
+    installation_id: "12345"
+    default_branch_only: true
+  "67890":
+    installation_id: "67890"
+"#;
+        let config: UserPermissionsConfig = serde_yaml::from_str(yaml).unwrap();
+        config.validate().unwrap();
+
+        assert!(config.is_default_branch_only(12345, "owner/repo"));
+        assert!(!config.is_default_branch_only(67890, "owner/repo"));
+        assert!(!config.is_default_branch_only(99999, "owner/repo"));
+    }
+
+    #[test]
+    fn test_default_branch_only_precedence() {
+        let yaml = r#"
+default_branch_only: true
+installations:
+  "12345":
+    installation_id: "12345"
+    default_branch_only: false
+    repositories:
+      "owner/service-*":
+        repository: "owner/service-*"
+        users: {}
+        default_branch_only: true
+      "owner/service-legacy":
+        repository: "owner/service-legacy"
+        users: {}
+        default_branch_only: false
+      "owner/docs":
+        repository: "owner/docs"
+        users: {}
+  "67890":
+    installation_id: "67890"
+"#;
+        let config: UserPermissionsConfig = serde_yaml::from_str(yaml).unwrap();
+        config.validate().unwrap();
+
+        // Repository entries override the installation, the most specific first
+        assert!(config.is_default_branch_only(12345, "owner/service-api"));
+        assert!(!config.is_default_branch_only(12345, "owner/service-legacy"));
+        // Repositories without a setting use the installation's
+        assert!(!config.is_default_branch_only(12345, "owner/docs"));
+        assert!(!config.is_default_branch_only(12345, "owner/other"));
+        // Installations without a setting use the top-level one
+        assert!(config.is_default_branch_only(67890, "owner/repo"));
+        assert!(config.is_default_branch_only(99999, "owner/repo"));
+    }
+
+    #[test]
+    fn test_confirm_freeze_all() {
+        let yaml = r#"
+installations:
+  "12345":
+    installation_id: "12345"
+    confirm_freeze_all: true
+  "67890":
+    installation_id: "67890"
+"#;
+        let config: UserPermissionsConfig = serde_yaml::from_str(yaml).unwrap();
+        config.validate().unwrap();
+
+        assert!(config.requires_freeze_all_confirmation(12345));
+        assert!(!config.requires_freeze_all_confirmation(67890));
+        assert!(!config.requires_freeze_all_confirmation(99999));
+    }
+
+    #[test]
+    fn test_reason_problem() {
+        let yaml = r#"
+installations:
+  "12345":
+    installation_id: "12345"
+    require_reason: true
+    min_reason_length: 10
+  "67890":
+    installation_id: "67890"
+    require_reason: true
+"#;
+        let config: UserPermissionsConfig = serde_yaml::from_str(yaml).unwrap();
+        config.validate().unwrap();
+
+        // Exactly the minimum is enough, one character less is not
+        assert_eq!(config.reason_problem(12345, Some("0123456789")), None);
+        let problem = config.reason_problem(12345, Some("012345678")).unwrap();
+        assert!(problem.contains("at least 10 characters"));
+        // Surrounding whitespace does not count
+        assert!(config.reason_problem(12345, Some("  x        ")).is_some());
+        let problem = config.reason_problem(12345, None).unwrap();
+        assert!(problem.contains("required"));
+
+        // Without a minimum, any reason will do
+        assert_eq!(config.reason_problem(67890, Some("x")), None);
+        assert!(config.reason_problem(67890, Some(" ")).is_some());
+    }
+
+    #[test]
+    fn test_min_reason_length_disabled() {
+        let yaml = r#"
+installations:
+  "12345":
+    installation_id: "12345"
+    min_reason_length: 10
+"#;
+        let config: UserPermissionsConfig = serde_yaml::from_str(yaml).unwrap();
+        config.validate().unwrap();
+
+        // The minimum only applies when a reason is required
+        assert_eq!(config.reason_problem(12345, Some("x")), None);
+        assert_eq!(config.reason_problem(12345, None), None);
+        assert_eq!(config.reason_problem(99999, None), None);
+
+        let yaml = r#"
+installations:
+  "12345":
+    installation_id: "12345"
+    require_reason: true
+    min_reason_length: 0
+"#;
+        let config: UserPermissionsConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_auto_unlock_initiating_pr() {
+        let yaml = r#"
+installations:
+  "12345":
+    installation_id: "12345"
+    auto_unlock_initiating_pr: true
+  "67890":
+    installation_id: "67890"
+"#;
+        let config: UserPermissionsConfig = serde_yaml::from_str(yaml).unwrap();
+        config.validate().unwrap();
+
+        assert!(config.auto_unlocks_initiating_pr(12345));
+        assert!(!config.auto_unlocks_initiating_pr(67890));
+        assert!(!config.auto_unlocks_initiating_pr(99999));
+    }
+
+    #[test]
+    fn test_command_prefix() {
+        let yaml = r#"
+installations:
+  "12345":
+    installation_id: "12345"
+    command_prefix: frezze
+  "67890":
+    installation_id: "67890"
+"#;
+        let config: UserPermissionsConfig = serde_yaml::from_str(yaml).unwrap();
+        config.validate().unwrap();
+
+        assert_eq!(config.command_prefix(12345), Some("frezze"));
+        assert_eq!(config.command_prefix(67890), None);
+        assert_eq!(config.command_prefix(99999), None);
+
+        let invalid: UserPermissionsConfig = serde_yaml::from_str(
+            r#"
+installations:
+  "12345":
+    installation_id: "12345"
+    command_prefix: "two words"
+"#,
+        )
+        .unwrap();
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_release_freeze_duration() {
+        let yaml = r#"
+installations:
+  "12345":
+    installation_id: "12345"
+    release_freeze_hours: 24
+  "67890":
+    installation_id: "67890"
+"#;
+        let config: UserPermissionsConfig = serde_yaml::from_str(yaml).unwrap();
+        config.validate().unwrap();
+
+        assert_eq!(
+            config.release_freeze_duration(12345),
+            Some(chrono::Duration::hours(24))
+        );
+        assert_eq!(config.release_freeze_duration(67890), None);
+        assert_eq!(config.release_freeze_duration(99999), None);
+
+        let invalid: UserPermissionsConfig = serde_yaml::from_str(
+            r#"
+installations:
+  "12345":
+    installation_id: "12345"
+    release_freeze_hours: 0
+"#,
+        )
+        .unwrap();
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_max_repos_per_command() {
+        let yaml = r#"
+installations:
+  "12345":
+    installation_id: "12345"
+    max_repos_per_command: 100
+  "67890":
+    installation_id: "67890"
+"#;
+        let config: UserPermissionsConfig = serde_yaml::from_str(yaml).unwrap();
+        config.validate().unwrap();
+
+        assert_eq!(config.max_repos_per_command(12345), 100);
+        assert_eq!(
+            config.max_repos_per_command(67890),
+            DEFAULT_MAX_REPOS_PER_COMMAND
+        );
+        assert_eq!(
+            config.max_repos_per_command(99999),
+            DEFAULT_MAX_REPOS_PER_COMMAND
+        );
+
+        let invalid: UserPermissionsConfig = serde_yaml::from_str(
+            r#"
+installations:
+  "12345":
+    installation_id: "12345"
+    max_repos_per_command: 0
+"#,
+        )
+        .unwrap();
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_unfreeze_approvers() {
+        let yaml = r#"
+installations:
+  "12345":
+    installation_id: "12345"
+    unfreeze_approvers: ["@release-manager", "cto", " "]
+  "67890":
+    installation_id: "67890"
+"#;
+        let config: UserPermissionsConfig = serde_yaml::from_str(yaml).unwrap();
+        config.validate().unwrap();
+
+        assert_eq!(
+            config.unfreeze_approvers(12345),
+            vec!["release-manager", "cto"]
+        );
+        assert!(config.unfreeze_approvers(67890).is_empty());
+        assert!(config.unfreeze_approvers(99999).is_empty());
+    }
+
+    #[test]
+    fn test_no_config_mode() {
+        let status = crate::freezer::commands::parse("/status").unwrap().command;
+        let freeze = crate::freezer::commands::parse("/freeze --duration 2h")
+            .unwrap()
+            .command;
+
+        assert_eq!(NoConfigMode::default(), NoConfigMode::StatusOnly);
+        assert!(NoConfigMode::StatusOnly.allows(&status));
+        assert!(!NoConfigMode::StatusOnly.allows(&freeze));
+        assert!(!NoConfigMode::DenyAll.allows(&status));
+        assert!(!NoConfigMode::DenyAll.allows(&freeze));
+        assert!(NoConfigMode::AllowAll.allows(&status));
+        assert!(NoConfigMode::AllowAll.allows(&freeze));
+
+        assert_eq!(
+            "allow-all".parse::<NoConfigMode>().unwrap(),
+            NoConfigMode::AllowAll
+        );
+        assert_eq!(
+            " Deny-All ".parse::<NoConfigMode>().unwrap(),
+            NoConfigMode::DenyAll
+        );
+        assert!("allow".parse::<NoConfigMode>().is_err());
+    }
 }
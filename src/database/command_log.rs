@@ -0,0 +1,183 @@
+//! Database operations for the command audit log.
+
+use anyhow::Result;
+use sqlx::{Row, SqlitePool};
+
+use crate::{
+    database::models::{CommandLog, CommandResult},
+    installation::InstallationId,
+};
+
+impl CommandLog {
+    /// Records a command in the audit log.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `log` - The command log entry to store
+    pub async fn create(pool: &SqlitePool, log: &CommandLog) -> Result<CommandLog> {
+        let result_str = log.result.to_string();
+        sqlx::query(
+            r#"
+            INSERT INTO command_logs
+            (id, installation_id, repository, user_login, command, comment_id, result, error_message, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+        )
+        .bind(&log.id)
+        .bind(log.installation_id)
+        .bind(&log.repository)
+        .bind(&log.user_login)
+        .bind(&log.command)
+        .bind(log.comment_id)
+        .bind(result_str)
+        .bind(&log.error_message)
+        .bind(log.created_at)
+        .execute(pool)
+        .await?;
+
+        Ok(log.clone())
+    }
+
+    /// Retrieves command log entries with optional filtering.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `installation_id` - Optional GitHub installation ID filter
+    /// * `repository` - Optional repository name filter (format: "owner/repo")
+    /// * `limit` - Maximum number of entries to return
+    ///
+    /// # Returns
+    ///
+    /// Returns the matching entries ordered by creation date (newest first).
+    pub async fn list(
+        pool: &SqlitePool,
+        installation_id: Option<InstallationId>,
+        repository: Option<&str>,
+        limit: u32,
+    ) -> Result<Vec<CommandLog>> {
+        let mut query = "SELECT * FROM command_logs WHERE 1=1".to_string();
+        let mut param_count = 0;
+
+        if installation_id.is_some() {
+            param_count += 1;
+            query.push_str(&format!(" AND installation_id = ${}", param_count));
+        }
+
+        if repository.is_some() {
+            param_count += 1;
+            query.push_str(&format!(" AND repository = ${} COLLATE NOCASE", param_count));
+        }
+
+        param_count += 1;
+        query.push_str(&format!(" ORDER BY created_at DESC LIMIT ${}", param_count));
+
+        let mut sql_query = sqlx::query(&query);
+
+        if let Some(inst_id) = installation_id {
+            sql_query = sql_query.bind(inst_id.get());
+        }
+
+        if let Some(repo) = repository {
+            sql_query = sql_query.bind(repo);
+        }
+
+        let rows = sql_query.bind(limit as i64).fetch_all(pool).await?;
+
+        let mut logs = Vec::new();
+        for row in rows {
+            logs.push(CommandLog {
+                id: row.get("id"),
+                installation_id: row.get("installation_id"),
+                repository: row.get("repository"),
+                user_login: row.get("user_login"),
+                command: row.get("command"),
+                comment_id: row.get("comment_id"),
+                result: CommandResult::from(row.get::<String, _>("result").as_str()),
+                error_message: row.get("error_message"),
+                created_at: row.get("created_at"),
+            });
+        }
+
+        Ok(logs)
+    }
+
+    /// Checks whether a command has already run, or is running, for a comment.
+    ///
+    /// Used to avoid running a command twice when its comment is edited
+    /// without changing the command itself. Denied commands never ran, so
+    /// an edit may still run them once the user is allowed to.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `installation_id` - GitHub installation ID
+    /// * `comment_id` - ID of the comment that triggered the command
+    /// * `command` - The command text to look for
+    pub async fn exists_for_comment(
+        pool: &SqlitePool,
+        installation_id: InstallationId,
+        comment_id: u64,
+        command: &str,
+    ) -> Result<bool> {
+        let row = sqlx::query(
+            r#"
+            SELECT COUNT(*) AS count FROM command_logs
+            WHERE installation_id = $1 AND comment_id = $2 AND command = $3
+            AND result != $4
+            "#,
+        )
+        .bind(installation_id.get())
+        .bind(comment_id as i64)
+        .bind(command)
+        .bind(CommandResult::Denied.to_string())
+        .fetch_one(pool)
+        .await?;
+
+        Ok(row.get::<i64, _>("count") > 0)
+    }
+
+    /// Updates the outcome of a command recorded as pending before it ran.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `installation_id` - GitHub installation ID
+    /// * `comment_id` - ID of the comment that triggered the command
+    /// * `command` - The command text that was recorded
+    /// * `result` - Outcome of the command
+    /// * `error_message` - Optional error details, or a note on the outcome
+    ///
+    /// # Returns
+    ///
+    /// `true` if a pending command was updated, `false` if none matched.
+    pub async fn update_result(
+        pool: &SqlitePool,
+        installation_id: InstallationId,
+        comment_id: u64,
+        command: &str,
+        result: &CommandResult,
+        error_message: Option<&str>,
+    ) -> Result<bool> {
+        let updated = sqlx::query(
+            r#"
+            UPDATE command_logs
+            SET result = $1, error_message = $2
+            WHERE installation_id = $3 AND comment_id = $4 AND command = $5
+            AND result = $6
+            "#,
+        )
+        .bind(result.to_string())
+        .bind(error_message)
+        .bind(installation_id.get())
+        .bind(comment_id as i64)
+        .bind(command)
+        .bind(CommandResult::Pending.to_string())
+        .execute(pool)
+        .await?
+        .rows_affected();
+
+        Ok(updated > 0)
+    }
+}
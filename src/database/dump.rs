@@ -0,0 +1,361 @@
+//! Portable export and import of the database.
+//!
+//! A dump holds every freeze, command log entry, unlocked PR and permission
+//! record as JSON, so installations can be moved between Frezze deployments
+//! or restored after losing the database. Importing keeps rows that already
+//! exist: a row is skipped when its id, or an active freeze of the same
+//! target, is already present.
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, SqlitePool};
+
+use crate::database::models::{
+    CommandLog, CommandResult, FreezeRecord, PermissionRecord, UnlockedPr,
+};
+
+/// Version of the dump format, bumped on incompatible changes
+pub const DUMP_VERSION: u32 = 1;
+
+/// Every row of the database that is worth carrying over
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Dump {
+    pub version: u32,
+    pub freeze_records: Vec<FreezeRecord>,
+    pub command_logs: Vec<CommandLog>,
+    pub unlocked_prs: Vec<UnlockedPr>,
+    pub permission_records: Vec<PermissionRecord>,
+}
+
+/// Rows loaded and skipped by an import
+#[derive(Debug, Default, PartialEq)]
+pub struct ImportSummary {
+    pub imported: u64,
+    pub skipped: u64,
+}
+
+impl ImportSummary {
+    fn add(&mut self, inserted: bool) {
+        if inserted {
+            self.imported += 1;
+        } else {
+            self.skipped += 1;
+        }
+    }
+}
+
+impl Dump {
+    /// Reads every exported table.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    pub async fn export(pool: &SqlitePool) -> Result<Dump> {
+        let mut freeze_records = FreezeRecord::list_for_export(pool).await?;
+        freeze_records.sort_by_key(|r| r.created_at);
+
+        let command_logs = sqlx::query("SELECT * FROM command_logs ORDER BY created_at")
+            .fetch_all(pool)
+            .await?
+            .into_iter()
+            .map(|row| CommandLog {
+                id: row.get("id"),
+                installation_id: row.get("installation_id"),
+                repository: row.get("repository"),
+                user_login: row.get("user_login"),
+                command: row.get("command"),
+                comment_id: row.get("comment_id"),
+                result: CommandResult::from(row.get::<String, _>("result").as_str()),
+                error_message: row.get("error_message"),
+                created_at: row.get("created_at"),
+            })
+            .collect();
+
+        let unlocked_prs =
+            sqlx::query_as::<_, UnlockedPr>("SELECT * FROM unlocked_prs ORDER BY unlocked_at")
+                .fetch_all(pool)
+                .await?;
+
+        let permission_records = sqlx::query_as::<_, PermissionRecord>(
+            "SELECT * FROM permission_records ORDER BY created_at",
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(Dump {
+            version: DUMP_VERSION,
+            freeze_records,
+            command_logs,
+            unlocked_prs,
+            permission_records,
+        })
+    }
+
+    /// Loads a dump, skipping rows that conflict with existing ones.
+    ///
+    /// Everything is imported in a single transaction, so a failing import
+    /// leaves the database untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    pub async fn import(&self, pool: &SqlitePool) -> Result<ImportSummary> {
+        if self.version != DUMP_VERSION {
+            return Err(anyhow!(
+                "Unsupported dump version {}, expected {}",
+                self.version,
+                DUMP_VERSION
+            ));
+        }
+
+        let mut tx = pool.begin().await?;
+        let mut summary = ImportSummary::default();
+
+        for record in &self.freeze_records {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO freeze_records
+                (id, repository, installation_id, started_at, expires_at, ended_at, reason,
+                 initiated_by, ended_by, status, branch, end_reason, environment, soft, category, deleted_at, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
+                ON CONFLICT DO NOTHING
+                "#,
+            )
+            .bind(&record.id)
+            .bind(&record.repository)
+            .bind(record.installation_id)
+            .bind(record.started_at)
+            .bind(record.expires_at)
+            .bind(record.ended_at)
+            .bind(&record.reason)
+            .bind(&record.initiated_by)
+            .bind(&record.ended_by)
+            .bind(record.status.to_string())
+            .bind(&record.branch)
+            .bind(&record.end_reason)
+            .bind(&record.environment)
+            .bind(record.soft)
+            .bind(record.category.map(|c| c.to_string()))
+            .bind(record.deleted_at)
+            .bind(record.created_at)
+            .execute(&mut *tx)
+            .await?;
+            summary.add(result.rows_affected() > 0);
+        }
+
+        for log in &self.command_logs {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO command_logs
+                (id, installation_id, repository, user_login, command, comment_id, result,
+                 error_message, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                ON CONFLICT DO NOTHING
+                "#,
+            )
+            .bind(&log.id)
+            .bind(log.installation_id)
+            .bind(&log.repository)
+            .bind(&log.user_login)
+            .bind(&log.command)
+            .bind(log.comment_id)
+            .bind(log.result.to_string())
+            .bind(&log.error_message)
+            .bind(log.created_at)
+            .execute(&mut *tx)
+            .await?;
+            summary.add(result.rows_affected() > 0);
+        }
+
+        for unlocked in &self.unlocked_prs {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO unlocked_prs
+                (id, repository, installation_id, pr_number, unlocked_by, unlocked_at)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                ON CONFLICT DO NOTHING
+                "#,
+            )
+            .bind(&unlocked.id)
+            .bind(&unlocked.repository)
+            .bind(unlocked.installation_id)
+            .bind(unlocked.pr_number)
+            .bind(&unlocked.unlocked_by)
+            .bind(unlocked.unlocked_at.to_rfc3339())
+            .execute(&mut *tx)
+            .await?;
+            summary.add(result.rows_affected() > 0);
+        }
+
+        for permission in &self.permission_records {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO permission_records
+                (id, installation_id, repository, user_login, role, can_freeze, can_unfreeze,
+                 can_emergency_override, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                ON CONFLICT DO NOTHING
+                "#,
+            )
+            .bind(&permission.id)
+            .bind(permission.installation_id)
+            .bind(&permission.repository)
+            .bind(&permission.user_login)
+            .bind(&permission.role)
+            .bind(permission.can_freeze)
+            .bind(permission.can_unfreeze)
+            .bind(permission.can_emergency_override)
+            .bind(permission.created_at)
+            .execute(&mut *tx)
+            .await?;
+            summary.add(result.rows_affected() > 0);
+        }
+
+        tx.commit().await?;
+        Ok(summary)
+    }
+
+    /// Total number of rows in the dump
+    pub fn row_count(&self) -> usize {
+        self.freeze_records.len()
+            + self.command_logs.len()
+            + self.unlocked_prs.len()
+            + self.permission_records.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{Database, models::FreezeStatus};
+    use chrono::Utc;
+
+    async fn seed(pool: &SqlitePool) {
+        let mut ended = FreezeRecord::new(
+            "owner/repo".to_string(),
+            12345.into(),
+            Utc::now() - chrono::Duration::hours(3),
+            Some(Utc::now() - chrono::Duration::hours(1)),
+            Some("release".to_string()),
+            "alice".to_string(),
+            None,
+        );
+        ended.status = FreezeStatus::Ended;
+        ended.ended_by = Some("bob".to_string());
+        FreezeRecord::create(pool, &ended).await.unwrap();
+
+        let active = FreezeRecord::new(
+            "owner/repo".to_string(),
+            12345.into(),
+            Utc::now(),
+            Some(Utc::now() + chrono::Duration::hours(2)),
+            Some("incident".to_string()),
+            "alice".to_string(),
+            Some("main".to_string()),
+        );
+        FreezeRecord::create(pool, &active).await.unwrap();
+
+        let log = CommandLog::new(
+            12345.into(),
+            "owner/repo".to_string(),
+            "alice".to_string(),
+            "/freeze --branch main".to_string(),
+            7,
+            CommandResult::Success,
+            None,
+        );
+        CommandLog::create(pool, &log).await.unwrap();
+
+        UnlockedPr::unlock_pr(pool, 12345.into(), "owner/repo", 42, "alice")
+            .await
+            .unwrap();
+
+        sqlx::query(
+            r#"
+            INSERT INTO permission_records
+            (id, installation_id, repository, user_login, role, can_freeze, can_unfreeze)
+            VALUES ('perm-1', 12345, 'owner/repo', 'alice', 'maintainer', 1, 1)
+            "#,
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_export_import_round_trip() {
+        let source = Database::new_in_memory().await;
+        seed(source.pool()).await;
+
+        let dump = Dump::export(source.pool()).await.unwrap();
+        assert_eq!(dump.freeze_records.len(), 2);
+        assert_eq!(dump.command_logs.len(), 1);
+        assert_eq!(dump.unlocked_prs.len(), 1);
+        assert_eq!(dump.permission_records.len(), 1);
+
+        // The dump survives being written out as JSON
+        let json = serde_json::to_string(&dump).unwrap();
+        let dump: Dump = serde_json::from_str(&json).unwrap();
+
+        let target = Database::new_in_memory().await;
+        let summary = dump.import(target.pool()).await.unwrap();
+        assert_eq!(
+            summary,
+            ImportSummary {
+                imported: 5,
+                skipped: 0
+            }
+        );
+
+        let reimported = Dump::export(target.pool()).await.unwrap();
+        assert_eq!(
+            serde_json::to_value(&reimported).unwrap(),
+            serde_json::to_value(&dump).unwrap()
+        );
+        let active: Vec<_> = reimported
+            .freeze_records
+            .iter()
+            .filter(|r| matches!(r.status, FreezeStatus::Active))
+            .collect();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].branch.as_deref(), Some("main"));
+    }
+
+    #[tokio::test]
+    async fn test_import_skips_existing_rows() {
+        let db = Database::new_in_memory().await;
+        seed(db.pool()).await;
+
+        let dump = Dump::export(db.pool()).await.unwrap();
+        let summary = dump.import(db.pool()).await.unwrap();
+        assert_eq!(
+            summary,
+            ImportSummary {
+                imported: 0,
+                skipped: 5
+            }
+        );
+
+        // An active freeze under another id still conflicts with the active one
+        let mut conflicting = dump.clone();
+        conflicting.command_logs.clear();
+        conflicting.unlocked_prs.clear();
+        conflicting.permission_records.clear();
+        conflicting
+            .freeze_records
+            .retain(|r| matches!(r.status, FreezeStatus::Active));
+        conflicting.freeze_records[0].id = "another-id".to_string();
+        let summary = conflicting.import(db.pool()).await.unwrap();
+        assert_eq!(summary.skipped, 1);
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_unknown_version() {
+        let db = Database::new_in_memory().await;
+        let mut dump = Dump::export(db.pool()).await.unwrap();
+        assert_eq!(dump.row_count(), 0);
+
+        dump.version = DUMP_VERSION + 1;
+        assert!(dump.import(db.pool()).await.is_err());
+    }
+}
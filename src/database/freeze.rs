@@ -3,11 +3,46 @@
 //! This module provides CRUD operations for managing repository freeze states,
 //! user permissions, and command audit logs in the SQLite database.
 
+use std::sync::atomic::{AtomicU64, Ordering};
+
 use anyhow::Result;
 use chrono::{DateTime, Utc};
-use sqlx::{Row, SqlitePool};
+use sqlx::{Row, SqlitePool, sqlite::SqliteRow};
+use tracing::warn;
+
+use crate::{
+    database::models::{FreezeRecord, FreezeStatus},
+    installation::InstallationId,
+    repository::Repository,
+};
 
-use crate::database::models::{FreezeRecord, FreezeStatus};
+/// Freezes rejected for overlapping an active freeze since startup
+static OVERLAP_REJECTIONS: AtomicU64 = AtomicU64::new(0);
+
+/// Maps a `freeze_records` row onto a freeze record
+pub(crate) fn record_from_row(row: &SqliteRow) -> Result<FreezeRecord> {
+    Ok(FreezeRecord {
+        id: row.try_get("id")?,
+        repository: row.try_get("repository")?,
+        installation_id: row.try_get("installation_id")?,
+        started_at: row.try_get("started_at")?,
+        expires_at: row.try_get("expires_at")?,
+        ended_at: row.try_get("ended_at")?,
+        reason: row.try_get("reason")?,
+        initiated_by: row.try_get("initiated_by")?,
+        ended_by: row.try_get("ended_by")?,
+        status: FreezeStatus::from(row.try_get::<String, _>("status")?.as_str()),
+        branch: row.try_get("branch")?,
+        end_reason: row.try_get("end_reason")?,
+        environment: row.try_get("environment")?,
+        soft: row.try_get("soft")?,
+        category: row
+            .try_get::<Option<String>, _>("category")?
+            .and_then(|c| c.parse().ok()),
+        deleted_at: row.try_get("deleted_at")?,
+        created_at: row.try_get("created_at")?,
+    })
+}
 
 /// Helper function to parse SQLite datetime string to DateTime<Utc>
 fn parse_datetime(datetime_str: &str) -> Result<DateTime<Utc>> {
@@ -24,13 +59,58 @@ fn parse_optional_datetime(datetime_str: Option<String>) -> Result<Option<DateTi
     }
 }
 
+/// Checks whether an active freeze started more than `max_age` ago.
+///
+/// Such freezes are most likely forgotten, so lookups treat them as expired
+/// and log a warning until the worker marks them expired.
+fn exceeds_max_age(
+    id: &str,
+    repository: &str,
+    started_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+    max_age: Option<chrono::Duration>,
+) -> bool {
+    let stale = max_age.is_some_and(|max_age| now - started_at > max_age);
+    if stale {
+        warn!(
+            "Ignoring freeze {} of {} active since {}, it is older than the maximum freeze age",
+            id, repository, started_at
+        );
+    }
+    stale
+}
+
+/// Returns the `owner/*` repository under which organization-wide freezes of
+/// the given repository's owner are stored.
+fn org_wildcard_of(repository: &str) -> String {
+    let owner = repository.split('/').next().unwrap_or(repository);
+    Repository::org_wildcard(owner).full_name()
+}
+
 /// Database operations for freeze records.
 impl FreezeRecord {
+    /// Returns how many freezes [`FreezeRecord::create`] rejected for
+    /// overlapping an active freeze since startup.
+    ///
+    /// Outside of tests, the running total is only reported in the warning
+    /// logged for each rejection, as there is no metrics endpoint to expose it.
+    #[cfg(test)]
+    fn overlap_rejections() -> u64 {
+        OVERLAP_REJECTIONS.load(Ordering::Relaxed)
+    }
+
     /// Creates a new freeze record in the database.
     ///
-    /// This method checks for overlapping active freeze records before creating
-    /// a new one to prevent conflicts. A freeze record is considered overlapping
-    /// if it has any time period intersection with existing active freezes.
+    /// This method checks for overlapping active or scheduled freeze records
+    /// before creating a new one to prevent conflicts. A freeze record is
+    /// considered overlapping if it has any time period intersection with
+    /// existing active or scheduled freezes that target the same branch. Repository-wide freezes (no branch) overlap with
+    /// every branch, while freezes on different branches can coexist.
+    ///
+    /// The check alone is racy, so a partial unique index additionally allows at
+    /// most one active freeze per installation, repository and branch. Elapsed
+    /// active freezes for the same target are marked expired first so they do
+    /// not block the new one while waiting for the background worker.
     ///
     /// # Arguments
     ///
@@ -51,7 +131,7 @@ impl FreezeRecord {
     /// # async fn example(pool: &SqlitePool) -> anyhow::Result<()> {
     /// let record = FreezeRecord::new(
     ///     "owner/repo".to_string(),
-    ///     12345,
+    ///     12345.into(),
     ///     chrono::Utc::now(),
     ///     Some(chrono::Utc::now() + chrono::Duration::hours(2)),
     ///     Some("Emergency maintenance".to_string()),
@@ -63,66 +143,117 @@ impl FreezeRecord {
     /// # }
     /// ```
     pub async fn create(pool: &SqlitePool, record: &FreezeRecord) -> Result<FreezeRecord> {
-        // Check for overlapping active freeze records to prevent conflicts.
-        // Three overlap scenarios are checked:
+        // Check for overlapping active or scheduled freeze records to prevent
+        // conflicts, as a scheduled freeze takes the slot of the unique index once
+        // the worker activates it. Three overlap scenarios are checked:
         // 1. New freeze starts during an existing freeze
         // 2. New freeze ends during an existing freeze
         // 3. New freeze completely encompasses an existing freeze
-        let overlapping = sqlx::query!(
+        let overlapping: Option<String> = sqlx::query_scalar(
             r#"
-            SELECT COUNT(*) as count FROM freeze_records 
-            WHERE repository = $1 
+            SELECT initiated_by FROM freeze_records 
+            WHERE repository = $1 COLLATE NOCASE
             AND installation_id = $2 
-            AND status = 'active'
+            AND status IN ('active', 'scheduled')
             AND (
                 (started_at <= $3 AND (expires_at IS NULL OR expires_at > $3))
                 OR (started_at < $4 AND (expires_at IS NULL OR expires_at >= $4))
                 OR ($3 <= started_at AND ($4 IS NULL OR $4 > started_at))
             )
+            AND (branch IS NULL OR $5 IS NULL OR branch = $5)
+            AND COALESCE(environment, '') = COALESCE($6, '') COLLATE NOCASE
+            LIMIT 1
             "#,
-            record.repository,
-            record.installation_id,
-            record.started_at,
-            record.expires_at
         )
-        .fetch_one(pool)
+        .bind(&record.repository)
+        .bind(record.installation_id)
+        .bind(record.started_at)
+        .bind(record.expires_at)
+        .bind(&record.branch)
+        .bind(&record.environment)
+        .fetch_optional(pool)
         .await?;
 
-        if overlapping.count > 0 {
+        if let Some(conflicting_initiator) = overlapping {
+            let rejections = OVERLAP_REJECTIONS.fetch_add(1, Ordering::Relaxed) + 1;
+            warn!(
+                repository = %record.repository,
+                installation_id = record.installation_id,
+                initiated_by = %record.initiated_by,
+                conflicting_initiator = %conflicting_initiator,
+                rejections,
+                "Rejected freeze overlapping an active or scheduled freeze"
+            );
             return Err(anyhow::anyhow!(
                 "A freeze record already exists for this time period"
             ));
         }
 
+        // Free the slot held by an elapsed freeze the worker has not expired yet
+        if matches!(record.status, FreezeStatus::Active) {
+            sqlx::query(
+                r#"
+                UPDATE freeze_records
+                SET status = 'expired'
+                WHERE installation_id = $1
+                AND repository = $2 COLLATE NOCASE
+                AND COALESCE(branch, '') = COALESCE($3, '')
+                AND COALESCE(environment, '') = COALESCE($5, '') COLLATE NOCASE
+                AND status = 'active'
+                AND expires_at IS NOT NULL
+                AND expires_at <= $4
+                "#,
+            )
+            .bind(record.installation_id)
+            .bind(&record.repository)
+            .bind(&record.branch)
+            .bind(record.started_at)
+            .bind(&record.environment)
+            .execute(pool)
+            .await?;
+        }
+
         // Insert the new freeze record
         let status_str = record.status.to_string();
-        sqlx::query!(
+        let inserted = sqlx::query(
             r#"
             INSERT INTO freeze_records 
-            (id, repository, installation_id, started_at, expires_at, ended_at, reason, initiated_by, ended_by, status, branch, created_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            (id, repository, installation_id, started_at, expires_at, ended_at, reason, initiated_by, ended_by, status, branch, environment, soft, category, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
             "#,
-            record.id,
-            record.repository,
-            record.installation_id,
-            record.started_at,
-            record.expires_at,
-            record.ended_at,
-            record.reason,
-            record.initiated_by,
-            record.ended_by,
-            status_str,
-            record.branch,
-            record.created_at
         )
+        .bind(&record.id)
+        .bind(&record.repository)
+        .bind(record.installation_id)
+        .bind(record.started_at)
+        .bind(record.expires_at)
+        .bind(record.ended_at)
+        .bind(&record.reason)
+        .bind(&record.initiated_by)
+        .bind(&record.ended_by)
+        .bind(status_str)
+        .bind(&record.branch)
+        .bind(&record.environment)
+        .bind(record.soft)
+        .bind(record.category.map(|c| c.to_string()))
+        .bind(record.created_at)
         .execute(pool)
-        .await?;
+        .await;
 
-        Ok(record.clone())
+        match inserted {
+            Ok(_) => Ok(record.clone()),
+            // A concurrent command won the race for the same target
+            Err(sqlx::Error::Database(e)) if e.is_unique_violation() => Err(anyhow::anyhow!(
+                "A freeze record already exists for this time period"
+            )),
+            Err(e) => Err(e.into()),
+        }
     }
 
     /// Retrieves freeze records from the database with optional filtering.
     ///
+    /// Records soft-deleted by the retention worker are left out.
+    ///
     /// # Arguments
     ///
     /// * `pool` - Database connection pool
@@ -141,22 +272,49 @@ impl FreezeRecord {
     /// # use sqlx::SqlitePool;
     /// # async fn example(pool: &SqlitePool) -> anyhow::Result<()> {
     /// // Get all active freezes for a specific repository
-    /// let active_freezes = FreezeRecord::list(pool, Some(12345), Some("owner/repo"), Some(true)).await?;
+    /// let active_freezes = FreezeRecord::list(pool, Some(12345.into()), Some("owner/repo"), Some(true)).await?;
     ///
     /// // Get all freezes for an installation
-    /// let all_freezes = FreezeRecord::list(pool, Some(12345), None, None).await?;
+    /// let all_freezes = FreezeRecord::list(pool, Some(12345.into()), None, None).await?;
     /// # Ok(())
     /// # }
     /// ```
     pub async fn list(
         pool: &SqlitePool,
-        installation_id: Option<u64>,
+        installation_id: Option<InstallationId>,
         repository: Option<&str>,
         active: Option<bool>,
+    ) -> Result<Vec<FreezeRecord>> {
+        Self::list_records(pool, installation_id, repository, active, false).await
+    }
+
+    /// Retrieves every freeze record, including soft-deleted ones, for exports.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    ///
+    /// Returns all freeze records, ordered by creation date (newest first).
+    pub async fn list_for_export(pool: &SqlitePool) -> Result<Vec<FreezeRecord>> {
+        Self::list_records(pool, None, None, None, true).await
+    }
+
+    async fn list_records(
+        pool: &SqlitePool,
+        installation_id: Option<InstallationId>,
+        repository: Option<&str>,
+        active: Option<bool>,
+        include_deleted: bool,
     ) -> Result<Vec<FreezeRecord>> {
         let mut query = "SELECT * FROM freeze_records WHERE 1=1".to_string();
         let mut param_count = 0;
 
+        if !include_deleted {
+            query.push_str(" AND deleted_at IS NULL");
+        }
+
         if installation_id.is_some() {
             param_count += 1;
             query.push_str(&format!(" AND installation_id = ${}", param_count));
@@ -164,7 +322,7 @@ impl FreezeRecord {
 
         if repository.is_some() {
             param_count += 1;
-            query.push_str(&format!(" AND repository = ${}", param_count));
+            query.push_str(&format!(" AND repository = ${} COLLATE NOCASE", param_count));
         }
 
         if let Some(is_active) = active
@@ -178,7 +336,7 @@ impl FreezeRecord {
         let mut sql_query = sqlx::query(&query);
 
         if let Some(inst_id) = installation_id {
-            sql_query = sql_query.bind(inst_id as i64);
+            sql_query = sql_query.bind(inst_id.get());
         }
 
         if let Some(repo) = repository {
@@ -187,25 +345,169 @@ impl FreezeRecord {
 
         let rows = sql_query.fetch_all(pool).await?;
 
-        let mut records = Vec::new();
-        for row in rows {
-            records.push(FreezeRecord {
-                id: row.get("id"),
-                repository: row.get("repository"),
-                installation_id: row.get("installation_id"),
-                started_at: row.get("started_at"),
-                expires_at: row.get("expires_at"),
-                ended_at: row.get("ended_at"),
-                reason: row.get("reason"),
-                initiated_by: row.get("initiated_by"),
-                ended_by: row.get("ended_by"),
-                status: FreezeStatus::from(row.get::<String, _>("status").as_str()),
-                branch: row.get("branch"),
-                created_at: row.get("created_at"),
-            });
-        }
+        rows.iter().map(record_from_row).collect()
+    }
 
-        Ok(records)
+    /// Lists the scheduled freezes of a repository that have not started yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `installation_id` - GitHub installation ID
+    /// * `repository` - Repository name in "owner/repo" format
+    ///
+    /// # Returns
+    ///
+    /// Returns the scheduled freeze records, ordered by start time (soonest first).
+    pub async fn list_scheduled(
+        pool: &SqlitePool,
+        installation_id: InstallationId,
+        repository: &str,
+    ) -> Result<Vec<FreezeRecord>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM freeze_records
+            WHERE installation_id = $1
+            AND repository = $2 COLLATE NOCASE
+            AND status = 'scheduled'
+            ORDER BY started_at ASC
+            "#,
+        )
+        .bind(installation_id.get())
+        .bind(repository)
+        .fetch_all(pool)
+        .await?;
+
+        rows.iter().map(record_from_row).collect()
+    }
+
+    /// Lists the most recent distinct reasons given to a repository's freezes.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `installation_id` - GitHub installation ID
+    /// * `repository` - Repository name in "owner/repo" format
+    /// * `limit` - Maximum number of reasons to return
+    ///
+    /// # Returns
+    ///
+    /// Returns the reasons ordered by their last use (most recent first).
+    pub async fn recent_reasons(
+        pool: &SqlitePool,
+        installation_id: InstallationId,
+        repository: &str,
+        limit: u32,
+    ) -> Result<Vec<String>> {
+        let reasons = sqlx::query_scalar(
+            r#"
+            SELECT TRIM(reason) AS reason FROM freeze_records
+            WHERE installation_id = $1
+            AND repository = $2 COLLATE NOCASE
+            AND TRIM(COALESCE(reason, '')) != ''
+            AND deleted_at IS NULL
+            GROUP BY TRIM(reason)
+            ORDER BY MAX(created_at) DESC
+            LIMIT $3
+            "#,
+        )
+        .bind(installation_id.get())
+        .bind(repository)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(reasons)
+    }
+
+    /// Cancels a scheduled freeze of a repository before it starts.
+    ///
+    /// The record is kept with `FreezeStatus::Cancelled` so it stays in the
+    /// history. Freezes that already started, or belong to another repository,
+    /// are left untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `installation_id` - GitHub installation ID
+    /// * `repository` - Repository name in "owner/repo" format
+    /// * `id` - String UUID of the scheduled freeze
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if a scheduled freeze was cancelled.
+    pub async fn cancel_scheduled(
+        pool: &SqlitePool,
+        installation_id: InstallationId,
+        repository: &str,
+        id: &str,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE freeze_records
+            SET status = 'cancelled'
+            WHERE id = $1
+            AND installation_id = $2
+            AND repository = $3 COLLATE NOCASE
+            AND status = 'scheduled'
+            "#,
+        )
+        .bind(id.trim())
+        .bind(installation_id.get())
+        .bind(repository)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Activates a scheduled freeze whose start time has passed.
+    ///
+    /// If another freeze already holds the active slot of the same target, the
+    /// unique index rejects the activation. The scheduled freeze is then
+    /// cancelled with an end reason, so the worker does not retry it forever.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `id` - String UUID of the scheduled freeze
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the freeze became active, `false` if it was cancelled
+    /// or is no longer scheduled.
+    pub async fn activate_scheduled(pool: &SqlitePool, id: &str) -> Result<bool> {
+        let activated = sqlx::query(
+            r#"
+            UPDATE freeze_records
+            SET status = 'active'
+            WHERE id = $1
+            AND status = 'scheduled'
+            "#,
+        )
+        .bind(id)
+        .execute(pool)
+        .await;
+
+        match activated {
+            Ok(result) => Ok(result.rows_affected() > 0),
+            Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+                sqlx::query(
+                    r#"
+                    UPDATE freeze_records
+                    SET status = 'cancelled', ended_by = 'frezze', end_reason = $2
+                    WHERE id = $1
+                    AND status = 'scheduled'
+                    "#,
+                )
+                .bind(id)
+                .bind("Another freeze was already active when the scheduled freeze started")
+                .execute(pool)
+                .await?;
+                Ok(false)
+            }
+            Err(e) => Err(e.into()),
+        }
     }
 
     /// Updates the status of a freeze record.
@@ -219,6 +521,7 @@ impl FreezeRecord {
     /// * `id` - String UUID of the freeze record to update
     /// * `status` - New status to set
     /// * `ended_by` - Optional username of who ended the freeze (used when status is Ended)
+    /// * `end_reason` - Optional justification for ending the freeze
     ///
     /// # Returns
     ///
@@ -235,7 +538,8 @@ impl FreezeRecord {
     ///     pool,
     ///     freeze_id,
     ///     FreezeStatus::Ended,
-    ///     Some("admin".to_string())
+    ///     Some("admin".to_string()),
+    ///     Some("Release shipped".to_string())
     /// ).await?;
     /// # Ok(())
     /// # }
@@ -245,12 +549,14 @@ impl FreezeRecord {
         id: String,
         status: FreezeStatus,
         ended_by: Option<String>,
+        end_reason: Option<String>,
     ) -> Result<Option<FreezeRecord>> {
         let status_str = match status {
             FreezeStatus::Scheduled => "scheduled",
             FreezeStatus::Active => "active",
             FreezeStatus::Expired => "expired",
             FreezeStatus::Ended => "ended",
+            FreezeStatus::Cancelled => "cancelled",
         };
 
         let ended_at = if matches!(status, FreezeStatus::Ended) {
@@ -262,12 +568,13 @@ impl FreezeRecord {
         let result = sqlx::query!(
             r#"
             UPDATE freeze_records 
-            SET status = $1, ended_at = $2, ended_by = $3
-            WHERE id = $4
+            SET status = $1, ended_at = $2, ended_by = $3, end_reason = $4
+            WHERE id = $5
             "#,
             status_str,
             ended_at,
             ended_by,
+            end_reason,
             id
         )
         .execute(pool)
@@ -292,6 +599,11 @@ impl FreezeRecord {
                     ended_by: row.ended_by,
                     status: FreezeStatus::from(row.status.as_str()),
                     branch: row.branch,
+                    end_reason: row.end_reason,
+                    environment: row.environment,
+                    soft: row.soft,
+                    category: row.category.and_then(|c| c.parse().ok()),
+                    deleted_at: row.deleted_at.map(|dt| dt.and_utc()),
                     created_at: parse_datetime(&row.created_at)?,
                 })),
                 None => Ok(None),
@@ -301,6 +613,68 @@ impl FreezeRecord {
         }
     }
 
+    /// Reassigns ownership of a freeze record to another user.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `id` - String UUID of the freeze record to update
+    /// * `initiated_by` - GitHub username of the new owner
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if a record was updated, `false` if no record exists with the given ID.
+    pub async fn update_initiated_by(
+        pool: &SqlitePool,
+        id: &str,
+        initiated_by: &str,
+    ) -> Result<bool> {
+        let result = sqlx::query("UPDATE freeze_records SET initiated_by = $1 WHERE id = $2")
+            .bind(initiated_by)
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Updates the end time and reason of an active freeze record.
+    ///
+    /// Used when a new freeze is merged into an overlapping one instead of
+    /// creating a second record.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `id` - String UUID of the freeze record to update
+    /// * `expires_at` - New expiration time, or `None` for an indefinite freeze
+    /// * `reason` - New reason for the freeze
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if an active record was updated.
+    pub async fn update_window(
+        pool: &SqlitePool,
+        id: &str,
+        expires_at: Option<DateTime<Utc>>,
+        reason: Option<&str>,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE freeze_records
+            SET expires_at = $1, reason = $2
+            WHERE id = $3 AND status = 'active'
+            "#,
+        )
+        .bind(expires_at)
+        .bind(reason)
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     /// Retrieves freeze records that should currently be active.
     ///
     /// Returns all freeze records with 'active' status where:
@@ -346,6 +720,11 @@ impl FreezeRecord {
                 ended_by: row.ended_by,
                 status: FreezeStatus::from(row.status.as_str()),
                 branch: row.branch,
+                end_reason: row.end_reason,
+                environment: row.environment,
+                soft: row.soft,
+                category: row.category.and_then(|c| c.parse().ok()),
+                deleted_at: row.deleted_at.map(|dt| dt.and_utc()),
                 created_at: parse_datetime(&row.created_at).unwrap_or_else(|_| Utc::now()),
             });
         }
@@ -353,58 +732,294 @@ impl FreezeRecord {
         Ok(records)
     }
 
+    /// Retrieves scheduled freeze records whose start time has come.
+    ///
+    /// Used by the background worker to activate scheduled freezes. Freezes
+    /// that were cancelled before starting are never returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `now` - Current time
+    ///
+    /// # Returns
+    ///
+    /// Returns the scheduled freeze records to activate, ordered by start time.
+    pub async fn get_scheduled_freezes_to_activate(
+        pool: &SqlitePool,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<FreezeRecord>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM freeze_records
+            WHERE status = 'scheduled'
+            AND started_at <= $1
+            ORDER BY started_at ASC
+            "#,
+        )
+        .bind(now)
+        .fetch_all(pool)
+        .await?;
+
+        rows.iter().map(record_from_row).collect()
+    }
+
+    /// Retrieves active freeze records whose expiration time has passed.
+    ///
+    /// These records are no longer enforced and should be transitioned to
+    /// `FreezeStatus::Expired` by the background worker.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `now` - Current time
+    ///
+    /// # Returns
+    ///
+    /// Returns the elapsed freeze records, ordered by expiration time.
+    pub async fn get_expired_freezes(
+        pool: &SqlitePool,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<FreezeRecord>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM freeze_records
+            WHERE status = 'active'
+            AND expires_at IS NOT NULL
+            AND expires_at <= $1
+            ORDER BY expires_at ASC
+            "#,
+        )
+        .bind(now)
+        .fetch_all(pool)
+        .await?;
+
+        rows.iter().map(record_from_row).collect()
+    }
+
+    /// Lists active freezes that started more than `max_age` before `now`.
+    ///
+    /// These are treated as expired by [`FreezeRecord::is_frozen`] and
+    /// [`FreezeRecord::get_active_freeze`], and marked as expired by the
+    /// background worker.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `now` - Current time
+    /// * `max_age` - Age after which an active freeze is considered forgotten
+    ///
+    /// # Returns
+    ///
+    /// Returns the stale freeze records, oldest first.
+    pub async fn get_stale_freezes(
+        pool: &SqlitePool,
+        now: DateTime<Utc>,
+        max_age: chrono::Duration,
+    ) -> Result<Vec<FreezeRecord>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM freeze_records
+            WHERE status = 'active'
+            AND started_at < $1
+            ORDER BY started_at ASC
+            "#,
+        )
+        .bind(now - max_age)
+        .fetch_all(pool)
+        .await?;
+
+        rows.iter().map(record_from_row).collect()
+    }
+
+    /// Soft-deletes the finished freezes created before a cutoff.
+    ///
+    /// Soft-deleted records are left out of the history but still exported,
+    /// until [`FreezeRecord::purge_deleted`] removes them. Active and scheduled
+    /// freezes are never deleted.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `cutoff` - Freezes created before this time are soft-deleted
+    /// * `now` - Current time, recorded as the deletion time
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of records soft-deleted.
+    pub async fn soft_delete_before(
+        pool: &SqlitePool,
+        cutoff: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<u64> {
+        let deleted = sqlx::query(
+            r#"
+            UPDATE freeze_records
+            SET deleted_at = $1
+            WHERE deleted_at IS NULL
+            AND created_at < $2
+            AND status NOT IN ('active', 'scheduled')
+            "#,
+        )
+        .bind(now)
+        .bind(cutoff)
+        .execute(pool)
+        .await?
+        .rows_affected();
+
+        Ok(deleted)
+    }
+
+    /// Permanently deletes the records soft-deleted before a cutoff.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `cutoff` - Records soft-deleted before this time are removed
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of freeze records removed.
+    pub async fn purge_deleted(pool: &SqlitePool, cutoff: DateTime<Utc>) -> Result<u64> {
+        let mut tx = pool.begin().await?;
+
+        // Reminders only reference their freeze, see `prune_installation`
+        sqlx::query(
+            r#"
+            DELETE FROM freeze_reminders
+            WHERE freeze_id IN (SELECT id FROM freeze_records WHERE deleted_at < $1)
+            "#,
+        )
+        .bind(cutoff)
+        .execute(&mut *tx)
+        .await?;
+
+        let purged = sqlx::query("DELETE FROM freeze_records WHERE deleted_at < $1")
+            .bind(cutoff)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+        tx.commit().await?;
+
+        Ok(purged)
+    }
+
     /// Gets the active freeze record for a repository, if one exists.
     ///
+    /// An active organization-wide freeze of the repository owner takes
+    /// precedence over freezes of the repository itself.
+    ///
     /// # Arguments
     ///
     /// * `pool` - Database connection pool
     /// * `installation_id` - GitHub installation ID
     /// * `repository` - Repository name in "owner/repo" format
+    /// * `max_age` - Age after which active freezes are treated as expired, if any
     ///
     /// # Returns
     ///
     /// Returns the active freeze record if one exists, or None otherwise.
     pub async fn get_active_freeze(
         pool: &SqlitePool,
-        installation_id: i64,
+        installation_id: InstallationId,
         repository: &str,
+        max_age: Option<chrono::Duration>,
     ) -> Result<Option<FreezeRecord>> {
         let now = Utc::now();
-        let row = sqlx::query!(
+        let records = Self::list_in_effect(pool, installation_id, repository).await?;
+        Ok(records
+            .into_iter()
+            .find(|r| !exceeds_max_age(&r.id, &r.repository, r.started_at, now, max_age)))
+    }
+
+    /// Lists the freezes currently in effect for a repository.
+    ///
+    /// Includes active organization-wide freezes of the repository owner
+    /// (stored with an `owner/*` repository), which are returned first,
+    /// followed by the repository's own freezes, newest first. Environment
+    /// freezes do not block pull requests and are left out.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `installation_id` - GitHub installation ID
+    /// * `repository` - Repository name in "owner/repo" format
+    ///
+    /// # Returns
+    ///
+    /// Returns every active freeze record covering the repository right now.
+    pub async fn list_in_effect(
+        pool: &SqlitePool,
+        installation_id: InstallationId,
+        repository: &str,
+    ) -> Result<Vec<FreezeRecord>> {
+        let org = org_wildcard_of(repository);
+        let rows = sqlx::query(
             r#"
-            SELECT * FROM freeze_records 
-            WHERE installation_id = $1 
-            AND repository = $2 
+            SELECT * FROM freeze_records
+            WHERE installation_id = $1
+            AND (repository = $2 COLLATE NOCASE OR repository = $3 COLLATE NOCASE)
+            AND environment IS NULL
             AND status = 'active'
-            AND started_at <= $3 
-            AND (expires_at IS NULL OR expires_at > $3)
-            ORDER BY started_at DESC
-            LIMIT 1
+            AND started_at <= $4
+            AND (expires_at IS NULL OR expires_at > $4)
+            ORDER BY repository = $3 COLLATE NOCASE DESC, started_at DESC
             "#,
-            installation_id,
-            repository,
-            now
         )
-        .fetch_optional(pool)
+        .bind(installation_id.get())
+        .bind(repository)
+        .bind(&org)
+        .bind(Utc::now())
+        .fetch_all(pool)
         .await?;
 
-        match row {
-            Some(row) => Ok(Some(FreezeRecord {
-                id: row.id.unwrap_or_default(),
-                repository: row.repository,
-                installation_id: row.installation_id,
-                started_at: parse_datetime(&row.started_at).unwrap_or_else(|_| Utc::now()),
-                expires_at: parse_optional_datetime(row.expires_at).unwrap_or(None),
-                ended_at: parse_optional_datetime(row.ended_at).unwrap_or(None),
-                reason: row.reason,
-                initiated_by: row.initiated_by,
-                ended_by: row.ended_by,
-                status: FreezeStatus::from(row.status.as_str()),
-                branch: row.branch,
-                created_at: parse_datetime(&row.created_at).unwrap_or_else(|_| Utc::now()),
-            })),
-            None => Ok(None),
-        }
+        rows.iter().map(record_from_row).collect()
+    }
+
+    /// Lists the environment freezes currently in effect for a repository.
+    ///
+    /// Organization-wide environment freezes of the repository owner are
+    /// returned first, followed by the repository's own, newest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `installation_id` - GitHub installation ID
+    /// * `repository` - Repository name in "owner/repo" format
+    /// * `environment` - GitHub Environment name (e.g., "production"), matched case-insensitively
+    ///
+    /// # Returns
+    ///
+    /// Returns every active freeze blocking deployments to the environment right now.
+    pub async fn list_environment_in_effect(
+        pool: &SqlitePool,
+        installation_id: InstallationId,
+        repository: &str,
+        environment: &str,
+    ) -> Result<Vec<FreezeRecord>> {
+        let org = org_wildcard_of(repository);
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM freeze_records
+            WHERE installation_id = $1
+            AND (repository = $2 COLLATE NOCASE OR repository = $3 COLLATE NOCASE)
+            AND environment = $5 COLLATE NOCASE
+            AND status = 'active'
+            AND started_at <= $4
+            AND (expires_at IS NULL OR expires_at > $4)
+            ORDER BY repository = $3 COLLATE NOCASE DESC, started_at DESC
+            "#,
+        )
+        .bind(installation_id.get())
+        .bind(repository)
+        .bind(&org)
+        .bind(Utc::now())
+        .bind(environment)
+        .fetch_all(pool)
+        .await?;
+
+        rows.iter().map(record_from_row).collect()
     }
 
     /// Checks if a repository is currently frozen.
@@ -414,22 +1029,1028 @@ impl FreezeRecord {
     /// * `pool` - Database connection pool
     /// * `installation_id` - GitHub installation ID
     /// * `repository` - Repository name in "owner/repo" format
+    /// * `max_age` - Age after which active freezes are treated as expired, if any
     ///
     /// # Returns
     ///
-    /// Returns `true` if there are any active freeze records for the repository, `false` otherwise.
+    /// Returns `true` if there are any active freeze records for the repository
+    /// or an active organization-wide freeze of its owner, `false` otherwise.
+    /// Environment freezes and freezes older than `max_age` are not counted.
     pub async fn is_frozen(
         pool: &SqlitePool,
-        installation_id: i64,
+        installation_id: InstallationId,
         repository: &str,
+        max_age: Option<chrono::Duration>,
     ) -> Result<bool> {
-        let row = sqlx::query!(
-            "SELECT EXISTS(SELECT 1 FROM freeze_records WHERE installation_id = $1 AND repository = $2 AND status = 'active') as exists_active",
-            installation_id,
-            repository
+        let org = org_wildcard_of(repository);
+        let rows = sqlx::query(
+            r#"
+            SELECT id, repository, started_at FROM freeze_records
+            WHERE installation_id = $1
+            AND (repository = $2 COLLATE NOCASE OR repository = $3 COLLATE NOCASE)
+            AND environment IS NULL
+            AND status = 'active'
+            "#,
         )
-        .fetch_one(pool)
+        .bind(installation_id.get())
+        .bind(repository)
+        .bind(&org)
+        .fetch_all(pool)
         .await?;
-        Ok(row.exists_active != 0)
+
+        let now = Utc::now();
+        Ok(rows.iter().any(|row| {
+            !exceeds_max_age(
+                row.get("id"),
+                row.get("repository"),
+                row.get("started_at"),
+                now,
+                max_age,
+            )
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{
+        Database,
+        models::{CommandLog, CommandResult, FreezeCategory},
+    };
+
+    fn active_record(repository: &str, initiated_by: &str) -> FreezeRecord {
+        let now = Utc::now();
+        FreezeRecord::new(
+            repository.to_string(),
+            12345.into(),
+            now,
+            Some(now + chrono::Duration::hours(2)),
+            Some("maintenance".to_string()),
+            initiated_by.to_string(),
+            None,
+        )
+    }
+
+    fn scheduled_record(repository: &str, starts_in: chrono::Duration) -> FreezeRecord {
+        let start = Utc::now() + starts_in;
+        FreezeRecord::new_scheduled(
+            repository.to_string(),
+            12345.into(),
+            start,
+            Some(start + chrono::Duration::hours(2)),
+            Some("maintenance".to_string()),
+            "alice".to_string(),
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_list_scheduled() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let later = FreezeRecord::create(pool, &scheduled_record("owner/repo", chrono::Duration::days(2)))
+            .await
+            .unwrap();
+        let sooner = FreezeRecord::create(pool, &scheduled_record("owner/repo", chrono::Duration::days(1)))
+            .await
+            .unwrap();
+        FreezeRecord::create(pool, &scheduled_record("owner/other", chrono::Duration::days(1)))
+            .await
+            .unwrap();
+        FreezeRecord::create(pool, &active_record("owner/repo", "alice"))
+            .await
+            .unwrap();
+
+        let scheduled = FreezeRecord::list_scheduled(pool, 12345.into(), "Owner/Repo")
+            .await
+            .unwrap();
+        let ids: Vec<&str> = scheduled.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec![sooner.id.as_str(), later.id.as_str()]);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_scheduled() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let scheduled = FreezeRecord::create(pool, &scheduled_record("owner/repo", chrono::Duration::days(1)))
+            .await
+            .unwrap();
+        let active = FreezeRecord::create(pool, &active_record("owner/repo", "alice"))
+            .await
+            .unwrap();
+
+        // Other repositories and installations cannot cancel it
+        assert!(!FreezeRecord::cancel_scheduled(pool, 12345.into(), "owner/other", &scheduled.id)
+            .await
+            .unwrap());
+        assert!(!FreezeRecord::cancel_scheduled(pool, 67890.into(), "owner/repo", &scheduled.id)
+            .await
+            .unwrap());
+
+        // Active freezes are lifted with /unfreeze, not cancelled
+        assert!(!FreezeRecord::cancel_scheduled(pool, 12345.into(), "owner/repo", &active.id)
+            .await
+            .unwrap());
+
+        assert!(FreezeRecord::cancel_scheduled(pool, 12345.into(), "owner/repo", &scheduled.id)
+            .await
+            .unwrap());
+        assert!(FreezeRecord::list_scheduled(pool, 12345.into(), "owner/repo")
+            .await
+            .unwrap()
+            .is_empty());
+
+        // The record is kept as cancelled, and cannot be cancelled twice
+        let status: String = sqlx::query_scalar("SELECT status FROM freeze_records WHERE id = $1")
+            .bind(&scheduled.id)
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        assert!(matches!(
+            FreezeStatus::from(status.as_str()),
+            FreezeStatus::Cancelled
+        ));
+        assert!(!FreezeRecord::cancel_scheduled(pool, 12345.into(), "owner/repo", &scheduled.id)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_recent_reasons() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let reasons = [
+            Some("release 1.2"),
+            Some("incident"),
+            Some(" release 1.2 "),
+            None,
+            Some("  "),
+            Some("migration"),
+        ];
+        let start = Utc::now() - chrono::Duration::days(30);
+        for (i, reason) in reasons.into_iter().enumerate() {
+            let mut record = scheduled_record("owner/repo", chrono::Duration::days(i as i64 + 1));
+            record.reason = reason.map(str::to_string);
+            record.created_at = start + chrono::Duration::days(i as i64);
+            FreezeRecord::create(pool, &record).await.unwrap();
+        }
+        let mut other = scheduled_record("owner/other", chrono::Duration::days(1));
+        other.reason = Some("unrelated".to_string());
+        FreezeRecord::create(pool, &other).await.unwrap();
+
+        // Distinct reasons, most recently used first
+        let recent = FreezeRecord::recent_reasons(pool, 12345.into(), "Owner/Repo", 5)
+            .await
+            .unwrap();
+        assert_eq!(recent, vec!["migration", "release 1.2", "incident"]);
+
+        let recent = FreezeRecord::recent_reasons(pool, 12345.into(), "owner/repo", 2)
+            .await
+            .unwrap();
+        assert_eq!(recent, vec!["migration", "release 1.2"]);
+
+        let recent = FreezeRecord::recent_reasons(pool, 67890.into(), "owner/repo", 5)
+            .await
+            .unwrap();
+        assert!(recent.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_window() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let active = FreezeRecord::create(pool, &active_record("owner/repo", "alice"))
+            .await
+            .unwrap();
+        let expires_at = Utc::now() + chrono::Duration::hours(6);
+        assert!(FreezeRecord::update_window(pool, &active.id, Some(expires_at), Some("a; b"))
+            .await
+            .unwrap());
+
+        let updated = FreezeRecord::list_in_effect(pool, 12345.into(), "owner/repo")
+            .await
+            .unwrap();
+        assert_eq!(updated.len(), 1);
+        assert_eq!(updated[0].reason.as_deref(), Some("a; b"));
+        assert_eq!(
+            updated[0].expires_at.unwrap().timestamp(),
+            expires_at.timestamp()
+        );
+
+        // Only active freezes can be extended
+        let scheduled = FreezeRecord::create(pool, &scheduled_record("owner/repo", chrono::Duration::days(1)))
+            .await
+            .unwrap();
+        assert!(!FreezeRecord::update_window(pool, &scheduled.id, None, None)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_update_status_cancelled() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let scheduled = FreezeRecord::create(pool, &scheduled_record("owner/repo", chrono::Duration::hours(1)))
+            .await
+            .unwrap();
+        let cancelled = FreezeRecord::update_status(
+            pool,
+            scheduled.id.clone(),
+            FreezeStatus::Cancelled,
+            None,
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert!(matches!(cancelled.status, FreezeStatus::Cancelled));
+        assert_eq!(cancelled.status.to_string(), "cancelled");
+        assert!(cancelled.ended_at.is_none());
+
+        // Cancelled freezes are never activated by the scheduler
+        assert!(FreezeRecord::get_scheduled_freezes_to_activate(
+            pool,
+            Utc::now() + chrono::Duration::hours(2),
+        )
+        .await
+        .unwrap()
+        .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_schedule_is_not_activated() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let kept = FreezeRecord::create(pool, &scheduled_record("owner/repo", chrono::Duration::hours(1)))
+            .await
+            .unwrap();
+        let cancelled = FreezeRecord::create(pool, &scheduled_record("owner/other", chrono::Duration::hours(1)))
+            .await
+            .unwrap();
+        FreezeRecord::cancel_scheduled(pool, 12345.into(), "owner/other", &cancelled.id)
+            .await
+            .unwrap();
+
+        // Not due yet
+        assert!(FreezeRecord::get_scheduled_freezes_to_activate(pool, Utc::now())
+            .await
+            .unwrap()
+            .is_empty());
+
+        let due = FreezeRecord::get_scheduled_freezes_to_activate(
+            pool,
+            Utc::now() + chrono::Duration::hours(2),
+        )
+        .await
+        .unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].id, kept.id);
+    }
+
+    #[tokio::test]
+    async fn test_scheduled_freezes_count_as_overlapping() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        FreezeRecord::create(pool, &scheduled_record("owner/repo", chrono::Duration::hours(1)))
+            .await
+            .unwrap();
+
+        // An overlapping schedule and an open-ended freeze started now are rejected
+        assert!(
+            FreezeRecord::create(pool, &scheduled_record("owner/repo", chrono::Duration::hours(2)))
+                .await
+                .is_err()
+        );
+        let mut open_ended = active_record("owner/repo", "bob");
+        open_ended.expires_at = None;
+        assert!(FreezeRecord::create(pool, &open_ended).await.is_err());
+
+        // A freeze ending before the schedule starts is fine
+        let mut short = active_record("owner/repo", "bob");
+        short.expires_at = Some(short.started_at + chrono::Duration::minutes(30));
+        FreezeRecord::create(pool, &short).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_activate_scheduled_cancels_when_already_frozen() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let active = FreezeRecord::create(pool, &active_record("owner/repo", "alice"))
+            .await
+            .unwrap();
+        let scheduled = FreezeRecord::create(pool, &scheduled_record("owner/repo", chrono::Duration::days(1)))
+            .await
+            .unwrap();
+        // Freezes created before overlaps with schedules were rejected
+        sqlx::query("UPDATE freeze_records SET started_at = $1 WHERE id = $2")
+            .bind(Utc::now())
+            .bind(&scheduled.id)
+            .execute(pool)
+            .await
+            .unwrap();
+
+        assert!(!FreezeRecord::activate_scheduled(pool, &scheduled.id).await.unwrap());
+        let (status, end_reason): (String, Option<String>) =
+            sqlx::query_as("SELECT status, end_reason FROM freeze_records WHERE id = $1")
+                .bind(&scheduled.id)
+                .fetch_one(pool)
+                .await
+                .unwrap();
+        assert!(matches!(
+            FreezeStatus::from(status.as_str()),
+            FreezeStatus::Cancelled
+        ));
+        assert!(end_reason.is_some());
+
+        // Once cancelled, the scheduler no longer picks it up
+        assert!(FreezeRecord::get_scheduled_freezes_to_activate(pool, Utc::now())
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Without a conflicting freeze the schedule becomes active
+        FreezeRecord::update_status(pool, active.id, FreezeStatus::Ended, None, None)
+            .await
+            .unwrap();
+        let next = FreezeRecord::create(pool, &scheduled_record("owner/repo", chrono::Duration::days(1)))
+            .await
+            .unwrap();
+        assert!(FreezeRecord::activate_scheduled(pool, &next.id).await.unwrap());
+        let status: String = sqlx::query_scalar("SELECT status FROM freeze_records WHERE id = $1")
+            .bind(&next.id)
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        assert!(matches!(
+            FreezeStatus::from(status.as_str()),
+            FreezeStatus::Active
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_update_status_records_end_reason() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let record = FreezeRecord::create(pool, &active_record("owner/repo", "alice"))
+            .await
+            .unwrap();
+
+        let ended = FreezeRecord::update_status(
+            pool,
+            record.id.clone(),
+            FreezeStatus::Ended,
+            Some("bob".to_string()),
+            Some("Release shipped".to_string()),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert!(matches!(ended.status, FreezeStatus::Ended));
+        assert_eq!(ended.ended_by.as_deref(), Some("bob"));
+        assert_eq!(ended.end_reason.as_deref(), Some("Release shipped"));
+
+        let records = FreezeRecord::list(pool, Some(12345.into()), Some("owner/repo"), None)
+            .await
+            .unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].end_reason.as_deref(), Some("Release shipped"));
+    }
+
+    #[tokio::test]
+    async fn test_update_initiated_by() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let record = FreezeRecord::create(pool, &active_record("owner/repo", "alice"))
+            .await
+            .unwrap();
+
+        assert!(
+            FreezeRecord::update_initiated_by(pool, &record.id, "bob")
+                .await
+                .unwrap()
+        );
+
+        let active = FreezeRecord::get_active_freeze(pool, 12345.into(), "owner/repo", None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(active.initiated_by, "bob");
+
+        assert!(
+            !FreezeRecord::update_initiated_by(pool, "missing-id", "bob")
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_command_log_round_trip() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let log = CommandLog::new(
+            12345.into(),
+            "owner/repo".to_string(),
+            "alice".to_string(),
+            "freeze-reassign --to bob".to_string(),
+            42,
+            CommandResult::Success,
+            None,
+        );
+        CommandLog::create(pool, &log).await.unwrap();
+
+        let logs = CommandLog::list(pool, Some(12345.into()), Some("owner/repo"), 10)
+            .await
+            .unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].user_login, "alice");
+        assert_eq!(logs[0].comment_id, 42);
+        assert_eq!(logs[0].result, CommandResult::Success);
+
+        let other = CommandLog::list(pool, Some(12345.into()), Some("owner/other"), 10)
+            .await
+            .unwrap();
+        assert!(other.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_command_log_exists_for_comment() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let log = CommandLog::new(
+            12345.into(),
+            "owner/repo".to_string(),
+            "alice".to_string(),
+            "/freeze --duration 2h".to_string(),
+            42,
+            CommandResult::Success,
+            None,
+        );
+        CommandLog::create(pool, &log).await.unwrap();
+
+        // Same comment, same command: an edit that must not run again
+        assert!(
+            CommandLog::exists_for_comment(pool, 12345.into(), 42, "/freeze --duration 2h")
+                .await
+                .unwrap()
+        );
+        // Same comment, corrected command
+        assert!(
+            !CommandLog::exists_for_comment(pool, 12345.into(), 42, "/freeze --duration 3h")
+                .await
+                .unwrap()
+        );
+        // Same command from another comment
+        assert!(
+            !CommandLog::exists_for_comment(pool, 12345.into(), 43, "/freeze --duration 2h")
+                .await
+                .unwrap()
+        );
+
+        // Denied commands never ran, so an edit may run them
+        let denied = CommandLog::new(
+            12345.into(),
+            "owner/repo".to_string(),
+            "bob".to_string(),
+            "/unfreeze".to_string(),
+            44,
+            CommandResult::Denied,
+            None,
+        );
+        CommandLog::create(pool, &denied).await.unwrap();
+        assert!(
+            !CommandLog::exists_for_comment(pool, 12345.into(), 44, "/unfreeze")
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_command_log_update_result() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let log = CommandLog::new(
+            12345.into(),
+            "owner/repo".to_string(),
+            "alice".to_string(),
+            "/freeze-all".to_string(),
+            42,
+            CommandResult::Pending,
+            None,
+        );
+        CommandLog::create(pool, &log).await.unwrap();
+        // An earlier, denied run of the same comment keeps its result
+        let denied = CommandLog {
+            id: uuid::Uuid::new_v4().to_string(),
+            result: CommandResult::Denied,
+            ..log.clone()
+        };
+        CommandLog::create(pool, &denied).await.unwrap();
+
+        let partial = CommandResult::PartialSuccess {
+            succeeded: 3,
+            failed: 2,
+        };
+        let updated = CommandLog::update_result(
+            pool,
+            12345.into(),
+            42,
+            "/freeze-all",
+            &partial,
+            Some("Failed to freeze owner/other: archived"),
+        )
+        .await
+        .unwrap();
+        assert!(updated);
+
+        let logs = CommandLog::list(pool, Some(12345.into()), Some("owner/repo"), 10)
+            .await
+            .unwrap();
+        let updated = logs.iter().find(|l| l.id == log.id).unwrap();
+        assert_eq!(updated.result, partial);
+        assert_eq!(
+            updated.error_message.as_deref(),
+            Some("Failed to freeze owner/other: archived")
+        );
+        let untouched = logs.iter().find(|l| l.id == denied.id).unwrap();
+        assert_eq!(untouched.result, CommandResult::Denied);
+
+        // Commands that were never recorded are left alone
+        let updated = CommandLog::update_result(
+            pool,
+            12345.into(),
+            43,
+            "/freeze-all",
+            &CommandResult::Failure,
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(!updated);
+    }
+
+    #[test]
+    fn test_command_result_round_trip() {
+        for result in [
+            CommandResult::Pending,
+            CommandResult::Success,
+            CommandResult::Failure,
+            CommandResult::Denied,
+            CommandResult::PartialSuccess {
+                succeeded: 3,
+                failed: 2,
+            },
+        ] {
+            assert_eq!(CommandResult::from(result.to_string().as_str()), result);
+        }
+        assert_eq!(
+            CommandResult::PartialSuccess {
+                succeeded: 3,
+                failed: 2
+            }
+            .to_string(),
+            "partial (3/5)"
+        );
+    }
+
+    #[test]
+    fn test_freeze_category_parsing() {
+        for category in FreezeCategory::ALL {
+            assert_eq!(category.to_string().parse::<FreezeCategory>(), Ok(category));
+        }
+        assert_eq!(
+            " Incident ".parse::<FreezeCategory>(),
+            Ok(FreezeCategory::Incident)
+        );
+
+        // Unknown categories list the valid ones
+        let error = "hotfix".parse::<FreezeCategory>().unwrap_err();
+        assert!(error.contains("`hotfix`"));
+        assert!(error.contains("deploy, incident, maintenance, compliance"));
+    }
+
+    #[tokio::test]
+    async fn test_category_is_stored() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let categorized =
+            active_record("owner/repo", "alice").with_category(Some(FreezeCategory::Incident));
+        FreezeRecord::create(pool, &categorized).await.unwrap();
+        FreezeRecord::create(pool, &active_record("owner/other", "alice"))
+            .await
+            .unwrap();
+
+        let records = FreezeRecord::list(pool, Some(12345.into()), None, None)
+            .await
+            .unwrap();
+        let stored = records.iter().find(|r| r.id == categorized.id).unwrap();
+        assert_eq!(stored.category, Some(FreezeCategory::Incident));
+        let uncategorized = records.iter().find(|r| r.id != categorized.id).unwrap();
+        assert!(uncategorized.category.is_none());
+
+        let active = FreezeRecord::get_active_freezes(pool).await.unwrap();
+        let stored = active.iter().find(|r| r.id == categorized.id).unwrap();
+        assert_eq!(stored.category, Some(FreezeCategory::Incident));
+    }
+
+    #[tokio::test]
+    async fn test_soft_deleted_freezes_are_not_listed() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let ended = FreezeRecord::create(pool, &active_record("owner/repo", "alice"))
+            .await
+            .unwrap();
+        FreezeRecord::update_status(pool, ended.id.clone(), FreezeStatus::Ended, None, None)
+            .await
+            .unwrap();
+        let active = FreezeRecord::create(pool, &active_record("owner/other", "alice"))
+            .await
+            .unwrap();
+
+        // Only finished freezes are soft-deleted
+        let now = Utc::now();
+        let cutoff = now + chrono::Duration::hours(1);
+        let deleted = FreezeRecord::soft_delete_before(pool, cutoff, now)
+            .await
+            .unwrap();
+        assert_eq!(deleted, 1);
+
+        let records = FreezeRecord::list(pool, Some(12345.into()), None, None)
+            .await
+            .unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].id, active.id);
+        assert!(
+            FreezeRecord::recent_reasons(pool, 12345.into(), "owner/repo", 5)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        // Exports keep them until they are purged
+        let exported = FreezeRecord::list_for_export(pool).await.unwrap();
+        let deleted = exported.iter().find(|r| r.id == ended.id).unwrap();
+        assert!(deleted.deleted_at.is_some());
+
+        let purged = FreezeRecord::purge_deleted(pool, now - chrono::Duration::hours(1))
+            .await
+            .unwrap();
+        assert_eq!(purged, 0);
+        let purged = FreezeRecord::purge_deleted(pool, cutoff).await.unwrap();
+        assert_eq!(purged, 1);
+        assert_eq!(FreezeRecord::list_for_export(pool).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_expired_freezes() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let now = Utc::now();
+        let elapsed = FreezeRecord::new(
+            "owner/elapsed".to_string(),
+            12345.into(),
+            now - chrono::Duration::hours(3),
+            Some(now - chrono::Duration::hours(1)),
+            None,
+            "alice".to_string(),
+            None,
+        );
+        let elapsed = FreezeRecord::create(pool, &elapsed).await.unwrap();
+        FreezeRecord::create(pool, &active_record("owner/running", "alice"))
+            .await
+            .unwrap();
+
+        let expired = FreezeRecord::get_expired_freezes(pool, Utc::now()).await.unwrap();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].id, elapsed.id);
+
+        FreezeRecord::update_status(pool, elapsed.id, FreezeStatus::Expired, None, None)
+            .await
+            .unwrap();
+        assert!(FreezeRecord::get_expired_freezes(pool, Utc::now()).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_freezes_older_than_max_age_are_ignored() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let now = Utc::now();
+        let forgotten = FreezeRecord::new(
+            "owner/repo".to_string(),
+            12345.into(),
+            now - chrono::Duration::days(30),
+            None,
+            Some("release".to_string()),
+            "alice".to_string(),
+            None,
+        );
+        let forgotten = FreezeRecord::create(pool, &forgotten).await.unwrap();
+        FreezeRecord::create(pool, &active_record("owner/other", "bob"))
+            .await
+            .unwrap();
+
+        // Without a maximum age the forgotten freeze still blocks the repository
+        assert!(
+            FreezeRecord::is_frozen(pool, 12345.into(), "owner/repo", None)
+                .await
+                .unwrap()
+        );
+
+        let max_age = Some(chrono::Duration::days(7));
+        assert!(
+            !FreezeRecord::is_frozen(pool, 12345.into(), "owner/repo", max_age)
+                .await
+                .unwrap()
+        );
+        assert!(
+            FreezeRecord::get_active_freeze(pool, 12345.into(), "owner/repo", max_age)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        // Recent freezes are unaffected
+        assert!(
+            FreezeRecord::is_frozen(pool, 12345.into(), "owner/other", max_age)
+                .await
+                .unwrap()
+        );
+
+        let stale = FreezeRecord::get_stale_freezes(pool, now, chrono::Duration::days(7))
+            .await
+            .unwrap();
+        let ids: Vec<_> = stale.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec![forgotten.id.as_str()]);
+
+        FreezeRecord::update_status(pool, forgotten.id, FreezeStatus::Expired, None, None)
+            .await
+            .unwrap();
+        assert!(
+            FreezeRecord::get_stale_freezes(pool, now, chrono::Duration::days(7))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_create_allows_single_active_freeze() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let first = active_record("owner/repo", "alice");
+        let second = active_record("owner/repo", "bob");
+
+        let (a, b) = tokio::join!(
+            FreezeRecord::create(pool, &first),
+            FreezeRecord::create(pool, &second)
+        );
+        assert!(a.is_ok() ^ b.is_ok(), "exactly one create must succeed");
+
+        let err = a.err().or(b.err()).unwrap();
+        assert!(err.to_string().contains("already exists"));
+
+        let active = FreezeRecord::list(pool, Some(12345.into()), Some("owner/repo"), Some(true))
+            .await
+            .unwrap();
+        assert_eq!(active.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_create_replaces_elapsed_active_freeze() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let now = Utc::now();
+        let elapsed = FreezeRecord::new(
+            "owner/repo".to_string(),
+            12345.into(),
+            now - chrono::Duration::hours(3),
+            Some(now - chrono::Duration::hours(1)),
+            None,
+            "alice".to_string(),
+            None,
+        );
+        FreezeRecord::create(pool, &elapsed).await.unwrap();
+
+        FreezeRecord::create(pool, &active_record("owner/repo", "bob"))
+            .await
+            .unwrap();
+
+        let active = FreezeRecord::list(pool, Some(12345.into()), Some("owner/repo"), Some(true))
+            .await
+            .unwrap();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].initiated_by, "bob");
+    }
+
+    #[tokio::test]
+    async fn test_branch_freezes_can_coexist() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let on_branch = |branch: &str| {
+            let mut record = active_record("owner/repo", "alice");
+            record.branch = Some(branch.to_string());
+            record
+        };
+
+        FreezeRecord::create(pool, &on_branch("main")).await.unwrap();
+        FreezeRecord::create(pool, &on_branch("release")).await.unwrap();
+
+        // Same branch and repository-wide freezes still conflict
+        assert!(FreezeRecord::create(pool, &on_branch("main")).await.is_err());
+        assert!(
+            FreezeRecord::create(pool, &active_record("owner/repo", "bob"))
+                .await
+                .is_err()
+        );
+
+        let active = FreezeRecord::list(pool, Some(12345.into()), Some("owner/repo"), Some(true))
+            .await
+            .unwrap();
+        assert_eq!(active.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_overlap_rejections_are_counted() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        FreezeRecord::create(pool, &active_record("owner/repo", "alice"))
+            .await
+            .unwrap();
+        let before = FreezeRecord::overlap_rejections();
+
+        let err = FreezeRecord::create(pool, &active_record("owner/repo", "bob"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+
+        // Other tests may reject overlaps concurrently
+        assert!(FreezeRecord::overlap_rejections() > before);
+    }
+
+    #[tokio::test]
+    async fn test_repository_lookups_ignore_case() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        FreezeRecord::create(pool, &active_record("Owner/Repo", "alice"))
+            .await
+            .unwrap();
+
+        // Lookups with a different spelling find the freeze
+        assert!(FreezeRecord::is_frozen(pool, 12345.into(), "owner/repo", None).await.unwrap());
+        assert!(
+            FreezeRecord::get_active_freeze(pool, 12345.into(), "OWNER/REPO", None)
+                .await
+                .unwrap()
+                .is_some()
+        );
+        let active = FreezeRecord::list(pool, Some(12345.into()), Some("owner/repo"), Some(true))
+            .await
+            .unwrap();
+        assert_eq!(active.len(), 1);
+
+        // A second freeze with a different spelling conflicts with the first
+        assert!(
+            FreezeRecord::create(pool, &active_record("owner/repo", "bob"))
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_org_freeze_covers_owner_repositories() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let org = FreezeRecord::create(pool, &active_record("owner/*", "alice"))
+            .await
+            .unwrap();
+
+        // Every repository of the owner is frozen, regardless of case
+        assert!(FreezeRecord::is_frozen(pool, 12345.into(), "owner/repo", None).await.unwrap());
+        assert!(FreezeRecord::is_frozen(pool, 12345.into(), "Owner/Other", None).await.unwrap());
+        let active = FreezeRecord::get_active_freeze(pool, 12345.into(), "owner/repo", None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(active.id, org.id);
+
+        // Other owners and installations are unaffected
+        assert!(!FreezeRecord::is_frozen(pool, 12345.into(), "someone/repo", None).await.unwrap());
+        assert!(!FreezeRecord::is_frozen(pool, 99999.into(), "owner/repo", None).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_org_freeze_takes_precedence_over_repo_freeze() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let repo = FreezeRecord::create(pool, &active_record("owner/repo", "bob"))
+            .await
+            .unwrap();
+        let org = FreezeRecord::create(pool, &active_record("owner/*", "alice"))
+            .await
+            .unwrap();
+
+        let in_effect = FreezeRecord::list_in_effect(pool, 12345.into(), "owner/repo")
+            .await
+            .unwrap();
+        let ids: Vec<_> = in_effect.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec![org.id.as_str(), repo.id.as_str()]);
+
+        let active = FreezeRecord::get_active_freeze(pool, 12345.into(), "owner/repo", None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(active.id, org.id);
+
+        // Once the organization freeze ends, the repository freeze applies again
+        FreezeRecord::update_status(pool, org.id, FreezeStatus::Ended, None, None)
+            .await
+            .unwrap();
+        let active = FreezeRecord::get_active_freeze(pool, 12345.into(), "owner/repo", None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(active.id, repo.id);
+    }
+
+    #[tokio::test]
+    async fn test_environment_freeze_does_not_block_pull_requests() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let env = FreezeRecord::create(
+            pool,
+            &active_record("owner/repo", "alice").with_environment(Some("production".to_string())),
+        )
+        .await
+        .unwrap();
+
+        // Pull requests are not affected by an environment freeze
+        assert!(!FreezeRecord::is_frozen(pool, 12345.into(), "owner/repo", None).await.unwrap());
+        assert!(
+            FreezeRecord::list_in_effect(pool, 12345.into(), "owner/repo")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        // Deployments to the environment are, case-insensitively
+        let blocking = FreezeRecord::list_environment_in_effect(pool, 12345.into(), "owner/repo", "Production")
+            .await
+            .unwrap();
+        assert_eq!(blocking.len(), 1);
+        assert_eq!(blocking[0].id, env.id);
+        assert_eq!(blocking[0].environment.as_deref(), Some("production"));
+        assert!(
+            FreezeRecord::list_environment_in_effect(pool, 12345.into(), "owner/repo", "staging")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        // A regular freeze can coexist with the environment freeze
+        let repo = FreezeRecord::create(pool, &active_record("owner/repo", "bob"))
+            .await
+            .unwrap();
+        assert!(FreezeRecord::is_frozen(pool, 12345.into(), "owner/repo", None).await.unwrap());
+        let blocking = FreezeRecord::list_environment_in_effect(pool, 12345.into(), "owner/repo", "production")
+            .await
+            .unwrap();
+        assert_eq!(blocking.len(), 1);
+        assert_ne!(blocking[0].id, repo.id);
+
+        // But not with a second freeze of the same environment
+        assert!(
+            FreezeRecord::create(
+                pool,
+                &active_record("owner/repo", "carol")
+                    .with_environment(Some("PRODUCTION".to_string())),
+            )
+            .await
+            .is_err()
+        );
     }
 }
@@ -0,0 +1,251 @@
+//! Database operations for freeze reminder tracking.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::{Row, SqlitePool};
+
+use crate::{
+    database::{
+        freeze::record_from_row,
+        models::{FreezeRecord, FreezeReminder},
+    },
+    installation::InstallationId,
+};
+
+impl FreezeReminder {
+    /// Starts tracking reminders for a freeze requested from an issue or PR.
+    ///
+    /// Tracking an already tracked freeze is a no-op.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `freeze_id` - ID of the freeze record
+    /// * `repository` - Repository of the issue or PR, in "owner/repo" format
+    /// * `issue_number` - Issue or PR number where the freeze was requested
+    pub async fn track(
+        pool: &SqlitePool,
+        freeze_id: &str,
+        repository: &str,
+        issue_number: u64,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO freeze_reminders
+            (freeze_id, repository, issue_number, last_reminded_at)
+            VALUES ($1, $2, $3, NULL)
+            "#,
+        )
+        .bind(freeze_id)
+        .bind(repository)
+        .bind(issue_number as i64)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Retrieves the active freezes that are tracked for reminders.
+    ///
+    /// # Returns
+    ///
+    /// Returns each active freeze record together with its reminder state,
+    /// ordered by start time (oldest first).
+    pub async fn list_active(pool: &SqlitePool) -> Result<Vec<(FreezeRecord, FreezeReminder)>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT f.*, r.repository AS thread_repository, r.issue_number, r.last_reminded_at
+            FROM freeze_records f
+            JOIN freeze_reminders r ON r.freeze_id = f.id
+            WHERE f.status = 'active'
+            ORDER BY f.started_at ASC
+            "#,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut tracked = Vec::new();
+        for row in rows {
+            let record = record_from_row(&row)?;
+            let reminder = FreezeReminder {
+                freeze_id: record.id.clone(),
+                repository: row.get("thread_repository"),
+                issue_number: row.get("issue_number"),
+                last_reminded_at: row.get("last_reminded_at"),
+            };
+            tracked.push((record, reminder));
+        }
+
+        Ok(tracked)
+    }
+
+    /// Finds the active freeze requested from an issue or PR.
+    ///
+    /// Lets commands replied in a reminder thread act on the freeze it is
+    /// about, even when that freeze is for other repositories than the thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `installation_id` - GitHub App installation ID
+    /// * `repository` - Repository of the issue or PR, in "owner/repo" format
+    /// * `issue_number` - Issue or PR number of the thread
+    ///
+    /// # Returns
+    ///
+    /// The most recently started active freeze tracked for the thread, if any.
+    pub async fn find_active_in_thread(
+        pool: &SqlitePool,
+        installation_id: InstallationId,
+        repository: &str,
+        issue_number: u64,
+    ) -> Result<Option<FreezeRecord>> {
+        let row = sqlx::query(
+            r#"
+            SELECT f.*
+            FROM freeze_records f
+            JOIN freeze_reminders r ON r.freeze_id = f.id
+            WHERE f.status = 'active'
+            AND f.installation_id = $1
+            AND r.issue_number = $2
+            AND COALESCE(r.repository, f.repository) = $3 COLLATE NOCASE
+            ORDER BY f.started_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(installation_id.get())
+        .bind(issue_number as i64)
+        .bind(repository)
+        .fetch_optional(pool)
+        .await?;
+
+        row.as_ref().map(record_from_row).transpose()
+    }
+
+    /// Records that the initiator of a freeze has just been reminded.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `freeze_id` - ID of the freeze record
+    /// * `reminded_at` - When the reminder was posted
+    pub async fn mark_reminded(
+        pool: &SqlitePool,
+        freeze_id: &str,
+        reminded_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query("UPDATE freeze_reminders SET last_reminded_at = $1 WHERE freeze_id = $2")
+            .bind(reminded_at)
+            .bind(freeze_id)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{Database, models::FreezeStatus};
+
+    #[tokio::test]
+    async fn test_track_and_mark_reminded() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let now = Utc::now();
+        let record = FreezeRecord::new(
+            "owner/repo".to_string(),
+            12345.into(),
+            now - chrono::Duration::hours(30),
+            None,
+            None,
+            "alice".to_string(),
+            None,
+        );
+        FreezeRecord::create(pool, &record).await.unwrap();
+
+        FreezeReminder::track(pool, &record.id, "owner/repo", 7)
+            .await
+            .unwrap();
+        // Tracking twice keeps the original entry
+        FreezeReminder::track(pool, &record.id, "owner/repo", 8)
+            .await
+            .unwrap();
+
+        let tracked = FreezeReminder::list_active(pool).await.unwrap();
+        assert_eq!(tracked.len(), 1);
+        assert_eq!(tracked[0].0.id, record.id);
+        assert_eq!(tracked[0].1.issue_number, 7);
+        assert_eq!(tracked[0].1.thread_repository(&tracked[0].0), "owner/repo");
+        assert!(tracked[0].1.last_reminded_at.is_none());
+
+        FreezeReminder::mark_reminded(pool, &record.id, now).await.unwrap();
+        let tracked = FreezeReminder::list_active(pool).await.unwrap();
+        assert_eq!(tracked[0].1.last_reminded_at, Some(now));
+    }
+
+    #[tokio::test]
+    async fn test_find_active_in_thread() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let now = Utc::now();
+        let freeze = |repository: &str| {
+            FreezeRecord::new(
+                repository.to_string(),
+                12345.into(),
+                now - chrono::Duration::hours(30),
+                None,
+                None,
+                "alice".to_string(),
+                None,
+            )
+        };
+
+        // An organization-wide freeze requested from an issue of owner/repo
+        let org = freeze("owner/*");
+        FreezeRecord::create(pool, &org).await.unwrap();
+        FreezeReminder::track(pool, &org.id, "owner/repo", 7)
+            .await
+            .unwrap();
+
+        let found = FreezeReminder::find_active_in_thread(pool, 12345.into(), "Owner/Repo", 7)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.id, org.id);
+        assert_eq!(found.repository, "owner/*");
+
+        // Reminders are posted on owner/repo, not on the frozen "owner/*"
+        let tracked = FreezeReminder::list_active(pool).await.unwrap();
+        assert_eq!(tracked[0].1.thread_repository(&tracked[0].0), "owner/repo");
+
+        // Other threads and installations do not resolve to the freeze
+        for (installation_id, repository, issue_number) in [
+            (12345, "owner/repo", 8),
+            (12345, "owner/other", 7),
+            (67890, "owner/repo", 7),
+        ] {
+            let found = FreezeReminder::find_active_in_thread(
+                pool,
+                installation_id.into(),
+                repository,
+                issue_number,
+            )
+            .await
+            .unwrap();
+            assert!(found.is_none());
+        }
+
+        // Ended freezes are no longer resolved
+        FreezeRecord::update_status(pool, org.id.clone(), FreezeStatus::Ended, None, None)
+            .await
+            .unwrap();
+        let found = FreezeReminder::find_active_in_thread(pool, 12345.into(), "owner/repo", 7)
+            .await
+            .unwrap();
+        assert!(found.is_none());
+    }
+}
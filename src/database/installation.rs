@@ -0,0 +1,227 @@
+//! Database cleanup for GitHub App installations that were removed.
+//!
+//! Once an installation is deleted Frezze can no longer act on its
+//! repositories, so its freezes are ended right away. The rest of its rows are
+//! kept as history unless pruning is enabled, in which case everything stored
+//! for the installation is deleted.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+use crate::installation::InstallationId;
+
+/// Reason recorded on the freezes of a deleted installation
+pub const INSTALLATION_DELETED_REASON: &str = "installation deleted";
+
+/// Tables holding rows of an installation, in deletion order
+const INSTALLATION_TABLES: &[&str] = &[
+    "freeze_records",
+    "permission_records",
+    "command_logs",
+    "unlocked_prs",
+    "pending_confirmations",
+    "pending_unfreezes",
+    "repository_refresh_stats",
+    "status_comments",
+];
+
+/// Freezes of a deleted installation that were stopped
+#[derive(Debug, Default, PartialEq)]
+pub struct EndedFreezes {
+    /// Active freezes marked as ended
+    pub ended: u64,
+    /// Scheduled freezes cancelled before they could start
+    pub cancelled: u64,
+}
+
+/// Ends the active freezes of a deleted installation and cancels its scheduled ones.
+///
+/// # Arguments
+///
+/// * `pool` - Database connection pool
+/// * `installation_id` - GitHub App installation ID
+/// * `ended_by` - GitHub username who deleted the installation, if known
+/// * `now` - When the installation was deleted
+pub async fn end_installation_freezes(
+    pool: &SqlitePool,
+    installation_id: InstallationId,
+    ended_by: Option<&str>,
+    now: DateTime<Utc>,
+) -> Result<EndedFreezes> {
+    let ended = sqlx::query(
+        r#"
+        UPDATE freeze_records
+        SET status = 'ended', ended_at = $1, ended_by = $2, end_reason = $3
+        WHERE installation_id = $4 AND status = 'active'
+        "#,
+    )
+    .bind(now)
+    .bind(ended_by)
+    .bind(INSTALLATION_DELETED_REASON)
+    .bind(installation_id.get())
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    let cancelled = sqlx::query(
+        r#"
+        UPDATE freeze_records
+        SET status = 'cancelled', end_reason = $1
+        WHERE installation_id = $2 AND status = 'scheduled'
+        "#,
+    )
+    .bind(INSTALLATION_DELETED_REASON)
+    .bind(installation_id.get())
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    Ok(EndedFreezes { ended, cancelled })
+}
+
+/// Deletes every row stored for an installation.
+///
+/// # Arguments
+///
+/// * `pool` - Database connection pool
+/// * `installation_id` - GitHub App installation ID
+///
+/// # Returns
+///
+/// The number of rows deleted.
+pub async fn prune_installation(pool: &SqlitePool, installation_id: InstallationId) -> Result<u64> {
+    let mut tx = pool.begin().await?;
+
+    // Reminders only reference their freeze, and SQLite does not cascade
+    // deletes unless foreign keys are enabled on the connection
+    let mut deleted = sqlx::query(
+        r#"
+        DELETE FROM freeze_reminders
+        WHERE freeze_id IN (SELECT id FROM freeze_records WHERE installation_id = $1)
+        "#,
+    )
+    .bind(installation_id.get())
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    for table in INSTALLATION_TABLES {
+        deleted += sqlx::query(&format!("DELETE FROM {} WHERE installation_id = $1", table))
+            .bind(installation_id.get())
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+    }
+
+    tx.commit().await?;
+
+    Ok(deleted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{
+        Database,
+        models::{FreezeRecord, FreezeReminder, FreezeStatus, UnlockedPr},
+    };
+
+    fn freeze(installation_id: i64, repository: &str, started_at: DateTime<Utc>) -> FreezeRecord {
+        FreezeRecord::new(
+            repository.to_string(),
+            installation_id.into(),
+            started_at,
+            None,
+            Some("release".to_string()),
+            "alice".to_string(),
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_end_installation_freezes() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let now = Utc::now();
+        let active = freeze(12345, "owner/repo", now - chrono::Duration::hours(1));
+        let scheduled = FreezeRecord::new_scheduled(
+            "owner/other".to_string(),
+            12345.into(),
+            now + chrono::Duration::hours(1),
+            None,
+            None,
+            "alice".to_string(),
+            None,
+        );
+        let other_installation = freeze(67890, "owner/repo", now - chrono::Duration::hours(1));
+        for record in [&active, &scheduled, &other_installation] {
+            FreezeRecord::create(pool, record).await.unwrap();
+        }
+
+        let stopped = end_installation_freezes(pool, 12345.into(), Some("alice"), now)
+            .await
+            .unwrap();
+        assert_eq!(
+            stopped,
+            EndedFreezes {
+                ended: 1,
+                cancelled: 1
+            }
+        );
+
+        let records = FreezeRecord::list(pool, Some(12345.into()), None, None)
+            .await
+            .unwrap();
+        let ended = records.iter().find(|r| r.id == active.id).unwrap();
+        assert!(matches!(ended.status, FreezeStatus::Ended));
+        assert_eq!(ended.ended_by.as_deref(), Some("alice"));
+        assert_eq!(
+            ended.end_reason.as_deref(),
+            Some(INSTALLATION_DELETED_REASON)
+        );
+        let cancelled = records.iter().find(|r| r.id == scheduled.id).unwrap();
+        assert!(matches!(cancelled.status, FreezeStatus::Cancelled));
+
+        // Other installations keep their freezes
+        assert!(
+            FreezeRecord::is_frozen(pool, 67890.into(), "owner/repo", None)
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prune_installation() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let now = Utc::now();
+        let record = freeze(12345, "owner/repo", now - chrono::Duration::hours(1));
+        let kept = freeze(67890, "owner/repo", now - chrono::Duration::hours(1));
+        FreezeRecord::create(pool, &record).await.unwrap();
+        FreezeRecord::create(pool, &kept).await.unwrap();
+        FreezeReminder::track(pool, &record.id, "owner/repo", 7)
+            .await
+            .unwrap();
+        UnlockedPr::unlock_pr(pool, 12345.into(), "owner/repo", 42, "alice")
+            .await
+            .unwrap();
+
+        let deleted = prune_installation(pool, 12345.into()).await.unwrap();
+        assert_eq!(deleted, 3);
+
+        let records = FreezeRecord::list(pool, Some(12345.into()), None, None)
+            .await
+            .unwrap();
+        assert!(records.is_empty());
+        assert!(FreezeReminder::list_active(pool).await.unwrap().is_empty());
+
+        // Other installations are left alone
+        let records = FreezeRecord::list(pool, Some(67890.into()), None, None)
+            .await
+            .unwrap();
+        assert_eq!(records.len(), 1);
+    }
+}
@@ -7,6 +7,14 @@
 //! # Modules
 //!
 //! - [`freeze`] - CRUD operations for freeze records, permissions, and command logs
+//! - [`command_log`] - Audit trail of executed commands
+//! - [`dump`] - Portable JSON export and import of the database
+//! - [`freeze_reminder`] - Reminder tracking for long-running freezes
+//! - [`installation`] - Cleanup after an installation is deleted
+//! - [`pending_confirmation`] - Commands waiting for confirmation
+//! - [`pending_unfreeze`] - Unfreezes waiting for a required approver
+//! - [`refresh_stats`] - Per-repository PR refresh statistics
+//! - [`status_comment`] - Pinned freeze status comments of issues
 //! - [`models`] - Data structures representing database entities
 //!
 //! # Example
@@ -34,8 +42,16 @@ use sqlx::{migrate::Migrator, pool::PoolOptions};
 use std::path::Path;
 use tracing::info;
 
+pub mod command_log;
+pub mod dump;
 pub mod freeze;
+pub mod freeze_reminder;
+pub mod installation;
 pub mod models;
+pub mod pending_confirmation;
+pub mod pending_unfreeze;
+pub mod refresh_stats;
+pub mod status_comment;
 pub mod unlocked_pr;
 
 /// Database connection manager for the Frezze application.
@@ -122,6 +138,21 @@ impl Database {
         }
     }
 
+    /// Creates a migrated in-memory Database instance for testing.
+    ///
+    /// Unlike `new_mock()`, this provides a real connection so database
+    /// operations can be exercised in unit tests.
+    #[cfg(test)]
+    pub async fn new_in_memory() -> Self {
+        Database::new("sqlite::memory:", "migrations", 1)
+            .connect()
+            .await
+            .expect("Failed to connect to in-memory database")
+            .migrate()
+            .await
+            .expect("Failed to migrate in-memory database")
+    }
+
     /// Establishes a connection to the sqlite database.
     ///
     /// Creates a connection pool with the configured maximum connections
@@ -184,7 +215,8 @@ impl Database {
     ///
     /// This method will return an error if:
     /// - Database connection is not established (call `connect()` first)
-    /// - Migration files are not found or invalid
+    /// - The migrations directory does not exist or contains no `.sql` files
+    /// - Migration files are invalid
     /// - SQL migration execution fails
     /// - Database permissions are insufficient
     ///
@@ -207,7 +239,10 @@ impl Database {
             None => return Err(anyhow::anyhow!("Database connection not established")),
         };
 
-        Migrator::new(Path::new(&self.migrations_path))
+        let migrations_path = Path::new(&self.migrations_path);
+        check_migrations_dir(migrations_path)?;
+
+        Migrator::new(migrations_path)
             .await?
             .run(&conn)
             .await?;
@@ -246,3 +281,56 @@ impl Database {
             .ok_or_else(|| anyhow::anyhow!("Database connection not established"))
     }
 }
+
+/// Verifies that the migrations directory exists and contains migration files.
+///
+/// Catches a misconfigured `MIGRATIONS_PATH` early with a readable error instead
+/// of the opaque failure reported by the migrator.
+fn check_migrations_dir(path: &Path) -> Result<(), anyhow::Error> {
+    if !path.is_dir() {
+        return Err(anyhow::anyhow!(
+            "Migrations directory not found at {}",
+            path.display()
+        ));
+    }
+
+    let has_migrations = std::fs::read_dir(path)?
+        .filter_map(|entry| entry.ok())
+        .any(|entry| entry.path().extension().is_some_and(|ext| ext == "sql"));
+
+    if !has_migrations {
+        return Err(anyhow::anyhow!(
+            "No migration files found in migrations directory {}",
+            path.display()
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_migrate_reports_missing_migrations_dir() {
+        let result = Database::new("sqlite::memory:", "does/not/exist", 1)
+            .connect()
+            .await
+            .unwrap()
+            .migrate()
+            .await;
+
+        let err = result.unwrap_err().to_string();
+        assert_eq!(err, "Migrations directory not found at does/not/exist");
+    }
+
+    #[test]
+    fn test_check_migrations_dir() {
+        assert!(check_migrations_dir(Path::new("migrations")).is_ok());
+
+        let empty = tempfile::tempdir().unwrap();
+        let err = check_migrations_dir(empty.path()).unwrap_err().to_string();
+        assert!(err.starts_with("No migration files found"));
+    }
+}
@@ -9,6 +9,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+use crate::installation::InstallationId;
+
 /// User role within a repository or organization.
 ///
 /// Defines the level of access and permissions a user has for freeze operations.
@@ -66,6 +68,8 @@ pub enum FreezeStatus {
     Expired,
     /// Freeze has been manually ended by a user
     Ended,
+    /// Scheduled freeze was cancelled before it started
+    Cancelled,
 }
 
 impl Display for FreezeStatus {
@@ -76,6 +80,7 @@ impl Display for FreezeStatus {
             FreezeStatus::Active => write!(f, "active"),
             FreezeStatus::Expired => write!(f, "expired"),
             FreezeStatus::Ended => write!(f, "ended"),
+            FreezeStatus::Cancelled => write!(f, "cancelled"),
         }
     }
 }
@@ -96,11 +101,78 @@ impl From<&str> for FreezeStatus {
             "active" => FreezeStatus::Active,
             "expired" => FreezeStatus::Expired,
             "ended" => FreezeStatus::Ended,
+            "cancelled" => FreezeStatus::Cancelled,
             _ => panic!("Unknown freeze status: {status}"),
         }
     }
 }
 
+/// Structured category of a freeze, given with `--category`.
+///
+/// Unlike the free-form reason, categories come from a fixed set so freezes
+/// can be grouped by why they happened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FreezeCategory {
+    /// Freeze around a deployment or release
+    Deploy,
+    /// Freeze while an incident is handled
+    Incident,
+    /// Freeze during planned maintenance
+    Maintenance,
+    /// Freeze required by an audit or compliance process
+    Compliance,
+}
+
+impl FreezeCategory {
+    /// Every category, in the order they are listed to users
+    pub const ALL: [FreezeCategory; 4] = [
+        FreezeCategory::Deploy,
+        FreezeCategory::Incident,
+        FreezeCategory::Maintenance,
+        FreezeCategory::Compliance,
+    ];
+
+    /// Comma-separated list of the valid categories, for error messages
+    pub fn valid_values() -> String {
+        Self::ALL
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl Display for FreezeCategory {
+    /// Formats the FreezeCategory for display as a lowercase string.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FreezeCategory::Deploy => write!(f, "deploy"),
+            FreezeCategory::Incident => write!(f, "incident"),
+            FreezeCategory::Maintenance => write!(f, "maintenance"),
+            FreezeCategory::Compliance => write!(f, "compliance"),
+        }
+    }
+}
+
+impl std::str::FromStr for FreezeCategory {
+    type Err = String;
+
+    /// Parses a category case-insensitively, listing the valid ones on error.
+    fn from_str(category: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|c| c.to_string().eq_ignore_ascii_case(category.trim()))
+            .ok_or_else(|| {
+                format!(
+                    "Unknown category `{}`. Valid categories are: {}",
+                    category,
+                    Self::valid_values()
+                )
+            })
+    }
+}
+
 /// Database record representing a repository freeze.
 ///
 /// Tracks all information about a freeze including timing, reason, and status.
@@ -131,6 +203,20 @@ pub struct FreezeRecord {
     /// Optional branch name to restrict freeze to specific branch (e.g., "main")
     /// NULL means the freeze applies to all branches
     pub branch: Option<String>,
+    /// Optional justification given when the freeze was ended
+    pub end_reason: Option<String>,
+    /// Optional GitHub Environment (e.g., "production") whose deployments are blocked
+    /// NULL means a regular freeze that blocks pull requests
+    pub environment: Option<String>,
+    /// Whether blocked PRs are marked as requiring action instead of failing
+    #[serde(default)]
+    pub soft: bool,
+    /// Optional structured category of the freeze (e.g., incident)
+    #[serde(default)]
+    pub category: Option<FreezeCategory>,
+    /// When the record was soft-deleted by the retention worker (if applicable)
+    #[serde(default)]
+    pub deleted_at: Option<DateTime<Utc>>,
     /// When this record was created in the database
     pub created_at: DateTime<Utc>,
 }
@@ -153,7 +239,7 @@ impl FreezeRecord {
     /// A new FreezeRecord with generated UUID, active status, and current timestamp.
     pub fn new(
         repository: String,
-        installation_id: u64,
+        installation_id: InstallationId,
         started_at: DateTime<Utc>,
         expires_at: Option<DateTime<Utc>>,
         reason: Option<String>,
@@ -163,7 +249,7 @@ impl FreezeRecord {
         FreezeRecord {
             id: uuid::Uuid::new_v4().to_string(),
             repository,
-            installation_id: installation_id as i64,
+            installation_id: installation_id.get(),
             started_at,
             expires_at,
             ended_at: None,
@@ -172,6 +258,11 @@ impl FreezeRecord {
             ended_by: None,
             status: FreezeStatus::Active, // default to active
             branch,
+            end_reason: None,
+            environment: None,
+            soft: false,
+            category: None,
+            deleted_at: None,
             created_at: Utc::now(),
         }
     }
@@ -195,7 +286,7 @@ impl FreezeRecord {
     /// A new FreezeRecord with generated UUID, scheduled status, and current timestamp.
     pub fn new_scheduled(
         repository: String,
-        installation_id: u64,
+        installation_id: InstallationId,
         started_at: DateTime<Utc>,
         expires_at: Option<DateTime<Utc>>,
         reason: Option<String>,
@@ -205,7 +296,7 @@ impl FreezeRecord {
         FreezeRecord {
             id: uuid::Uuid::new_v4().to_string(),
             repository,
-            installation_id: installation_id as i64,
+            installation_id: installation_id.get(),
             started_at,
             expires_at,
             ended_at: None,
@@ -214,9 +305,239 @@ impl FreezeRecord {
             ended_by: None,
             status: FreezeStatus::Scheduled,
             branch,
+            end_reason: None,
+            environment: None,
+            soft: false,
+            category: None,
+            deleted_at: None,
             created_at: Utc::now(),
         }
     }
+
+    /// Scopes the freeze to deployments of a GitHub Environment.
+    pub fn with_environment(mut self, environment: Option<String>) -> Self {
+        self.environment = environment;
+        self
+    }
+
+    /// Marks PRs blocked by the freeze as requiring action instead of failing.
+    pub fn with_soft(mut self, soft: bool) -> Self {
+        self.soft = soft;
+        self
+    }
+
+    /// Files the freeze under a structured category.
+    pub fn with_category(mut self, category: Option<FreezeCategory>) -> Self {
+        self.category = category;
+        self
+    }
+}
+
+/// Reminder tracking for an active freeze.
+///
+/// Stores the issue or PR a freeze was requested from so the initiator can be
+/// reminded there about long-running freezes, and when that last happened.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct FreezeReminder {
+    /// ID of the tracked freeze record
+    pub freeze_id: String,
+    /// Repository of the issue or PR, `None` if it is the frozen repository
+    pub repository: Option<String>,
+    /// Issue or PR number where the freeze command was issued
+    pub issue_number: i64,
+    /// When the initiator was last reminded (if ever)
+    pub last_reminded_at: Option<DateTime<Utc>>,
+}
+
+impl FreezeReminder {
+    /// Returns the repository of the issue or PR reminders are posted on.
+    pub fn thread_repository<'a>(&'a self, record: &'a FreezeRecord) -> &'a str {
+        self.repository.as_deref().unwrap_or(&record.repository)
+    }
+}
+
+/// Command waiting for its initiator to confirm it.
+///
+/// Destructive org-wide commands are only run once the user who requested
+/// them replies with the token of the pending confirmation before it expires.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PendingConfirmation {
+    /// Token the initiator has to reply with
+    pub token: String,
+    /// GitHub App installation ID the command was issued in
+    pub installation_id: i64,
+    /// Repository name in "owner/repo" format where the command was issued
+    pub repository: String,
+    /// GitHub username who issued the command
+    pub requested_by: String,
+    /// Command line to run once confirmed
+    pub command_line: String,
+    /// When the confirmation was requested
+    pub created_at: DateTime<Utc>,
+    /// When the token stops being accepted
+    pub expires_at: DateTime<Utc>,
+}
+
+impl PendingConfirmation {
+    /// Creates a new pending confirmation with a fresh token.
+    ///
+    /// # Arguments
+    ///
+    /// * `installation_id` - GitHub App installation ID
+    /// * `repository` - Repository name in "owner/repo" format
+    /// * `requested_by` - GitHub username who issued the command
+    /// * `command_line` - Command line to run once confirmed
+    /// * `ttl` - How long the token is accepted
+    pub fn new(
+        installation_id: InstallationId,
+        repository: String,
+        requested_by: String,
+        command_line: String,
+        ttl: chrono::Duration,
+    ) -> Self {
+        let now = Utc::now();
+        // Short enough to type, random enough to not be guessed within the TTL
+        let token = uuid::Uuid::new_v4().simple().to_string()[..8].to_string();
+        Self {
+            token,
+            installation_id: installation_id.get(),
+            repository,
+            requested_by,
+            command_line,
+            created_at: now,
+            expires_at: now + ttl,
+        }
+    }
+
+    /// Checks whether the token is no longer accepted at the given time.
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+}
+
+/// `/unfreeze` waiting for one of the installation's required approvers.
+///
+/// The unfreeze only runs once a user listed in `approvers` approves it with
+/// `/approve-unfreeze` before it expires.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingUnfreeze {
+    /// GitHub App installation ID the unfreeze was requested in
+    pub installation_id: i64,
+    /// Repository name in "owner/repo" format to unfreeze
+    pub repository: String,
+    /// GitHub username who requested the unfreeze
+    pub requested_by: String,
+    /// Command line to run once approved
+    pub command_line: String,
+    /// GitHub usernames allowed to approve the unfreeze
+    pub approvers: Vec<String>,
+    /// When the unfreeze was requested
+    pub created_at: DateTime<Utc>,
+    /// When the request stops being approvable
+    pub expires_at: DateTime<Utc>,
+}
+
+impl PendingUnfreeze {
+    /// Creates a new pending unfreeze.
+    ///
+    /// # Arguments
+    ///
+    /// * `installation_id` - GitHub App installation ID
+    /// * `repository` - Repository name in "owner/repo" format
+    /// * `requested_by` - GitHub username who requested the unfreeze
+    /// * `command_line` - Command line to run once approved
+    /// * `approvers` - GitHub usernames allowed to approve the unfreeze
+    /// * `ttl` - How long the request can be approved
+    pub fn new(
+        installation_id: InstallationId,
+        repository: String,
+        requested_by: String,
+        command_line: String,
+        approvers: Vec<String>,
+        ttl: chrono::Duration,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            installation_id: installation_id.get(),
+            repository,
+            requested_by,
+            command_line,
+            approvers,
+            created_at: now,
+            expires_at: now + ttl,
+        }
+    }
+
+    /// Checks whether a user is one of the required approvers.
+    pub fn is_approver(&self, login: &str) -> bool {
+        let login = login.trim_start_matches('@');
+        self.approvers
+            .iter()
+            .any(|approver| approver.trim_start_matches('@').eq_ignore_ascii_case(login))
+    }
+
+    /// Checks whether the request can no longer be approved at the given time.
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+}
+
+/// PR refresh statistics of a repository, used to debug stuck check runs.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RefreshStats {
+    /// GitHub App installation ID for this repository
+    pub installation_id: i64,
+    /// Repository name in "owner/repo" format
+    pub repository: String,
+    /// When the repository's PRs were last refreshed
+    pub last_refreshed_at: DateTime<Utc>,
+    /// Retries needed by the last refresh
+    pub last_retries: i64,
+    /// Retries needed by all refreshes so far
+    pub total_retries: i64,
+}
+
+/// Comment kept up to date with the freeze status of an issue.
+///
+/// `/status --pinned` edits this comment instead of posting a new one.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct StatusComment {
+    /// GitHub App installation ID for this repository
+    pub installation_id: i64,
+    /// Repository name in "owner/repo" format
+    pub repository: String,
+    /// Issue or PR number the comment was posted on
+    pub issue_number: i64,
+    /// ID of the status comment
+    pub comment_id: i64,
+    /// When the comment was last written
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Database record of a user's permissions in a repository.
+///
+/// Permissions are configured in the YAML file; these rows are only carried
+/// along when a database is exported or imported.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PermissionRecord {
+    /// Unique identifier for this permission record
+    pub id: String,
+    /// GitHub App installation ID for this repository
+    pub installation_id: i64,
+    /// Repository name in "owner/repo" format
+    pub repository: String,
+    /// GitHub username the permissions apply to
+    pub user_login: String,
+    /// Role of the user (e.g. "admin", "maintainer")
+    pub role: String,
+    /// Whether the user can freeze the repository
+    pub can_freeze: bool,
+    /// Whether the user can unfreeze the repository
+    pub can_unfreeze: bool,
+    /// Whether the user can bypass freeze safety guards
+    pub can_emergency_override: bool,
+    /// When this record was created in the database
+    pub created_at: DateTime<Utc>,
 }
 
 /// Database record representing an unlocked PR during a freeze.
@@ -235,3 +556,133 @@ pub struct UnlockedPr {
     /// When the PR was unlocked
     pub unlocked_at: DateTime<Utc>,
 }
+
+/// Outcome of a command recorded in the audit log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum CommandResult {
+    /// Command was recorded and has not finished running yet
+    Pending,
+    /// Command completed successfully
+    Success,
+    /// Command was attempted but failed
+    Failure,
+    /// Command was rejected by the permission system
+    Denied,
+    /// Command acting on several repositories succeeded for only some of them
+    PartialSuccess {
+        /// Repositories the command succeeded for
+        succeeded: usize,
+        /// Repositories the command failed for
+        failed: usize,
+    },
+}
+
+impl Display for CommandResult {
+    /// Formats the CommandResult for display as a lowercase string.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CommandResult::Pending => write!(f, "pending"),
+            CommandResult::Success => write!(f, "success"),
+            CommandResult::Failure => write!(f, "failure"),
+            CommandResult::Denied => write!(f, "denied"),
+            CommandResult::PartialSuccess { succeeded, failed } => {
+                write!(f, "partial ({}/{})", succeeded, succeeded + failed)
+            }
+        }
+    }
+}
+
+impl From<&str> for CommandResult {
+    /// Converts a string slice to a CommandResult.
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - String representation of the command result
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result string is not recognized.
+    fn from(result: &str) -> Self {
+        match result {
+            "pending" => CommandResult::Pending,
+            "success" => CommandResult::Success,
+            "failure" => CommandResult::Failure,
+            "denied" => CommandResult::Denied,
+            _ => result
+                .strip_prefix("partial (")
+                .and_then(|counts| counts.strip_suffix(')'))
+                .and_then(|counts| counts.split_once('/'))
+                .and_then(|(succeeded, total)| {
+                    let succeeded: usize = succeeded.parse().ok()?;
+                    let total: usize = total.parse().ok()?;
+                    Some(CommandResult::PartialSuccess {
+                        succeeded,
+                        failed: total.checked_sub(succeeded)?,
+                    })
+                })
+                .unwrap_or_else(|| panic!("Unknown command result: {result}")),
+        }
+    }
+}
+
+/// Database record representing an executed command, used as an audit trail.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CommandLog {
+    /// Unique identifier for this log entry
+    pub id: String,
+    /// GitHub App installation ID the command was executed in
+    pub installation_id: i64,
+    /// Repository name in "owner/repo" format
+    pub repository: String,
+    /// GitHub username who executed the command
+    pub user_login: String,
+    /// The command that was executed
+    pub command: String,
+    /// ID of the GitHub comment that triggered the command
+    pub comment_id: i64,
+    /// Outcome of the command
+    pub result: CommandResult,
+    /// Error details when the command did not succeed, or a note on its outcome
+    pub error_message: Option<String>,
+    /// When the command was executed
+    pub created_at: DateTime<Utc>,
+}
+
+impl CommandLog {
+    /// Creates a new CommandLog entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `installation_id` - GitHub App installation ID
+    /// * `repository` - Repository name in "owner/repo" format
+    /// * `user_login` - GitHub username who executed the command
+    /// * `command` - The command that was executed
+    /// * `comment_id` - ID of the comment that triggered the command
+    /// * `result` - Outcome of the command
+    /// * `error_message` - Optional error details
+    ///
+    /// # Returns
+    ///
+    /// A new CommandLog with generated UUID and current timestamp.
+    pub fn new(
+        installation_id: InstallationId,
+        repository: String,
+        user_login: String,
+        command: String,
+        comment_id: u64,
+        result: CommandResult,
+        error_message: Option<String>,
+    ) -> CommandLog {
+        CommandLog {
+            id: uuid::Uuid::new_v4().to_string(),
+            installation_id: installation_id.get(),
+            repository,
+            user_login,
+            command,
+            comment_id: comment_id as i64,
+            result,
+            error_message,
+            created_at: Utc::now(),
+        }
+    }
+}
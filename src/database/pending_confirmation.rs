@@ -0,0 +1,219 @@
+//! Database operations for commands waiting for confirmation.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::{Row, SqlitePool};
+
+use crate::{database::models::PendingConfirmation, installation::InstallationId};
+
+impl PendingConfirmation {
+    /// Stores a pending confirmation, dropping the ones that already expired.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `confirmation` - Pending confirmation to store
+    pub async fn create(pool: &SqlitePool, confirmation: &PendingConfirmation) -> Result<()> {
+        sqlx::query("DELETE FROM pending_confirmations WHERE expires_at <= $1")
+            .bind(confirmation.created_at)
+            .execute(pool)
+            .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO pending_confirmations
+            (token, installation_id, repository, requested_by, command_line, created_at, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+        )
+        .bind(&confirmation.token)
+        .bind(confirmation.installation_id)
+        .bind(&confirmation.repository)
+        .bind(&confirmation.requested_by)
+        .bind(&confirmation.command_line)
+        .bind(confirmation.created_at)
+        .bind(confirmation.expires_at)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Consumes a pending confirmation.
+    ///
+    /// The token is only accepted from the user who requested it, in the
+    /// repository it was requested in, and is removed once taken so it cannot
+    /// be replayed.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `installation_id` - GitHub App installation ID
+    /// * `repository` - Repository name in "owner/repo" format
+    /// * `requested_by` - GitHub username confirming the command
+    /// * `token` - Token replied by the user
+    /// * `now` - Current time, to reject expired tokens
+    ///
+    /// # Returns
+    ///
+    /// The confirmed command, or `None` if the token is unknown or expired.
+    pub async fn take(
+        pool: &SqlitePool,
+        installation_id: InstallationId,
+        repository: &str,
+        requested_by: &str,
+        token: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<PendingConfirmation>> {
+        let row = sqlx::query(
+            r#"
+            DELETE FROM pending_confirmations
+            WHERE token = $1 AND installation_id = $2 AND repository = $3 AND requested_by = $4
+            RETURNING *
+            "#,
+        )
+        .bind(token.trim())
+        .bind(installation_id.get())
+        .bind(repository)
+        .bind(requested_by)
+        .fetch_optional(pool)
+        .await?;
+
+        let confirmation = row.map(|row| PendingConfirmation {
+            token: row.get("token"),
+            installation_id: row.get("installation_id"),
+            repository: row.get("repository"),
+            requested_by: row.get("requested_by"),
+            command_line: row.get("command_line"),
+            created_at: row.get("created_at"),
+            expires_at: row.get("expires_at"),
+        });
+
+        Ok(confirmation.filter(|c| !c.is_expired(now)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+
+    fn confirmation(ttl: chrono::Duration) -> PendingConfirmation {
+        PendingConfirmation::new(
+            12345.into(),
+            "owner/repo".to_string(),
+            "alice".to_string(),
+            "/freeze-all --duration 2h".to_string(),
+            ttl,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_issue_and_take() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let pending = confirmation(chrono::Duration::minutes(10));
+        assert_eq!(pending.token.len(), 8);
+        PendingConfirmation::create(pool, &pending).await.unwrap();
+
+        let taken = PendingConfirmation::take(
+            pool,
+            12345.into(),
+            "Owner/Repo",
+            "alice",
+            &pending.token,
+            Utc::now(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(taken.command_line, "/freeze-all --duration 2h");
+
+        // A token can only be used once
+        let replayed = PendingConfirmation::take(
+            pool,
+            12345.into(),
+            "owner/repo",
+            "alice",
+            &pending.token,
+            Utc::now(),
+        )
+        .await
+        .unwrap();
+        assert!(replayed.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_take_rejects_other_requests() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let pending = confirmation(chrono::Duration::minutes(10));
+        PendingConfirmation::create(pool, &pending).await.unwrap();
+
+        let now = Utc::now();
+        let attempts = [
+            (12345, "owner/repo", "mallory", pending.token.as_str()),
+            (12345, "owner/other", "alice", pending.token.as_str()),
+            (67890, "owner/repo", "alice", pending.token.as_str()),
+            (12345, "owner/repo", "alice", "deadbeef"),
+        ];
+        for (installation_id, repository, user, token) in attempts {
+            let taken = PendingConfirmation::take(
+                pool,
+                installation_id.into(),
+                repository,
+                user,
+                token,
+                now,
+            )
+            .await
+            .unwrap();
+            assert!(taken.is_none());
+        }
+
+        // The rejected attempts did not consume the token
+        let taken =
+            PendingConfirmation::take(pool, 12345.into(), "owner/repo", "alice", &pending.token, now)
+                .await
+                .unwrap();
+        assert!(taken.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_take_rejects_expired_token() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let pending = confirmation(chrono::Duration::minutes(10));
+        PendingConfirmation::create(pool, &pending).await.unwrap();
+
+        let later = Utc::now() + chrono::Duration::minutes(11);
+        let taken =
+            PendingConfirmation::take(pool, 12345.into(), "owner/repo", "alice", &pending.token, later)
+                .await
+                .unwrap();
+        assert!(taken.is_none());
+        assert!(pending.is_expired(later));
+        assert!(!pending.is_expired(pending.created_at));
+    }
+
+    #[tokio::test]
+    async fn test_create_drops_expired_confirmations() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let expired = confirmation(chrono::Duration::zero());
+        PendingConfirmation::create(pool, &expired).await.unwrap();
+        PendingConfirmation::create(pool, &confirmation(chrono::Duration::minutes(10)))
+            .await
+            .unwrap();
+
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pending_confirmations")
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+}
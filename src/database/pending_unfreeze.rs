@@ -0,0 +1,207 @@
+//! Database operations for unfreezes waiting for approval.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::{Row, SqlitePool};
+
+use crate::{database::models::PendingUnfreeze, installation::InstallationId};
+
+impl PendingUnfreeze {
+    /// Stores a pending unfreeze, replacing the one of the same repository.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `pending` - Pending unfreeze to store
+    pub async fn create(pool: &SqlitePool, pending: &PendingUnfreeze) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO pending_unfreezes
+            (installation_id, repository, requested_by, command_line, approvers, created_at, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+        )
+        .bind(pending.installation_id)
+        .bind(&pending.repository)
+        .bind(&pending.requested_by)
+        .bind(&pending.command_line)
+        .bind(pending.approvers.join(","))
+        .bind(pending.created_at)
+        .bind(pending.expires_at)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Gets the pending unfreeze of a repository.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `installation_id` - GitHub App installation ID
+    /// * `repository` - Repository name in "owner/repo" format
+    /// * `now` - Current time, to ignore expired requests
+    ///
+    /// # Returns
+    ///
+    /// The pending unfreeze, or `None` if there is none or it expired.
+    pub async fn find(
+        pool: &SqlitePool,
+        installation_id: InstallationId,
+        repository: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<PendingUnfreeze>> {
+        let row = sqlx::query(
+            r#"
+            SELECT * FROM pending_unfreezes
+            WHERE installation_id = $1 AND repository = $2
+            "#,
+        )
+        .bind(installation_id.get())
+        .bind(repository)
+        .fetch_optional(pool)
+        .await?;
+
+        let pending = row.map(|row| PendingUnfreeze {
+            installation_id: row.get("installation_id"),
+            repository: row.get("repository"),
+            requested_by: row.get("requested_by"),
+            command_line: row.get("command_line"),
+            approvers: row
+                .get::<String, _>("approvers")
+                .split(',')
+                .filter(|a| !a.is_empty())
+                .map(str::to_string)
+                .collect(),
+            created_at: row.get("created_at"),
+            expires_at: row.get("expires_at"),
+        });
+
+        Ok(pending.filter(|p| !p.is_expired(now)))
+    }
+
+    /// Removes the pending unfreeze of a repository once it is approved.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `installation_id` - GitHub App installation ID
+    /// * `repository` - Repository name in "owner/repo" format
+    ///
+    /// # Returns
+    ///
+    /// `true` if a request was removed, `false` if another approval took it first.
+    pub async fn remove(
+        pool: &SqlitePool,
+        installation_id: InstallationId,
+        repository: &str,
+    ) -> Result<bool> {
+        let removed = sqlx::query(
+            r#"
+            DELETE FROM pending_unfreezes
+            WHERE installation_id = $1 AND repository = $2
+            "#,
+        )
+        .bind(installation_id.get())
+        .bind(repository)
+        .execute(pool)
+        .await?
+        .rows_affected();
+
+        Ok(removed > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+
+    fn pending(command_line: &str, ttl: chrono::Duration) -> PendingUnfreeze {
+        PendingUnfreeze::new(
+            12345.into(),
+            "owner/repo".to_string(),
+            "alice".to_string(),
+            command_line.to_string(),
+            vec!["release-manager".to_string(), "cto".to_string()],
+            ttl,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_create_find_and_remove() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let request = pending("/unfreeze", chrono::Duration::hours(1));
+        PendingUnfreeze::create(pool, &request).await.unwrap();
+
+        let found = PendingUnfreeze::find(pool, 12345.into(), "Owner/Repo", Utc::now())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.requested_by, "alice");
+        assert_eq!(found.approvers, vec!["release-manager", "cto"]);
+
+        // A new request replaces the pending one
+        let replacement = pending("/unfreeze --branch main", chrono::Duration::hours(1));
+        PendingUnfreeze::create(pool, &replacement).await.unwrap();
+        let found = PendingUnfreeze::find(pool, 12345.into(), "owner/repo", Utc::now())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.command_line, "/unfreeze --branch main");
+
+        // Requests can only be approved once
+        assert!(
+            PendingUnfreeze::remove(pool, 12345.into(), "owner/repo")
+                .await
+                .unwrap()
+        );
+        assert!(
+            !PendingUnfreeze::remove(pool, 12345.into(), "owner/repo")
+                .await
+                .unwrap()
+        );
+        assert!(
+            PendingUnfreeze::find(pool, 12345.into(), "owner/repo", Utc::now())
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_find_ignores_expired_and_other_requests() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let request = pending("/unfreeze", chrono::Duration::hours(1));
+        PendingUnfreeze::create(pool, &request).await.unwrap();
+
+        let later = Utc::now() + chrono::Duration::hours(2);
+        assert!(
+            PendingUnfreeze::find(pool, 12345.into(), "owner/repo", later)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        for (installation_id, repository) in [(12345, "owner/other"), (67890, "owner/repo")] {
+            let found = PendingUnfreeze::find(pool, installation_id.into(), repository, Utc::now())
+                .await
+                .unwrap();
+            assert!(found.is_none());
+        }
+    }
+
+    #[test]
+    fn test_is_approver() {
+        let request = pending("/unfreeze", chrono::Duration::hours(1));
+
+        assert!(request.is_approver("release-manager"));
+        assert!(request.is_approver("@CTO"));
+        assert!(!request.is_approver("alice"));
+        assert!(!request.is_approver("mallory"));
+    }
+}
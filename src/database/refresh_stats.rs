@@ -0,0 +1,124 @@
+//! Database operations for per-repository PR refresh statistics.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::{Row, SqlitePool};
+
+use crate::{database::models::RefreshStats, installation::InstallationId};
+
+impl RefreshStats {
+    /// Records a completed refresh of a repository's PRs.
+    ///
+    /// Overwrites the last refresh time and retry count, and adds the retries
+    /// to the running total.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `installation_id` - GitHub App installation ID
+    /// * `repository` - Repository name in "owner/repo" format
+    /// * `refreshed_at` - When the refresh finished
+    /// * `retries` - Number of retries the refresh needed
+    pub async fn record(
+        pool: &SqlitePool,
+        installation_id: InstallationId,
+        repository: &str,
+        refreshed_at: DateTime<Utc>,
+        retries: usize,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO repository_refresh_stats
+            (installation_id, repository, last_refreshed_at, last_retries, total_retries)
+            VALUES ($1, $2, $3, $4, $4)
+            ON CONFLICT (installation_id, repository) DO UPDATE SET
+                last_refreshed_at = excluded.last_refreshed_at,
+                last_retries = excluded.last_retries,
+                total_retries = total_retries + excluded.last_retries
+            "#,
+        )
+        .bind(installation_id.get())
+        .bind(repository)
+        .bind(refreshed_at)
+        .bind(retries as i64)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Gets the refresh statistics of a repository, if it was ever refreshed.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `installation_id` - GitHub App installation ID
+    /// * `repository` - Repository name in "owner/repo" format
+    pub async fn get(
+        pool: &SqlitePool,
+        installation_id: InstallationId,
+        repository: &str,
+    ) -> Result<Option<RefreshStats>> {
+        let row = sqlx::query(
+            r#"
+            SELECT * FROM repository_refresh_stats
+            WHERE installation_id = $1 AND repository = $2
+            "#,
+        )
+        .bind(installation_id.get())
+        .bind(repository)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row.map(|row| RefreshStats {
+            installation_id: row.get("installation_id"),
+            repository: row.get("repository"),
+            last_refreshed_at: row.get("last_refreshed_at"),
+            last_retries: row.get("last_retries"),
+            total_retries: row.get("total_retries"),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+
+    #[tokio::test]
+    async fn test_record_updates_last_refresh() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        assert!(
+            RefreshStats::get(pool, 12345.into(), "owner/repo")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let first = Utc::now() - chrono::Duration::minutes(5);
+        RefreshStats::record(pool, 12345.into(), "owner/repo", first, 2)
+            .await
+            .unwrap();
+        let stats = RefreshStats::get(pool, 12345.into(), "owner/repo")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stats.last_refreshed_at, first);
+        assert_eq!(stats.last_retries, 2);
+        assert_eq!(stats.total_retries, 2);
+
+        let second = Utc::now();
+        RefreshStats::record(pool, 12345.into(), "Owner/Repo", second, 1)
+            .await
+            .unwrap();
+        let stats = RefreshStats::get(pool, 12345.into(), "owner/repo")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stats.last_refreshed_at, second);
+        assert_eq!(stats.last_retries, 1);
+        assert_eq!(stats.total_retries, 3);
+    }
+}
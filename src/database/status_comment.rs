@@ -0,0 +1,126 @@
+//! Database operations for pinned freeze status comments.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+use crate::{database::models::StatusComment, installation::InstallationId};
+
+impl StatusComment {
+    /// Records the status comment of an issue, replacing any previous one.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `installation_id` - GitHub App installation ID
+    /// * `repository` - Repository name in "owner/repo" format
+    /// * `issue_number` - Issue or PR number the comment was posted on
+    /// * `comment_id` - ID of the status comment
+    /// * `updated_at` - When the comment was written
+    pub async fn record(
+        pool: &SqlitePool,
+        installation_id: InstallationId,
+        repository: &str,
+        issue_number: u64,
+        comment_id: u64,
+        updated_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO status_comments
+            (installation_id, repository, issue_number, comment_id, updated_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (installation_id, repository, issue_number) DO UPDATE SET
+                comment_id = excluded.comment_id,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(installation_id.get())
+        .bind(repository)
+        .bind(issue_number as i64)
+        .bind(comment_id as i64)
+        .bind(updated_at)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Gets the ID of the status comment of an issue, if one was posted.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `installation_id` - GitHub App installation ID
+    /// * `repository` - Repository name in "owner/repo" format
+    /// * `issue_number` - Issue or PR number
+    pub async fn find(
+        pool: &SqlitePool,
+        installation_id: InstallationId,
+        repository: &str,
+        issue_number: u64,
+    ) -> Result<Option<u64>> {
+        let comment_id: Option<i64> = sqlx::query_scalar(
+            r#"
+            SELECT comment_id FROM status_comments
+            WHERE installation_id = $1 AND repository = $2 AND issue_number = $3
+            "#,
+        )
+        .bind(installation_id.get())
+        .bind(repository)
+        .bind(issue_number as i64)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(comment_id.map(|id| id as u64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+
+    #[tokio::test]
+    async fn test_record_and_find() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        assert!(
+            StatusComment::find(pool, 12345.into(), "owner/repo", 7)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        StatusComment::record(pool, 12345.into(), "owner/repo", 7, 1001, Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(
+            StatusComment::find(pool, 12345.into(), "Owner/Repo", 7)
+                .await
+                .unwrap(),
+            Some(1001)
+        );
+
+        // A replacement comment overwrites the previous one
+        StatusComment::record(pool, 12345.into(), "owner/repo", 7, 1002, Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(
+            StatusComment::find(pool, 12345.into(), "owner/repo", 7)
+                .await
+                .unwrap(),
+            Some(1002)
+        );
+
+        // Other issues and installations have their own comment
+        for (installation_id, issue_number) in [(12345, 8), (67890, 7)] {
+            let found =
+                StatusComment::find(pool, installation_id.into(), "owner/repo", issue_number)
+                    .await
+                    .unwrap();
+            assert!(found.is_none());
+        }
+    }
+}
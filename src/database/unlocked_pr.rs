@@ -2,53 +2,79 @@ use anyhow::Result;
 use chrono::Utc;
 use sqlx::SqlitePool;
 
-use crate::database::models::UnlockedPr;
+use crate::{database::models::UnlockedPr, installation::InstallationId};
 
 impl UnlockedPr {
-    /// Unlock a specific PR during a freeze
+    /// Unlock a specific PR during a freeze.
+    ///
+    /// Unlocking a PR that is already unlocked keeps the original unlock, so
+    /// running `/unlock-pr` twice is harmless.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the PR got unlocked, or the user who had already unlocked it.
     pub async fn unlock_pr(
         pool: &SqlitePool,
-        installation_id: i64,
+        installation_id: InstallationId,
         repository: &str,
         pr_number: u64,
         unlocked_by: &str,
-    ) -> Result<(), anyhow::Error> {
+    ) -> Result<Option<String>, anyhow::Error> {
         let id = uuid::Uuid::new_v4().to_string();
+        let installation_id = installation_id.get();
         let now = Utc::now();
         let pr = pr_number as i64;
         let unlocked_at = now.to_rfc3339();
 
-        sqlx::query!(
+        let existing: Option<String> = sqlx::query_scalar(
+            r#"
+            SELECT unlocked_by FROM unlocked_prs
+            WHERE installation_id = $1 AND repository = $2 COLLATE NOCASE AND pr_number = $3
+            "#,
+        )
+        .bind(installation_id)
+        .bind(repository)
+        .bind(pr)
+        .fetch_optional(pool)
+        .await?;
+        if existing.is_some() {
+            return Ok(existing);
+        }
+
+        // A concurrent unlock of the same PR wins over this one
+        sqlx::query(
             r#"
-            INSERT OR REPLACE INTO unlocked_prs
+            INSERT INTO unlocked_prs
             (id, repository, installation_id, pr_number, unlocked_by, unlocked_at)
-            VALUES (?, ?, ?, ?, ?, ?)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (installation_id, repository, pr_number) DO NOTHING
             "#,
-            id,
-            repository,
-            installation_id,
-            pr,
-            unlocked_by,
-            unlocked_at
         )
+        .bind(id)
+        .bind(repository)
+        .bind(installation_id)
+        .bind(pr)
+        .bind(unlocked_by)
+        .bind(unlocked_at)
         .execute(pool)
         .await?;
 
-        Ok(())
+        Ok(None)
     }
 
     /// Check if a PR is unlocked during a freeze
     pub async fn is_pr_unlocked(
         pool: &SqlitePool,
-        installation_id: i64,
+        installation_id: InstallationId,
         repository: &str,
         pr_number: u64,
     ) -> Result<bool> {
+        let installation_id = installation_id.get();
         let pr = pr_number as i64;
         let result = sqlx::query!(
             r#"
             SELECT COUNT(*) as count FROM unlocked_prs
-            WHERE installation_id = ? AND repository = ? AND pr_number = ?
+            WHERE installation_id = ? AND repository = ? COLLATE NOCASE AND pr_number = ?
             "#,
             installation_id,
             repository,
@@ -63,13 +89,14 @@ impl UnlockedPr {
     /// Clear all unlocked PRs for a repository (called when freeze ends)
     pub async fn clear_unlocked_prs(
         pool: &SqlitePool,
-        installation_id: i64,
+        installation_id: InstallationId,
         repository: &str,
     ) -> Result<()> {
+        let installation_id = installation_id.get();
         sqlx::query!(
             r#"
             DELETE FROM unlocked_prs
-            WHERE installation_id = ? AND repository = ?
+            WHERE installation_id = ? AND repository = ? COLLATE NOCASE
             "#,
             installation_id,
             repository
@@ -80,3 +107,57 @@ impl UnlockedPr {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+
+    #[tokio::test]
+    async fn test_unlock_pr_twice() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        let first = UnlockedPr::unlock_pr(pool, 12345.into(), "owner/repo", 42, "alice")
+            .await
+            .unwrap();
+        assert_eq!(first, None);
+
+        // The second unlock is a no-op reporting the original unlock
+        let second = UnlockedPr::unlock_pr(pool, 12345.into(), "owner/repo", 42, "alice")
+            .await
+            .unwrap();
+        assert_eq!(second, Some("alice".to_string()));
+        assert!(
+            UnlockedPr::is_pr_unlocked(pool, 12345.into(), "owner/repo", 42)
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unlock_pr_by_other_user() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+
+        UnlockedPr::unlock_pr(pool, 12345.into(), "owner/repo", 42, "alice")
+            .await
+            .unwrap();
+        let again = UnlockedPr::unlock_pr(pool, 12345.into(), "Owner/Repo", 42, "bob")
+            .await
+            .unwrap();
+        assert_eq!(again, Some("alice".to_string()));
+
+        let unlocked_by: Vec<String> = sqlx::query_scalar("SELECT unlocked_by FROM unlocked_prs")
+            .fetch_all(pool)
+            .await
+            .unwrap();
+        assert_eq!(unlocked_by, vec!["alice".to_string()]);
+
+        // Other PRs are unlocked separately
+        let other = UnlockedPr::unlock_pr(pool, 12345.into(), "owner/repo", 43, "bob")
+            .await
+            .unwrap();
+        assert_eq!(other, None);
+    }
+}
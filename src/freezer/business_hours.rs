@@ -0,0 +1,264 @@
+//! Business-hours calendar used for freezes measured in working time.
+//!
+//! A duration such as `--duration 8bh` counts only the hours that fall inside the
+//! configured working window (by default Monday to Friday, 09:00-17:00 UTC).
+//! Nights and non-working days are skipped, and the concrete expiration instant
+//! is computed up front so the stored freeze behaves like any other.
+
+use anyhow::{Result, anyhow};
+use chrono::{
+    DateTime, Datelike, Days, Duration, FixedOffset, NaiveDateTime, NaiveTime, TimeZone, Utc,
+    Weekday,
+};
+
+/// Working days, hours and UTC offset used to count business hours.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BusinessHours {
+    /// Days on which business hours are counted
+    pub work_days: Vec<Weekday>,
+    /// Local hour at which the working day starts (0-23)
+    pub start_hour: u32,
+    /// Local hour at which the working day ends (1-24), exclusive
+    pub end_hour: u32,
+    /// Offset of the local time the working window is expressed in
+    pub utc_offset: FixedOffset,
+}
+
+impl Default for BusinessHours {
+    fn default() -> Self {
+        Self {
+            work_days: vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+            start_hour: 9,
+            end_hour: 17,
+            utc_offset: FixedOffset::east_opt(0).unwrap(),
+        }
+    }
+}
+
+impl BusinessHours {
+    /// Builds a calendar from its textual settings, using the defaults for unset values.
+    ///
+    /// # Arguments
+    ///
+    /// * `work_days` - Comma-separated weekdays (e.g. "mon,tue,wed,thu,fri")
+    /// * `hours` - Working window as `<start>-<end>` local hours (e.g. "9-17")
+    /// * `utc_offset` - Offset of the local time (e.g. "+02:00")
+    pub fn parse(
+        work_days: Option<&str>,
+        hours: Option<&str>,
+        utc_offset: Option<&str>,
+    ) -> Result<Self> {
+        let mut business_hours = Self::default();
+
+        if let Some(days) = work_days {
+            business_hours.work_days = days
+                .split(',')
+                .map(|d| {
+                    d.trim()
+                        .parse::<Weekday>()
+                        .map_err(|_| anyhow!("Invalid business day '{}'", d.trim()))
+                })
+                .collect::<Result<_>>()?;
+            if business_hours.work_days.is_empty() {
+                return Err(anyhow!("At least one business day is required"));
+            }
+        }
+
+        if let Some(hours) = hours {
+            let (start, end) = hours
+                .split_once('-')
+                .ok_or_else(|| anyhow!("Invalid business hours '{}', expected '9-17'", hours))?;
+            let parse_hour = |h: &str| {
+                h.trim()
+                    .parse::<u32>()
+                    .map_err(|_| anyhow!("Invalid business hour '{}'", h.trim()))
+            };
+            business_hours.start_hour = parse_hour(start)?;
+            business_hours.end_hour = parse_hour(end)?;
+            if business_hours.end_hour > 24
+                || business_hours.start_hour >= business_hours.end_hour
+            {
+                return Err(anyhow!(
+                    "Invalid business hours '{}': the start must be before the end and within 0-24",
+                    hours
+                ));
+            }
+        }
+
+        if let Some(offset) = utc_offset {
+            business_hours.utc_offset = offset
+                .trim()
+                .parse()
+                .map_err(|_| anyhow!("Invalid business hours UTC offset '{}'", offset))?;
+        }
+
+        Ok(business_hours)
+    }
+
+    /// Returns the instant reached after counting `hours` business hours from `start`.
+    ///
+    /// Time outside the working window is skipped, so a freeze started on a
+    /// Friday afternoon continues on the next working day. An end beyond the
+    /// dates chrono can represent saturates to the latest representable instant.
+    pub fn advance(&self, start: DateTime<Utc>, hours: u32) -> DateTime<Utc> {
+        let mut work_days = self.work_days.clone();
+        work_days.sort_by_key(Weekday::num_days_from_monday);
+        work_days.dedup();
+        let weekly_hours = work_days.len() as u32 * self.end_hour.saturating_sub(self.start_hour);
+        if hours == 0 || weekly_hours == 0 {
+            return start;
+        }
+
+        // Any seven days hold exactly one working week, so whole weeks are skipped
+        // at once. The last one is counted below so that a multiple of a working
+        // week ends at closing time rather than at the next opening.
+        let weeks = (hours - 1) / weekly_hours;
+        let mut remaining = Duration::hours((hours - weeks * weekly_hours) as i64);
+        let Some(mut cursor) = start
+            .with_timezone(&self.utc_offset)
+            .naive_local()
+            .checked_add_days(Days::new(7 * weeks as u64))
+        else {
+            return DateTime::<Utc>::MAX_UTC;
+        };
+
+        loop {
+            let date = cursor.date();
+            if self.work_days.contains(&date.weekday()) {
+                let midnight = date.and_time(NaiveTime::MIN);
+                let day_start = midnight + Duration::hours(self.start_hour as i64);
+                let day_end = midnight + Duration::hours(self.end_hour as i64);
+
+                let from = cursor.max(day_start);
+                if from < day_end {
+                    let available = day_end - from;
+                    if remaining <= available {
+                        return self.to_utc(from + remaining);
+                    }
+                    remaining -= available;
+                }
+            }
+            let Some(next) = date.succ_opt() else {
+                return DateTime::<Utc>::MAX_UTC;
+            };
+            cursor = next.and_time(NaiveTime::MIN);
+        }
+    }
+
+    /// Converts a local datetime of the working window back to UTC
+    fn to_utc(&self, local: NaiveDateTime) -> DateTime<Utc> {
+        self.utc_offset
+            .from_local_datetime(&local)
+            .single()
+            .expect("fixed offsets map local times unambiguously")
+            .with_timezone(&Utc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    #[test]
+    fn test_advance_within_a_day() {
+        let hours = BusinessHours::default();
+        // Monday 10:00 + 3 business hours
+        assert_eq!(hours.advance(utc(2025, 1, 6, 10, 0), 3), utc(2025, 1, 6, 13, 0));
+        // A full working day ends exactly at closing time
+        assert_eq!(hours.advance(utc(2025, 1, 6, 9, 0), 8), utc(2025, 1, 6, 17, 0));
+    }
+
+    #[test]
+    fn test_advance_skips_nights() {
+        let hours = BusinessHours::default();
+        // Monday 15:00 + 4h: 2h on Monday, 2h on Tuesday morning
+        assert_eq!(hours.advance(utc(2025, 1, 6, 15, 0), 4), utc(2025, 1, 7, 11, 0));
+        // Started before opening time, counting begins at 09:00
+        assert_eq!(hours.advance(utc(2025, 1, 6, 6, 30), 2), utc(2025, 1, 6, 11, 0));
+        // Started after closing time, counting begins the next morning
+        assert_eq!(hours.advance(utc(2025, 1, 6, 20, 0), 1), utc(2025, 1, 7, 10, 0));
+    }
+
+    #[test]
+    fn test_advance_crosses_weekend() {
+        let hours = BusinessHours::default();
+        // Friday 14:00 + 8h: 3h on Friday, 5h on Monday
+        assert_eq!(hours.advance(utc(2025, 1, 10, 14, 0), 8), utc(2025, 1, 13, 14, 0));
+        // Started on Saturday, counting begins on Monday
+        assert_eq!(hours.advance(utc(2025, 1, 11, 12, 0), 8), utc(2025, 1, 13, 17, 0));
+        // Two full working days from Thursday morning end on Friday evening
+        assert_eq!(hours.advance(utc(2025, 1, 9, 9, 0), 16), utc(2025, 1, 10, 17, 0));
+        // One more hour rolls over the weekend
+        assert_eq!(hours.advance(utc(2025, 1, 9, 9, 0), 17), utc(2025, 1, 13, 10, 0));
+    }
+
+    #[test]
+    fn test_advance_with_utc_offset() {
+        let hours = BusinessHours::parse(None, None, Some("+02:00")).unwrap();
+        // 07:00 UTC is 09:00 local, so 8h end at 17:00 local, 15:00 UTC
+        assert_eq!(hours.advance(utc(2025, 1, 6, 7, 0), 8), utc(2025, 1, 6, 15, 0));
+        // Friday 22:30 UTC is already Saturday locally
+        assert_eq!(hours.advance(utc(2025, 1, 10, 22, 30), 1), utc(2025, 1, 13, 8, 0));
+    }
+
+    #[test]
+    fn test_advance_skips_whole_weeks() {
+        let hours = BusinessHours::default();
+        // 52 working weeks from Monday morning end on a Friday evening
+        assert_eq!(
+            hours.advance(utc(2025, 1, 6, 9, 0), 52 * 40),
+            utc(2026, 1, 2, 17, 0)
+        );
+        // One more hour rolls over to the next Monday
+        assert_eq!(
+            hours.advance(utc(2025, 1, 6, 9, 0), 52 * 40 + 1),
+            utc(2026, 1, 5, 10, 0)
+        );
+        // Started mid-week, the week boundary falls mid-week as well
+        assert_eq!(
+            hours.advance(utc(2025, 1, 8, 13, 0), 40 + 3),
+            utc(2025, 1, 15, 16, 0)
+        );
+    }
+
+    #[test]
+    fn test_advance_saturates_past_the_last_date() {
+        let hours = BusinessHours::default();
+        let start = DateTime::<Utc>::MAX_UTC - Duration::days(30);
+        assert_eq!(hours.advance(start, u32::MAX), DateTime::<Utc>::MAX_UTC);
+    }
+
+    #[test]
+    fn test_advance_zero_hours() {
+        let hours = BusinessHours::default();
+        let start = utc(2025, 1, 11, 12, 0);
+        assert_eq!(hours.advance(start, 0), start);
+    }
+
+    #[test]
+    fn test_parse() {
+        let hours = BusinessHours::parse(Some("sun,mon,tue,wed,thu"), Some("8-16"), None).unwrap();
+        assert_eq!(hours.work_days.len(), 5);
+        assert_eq!(hours.work_days[0], Weekday::Sun);
+        assert_eq!((hours.start_hour, hours.end_hour), (8, 16));
+        // Sunday 15:00 + 2h: 1h on Sunday, 1h on Monday
+        assert_eq!(hours.advance(utc(2025, 1, 5, 15, 0), 2), utc(2025, 1, 6, 9, 0));
+
+        assert_eq!(BusinessHours::parse(None, None, None).unwrap(), BusinessHours::default());
+        assert!(BusinessHours::parse(Some("mon,funday"), None, None).is_err());
+        assert!(BusinessHours::parse(None, Some("17-9"), None).is_err());
+        assert!(BusinessHours::parse(None, Some("9-25"), None).is_err());
+        assert!(BusinessHours::parse(None, Some("nine"), None).is_err());
+        assert!(BusinessHours::parse(None, None, Some("later")).is_err());
+    }
+}
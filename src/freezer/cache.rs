@@ -0,0 +1,194 @@
+//! Short-lived cache of the freezes in effect for a repository.
+//!
+//! Busy repositories produce a steady stream of pull request events, each of
+//! which needs to know whether the repository is frozen. The cache keeps the
+//! freezes found for a repository for a few seconds so those events do not all
+//! hit the database. Entries are dropped explicitly whenever a freeze is
+//! created, ended or activated, so the TTL only bounds how long an entry may
+//! live, not how stale the freeze state can be.
+
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::{database::models::FreezeRecord, installation::InstallationId};
+
+/// How long freeze state is served from the cache
+pub const FREEZE_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Freezes in effect for a repository, as cached at a point in time
+#[derive(Debug)]
+struct CacheEntry {
+    cached_at: Instant,
+    records: Vec<FreezeRecord>,
+}
+
+/// Cache of the freezes in effect per installation and repository.
+#[derive(Debug)]
+pub struct FreezeCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<(InstallationId, String), CacheEntry>>,
+}
+
+impl Default for FreezeCache {
+    fn default() -> Self {
+        Self::new(FREEZE_CACHE_TTL)
+    }
+}
+
+impl FreezeCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Repository names are case-insensitive on GitHub
+    fn key(installation_id: InstallationId, repository: &str) -> (InstallationId, String) {
+        (installation_id, repository.to_lowercase())
+    }
+
+    /// Returns the cached freezes of a repository, unless missing or outdated.
+    pub fn get(
+        &self,
+        installation_id: InstallationId,
+        repository: &str,
+    ) -> Option<Vec<FreezeRecord>> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let key = Self::key(installation_id, repository);
+        match entries.get(&key) {
+            Some(entry) if entry.cached_at.elapsed() < self.ttl => Some(entry.records.clone()),
+            Some(_) => {
+                entries.remove(&key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Caches the freezes found for a repository.
+    pub fn insert(
+        &self,
+        installation_id: InstallationId,
+        repository: &str,
+        records: Vec<FreezeRecord>,
+    ) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.insert(
+            Self::key(installation_id, repository),
+            CacheEntry {
+                cached_at: Instant::now(),
+                records,
+            },
+        );
+    }
+
+    /// Drops the cached freezes of a repository after its freeze state changed.
+    ///
+    /// For the `owner/*` wildcard of an organization-wide freeze, every
+    /// repository of that owner is dropped.
+    pub fn invalidate(&self, installation_id: InstallationId, repository: &str) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let repository = repository.to_lowercase();
+        match repository.strip_suffix("/*") {
+            Some(owner) => {
+                let prefix = format!("{}/", owner);
+                entries
+                    .retain(|(id, repo), _| *id != installation_id || !repo.starts_with(&prefix));
+            }
+            None => {
+                entries.remove(&(installation_id, repository));
+            }
+        }
+    }
+
+    /// Drops every cached repository of an installation, e.g. once it is deleted.
+    pub fn invalidate_installation(&self, installation_id: InstallationId) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.retain(|(id, _), _| *id != installation_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn record(repository: &str) -> FreezeRecord {
+        FreezeRecord::new(
+            repository.to_string(),
+            12345.into(),
+            Utc::now(),
+            None,
+            Some("release".to_string()),
+            "alice".to_string(),
+            None,
+        )
+    }
+
+    #[test]
+    fn test_hit_and_miss() {
+        let cache = FreezeCache::default();
+        assert!(cache.get(12345.into(), "owner/repo").is_none());
+
+        cache.insert(12345.into(), "owner/repo", vec![record("owner/repo")]);
+        let cached = cache.get(12345.into(), "Owner/Repo").unwrap();
+        assert_eq!(cached.len(), 1);
+
+        // An unfrozen repository is cached as well
+        cache.insert(12345.into(), "owner/other", Vec::new());
+        assert!(cache.get(12345.into(), "owner/other").unwrap().is_empty());
+
+        // Other installations do not share entries
+        assert!(cache.get(67890.into(), "owner/repo").is_none());
+    }
+
+    #[test]
+    fn test_expired_entries_miss() {
+        let cache = FreezeCache::new(Duration::ZERO);
+        cache.insert(12345.into(), "owner/repo", vec![record("owner/repo")]);
+        assert!(cache.get(12345.into(), "owner/repo").is_none());
+    }
+
+    #[test]
+    fn test_invalidate() {
+        let cache = FreezeCache::default();
+        cache.insert(12345.into(), "owner/repo", Vec::new());
+        cache.insert(12345.into(), "owner/other", Vec::new());
+
+        cache.invalidate(12345.into(), "Owner/Repo");
+        assert!(cache.get(12345.into(), "owner/repo").is_none());
+        assert!(cache.get(12345.into(), "owner/other").is_some());
+    }
+
+    #[test]
+    fn test_invalidate_org_wildcard() {
+        let cache = FreezeCache::default();
+        cache.insert(12345.into(), "owner/repo", Vec::new());
+        cache.insert(12345.into(), "owner/other", Vec::new());
+        cache.insert(12345.into(), "someone/repo", Vec::new());
+        cache.insert(67890.into(), "owner/repo", Vec::new());
+
+        cache.invalidate(12345.into(), "owner/*");
+        assert!(cache.get(12345.into(), "owner/repo").is_none());
+        assert!(cache.get(12345.into(), "owner/other").is_none());
+        assert!(cache.get(12345.into(), "someone/repo").is_some());
+        assert!(cache.get(67890.into(), "owner/repo").is_some());
+    }
+
+    #[test]
+    fn test_invalidate_installation() {
+        let cache = FreezeCache::default();
+        cache.insert(12345.into(), "owner/repo", Vec::new());
+        cache.insert(12345.into(), "someone/repo", Vec::new());
+        cache.insert(67890.into(), "owner/repo", Vec::new());
+
+        cache.invalidate_installation(12345.into());
+        assert!(cache.get(12345.into(), "owner/repo").is_none());
+        assert!(cache.get(12345.into(), "someone/repo").is_none());
+        assert!(cache.get(67890.into(), "owner/repo").is_some());
+    }
+}
@@ -9,10 +9,17 @@
 //! - `/freeze` - Freeze the current repository or specific repositories with `--repo`
 //! - `/freeze-all` - Freeze all repositories in the organization or specific repositories with `--repo`
 //! - `/unfreeze` - Unfreeze the current repository
-//! - `/unfreeze-all` - Unfreeze all repositories in the organization
+//! - `/unfreeze-all` - Unfreeze all repositories in the organization or specific repositories with `--repo`
 //! - `/status` - Show freeze status for repositories
 //! - `/schedule-freeze` - Schedule a freeze for specific time periods
 //! - `/unlock-pr` - Unlock a specific PR during a freeze
+//! - `/freeze-reassign` - Transfer ownership of the active freeze to another user
+//! - `/extend` - Extend the active freeze, e.g. in reply to a freeze reminder
+//! - `/audit` - Show the most recent commands run in the current repository
+//! - `/preview` - Render bot messages with sample data
+//! - `/scheduled` - List the scheduled freezes that have not started yet
+//! - `/cancel-schedule` - Cancel a scheduled freeze before it starts
+//! - `/scheduler-preview` - Show what the scheduler will do on its next run
 //!
 //! # Branch-based Freezes
 //!
@@ -24,6 +31,12 @@
 //!
 //! When `--branch` is not specified, the freeze applies to all branches (backward compatible behavior).
 //!
+//! # Timezones
+//!
+//! `/schedule-freeze` accepts RFC3339 datetimes by default. With `--timezone` (an IANA name such
+//! as `Europe/Rome`), `--from` and `--to` may also be given as local datetimes like
+//! `"2025-01-15 10:00"`; they are converted to UTC before being stored.
+//!
 //! # Example Usage
 //!
 //! ```
@@ -54,33 +67,74 @@
 //! ```
 use clap::{Parser, Subcommand};
 
-use chrono::{DateTime, Duration, Utc};
+use chrono::{DateTime, Duration, LocalResult, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
 use clap::Args;
 use tracing::error;
 
-use crate::freezer::errors::ParsingError;
+use crate::freezer::{business_hours::BusinessHours, errors::ParsingError};
 
 pub fn parse(input: &str) -> Result<Cli, ParsingError> {
+    parse_with_prefix(input, None)
+}
+
+/// Parses a command that must start with `/<prefix>`, e.g. `/frezze freeze`.
+///
+/// Installations running several bots configure a prefix so Frezze ignores
+/// commands meant for the others. Without a prefix this behaves like [`parse`].
+pub fn parse_with_prefix(input: &str, prefix: Option<&str>) -> Result<Cli, ParsingError> {
     if input.is_empty() || !input.starts_with("/") {
         return Err(ParsingError::NotACommand);
     }
 
-    let input = input.trim_start_matches("/");
+    let mut input = input.trim_start_matches("/");
+    if let Some(prefix) = prefix.map(|p| p.trim_start_matches('/')) {
+        input = input
+            .strip_prefix(prefix)
+            .filter(|rest| rest.starts_with(char::is_whitespace))
+            .ok_or(ParsingError::NotACommand)?
+            .trim_start();
+    }
 
     let args = shell_words::split(input).map_err(|e| {
         error!("MalformedCommand: {:?}", e);
         ParsingError::MalformedCommand
     })?;
 
-    let mut argv = vec!["bin".to_string()];
+    let mut argv = vec![BIN_NAME.to_string()];
     argv.extend(args);
 
-    Cli::try_parse_from(argv).map_err(|e| {
-        error!("MalformedCommand: {:?}", e);
-        ParsingError::MalformedCommand
+    Cli::try_parse_from(argv).map_err(|e| match e.kind() {
+        clap::error::ErrorKind::DisplayHelp | clap::error::ErrorKind::DisplayVersion => {
+            ParsingError::HelpRequested(render_help(&e, prefix))
+        }
+        _ => {
+            error!("MalformedCommand: {:?}", e);
+            ParsingError::MalformedCommand
+        }
     })
 }
 
+/// Program name handed to clap, shown in help and version output
+const BIN_NAME: &str = "frezze";
+
+/// Renders the help or version clap printed for `--help` as comment text.
+///
+/// Commands are typed as `/freeze` (or `/<prefix> freeze`) rather than run
+/// as a binary, so the usage lines are rewritten accordingly.
+fn render_help(error: &clap::Error, prefix: Option<&str>) -> String {
+    let invocation = match prefix.map(|p| p.trim_start_matches('/')) {
+        Some(prefix) => format!("Usage: /{} ", prefix),
+        None => "Usage: /".to_string(),
+    };
+    error
+        .render()
+        .to_string()
+        .replace(&format!("Usage: {} ", BIN_NAME), &invocation)
+        .trim_end()
+        .to_string()
+}
+
 #[derive(Parser)]
 #[command(author, version, about)]
 pub struct Cli {
@@ -97,26 +151,92 @@ pub enum Command {
     FreezeAll(FreezeArgs),
     /// Unfreeze the current repository. Optionally unfreeze only a specific branch with --branch.
     Unfreeze(UnfreezeArgs),
-    /// Unfreeze all repositories in the organization
-    UnfreezeAll,
+    /// Unfreeze all repositories in the organization or specific repositories with --repo. Optionally unfreeze only a specific branch with --branch.
+    UnfreezeAll(UnfreezeAllArgs),
     /// Show freeze status for specified repositories
     Status(StatusArgs),
     /// Schedule a freeze for a specific time period. Optionally schedule for a specific branch with --branch.
     ScheduleFreeze(ScheduleFreezeArgs),
     /// Unlock a specific PR during a freeze
     UnlockPr(UnlockPrArgs),
+    /// Transfer ownership of the active freeze to another user. Optionally target a specific branch freeze with --branch.
+    FreezeReassign(FreezeReassignArgs),
+    /// Extend the active freeze by a duration. In the thread a freeze was requested from, extends that freeze.
+    Extend(ExtendArgs),
+    /// Show the most recent commands run in the current repository
+    Audit(AuditArgs),
+    /// Render bot messages with sample data, to review message changes
+    Preview(PreviewArgs),
+    /// List the scheduled freezes of the current repository that have not started yet
+    Scheduled,
+    /// Cancel a scheduled freeze of the current repository before it starts
+    CancelSchedule(CancelScheduleArgs),
+    /// Show the freezes the scheduler will activate or expire on its next run
+    SchedulerPreview,
+    /// Compare the Frezze check of open PRs with the freeze state, fixing drifted checks with --fix
+    Reconcile(ReconcileArgs),
+    /// Approve the pending unfreeze of the current repository, for installations with required approvers
+    ApproveUnfreeze,
+}
+
+impl Command {
+    /// Names of all commands as typed in comments, without the leading slash.
+    pub const NAMES: &'static [&'static str] = &[
+        "freeze",
+        "freeze-all",
+        "unfreeze",
+        "unfreeze-all",
+        "status",
+        "schedule-freeze",
+        "unlock-pr",
+        "freeze-reassign",
+        "extend",
+        "audit",
+        "preview",
+        "scheduled",
+        "cancel-schedule",
+        "scheduler-preview",
+        "reconcile",
+        "approve-unfreeze",
+    ];
+
+    /// Returns the name of the command as typed in comments, without the leading slash.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Command::Freeze(_) => "freeze",
+            Command::FreezeAll(_) => "freeze-all",
+            Command::Unfreeze(_) => "unfreeze",
+            Command::UnfreezeAll(_) => "unfreeze-all",
+            Command::Status(_) => "status",
+            Command::ScheduleFreeze(_) => "schedule-freeze",
+            Command::UnlockPr(_) => "unlock-pr",
+            Command::FreezeReassign(_) => "freeze-reassign",
+            Command::Extend(_) => "extend",
+            Command::Audit(_) => "audit",
+            Command::Preview(_) => "preview",
+            Command::Scheduled => "scheduled",
+            Command::CancelSchedule(_) => "cancel-schedule",
+            Command::SchedulerPreview => "scheduler-preview",
+            Command::Reconcile(_) => "reconcile",
+            Command::ApproveUnfreeze => "approve-unfreeze",
+        }
+    }
 }
 
 #[derive(Args, Debug)]
 pub struct FreezeArgs {
-    /// Duration to freeze (e.g. "3h", "15m"), optional
+    /// Duration to freeze (e.g. "3h", "15m", or "8bh" for business hours), optional
     #[arg(long, value_parser = parse_duration_2)]
-    pub duration: Option<Duration>,
+    pub duration: Option<FreezeDuration>,
 
     /// Reason for freezing, optional
     #[arg(long)]
     pub reason: Option<String>,
 
+    /// Path of a file in the repository whose contents are used as the reason, optional
+    #[arg(long, conflicts_with = "reason")]
+    pub reason_file: Option<String>,
+
     /// List of repositories to freeze (supports comma-separated values or multiple --repo flags)
     #[arg(long = "repo", value_delimiter = ',')]
     pub repos: Vec<String>,
@@ -124,6 +244,51 @@ pub struct FreezeArgs {
     /// Branch to freeze (e.g. "main", "develop"), optional. If not specified, all branches are frozen.
     #[arg(long)]
     pub branch: Option<String>,
+
+    /// Succeed without changes when an active freeze already covers the target.
+    /// The existing freeze is kept as-is and is never superseded.
+    #[arg(long)]
+    pub if_not_frozen: bool,
+
+    /// Freeze every repository of the current repository's owner with a single
+    /// organization-wide freeze, lifted with `/unfreeze-all`.
+    #[arg(long, conflicts_with = "repos")]
+    pub org: bool,
+
+    /// GitHub Environment (e.g. "production") whose deployments are blocked instead
+    /// of pull requests, optional
+    #[arg(long, conflicts_with_all = ["repos", "org", "branch"])]
+    pub environment: Option<String>,
+
+    /// Token confirming a previous `/freeze-all` of the same user, for
+    /// installations that require confirmation
+    #[arg(long)]
+    pub confirm: Option<String>,
+
+    /// Extend an overlapping active freeze to also cover the new window,
+    /// appending the reason, instead of rejecting the freeze
+    #[arg(long, conflicts_with_all = ["if_not_frozen", "repos", "org", "environment"])]
+    pub merge: bool,
+
+    /// Preview the freeze without creating it, listing the authors of open
+    /// pull requests it would block
+    #[arg(long, conflicts_with_all = ["repos", "org", "environment", "merge", "confirm"])]
+    pub dry_run: bool,
+
+    /// Mark blocked pull requests as requiring action instead of failing their
+    /// check, so they stay reviewable without looking like broken CI
+    #[arg(long, conflicts_with_all = ["environment", "merge"])]
+    pub soft: bool,
+
+    /// Category of the freeze (deploy, incident, maintenance or compliance), optional.
+    /// Checked against the allowed categories before freezing.
+    #[arg(long, conflicts_with = "merge")]
+    pub category: Option<String>,
+
+    /// Reply only once the PRs of the repository are refreshed, with the number
+    /// of PRs whose check was updated
+    #[arg(long, conflicts_with_all = ["repos", "org", "environment", "merge", "dry_run"])]
+    pub wait: bool,
 }
 
 #[derive(Args, Debug)]
@@ -131,6 +296,22 @@ pub struct StatusArgs {
     /// List of repositories to check status for
     #[arg(long, value_delimiter = ',')]
     pub repos: Vec<String>,
+
+    /// Publish the status on the PR's Frezze check run instead of commenting
+    #[arg(long)]
+    pub as_check: bool,
+
+    /// Keep the status in a single comment of the issue, updated on every run
+    #[arg(long, conflicts_with = "as_check")]
+    pub pinned: bool,
+
+    /// Include when each repository's PRs were last refreshed and how many retries it took
+    #[arg(long)]
+    pub debug: bool,
+
+    /// Only report the freezes affecting this branch, optional
+    #[arg(long)]
+    pub branch: Option<String>,
 }
 
 #[derive(Args, Debug)]
@@ -142,21 +323,44 @@ pub struct UnfreezeArgs {
     /// Branch to unfreeze (e.g. "main", "develop"), optional. If not specified, unfreezes all branches.
     #[arg(long)]
     pub branch: Option<String>,
+
+    /// Environment to unfreeze (e.g. "production"), optional. If not specified, unfreezes all environments.
+    #[arg(long)]
+    pub environment: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct UnfreezeAllArgs {
+    /// List of repositories to unfreeze (supports comma-separated values or multiple --repo flags)
+    #[arg(long = "repo", value_delimiter = ',')]
+    pub repos: Vec<String>,
+
+    /// Branch to unfreeze (e.g. "main", "develop"), optional. If not specified, unfreezes all branches.
+    #[arg(long)]
+    pub branch: Option<String>,
+
+    /// Reason for lifting the freezes, recorded on every ended freeze
+    #[arg(long)]
+    pub reason: Option<String>,
 }
 
 #[derive(Args, Debug)]
 pub struct ScheduleFreezeArgs {
-    /// Start datetime for freeze (RFC3339 format)
-    #[arg(long, value_parser = parse_datetime)]
-    pub from: DateTime<Utc>,
+    /// Start datetime for freeze (RFC3339 format, or local "YYYY-MM-DD HH:MM" with --timezone)
+    #[arg(long, value_parser = parse_schedule_time)]
+    pub from: ScheduleTime,
+
+    /// End datetime for freeze (RFC3339 format, or local "YYYY-MM-DD HH:MM" with --timezone), optional
+    #[arg(long, value_parser = parse_schedule_time)]
+    pub to: Option<ScheduleTime>,
 
-    /// End datetime for freeze (RFC3339 format), optional
-    #[arg(long, value_parser = parse_datetime)]
-    pub to: Option<DateTime<Utc>>,
+    /// IANA timezone (e.g. "Europe/Rome") used to interpret local --from/--to values, optional
+    #[arg(long, value_parser = parse_timezone)]
+    pub timezone: Option<Tz>,
 
     /// Duration to freeze, optional
     #[arg(long, value_parser = parse_duration_2)]
-    pub duration: Option<Duration>,
+    pub duration: Option<FreezeDuration>,
 
     /// Reason for freezing, optional
     #[arg(long)]
@@ -167,6 +371,52 @@ pub struct ScheduleFreezeArgs {
     pub branch: Option<String>,
 }
 
+impl ScheduleFreezeArgs {
+    /// Resolves the `--from`/`--to` values into UTC instants.
+    ///
+    /// Local datetimes are interpreted in `--timezone`. Times that fall into a
+    /// DST gap are rejected; ambiguous times during a DST fold resolve to the
+    /// earlier instant.
+    pub fn resolve_window(&self) -> Result<(DateTime<Utc>, Option<DateTime<Utc>>), ParsingError> {
+        let from = self.from.to_utc(self.timezone)?;
+        let to = self
+            .to
+            .as_ref()
+            .map(|t| t.to_utc(self.timezone))
+            .transpose()?;
+        Ok((from, to))
+    }
+}
+
+/// A schedule boundary as written by the user.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScheduleTime {
+    /// RFC3339 datetime carrying its own offset
+    Absolute(DateTime<Utc>),
+    /// Datetime without offset, interpreted in the `--timezone` zone
+    Local(NaiveDateTime),
+}
+
+impl ScheduleTime {
+    /// Converts the time to UTC, using `timezone` for local values
+    pub fn to_utc(&self, timezone: Option<Tz>) -> Result<DateTime<Utc>, ParsingError> {
+        match self {
+            ScheduleTime::Absolute(dt) => Ok(*dt),
+            ScheduleTime::Local(naive) => {
+                let tz = timezone.ok_or_else(|| ParsingError::MissingTimezone(naive.to_string()))?;
+                match tz.from_local_datetime(naive) {
+                    LocalResult::Single(dt) => Ok(dt.with_timezone(&Utc)),
+                    LocalResult::Ambiguous(earliest, _) => Ok(earliest.with_timezone(&Utc)),
+                    LocalResult::None => Err(ParsingError::NonexistentLocalTime(
+                        naive.to_string(),
+                        tz.name().to_string(),
+                    )),
+                }
+            }
+        }
+    }
+}
+
 /// Arguments for unlocking a specific PR during a repository freeze.
 #[derive(Args, Debug)]
 pub struct UnlockPrArgs {
@@ -179,10 +429,124 @@ pub struct UnlockPrArgs {
     pub reason: Option<String>,
 }
 
-fn parse_datetime(s: &str) -> Result<DateTime<Utc>, String> {
-    s.parse::<DateTime<Utc>>().map_err(|e| e.to_string())
+/// Arguments for transferring ownership of an active freeze.
+#[derive(Args, Debug)]
+pub struct FreezeReassignArgs {
+    /// GitHub username of the new owner (e.g. "@octocat" or "octocat")
+    #[arg(long, value_parser = parse_username)]
+    pub to: String,
+
+    /// Branch of the freeze to reassign (e.g. "main"), optional. If not specified, all active freezes are reassigned.
+    #[arg(long)]
+    pub branch: Option<String>,
+}
+
+/// Arguments for extending an active freeze.
+#[derive(Args, Debug)]
+pub struct ExtendArgs {
+    /// How much longer the freeze should last (e.g. "2h", "30m" or "8bh" for business hours)
+    #[arg(value_parser = parse_duration_2)]
+    pub duration: FreezeDuration,
+
+    /// Branch of the freeze to extend (e.g. "main"), optional. Needed when several branch freezes are active.
+    #[arg(long)]
+    pub branch: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct AuditArgs {
+    /// Number of most recent commands to show
+    #[arg(long, default_value_t = 20)]
+    pub limit: u32,
+}
+
+#[derive(Args, Debug)]
+pub struct PreviewArgs {
+    /// Message type to render (e.g. "freeze-success"). Every message type is rendered when omitted.
+    pub message_type: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct ReconcileArgs {
+    /// Repositories to check (supports comma-separated values or multiple --repo flags).
+    /// Every repository of the installation is checked when omitted.
+    #[arg(long = "repo", value_delimiter = ',')]
+    pub repos: Vec<String>,
+
+    /// Re-publish the checks that disagree with the freeze state
+    #[arg(long)]
+    pub fix: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct CancelScheduleArgs {
+    /// ID of the scheduled freeze, as listed by `/scheduled`
+    #[arg(long)]
+    pub id: String,
+}
+
+/// Parses a schedule boundary, accepting RFC3339 or a naive local datetime.
+fn parse_schedule_time(s: &str) -> Result<ScheduleTime, String> {
+    if let Ok(dt) = s.parse::<DateTime<Utc>>() {
+        return Ok(ScheduleTime::Absolute(dt));
+    }
+
+    const LOCAL_FORMATS: [&str; 4] = [
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M",
+    ];
+    LOCAL_FORMATS
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(s, fmt).ok())
+        .map(ScheduleTime::Local)
+        .ok_or_else(|| format!("invalid datetime '{}'", s))
+}
+
+/// Parses an IANA timezone name such as "Europe/Rome".
+fn parse_timezone(s: &str) -> Result<Tz, String> {
+    s.parse::<Tz>()
+        .map_err(|_| format!("unknown timezone '{}'", s))
+}
+
+/// A freeze duration as written by the user.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FreezeDuration {
+    /// Wall-clock duration (e.g. "3h")
+    Wall(Duration),
+    /// Number of business hours, skipping time outside working hours (e.g. "8bh")
+    BusinessHours(u32),
+}
+
+impl FreezeDuration {
+    /// Resolves the duration into the wall-clock time between `start` and the expiration
+    pub fn resolve(&self, start: DateTime<Utc>, business_hours: &BusinessHours) -> Duration {
+        match self {
+            FreezeDuration::Wall(duration) => *duration,
+            FreezeDuration::BusinessHours(hours) => business_hours.advance(start, *hours) - start,
+        }
+    }
+}
+
+impl PartialEq<Duration> for FreezeDuration {
+    fn eq(&self, other: &Duration) -> bool {
+        matches!(self, FreezeDuration::Wall(duration) if duration == other)
+    }
 }
 
+/// Parses a GitHub username, accepting an optional leading `@` mention.
+fn parse_username(s: &str) -> Result<String, String> {
+    let username = s.trim().trim_start_matches('@');
+    if username.is_empty() {
+        return Err("username cannot be empty".to_string());
+    }
+    Ok(username.to_string())
+}
+
+/// Longest freeze duration accepted, in hours (ten years)
+pub const MAX_DURATION_HOURS: i64 = 10 * 366 * 24;
+
 /// Parses a duration string into a chrono::Duration.
 ///
 /// Supports both simple format (e.g., "2h", "30m") and ISO 8601 format (e.g., "PT2H30M").
@@ -206,33 +570,57 @@ fn parse_datetime(s: &str) -> Result<DateTime<Utc>, String> {
 /// * `PT<number>S` - seconds (e.g., "PT45S")
 /// * Combined formats (e.g., "PT2H30M", "P1DT2H30M")
 ///
+/// # Business Hours
+///
+/// * `<number>bh` - business hours (e.g., "8bh"), resolved against the configured
+///   working days and hours when the freeze is created
+///
+/// Durations above [`MAX_DURATION_HOURS`] hours are rejected, business hours included.
+///
 /// # Returns
 ///
-/// * `Ok(Duration)` - Successfully parsed duration
+/// * `Ok(FreezeDuration)` - Successfully parsed duration
 /// * `Err(ParseError::InvalidDuration)` - Invalid duration format
-fn parse_duration_2(duration_str: &str) -> Result<chrono::Duration, String> {
+pub fn parse_duration_2(duration_str: &str) -> Result<FreezeDuration, String> {
     let duration_str = duration_str.trim_matches('"');
 
+    if let Some(hours) = duration_str.strip_suffix("bh") {
+        return match hours.parse::<u32>() {
+            Ok(hours) if hours > 0 && i64::from(hours) <= MAX_DURATION_HOURS => {
+                Ok(FreezeDuration::BusinessHours(hours))
+            }
+            _ => Err(duration_str.to_string()),
+        };
+    }
+
     // Handle common duration formats like "2h", "30m", "1d", "45s"
     let duration_regex = regex::Regex::new(r"^(\d+)([smhd])$").unwrap();
 
-    if let Some(captures) = duration_regex.captures(duration_str) {
+    let duration = if let Some(captures) = duration_regex.captures(duration_str) {
         let value: i64 = captures[1].parse().map_err(|_| duration_str.to_string())?;
 
         let unit = &captures[2];
-        let duration = match unit {
-            "s" => chrono::Duration::seconds(value),
-            "m" => chrono::Duration::minutes(value),
-            "h" => chrono::Duration::hours(value),
-            "d" => chrono::Duration::days(value),
+        let unit_seconds = match unit {
+            "s" => 1,
+            "m" => 60,
+            "h" => 60 * 60,
+            "d" => 24 * 60 * 60,
             _ => return Err(duration_str.to_string()),
         };
 
-        Ok(duration)
+        value
+            .checked_mul(unit_seconds)
+            .and_then(chrono::Duration::try_seconds)
+            .ok_or_else(|| duration_str.to_string())?
     } else {
         // Try to parse as ISO 8601 duration (e.g., "PT2H30M")
-        parse_iso8601_duration(duration_str)
+        parse_iso8601_duration(duration_str)?
+    };
+
+    if duration > chrono::Duration::hours(MAX_DURATION_HOURS) {
+        return Err(duration_str.to_string());
     }
+    Ok(FreezeDuration::Wall(duration))
 }
 
 /// Parses an ISO 8601 duration string into a chrono::Duration.
@@ -329,6 +717,8 @@ mod tests {
                 assert!(args.reason.is_none());
                 assert!(args.repos.is_empty());
                 assert!(args.branch.is_none());
+                assert!(!args.if_not_frozen);
+                assert!(!args.merge);
             }
             _ => panic!("Expected Freeze command"),
         }
@@ -369,6 +759,16 @@ mod tests {
             _ => panic!("Expected Freeze command"),
         }
 
+        // Freeze only if not already frozen
+        let cli = parse_cli(&["freeze", "--if-not-frozen", "--duration", "2h"]);
+        match cli.command {
+            Command::Freeze(args) => {
+                assert!(args.if_not_frozen);
+                assert_eq!(args.duration.unwrap(), Duration::hours(2));
+            }
+            _ => panic!("Expected Freeze command"),
+        }
+
         // Freeze with single repo
         let cli = parse_cli(&["freeze", "--repo", "repo1"]);
         match cli.command {
@@ -500,9 +900,153 @@ mod tests {
                 assert!(args.duration.is_none());
                 assert!(args.reason.is_none());
                 assert_eq!(args.repos, vec!["repo1", "repo2"]);
+                assert!(!args.org);
             }
             _ => panic!("Expected FreezeAll command"),
         }
+
+        // Organization-wide freeze
+        let cli = parse_cli(&["freeze-all", "--org", "--duration", "2h"]);
+        match cli.command {
+            Command::FreezeAll(args) => {
+                assert!(args.org);
+                assert_eq!(args.duration.unwrap(), Duration::hours(2));
+            }
+            _ => panic!("Expected FreezeAll command"),
+        }
+        assert!(parse("/freeze-all --org --repo owner/repo").is_err());
+    }
+
+    #[test]
+    fn test_freeze_all_confirm() {
+        let cli = parse_cli(&["freeze-all", "--confirm", "1a2b3c4d"]);
+        match cli.command {
+            Command::FreezeAll(args) => assert_eq!(args.confirm.as_deref(), Some("1a2b3c4d")),
+            _ => panic!("Expected FreezeAll command"),
+        }
+        assert!(parse("/freeze-all --confirm").is_err());
+    }
+
+    #[test]
+    fn test_freeze_merge() {
+        let cli = parse_cli(&["freeze", "--merge", "--duration", "2h"]);
+        match cli.command {
+            Command::Freeze(args) => {
+                assert!(args.merge);
+                assert_eq!(args.duration.unwrap(), Duration::hours(2));
+            }
+            _ => panic!("Expected Freeze command"),
+        }
+        assert!(parse("/freeze --merge --if-not-frozen").is_err());
+        assert!(parse("/freeze --merge --repo owner/repo").is_err());
+        assert!(parse("/freeze --merge --org").is_err());
+    }
+
+    #[test]
+    fn test_freeze_dry_run() {
+        let cli = parse_cli(&["freeze", "--dry-run", "--branch", "main"]);
+        match cli.command {
+            Command::Freeze(args) => {
+                assert!(args.dry_run);
+                assert_eq!(args.branch.as_deref(), Some("main"));
+            }
+            _ => panic!("Expected Freeze command"),
+        }
+        assert!(parse("/freeze --dry-run --repo owner/repo").is_err());
+        assert!(parse("/freeze --dry-run --org").is_err());
+        assert!(parse("/freeze --dry-run --merge").is_err());
+    }
+
+    #[test]
+    fn test_freeze_soft() {
+        let cli = parse_cli(&["freeze", "--soft", "--duration", "2h"]);
+        match cli.command {
+            Command::Freeze(args) => assert!(args.soft),
+            _ => panic!("Expected Freeze command"),
+        }
+        let cli = parse_cli(&["freeze-all", "--soft"]);
+        match cli.command {
+            Command::FreezeAll(args) => assert!(args.soft),
+            _ => panic!("Expected FreezeAll command"),
+        }
+        let cli = parse_cli(&["freeze"]);
+        match cli.command {
+            Command::Freeze(args) => assert!(!args.soft),
+            _ => panic!("Expected Freeze command"),
+        }
+        assert!(parse("/freeze --soft --merge").is_err());
+        assert!(parse("/freeze --soft --environment production").is_err());
+    }
+
+    #[test]
+    fn test_freeze_category() {
+        let cli = parse_cli(&["freeze", "--category", "incident", "--duration", "2h"]);
+        match cli.command {
+            Command::Freeze(args) => assert_eq!(args.category.as_deref(), Some("incident")),
+            _ => panic!("Expected Freeze command"),
+        }
+        let cli = parse_cli(&["freeze-all", "--category", "deploy"]);
+        match cli.command {
+            Command::FreezeAll(args) => assert_eq!(args.category.as_deref(), Some("deploy")),
+            _ => panic!("Expected FreezeAll command"),
+        }
+        let cli = parse_cli(&["freeze"]);
+        match cli.command {
+            Command::Freeze(args) => assert!(args.category.is_none()),
+            _ => panic!("Expected Freeze command"),
+        }
+        assert!(parse("/freeze --category deploy --merge").is_err());
+    }
+
+    #[test]
+    fn test_freeze_wait() {
+        let cli = parse_cli(&["freeze", "--wait", "--duration", "2h"]);
+        match cli.command {
+            Command::Freeze(args) => assert!(args.wait),
+            _ => panic!("Expected Freeze command"),
+        }
+        let cli = parse_cli(&["freeze"]);
+        match cli.command {
+            Command::Freeze(args) => assert!(!args.wait),
+            _ => panic!("Expected Freeze command"),
+        }
+        assert!(parse("/freeze --wait --repo owner/repo").is_err());
+        assert!(parse("/freeze --wait --org").is_err());
+        assert!(parse("/freeze --wait --dry-run").is_err());
+    }
+
+    #[test]
+    fn test_freeze_environment() {
+        let cli = parse_cli(&["freeze", "--environment", "production", "--duration", "2h"]);
+        match cli.command {
+            Command::Freeze(args) => {
+                assert_eq!(args.environment.as_deref(), Some("production"));
+                assert_eq!(args.duration.unwrap(), Duration::hours(2));
+            }
+            _ => panic!("Expected Freeze command"),
+        }
+        assert!(parse("/freeze --environment production --repo owner/repo").is_err());
+        assert!(parse("/freeze --environment production --branch main").is_err());
+        assert!(parse("/freeze-all --environment production --org").is_err());
+
+        let cli = parse_cli(&["unfreeze", "--environment", "production"]);
+        match cli.command {
+            Command::Unfreeze(args) => assert_eq!(args.environment.as_deref(), Some("production")),
+            _ => panic!("Expected Unfreeze command"),
+        }
+    }
+
+    #[test]
+    fn test_freeze_reason_file() {
+        let cli = parse_cli(&["freeze", "--reason-file", ".github/FREEZE.md"]);
+        match cli.command {
+            Command::Freeze(args) => {
+                assert_eq!(args.reason_file.as_deref(), Some(".github/FREEZE.md"));
+                assert!(args.reason.is_none());
+            }
+            _ => panic!("Expected Freeze command"),
+        }
+        assert!(parse("/freeze --reason-file FREEZE.md --reason \"Release\"").is_err());
     }
 
     #[test]
@@ -548,6 +1092,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_unfreeze_all_command() {
+        // Unfreeze everything
+        let cli = parse_cli(&["unfreeze-all"]);
+        match cli.command {
+            Command::UnfreezeAll(args) => {
+                assert!(args.repos.is_empty());
+                assert!(args.branch.is_none());
+                assert!(args.reason.is_none());
+            }
+            _ => panic!("Expected UnfreezeAll command"),
+        }
+
+        // Unfreeze a subset on a specific branch
+        let cli = parse_cli(&[
+            "unfreeze-all",
+            "--repo",
+            "owner/repo1,owner/repo2",
+            "--repo",
+            "owner/repo3",
+            "--branch",
+            "main",
+            "--reason",
+            "Release shipped",
+        ]);
+        match cli.command {
+            Command::UnfreezeAll(args) => {
+                assert_eq!(args.repos, vec!["owner/repo1", "owner/repo2", "owner/repo3"]);
+                assert_eq!(args.branch.unwrap(), "main");
+                assert_eq!(args.reason.unwrap(), "Release shipped");
+            }
+            _ => panic!("Expected UnfreezeAll command"),
+        }
+    }
+
     #[test]
     fn test_status_command() {
         // Status with single repo
@@ -564,9 +1143,46 @@ mod tests {
         match cli.command {
             Command::Status(args) => {
                 assert_eq!(args.repos, vec!["repo1", "repo2", "repo3"]);
+                assert!(!args.as_check);
+            }
+            _ => panic!("Expected Status command"),
+        }
+
+        // Status published as a check run
+        let cli = parse_cli(&["status", "--as-check"]);
+        match cli.command {
+            Command::Status(args) => {
+                assert!(args.repos.is_empty());
+                assert!(args.as_check);
+                assert!(!args.debug);
+            }
+            _ => panic!("Expected Status command"),
+        }
+
+        // Status with refresh debug information
+        let cli = parse_cli(&["status", "--debug"]);
+        match cli.command {
+            Command::Status(args) => assert!(args.debug),
+            _ => panic!("Expected Status command"),
+        }
+
+        // Status of a single branch
+        let cli = parse_cli(&["status", "--branch", "main"]);
+        match cli.command {
+            Command::Status(args) => assert_eq!(args.branch.as_deref(), Some("main")),
+            _ => panic!("Expected Status command"),
+        }
+
+        // Status kept in a pinned comment
+        let cli = parse_cli(&["status", "--pinned"]);
+        match cli.command {
+            Command::Status(args) => {
+                assert!(args.pinned);
+                assert!(!args.as_check);
             }
             _ => panic!("Expected Status command"),
         }
+        assert!(parse("/status --pinned --as-check").is_err());
     }
 
     #[test]
@@ -580,7 +1196,7 @@ mod tests {
         let cli = parse_cli(&["schedule-freeze", "--from", &from_str]);
         match cli.command {
             Command::ScheduleFreeze(args) => {
-                assert_eq!(args.from.to_rfc3339(), from_str);
+                assert_eq!(args.resolve_window().unwrap().0.to_rfc3339(), from_str);
                 assert!(args.to.is_none());
                 assert!(args.duration.is_none());
                 assert!(args.reason.is_none());
@@ -602,8 +1218,9 @@ mod tests {
         ]);
         match cli.command {
             Command::ScheduleFreeze(args) => {
-                assert_eq!(args.from.to_rfc3339(), from_str);
-                assert_eq!(args.to.unwrap().to_rfc3339(), to_str);
+                let (from, to) = args.resolve_window().unwrap();
+                assert_eq!(from.to_rfc3339(), from_str);
+                assert_eq!(to.unwrap().to_rfc3339(), to_str);
                 assert_eq!(args.duration.unwrap(), Duration::hours(2));
                 assert_eq!(args.reason.unwrap(), "maintenance");
             }
@@ -611,6 +1228,75 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_schedule_freeze_with_timezone() {
+        let window = |args: &[&str]| {
+            let mut argv = vec!["schedule-freeze"];
+            argv.extend_from_slice(args);
+            match parse_cli(&argv).command {
+                Command::ScheduleFreeze(args) => args.resolve_window(),
+                _ => panic!("Expected ScheduleFreeze command"),
+            }
+        };
+
+        // Summer time in Rome is UTC+2
+        let (from, to) = window(&[
+            "--from",
+            "2025-07-01 10:00",
+            "--to",
+            "2025-07-01T18:30:00",
+            "--timezone",
+            "Europe/Rome",
+        ])
+        .unwrap();
+        assert_eq!(from.to_rfc3339(), "2025-07-01T08:00:00+00:00");
+        assert_eq!(to.unwrap().to_rfc3339(), "2025-07-01T16:30:00+00:00");
+
+        // Winter time in Rome is UTC+1
+        let (from, _) = window(&["--from", "2025-01-15 10:00", "--timezone", "Europe/Rome"]).unwrap();
+        assert_eq!(from.to_rfc3339(), "2025-01-15T09:00:00+00:00");
+
+        // RFC3339 values keep their own offset
+        let (from, _) = window(&[
+            "--from",
+            "2025-01-15T10:00:00Z",
+            "--timezone",
+            "Europe/Rome",
+        ])
+        .unwrap();
+        assert_eq!(from.to_rfc3339(), "2025-01-15T10:00:00+00:00");
+
+        // DST gap: 02:30 does not exist on spring-forward day in New York
+        assert!(matches!(
+            window(&["--from", "2025-03-09 02:30", "--timezone", "America/New_York"]),
+            Err(ParsingError::NonexistentLocalTime(_, _))
+        ));
+
+        // DST fold: 01:30 happens twice on fall-back day, the earlier (EDT) wins
+        let (from, _) =
+            window(&["--from", "2025-11-02 01:30", "--timezone", "America/New_York"]).unwrap();
+        assert_eq!(from.to_rfc3339(), "2025-11-02T05:30:00+00:00");
+
+        // Local times need a timezone
+        assert!(matches!(
+            window(&["--from", "2025-01-15 10:00"]),
+            Err(ParsingError::MissingTimezone(_))
+        ));
+    }
+
+    #[test]
+    fn test_schedule_freeze_rejects_unknown_timezone() {
+        let argv = [
+            "bin",
+            "schedule-freeze",
+            "--from",
+            "2025-01-15 10:00",
+            "--timezone",
+            "Mars/Olympus_Mons",
+        ];
+        assert!(Cli::try_parse_from(argv).is_err());
+    }
+
     #[test]
     fn test_duration_parsing() {
         // Test simple duration formats
@@ -631,6 +1317,39 @@ mod tests {
             parse_duration_2("P1DT2H30M").unwrap(),
             Duration::days(1) + Duration::hours(2) + Duration::minutes(30)
         );
+
+        // Durations beyond the maximum are rejected instead of overflowing
+        assert!(parse_duration_2("3660d").is_ok());
+        assert!(parse_duration_2("3661d").is_err());
+        assert!(parse_duration_2("9999999999999999d").is_err());
+        assert!(parse_duration_2("P4000D").is_err());
+    }
+
+    #[test]
+    fn test_business_hours_duration() {
+        assert_eq!(parse_duration_2("8bh").unwrap(), FreezeDuration::BusinessHours(8));
+        assert_ne!(parse_duration_2("8bh").unwrap(), Duration::hours(8));
+        assert!(parse_duration_2("0bh").is_err());
+        assert!(parse_duration_2("bh").is_err());
+        assert!(parse_duration_2("1.5bh").is_err());
+        assert!(parse_duration_2("4000000000bh").is_err());
+        assert!(parse_duration_2(&format!("{}bh", MAX_DURATION_HOURS)).is_ok());
+        assert!(parse_duration_2(&format!("{}bh", MAX_DURATION_HOURS + 1)).is_err());
+
+        let cli = parse_cli(&["freeze", "--duration", "8bh"]);
+        let Command::Freeze(args) = cli.command else {
+            panic!("Expected Freeze command");
+        };
+        // Friday 14:00 UTC: 3 hours on Friday and 5 on Monday
+        let start = Utc.with_ymd_and_hms(2025, 1, 10, 14, 0, 0).unwrap();
+        let duration = args.duration.unwrap().resolve(start, &BusinessHours::default());
+        assert_eq!(
+            start + duration,
+            Utc.with_ymd_and_hms(2025, 1, 13, 14, 0, 0).unwrap()
+        );
+
+        let wall = FreezeDuration::Wall(Duration::hours(8));
+        assert_eq!(wall.resolve(start, &BusinessHours::default()), Duration::hours(8));
     }
 
     #[test]
@@ -681,4 +1400,227 @@ mod tests {
             _ => panic!("Expected UnlockPr command"),
         }
     }
+
+    #[test]
+    fn test_freeze_reassign_command() {
+        // Reassign with an @-mention
+        let cli = parse_cli(&["freeze-reassign", "--to", "@octocat"]);
+        match cli.command {
+            Command::FreezeReassign(args) => {
+                assert_eq!(args.to, "octocat");
+                assert!(args.branch.is_none());
+            }
+            _ => panic!("Expected FreezeReassign command"),
+        }
+
+        // Reassign a branch freeze with a plain username
+        let cli = parse_cli(&["freeze-reassign", "--to", "octocat", "--branch", "main"]);
+        match cli.command {
+            Command::FreezeReassign(args) => {
+                assert_eq!(args.to, "octocat");
+                assert_eq!(args.branch.unwrap(), "main");
+            }
+            _ => panic!("Expected FreezeReassign command"),
+        }
+
+        // --to is required and cannot be empty
+        assert!(parse("/freeze-reassign").is_err());
+        assert!(parse("/freeze-reassign --to @").is_err());
+    }
+
+    #[test]
+    fn test_extend_command() {
+        let cli = parse_cli(&["extend", "2h"]);
+        match cli.command {
+            Command::Extend(args) => {
+                assert_eq!(args.duration, Duration::hours(2));
+                assert!(args.branch.is_none());
+            }
+            _ => panic!("Expected Extend command"),
+        }
+
+        let cli = parse_cli(&["extend", "8bh", "--branch", "main"]);
+        match cli.command {
+            Command::Extend(args) => {
+                assert_eq!(args.duration, FreezeDuration::BusinessHours(8));
+                assert_eq!(args.branch.unwrap(), "main");
+            }
+            _ => panic!("Expected Extend command"),
+        }
+
+        // The duration is required and must be valid
+        assert!(parse("/extend").is_err());
+        assert!(parse("/extend soon").is_err());
+    }
+
+    #[test]
+    fn test_audit_command() {
+        let cli = parse_cli(&["audit"]);
+        match cli.command {
+            Command::Audit(args) => assert_eq!(args.limit, 20),
+            _ => panic!("Expected Audit command"),
+        }
+
+        let cli = parse_cli(&["audit", "--limit", "5"]);
+        match cli.command {
+            Command::Audit(args) => assert_eq!(args.limit, 5),
+            _ => panic!("Expected Audit command"),
+        }
+        assert!(parse("/audit --limit many").is_err());
+    }
+
+    #[test]
+    fn test_preview_command() {
+        let cli = parse_cli(&["preview"]);
+        match cli.command {
+            Command::Preview(args) => assert!(args.message_type.is_none()),
+            _ => panic!("Expected Preview command"),
+        }
+
+        let cli = parse_cli(&["preview", "freeze-success"]);
+        match cli.command {
+            Command::Preview(args) => assert_eq!(args.message_type.unwrap(), "freeze-success"),
+            _ => panic!("Expected Preview command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_with_prefix() {
+        let cli = parse_with_prefix("/frezze freeze --duration 2h", Some("frezze")).unwrap();
+        match cli.command {
+            Command::Freeze(args) => assert_eq!(args.duration.unwrap(), Duration::hours(2)),
+            _ => panic!("Expected Freeze command"),
+        }
+        let cli = parse_with_prefix("/frz   unfreeze", Some("/frz")).unwrap();
+        assert_eq!(cli.command.name(), "unfreeze");
+
+        // Bare verbs and other bots' commands are not ours once a prefix is set
+        for input in ["/freeze", "/frezzefreeze", "/frezze", "/other freeze"] {
+            assert!(
+                matches!(
+                    parse_with_prefix(input, Some("frezze")),
+                    Err(ParsingError::NotACommand)
+                ),
+                "input: {}",
+                input
+            );
+        }
+        assert!(matches!(
+            parse_with_prefix("/frezze unknown", Some("frezze")),
+            Err(ParsingError::MalformedCommand)
+        ));
+
+        // Without a prefix, commands are bare verbs
+        assert_eq!(parse_with_prefix("/freeze", None).unwrap().command.name(), "freeze");
+        assert!(parse_with_prefix("/frezze freeze", None).is_err());
+    }
+
+    #[test]
+    fn test_scheduled_commands() {
+        assert!(matches!(parse_cli(&["scheduled"]).command, Command::Scheduled));
+
+        let cli = parse_cli(&["cancel-schedule", "--id", "0b4e7c1a-scheduled"]);
+        match cli.command {
+            Command::CancelSchedule(args) => assert_eq!(args.id, "0b4e7c1a-scheduled"),
+            _ => panic!("Expected CancelSchedule command"),
+        }
+        assert!(parse("/cancel-schedule").is_err());
+
+        assert!(matches!(
+            parse_cli(&["scheduler-preview"]).command,
+            Command::SchedulerPreview
+        ));
+    }
+
+    #[test]
+    fn test_reconcile_command() {
+        let cli = parse_cli(&["reconcile"]);
+        match cli.command {
+            Command::Reconcile(args) => {
+                assert!(args.repos.is_empty());
+                assert!(!args.fix);
+            }
+            _ => panic!("Expected Reconcile command"),
+        }
+
+        let cli = parse_cli(&["reconcile", "--repo", "owner/repo1,owner/repo2", "--fix"]);
+        match cli.command {
+            Command::Reconcile(args) => {
+                assert_eq!(args.repos, vec!["owner/repo1", "owner/repo2"]);
+                assert!(args.fix);
+            }
+            _ => panic!("Expected Reconcile command"),
+        }
+    }
+
+    #[test]
+    fn test_approve_unfreeze_command() {
+        assert!(matches!(
+            parse_cli(&["approve-unfreeze"]).command,
+            Command::ApproveUnfreeze
+        ));
+        assert!(parse("/approve-unfreeze --repo owner/repo").is_err());
+    }
+
+    #[test]
+    fn test_help_requested() {
+        for name in Command::NAMES {
+            for flag in ["--help", "-h"] {
+                match parse(&format!("/{} {}", name, flag)) {
+                    Err(ParsingError::HelpRequested(help)) => {
+                        assert!(
+                            help.contains(&format!("Usage: /{}", name)),
+                            "help of {}: {}",
+                            name,
+                            help
+                        );
+                    }
+                    _ => panic!("Expected help for /{} {}", name, flag),
+                }
+            }
+        }
+
+        // Help of a single option lists its description
+        match parse("/freeze --help") {
+            Err(ParsingError::HelpRequested(help)) => assert!(help.contains("--duration")),
+            _ => panic!("Expected help for /freeze"),
+        }
+
+        // `/help` lists every command
+        match parse("/help") {
+            Err(ParsingError::HelpRequested(help)) => {
+                assert!(help.contains("freeze-all"));
+                assert!(help.contains("Usage: /<COMMAND>"));
+            }
+            _ => panic!("Expected help for /help"),
+        }
+
+        // The usage shows the configured prefix
+        match parse_with_prefix("/frezze freeze --help", Some("frezze")) {
+            Err(ParsingError::HelpRequested(help)) => {
+                assert!(help.contains("Usage: /frezze freeze"))
+            }
+            _ => panic!("Expected help for /frezze freeze"),
+        }
+
+        // Unknown options are still malformed
+        assert!(matches!(
+            parse("/freeze --helpme"),
+            Err(ParsingError::MalformedCommand)
+        ));
+    }
+
+    #[test]
+    fn test_command_names() {
+        use clap::CommandFactory;
+
+        let subcommands: Vec<String> = Cli::command()
+            .get_subcommands()
+            .map(|c| c.get_name().to_string())
+            .collect();
+        assert_eq!(subcommands, Command::NAMES);
+
+        assert_eq!(parse("/freeze-all").unwrap().command.name(), "freeze-all");
+        assert_eq!(parse("/unlock-pr").unwrap().command.name(), "unlock-pr");
+    }
 }
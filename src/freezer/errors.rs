@@ -10,8 +10,32 @@ pub enum ParsingError {
     NotACommand,
     #[error("malformed command")]
     MalformedCommand,
+    #[error("help requested")]
+    HelpRequested(String),
     #[error("please use a valid command")]
     EmptyCommand,
     #[error("invalid datetime")]
     InvalidDateTime,
+    #[error("local datetime '{0}' requires --timezone")]
+    MissingTimezone(String),
+    #[error("'{0}' does not exist in timezone {1}")]
+    NonexistentLocalTime(String, String),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum CheckRunError {
+    #[error("missing permission to create check runs: {0}")]
+    Forbidden(String),
+    #[error("failed to create check run: {0}")]
+    Other(String),
+}
+
+impl CheckRunError {
+    /// Classifies a failed check run request by its HTTP status code
+    pub fn from_status(status_code: Option<u16>, message: String) -> Self {
+        match status_code {
+            Some(403) => CheckRunError::Forbidden(message),
+            _ => CheckRunError::Other(message),
+        }
+    }
 }
@@ -1,12 +1,19 @@
-use std::sync::Arc;
+use std::{future::Future, pin::Pin, sync::Arc};
 
 use crate::{
+    config::AppConfig,
     database::{
         Database,
-        models::{FreezeRecord, UnlockedPr},
+        models::{
+            CommandLog, CommandResult, FreezeCategory, FreezeRecord, FreezeReminder,
+            PendingConfirmation, PendingUnfreeze, RefreshStats, StatusComment, UnlockedPr,
+        },
     },
-    freezer::messages,
+    freezer::{business_hours::BusinessHours, cache::FreezeCache, commands, messages},
+    installation::InstallationId,
+    notifications::{FreezeEvent, FreezeEventKind, NotificationDispatcher},
     repository::Repository,
+    worker,
 };
 use anyhow::{Result, anyhow};
 use chrono::{DateTime, Utc};
@@ -14,10 +21,48 @@ use octofer::github::GitHubClient;
 use octofer::octocrab;
 use tracing::{error, info, warn};
 
-use super::pr_refresh::PrRefreshService;
+use super::pr_refresh::{PrRefreshService, PullRequestInfo, RefreshConfig, RefreshResult};
 
 pub const DEFAULT_FREEZE_DURATION: chrono::Duration = chrono::Duration::hours(2);
 
+/// How long a `/freeze-all` waits for its confirmation token
+pub const FREEZE_ALL_CONFIRMATION_TTL: chrono::Duration = chrono::Duration::minutes(10);
+
+/// How long a `/unfreeze` waits for one of the required approvers
+pub const UNFREEZE_APPROVAL_TTL: chrono::Duration = chrono::Duration::hours(24);
+
+/// Largest number of commands `/audit` will list
+pub const MAX_AUDIT_LIMIT: u32 = 100;
+
+/// Longest reason, in characters, read from a `--reason-file`
+pub const MAX_REASON_FILE_LENGTH: usize = 2000;
+
+/// Number of recent reasons suggested when a required reason is missing
+pub const RECENT_REASONS_LIMIT: u32 = 5;
+
+/// Options given with a freeze command, passed along to every freeze it creates
+#[derive(Debug, Clone, Default)]
+pub struct FreezeOptions {
+    /// How long the freeze lasts, [`DEFAULT_FREEZE_DURATION`] when not given
+    pub duration: Option<chrono::Duration>,
+    /// Repositories to freeze instead of the current one (`--repo`)
+    pub repos: Vec<String>,
+    /// Branch the freeze is limited to (`--branch`)
+    pub branch: Option<String>,
+    /// Environment whose deployments are frozen instead of pull requests (`--environment`)
+    pub environment: Option<String>,
+    /// Whether the initiator may bypass safety guards such as the minimum duration
+    pub can_override: bool,
+    /// Succeed without a new freeze when the target is already frozen (`--if-not-frozen`)
+    pub if_not_frozen: bool,
+    /// Mark blocked pull requests as requiring action instead of failing them (`--soft`)
+    pub soft: bool,
+    /// Structured reason of the freeze (`--category`)
+    pub category: Option<FreezeCategory>,
+    /// Wait for the pull request refresh and report its counts (`--wait`)
+    pub wait: bool,
+}
+
 #[derive(Debug)]
 pub struct StatusEntry {
     pub freeze_status: FreezeStatus,
@@ -89,33 +134,656 @@ impl StatusEntry {
     }
 }
 
+/// Parses `--repo` values into repositories, collecting an error for each invalid name.
+///
+/// Pasted GitHub URLs and `.git` suffixes are accepted, see [`Repository::parse_flexible`].
+///
+/// Repositories given more than once (case-insensitively) are kept once; the
+/// repeated names are returned as notes so the summary can mention them.
+fn parse_repositories(repo_names: &[String]) -> (Vec<Repository>, Vec<String>, Vec<String>) {
+    let mut repositories: Vec<Repository> = Vec::new();
+    let mut errors = Vec::new();
+    let mut duplicates = Vec::new();
+
+    for repo_name in repo_names {
+        match Repository::parse_flexible(repo_name) {
+            Some(repository)
+                if repositories
+                    .iter()
+                    .any(|r| r.full_name().eq_ignore_ascii_case(&repository.full_name())) =>
+            {
+                duplicates.push(format!("`{}` (duplicate)", repository.full_name()));
+            }
+            Some(repository) => repositories.push(repository),
+            None => errors.push(format!(
+                "Invalid repository format '{}'. Expected 'owner/repo' or a GitHub URL",
+                repo_name
+            )),
+        }
+    }
+
+    (repositories, errors, duplicates)
+}
+
+/// Normalises configured watchers into the users to mention.
+///
+/// Leading `@`s are stripped, duplicates are removed case-insensitively and the
+/// initiator is dropped since they already know about the change.
+fn resolve_watchers(watchers: &[String], initiator: &str) -> Vec<String> {
+    let mut resolved: Vec<String> = Vec::new();
+    for watcher in watchers {
+        let login = watcher.trim().trim_start_matches('@');
+        if login.is_empty()
+            || login.eq_ignore_ascii_case(initiator)
+            || resolved.iter().any(|w| w.eq_ignore_ascii_case(login))
+        {
+            continue;
+        }
+        resolved.push(login.to_string());
+    }
+    resolved
+}
+
+/// Picks the ops contacts to mention below a multi-repository summary.
+///
+/// Contacts are only pinged when some repositories failed, so that someone
+/// investigates them. The user who ran the command already reads the summary.
+fn failure_contacts(failed: usize, contacts: &[String], initiator: &str) -> Vec<String> {
+    if failed == 0 {
+        return Vec::new();
+    }
+    resolve_watchers(contacts, initiator)
+}
+
+/// Composes the alert posted when a PR is unlocked during a freeze.
+///
+/// The freeze's owner and the repository's watchers are mentioned, except
+/// for the user who unlocked the PR. Nothing is posted when alerts are
+/// disabled or nobody is left to alert.
+fn unlock_alert(
+    enabled: bool,
+    freeze: &FreezeRecord,
+    watchers: &[String],
+    unlocked_by: &str,
+    pr_number: u64,
+    pr_url: &str,
+    reason_str: &str,
+) -> Option<String> {
+    if !enabled {
+        return None;
+    }
+    let mut candidates = vec![freeze.initiated_by.clone()];
+    candidates.extend_from_slice(watchers);
+    let recipients = resolve_watchers(&candidates, unlocked_by);
+    if recipients.is_empty() {
+        return None;
+    }
+    Some(messages::pr_unlock_alert(
+        &freeze.repository,
+        pr_number,
+        pr_url,
+        unlocked_by,
+        reason_str,
+        &recipients,
+    ))
+}
+
+/// Returns why an installation repository cannot be frozen, if it cannot.
+///
+/// Archived repositories are read-only, and repositories without a default
+/// branch have no pull requests to block. Empty repositories that do report a
+/// default branch are only caught by [`FreezeManager::freezable_repositories`].
+fn skip_reason(repo: &octocrab::models::Repository) -> Option<&'static str> {
+    if repo.archived == Some(true) {
+        Some("archived")
+    } else if repo.default_branch.is_none() {
+        Some("empty")
+    } else {
+        None
+    }
+}
+
+/// Rejects freezes of archived repositories, which are read-only on GitHub.
+fn check_not_archived(repo: &octocrab::models::Repository) -> Result<()> {
+    if repo.archived == Some(true) {
+        return Err(anyhow!(
+            "Repository `{}` is archived and cannot be frozen",
+            Repository::from(repo).full_name()
+        ));
+    }
+    Ok(())
+}
+
+/// Splits installation repositories into freezable ones and notes for the skipped ones.
+fn partition_freezable(
+    repositories: &[octocrab::models::Repository],
+) -> (Vec<&octocrab::models::Repository>, Vec<String>) {
+    let mut usable = Vec::new();
+    let mut skipped = Vec::new();
+
+    for repo in repositories {
+        match skip_reason(repo) {
+            Some(reason) => skipped.push(format!(
+                "`{}` ({})",
+                Repository::from(repo).full_name(),
+                reason
+            )),
+            None => usable.push(repo),
+        }
+    }
+
+    (usable, skipped)
+}
+
+/// Maps the outcome of a command on several repositories onto its audit log result.
+fn multi_repo_result(
+    succeeded: usize,
+    failed: usize,
+    errors: &[String],
+) -> (CommandResult, Option<String>) {
+    let result = match (succeeded, failed) {
+        (_, 0) => CommandResult::Success,
+        (0, _) => CommandResult::Failure,
+        (succeeded, failed) => CommandResult::PartialSuccess { succeeded, failed },
+    };
+    (result, (!errors.is_empty()).then(|| errors.join("; ")))
+}
+
+/// Maps the outcome of a command on a single repository onto its audit log result.
+fn command_result<T>(outcome: &Result<T>) -> (CommandResult, Option<String>) {
+    match outcome {
+        Ok(_) => (CommandResult::Success, None),
+        Err(e) => (CommandResult::Failure, Some(e.to_string())),
+    }
+}
+
+/// Counts of the PR refresh shown after a `/freeze --wait`, or nothing
+/// when the freeze did not wait for the refresh.
+fn refresh_note(refreshed: Option<&RefreshResult>) -> String {
+    refreshed.map_or_else(String::new, |r| {
+        messages::format_refresh_display(r.successful_updates, r.failed_updates, r.total_prs)
+    })
+}
+
+/// Command a user replies with to confirm a `/freeze-all`
+fn confirm_command(command_prefix: Option<&str>, token: &str) -> String {
+    match command_prefix {
+        Some(prefix) => format!("/{} freeze-all --confirm {}", prefix.trim_start_matches('/'), token),
+        None => format!("/freeze-all --confirm {}", token),
+    }
+}
+
+/// Command a required approver replies with to approve a `/unfreeze`
+fn approve_unfreeze_command(command_prefix: Option<&str>) -> String {
+    match command_prefix {
+        Some(prefix) => format!("/{} approve-unfreeze", prefix.trim_start_matches('/')),
+        None => "/approve-unfreeze".to_string(),
+    }
+}
+
+/// Takes the pending unfreeze of a repository on behalf of one of its approvers.
+///
+/// The request stays pending when the user is not one of its approvers.
+async fn take_unfreeze_approval(
+    pool: &sqlx::SqlitePool,
+    installation_id: InstallationId,
+    repository: &str,
+    approver: &str,
+    now: DateTime<Utc>,
+) -> Result<PendingUnfreeze> {
+    let pending = PendingUnfreeze::find(pool, installation_id, repository, now)
+        .await?
+        .ok_or_else(|| {
+            anyhow!(
+                "There is no pending unfreeze of `{}` to approve",
+                repository
+            )
+        })?;
+
+    if !pending.is_approver(approver) {
+        let approvers: Vec<String> = pending
+            .approvers
+            .iter()
+            .map(|a| format!("@{}", a))
+            .collect();
+        return Err(anyhow!(
+            "Only {} can approve the unfreeze of `{}`",
+            approvers.join(", "),
+            repository
+        ));
+    }
+    if !PendingUnfreeze::remove(pool, installation_id, repository).await? {
+        return Err(anyhow!(
+            "The unfreeze of `{}` was already approved",
+            repository
+        ));
+    }
+
+    Ok(pending)
+}
+
+/// Finds the freeze in effect that already covers the given branch.
+///
+/// Repository-wide freezes cover every branch; branch freezes only cover
+/// their own branch.
+fn covering_freeze<'a>(
+    records: &'a [FreezeRecord],
+    branch: Option<&str>,
+    now: DateTime<Utc>,
+) -> Option<&'a FreezeRecord> {
+    records.iter().find(|r| {
+        let in_effect = r.started_at <= now && r.expires_at.is_none_or(|e| e > now);
+        let covers = match (&r.branch, branch) {
+            (None, _) => true,
+            (Some(frozen), Some(target)) => frozen == target,
+            (Some(_), None) => false,
+        };
+        in_effect && covers
+    })
+}
+
+/// Returns the repository's own active freeze a new freeze would overlap.
+///
+/// Organization-wide and environment freezes are never merged into. Fails when
+/// the new freeze overlaps several freezes, e.g. a repository-wide freeze on
+/// top of multiple branch freezes.
+fn merge_target<'a>(
+    records: &'a [FreezeRecord],
+    repository: &str,
+    branch: Option<&str>,
+) -> Result<Option<&'a FreezeRecord>> {
+    let overlapping: Vec<&FreezeRecord> = records
+        .iter()
+        .filter(|r| r.repository.eq_ignore_ascii_case(repository) && r.environment.is_none())
+        .filter(|r| match (r.branch.as_deref(), branch) {
+            (Some(frozen), Some(target)) => frozen == target,
+            _ => true,
+        })
+        .collect();
+
+    match overlapping.as_slice() {
+        [] => Ok(None),
+        [record] => Ok(Some(record)),
+        _ => Err(anyhow!(
+            "The freeze overlaps {} freezes of {}; use --branch to pick the one to merge into",
+            overlapping.len(),
+            repository
+        )),
+    }
+}
+
+/// End of the union of an existing freeze and a new window ending at `new_end`
+fn merged_expiry(
+    existing: Option<DateTime<Utc>>,
+    new_end: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    // An indefinite freeze already covers any window
+    existing.map(|end| end.max(new_end))
+}
+
+/// New end of a freeze extended by `duration`.
+///
+/// Timed freezes are pushed back from their current end. Open-ended freezes,
+/// the ones reminders are posted about, end `duration` from now instead.
+fn extended_expiry(
+    existing: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    duration: &commands::FreezeDuration,
+    business_hours: &BusinessHours,
+) -> DateTime<Utc> {
+    let start = existing.map_or(now, |end| end.max(now));
+    start + duration.resolve(start, business_hours)
+}
+
+/// Appends the reason of a merged freeze to the existing one, skipping repeats
+fn merged_reason(existing: Option<&str>, new: Option<&str>) -> Option<String> {
+    match (existing, new) {
+        (Some(existing), Some(new)) if existing == new => Some(existing.to_string()),
+        (Some(existing), Some(new)) => Some(format!("{}; {}", existing, new)),
+        (existing, new) => existing.or(new).map(str::to_string),
+    }
+}
+
+/// Lists the authors of open PRs a freeze would block, sorted and deduplicated.
+///
+/// PRs against other branches than the frozen one are not blocked, and
+/// neither are bots nor users exempt from freezes (allowed to override them).
+fn blocked_authors(
+    prs: &[PullRequestInfo],
+    branch: Option<&str>,
+    is_exempt: impl Fn(&str) -> bool,
+) -> Vec<String> {
+    let mut authors: Vec<String> = prs
+        .iter()
+        .filter(|pr| branch.is_none_or(|b| b == pr.base_ref) && !pr.is_bot)
+        .filter_map(|pr| pr.author.clone())
+        .filter(|login| !is_exempt(login))
+        .collect();
+    authors.sort_by_key(|login| login.to_lowercase());
+    authors.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    authors
+}
+
+/// Builds one status row per active freeze of a repository.
+///
+/// A repository can hold several active freezes when they target different
+/// branches. Repository-wide freezes are listed first, followed by branch
+/// freezes in alphabetical order. With a `branch`, only the freezes affecting
+/// it are listed: repository-wide freezes and the freezes of that branch.
+fn repository_status_entries(records: &[FreezeRecord], branch: Option<&str>) -> Vec<StatusEntry> {
+    let mut records: Vec<&FreezeRecord> = records
+        .iter()
+        .filter(|r| match (&r.branch, branch) {
+            (Some(frozen), Some(branch)) => frozen == branch,
+            _ => true,
+        })
+        .collect();
+    if records.is_empty() {
+        return vec![StatusEntry::not_frozen()];
+    }
+
+    records.sort_by(|a, b| a.branch.cmp(&b.branch));
+    records.into_iter().map(StatusEntry::frozen).collect()
+}
+
+/// Checks whether a user may transfer ownership of a freeze.
+///
+/// Admins can always reassign a freeze; anyone else must be its current owner.
+fn can_reassign(record: &FreezeRecord, requested_by: &str, requester_is_admin: bool) -> bool {
+    requester_is_admin || record.initiated_by.eq_ignore_ascii_case(requested_by)
+}
+
+/// Expands the built-in variables of a freeze reason.
+///
+/// `{repo}`, `{user}` and `{date}` are replaced with the repository, the user
+/// creating the freeze and the day it starts. `{{` and `}}` produce literal
+/// braces, and unknown variables are kept as typed.
+fn expand_reason(reason: &str, repository: &str, user: &str, start: DateTime<Utc>) -> String {
+    let date = start.format("%Y-%m-%d").to_string();
+    let mut expanded = String::with_capacity(reason.len());
+    let mut rest = reason;
+
+    while let Some(pos) = rest.find(['{', '}']) {
+        expanded.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            expanded.push_str(&rest[..1]);
+            rest = &rest[2..];
+            continue;
+        }
+
+        let variable = rest.strip_prefix('{').and_then(|inner| {
+            let end = inner.find('}')?;
+            let value = match &inner[..end] {
+                "repo" => repository,
+                "user" => user,
+                "date" => date.as_str(),
+                _ => return None,
+            };
+            // Both braces around the name are consumed
+            Some((value, end + 2))
+        });
+        match variable {
+            Some((value, len)) => {
+                expanded.push_str(value);
+                rest = &rest[len..];
+            }
+            None => {
+                expanded.push_str(&rest[..1]);
+                rest = &rest[1..];
+            }
+        }
+    }
+    expanded.push_str(rest);
+
+    expanded
+}
+
+/// Ensures a freeze lasts at least the configured minimum duration.
+///
+/// Users allowed to perform emergency overrides may bypass the check.
+fn check_min_duration(
+    duration: chrono::Duration,
+    min_duration: chrono::Duration,
+    can_override: bool,
+) -> Result<()> {
+    if can_override || duration >= min_duration {
+        return Ok(());
+    }
+
+    Err(anyhow!(
+        "Freeze duration of {}s is below the minimum of {}s",
+        duration.num_seconds(),
+        min_duration.num_seconds()
+    ))
+}
+
+/// Resolves when a scheduled freeze ends.
+///
+/// `end` wins over `duration`, but when both are given they must describe the
+/// same instant. The window must not end before it starts, even for users
+/// allowed to override the minimum duration.
+fn resolve_schedule_end(
+    start: DateTime<Utc>,
+    end: Option<DateTime<Utc>>,
+    duration: Option<chrono::Duration>,
+) -> Result<DateTime<Utc>> {
+    let end_time = match (end, duration) {
+        (Some(end), Some(dur)) if start + dur != end => {
+            return Err(anyhow!(
+                "--to ({}) does not match --from ({}) plus --duration ({}s)",
+                end.format("%Y-%m-%d %H:%M:%S UTC"),
+                start.format("%Y-%m-%d %H:%M:%S UTC"),
+                dur.num_seconds()
+            ));
+        }
+        (Some(end), _) => end,
+        (None, Some(dur)) => start + dur,
+        (None, None) => start + DEFAULT_FREEZE_DURATION,
+    };
+
+    if end_time <= start {
+        return Err(anyhow!(
+            "Freeze end ({}) must be after its start ({})",
+            end_time.format("%Y-%m-%d %H:%M:%S UTC"),
+            start.format("%Y-%m-%d %H:%M:%S UTC")
+        ));
+    }
+
+    Ok(end_time)
+}
+
+/// Extracts a freeze reason from a GitHub contents API response.
+///
+/// The base64 file content is decoded, trimmed and truncated to
+/// [`MAX_REASON_FILE_LENGTH`] characters.
+fn reason_from_contents(path: &str, contents: &serde_json::Value) -> Result<String> {
+    use base64::Engine;
+
+    if contents.get("type").and_then(|t| t.as_str()) != Some("file") {
+        return Err(anyhow!("Reason file `{}` is not a file", path));
+    }
+    let encoded = contents
+        .get("content")
+        .and_then(|c| c.as_str())
+        .filter(|_| contents.get("encoding").and_then(|e| e.as_str()) == Some("base64"))
+        .ok_or_else(|| anyhow!("Reason file `{}` is too large to be read", path))?;
+
+    // GitHub wraps the base64 content over multiple lines
+    let encoded: String = encoded.chars().filter(|c| !c.is_ascii_whitespace()).collect();
+    let decoded = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .map_err(|e| anyhow!("Reason file `{}` could not be decoded: {}", path, e))?;
+    let reason = String::from_utf8(decoded)
+        .map_err(|_| anyhow!("Reason file `{}` is not valid UTF-8 text", path))?;
+
+    let reason = reason.trim();
+    if reason.is_empty() {
+        return Err(anyhow!("Reason file `{}` is empty", path));
+    }
+    Ok(reason.chars().take(MAX_REASON_FILE_LENGTH).collect())
+}
+
+/// Pending write of a comment, resolving to the ID of the comment written
+type CommentWrite = Pin<Box<dyn Future<Output = Result<u64>> + Send>>;
+
+/// Writes the pinned status comment of an issue and records its ID.
+///
+/// `write` edits the comment with the given ID, or creates a new one when
+/// given `None`, and returns the ID of the comment written. A recorded
+/// comment that cannot be edited anymore, e.g. because it was deleted, is
+/// replaced by a new one.
+async fn write_status_comment(
+    pool: &sqlx::SqlitePool,
+    installation_id: InstallationId,
+    repository: &str,
+    issue_nr: u64,
+    write: impl Fn(Option<u64>) -> CommentWrite,
+) -> Result<u64> {
+    let comment_id = match StatusComment::find(pool, installation_id, repository, issue_nr).await? {
+        Some(comment_id) => match write(Some(comment_id)).await {
+            Ok(comment_id) => comment_id,
+            Err(e) => {
+                warn!(
+                    "Failed to update status comment {} on {}#{}, posting a new one: {}",
+                    comment_id, repository, issue_nr, e
+                );
+                write(None).await?
+            }
+        },
+        None => write(None).await?,
+    };
+
+    StatusComment::record(
+        pool,
+        installation_id,
+        repository,
+        issue_nr,
+        comment_id,
+        Utc::now(),
+    )
+    .await?;
+
+    Ok(comment_id)
+}
+
 pub struct FreezeManager {
     pub db: Arc<Database>,
     pub github: Arc<GitHubClient>,
     pub pr_refresh: PrRefreshService,
+    pub config: Arc<AppConfig>,
+    pub notifier: NotificationDispatcher,
+    pub cache: Arc<FreezeCache>,
 }
 
 impl FreezeManager {
     pub fn new(db: Arc<Database>, github: Arc<GitHubClient>) -> Self {
-        let pr_refresh = PrRefreshService::new(github.clone(), db.clone());
+        Self::with_config(db, github, Arc::new(AppConfig::default()))
+    }
+
+    pub fn with_config(
+        db: Arc<Database>,
+        github: Arc<GitHubClient>,
+        config: Arc<AppConfig>,
+    ) -> Self {
+        let pr_refresh = PrRefreshService::with_config(
+            github.clone(),
+            db.clone(),
+            RefreshConfig {
+                commit_status_fallback: config.commit_status_fallback,
+                frozen_conclusion: config.frozen_conclusion,
+                check_run_text_templates: config.check_run_text_templates.clone(),
+                github_url: config.github_url.clone(),
+                ..RefreshConfig::default()
+            },
+        );
+        let notifier = NotificationDispatcher::from_config(&config);
         FreezeManager {
             db,
             github,
             pr_refresh,
+            config,
+            notifier,
+            cache: Arc::new(FreezeCache::default()),
         }
     }
 
+    /// Shares a freeze cache with other managers, so their freeze changes
+    /// invalidate it.
+    pub fn with_cache(mut self, cache: Arc<FreezeCache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Recent reasons of a repository's freezes, to suggest when a reason is missing.
+    pub async fn recent_reasons(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+    ) -> Vec<String> {
+        FreezeRecord::recent_reasons(
+            self.db.pool(),
+            installation_id,
+            &repository.full_name(),
+            RECENT_REASONS_LIMIT,
+        )
+        .await
+        .unwrap_or_else(|e| {
+            error!(
+                "Failed to look up recent reasons of {}: {}",
+                repository.full_name(),
+                e
+            );
+            Vec::new()
+        })
+    }
+
+    /// Watchers configured for an installation's repository, excluding the user
+    /// who triggered the change.
+    pub fn watchers_for(
+        &self,
+        installation_id: InstallationId,
+        repository: &str,
+        initiator: &str,
+    ) -> Vec<String> {
+        self.config
+            .repository_watchers
+            .get(&(installation_id.get(), repository.to_lowercase()))
+            .map(|watchers| resolve_watchers(watchers, initiator))
+            .unwrap_or_default()
+    }
+
+    /// Mentions the installation's ops contacts when a multi-repository command failed somewhere
+    fn ops_contacts_display(
+        &self,
+        installation_id: InstallationId,
+        failed: usize,
+        initiator: &str,
+    ) -> String {
+        let contacts = self
+            .config
+            .ops_contacts
+            .get(&installation_id.get())
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        messages::format_ops_contacts_display(&failure_contacts(failed, contacts, initiator))
+    }
+
     pub async fn notify_comment_issue(
         &self,
-        installation_id: u64,
+        installation_id: InstallationId,
         repository: &Repository,
         issue_nr: u64,
         msg: &str,
     ) {
+        let themed = self.config.message_theme.apply(msg);
+        let msg = themed.as_str();
+
         // Create response comment
         let error = self
             .github
-            .with_installation_async(installation_id, async move |c| {
+            .with_installation_async(installation_id.as_u64(), async move |c| {
                 let repo = repository.clone();
                 c.issues(repo.owner, repo.name)
                     .create_comment(issue_nr, msg)
@@ -130,38 +798,122 @@ impl FreezeManager {
         }
     }
 
+    /// Posts a message as the pinned status comment of an issue, editing it if it exists.
+    pub async fn pin_status_comment(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        issue_nr: u64,
+        msg: &str,
+    ) {
+        let themed = self.config.message_theme.apply(msg);
+        let msg = themed.as_str();
+
+        let result = write_status_comment(
+            self.db.pool(),
+            installation_id,
+            &repository.full_name(),
+            issue_nr,
+            |comment_id| {
+                let github = self.github.clone();
+                let repo = repository.clone();
+                let msg = msg.to_string();
+                Box::pin(async move {
+                    github
+                        .with_installation_async(installation_id.as_u64(), async move |c| {
+                            let issues = c.issues(repo.owner, repo.name);
+                            let written = match comment_id {
+                                Some(comment_id) => {
+                                    issues
+                                        .update_comment(
+                                            octocrab::models::CommentId(comment_id),
+                                            msg,
+                                        )
+                                        .await
+                                }
+                                None => issues.create_comment(issue_nr, msg).await,
+                            };
+                            written
+                                .map(|comment| comment.id.into_inner())
+                                .map_err(|e| anyhow::anyhow!("Error: {:?}", e))
+                        })
+                        .await
+                })
+            },
+        )
+        .await;
+
+        if let Err(err) = result {
+            error!("Unable to write status comment: {:?}", err);
+        }
+    }
+
     pub async fn freeze(
         &self,
-        installation_id: u64,
+        installation_id: InstallationId,
         repository: &Repository,
-        duration: Option<chrono::Duration>,
         reason: Option<String>,
         initiated_by: String,
         issue_nr: u64,
-        repos: Vec<String>,
-        branch: Option<String>,
-    ) {
+        options: FreezeOptions,
+    ) -> (CommandResult, Option<String>) {
         // If repos are specified, this is a multi-repo freeze command
-        if !repos.is_empty() {
-            self.freeze_repos(
+        if !options.repos.is_empty() {
+            return self
+                .freeze_repos(installation_id, reason, initiated_by, issue_nr, &options)
+                .await;
+        }
+
+        if options.if_not_frozen {
+            match self
+                .find_covering_freeze(installation_id, repository, options.branch.as_deref())
+                .await
+            {
+                Ok(Some(existing)) => {
+                    let until_str = existing
+                        .expires_at
+                        .map(|e| e.format("%Y-%m-%d %H:%M:%S UTC").to_string());
+                    let branch_str = messages::format_branch_display(existing.branch);
+                    let outcome = messages::already_frozen(
+                        &repository.to_string(),
+                        until_str.as_deref(),
+                        &branch_str,
+                    );
+                    self.notify_comment_issue(installation_id, repository, issue_nr, &outcome)
+                        .await;
+                    return (CommandResult::Success, None);
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    let outcome = messages::freeze_error(&e.to_string());
+                    self.notify_comment_issue(installation_id, repository, issue_nr, &outcome)
+                        .await;
+                    return (CommandResult::Failure, Some(e.to_string()));
+                }
+            }
+        }
+
+        // Otherwise, freeze the current repository. Without `--wait` the reply
+        // does not wait for the PRs to be refreshed.
+        let frozen = if options.wait {
+            self.freeze_and_refresh(installation_id, repository, reason, initiated_by, &options)
+                .await
+                .map(|(record, refreshed)| (record, Some(refreshed)))
+        } else {
+            self.freeze_and_refresh_in_background(
                 installation_id,
-                duration,
+                repository,
                 reason,
                 initiated_by,
-                issue_nr,
-                repos,
-                branch,
+                &options,
             )
-            .await;
-            return;
-        }
-
-        // Otherwise, freeze the current repository
-        let outcome = match self
-            .handle_freeze(installation_id, repository, duration, reason, initiated_by, branch)
             .await
-        {
-            Ok(r) => {
+            .map(|record| (record, None))
+        };
+        let outcome = match &frozen {
+            Ok((r, refreshed)) => {
+                self.track_reminder(&r.id, repository, issue_nr).await;
+
                 let duration = if let Some(d) = r.expires_at {
                     d - r.started_at
                 } else {
@@ -170,686 +922,3468 @@ impl FreezeManager {
 
                 let duration_str = messages::format_duration_display(duration);
                 let reason_str = messages::format_reason_display(r.reason.clone());
-                let branch_str = messages::format_branch_display(r.branch);
-                messages::freeze_success(&repository.to_string(), &duration_str, &reason_str, &branch_str)
+                let missing_branch = self
+                    .missing_branch(installation_id, repository, r.branch.as_deref())
+                    .await;
+                let branch_str = messages::format_branch_display(r.branch.clone());
+                let watchers_str = messages::format_watchers_display(
+                    &self.watchers_for(installation_id, &repository.full_name(), &r.initiated_by),
+                );
+                format!(
+                    "{}{}{}{}",
+                    messages::freeze_success(&repository.to_string(), &duration_str, &reason_str, &branch_str),
+                    refresh_note(refreshed.as_ref()),
+                    messages::format_missing_branch_display(missing_branch),
+                    watchers_str
+                )
             }
             Err(e) => messages::freeze_error(&e.to_string()),
         };
 
         self.notify_comment_issue(installation_id, repository, issue_nr, &outcome)
             .await;
+        command_result(&frozen)
     }
 
-    async fn handle_freeze(
+    /// Freezes a repository, extending an overlapping freeze instead of
+    /// failing on it.
+    ///
+    /// The existing freeze keeps its start and owner, ends at the later of both
+    /// end times and gets the new reason appended. Without an overlapping
+    /// freeze, this is a regular freeze.
+    pub async fn freeze_merge(
         &self,
-        installation_id: u64,
+        installation_id: InstallationId,
         repository: &Repository,
-        duration: Option<chrono::Duration>,
         reason: Option<String>,
         initiated_by: String,
-        branch: Option<String>,
-    ) -> Result<FreezeRecord> {
-        // Create the record
-        let start = Utc::now();
-        let duration = match duration {
-            Some(d) => d,
-            None => DEFAULT_FREEZE_DURATION,
+        issue_nr: u64,
+        options: FreezeOptions,
+    ) -> (CommandResult, Option<String>) {
+        let target = match self
+            .find_merge_target(installation_id, repository, options.branch.as_deref())
+            .await
+        {
+            Ok(target) => target,
+            Err(e) => {
+                let outcome = messages::freeze_error(&e.to_string());
+                self.notify_comment_issue(installation_id, repository, issue_nr, &outcome)
+                    .await;
+                return (CommandResult::Failure, Some(e.to_string()));
+            }
         };
-        let record = FreezeRecord::new(
-            repository.full_name(),
-            installation_id,
-            start,
-            Some(start + duration),
-            reason,
-            initiated_by,
-            branch,
-        );
 
-        let conn = self
-            .db
-            .get_connection()
-            .map_err(|e| anyhow!("Failed to get database connection: {}", e))?;
+        let Some(target) = target else {
+            return self
+                .freeze(installation_id, repository, reason, initiated_by, issue_nr, options)
+                .await;
+        };
 
-        // Save it to database
-        let record = FreezeRecord::create(conn, &record).await?;
+        let merged = self
+            .merge_freeze(installation_id, repository, target, reason, &initiated_by, &options)
+            .await;
+        let outcome = match &merged {
+            Ok(record) => {
+                let until_str = record
+                    .expires_at
+                    .map(|e| e.format("%Y-%m-%d %H:%M:%S UTC").to_string());
+                messages::freeze_merged(
+                    &repository.to_string(),
+                    until_str.as_deref(),
+                    &messages::format_reason_display(record.reason.clone()),
+                    &messages::format_branch_display(record.branch.clone()),
+                )
+            }
+            Err(e) => messages::freeze_error(&e.to_string()),
+        };
 
-        // Refresh PRs after creating freeze
-        match self
+        self.notify_comment_issue(installation_id, repository, issue_nr, &outcome)
+            .await;
+        command_result(&merged)
+    }
+
+    /// Previews a freeze without creating it.
+    ///
+    /// Reports what would be frozen and which authors of open PRs would need
+    /// `/unlock-pr` to merge during the freeze, so the initiator can coordinate
+    /// with them beforehand.
+    pub async fn freeze_dry_run(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        reason: Option<String>,
+        issue_nr: u64,
+        options: &FreezeOptions,
+        is_exempt: impl Fn(&str) -> bool,
+    ) -> (CommandResult, Option<String>) {
+        let prs = self
             .pr_refresh
-            .refresh_repository_prs(
-                installation_id,
-                repository.owner(),
-                repository.name(),
-                Some(&record), // Pass the created freeze record
-            )
-            .await
-        {
-            Ok(result) => {
-                info!(
-                    "Successfully updated {} PRs for frozen repository {}",
-                    result.successful_updates,
-                    repository.full_name()
-                );
-                if !result.errors.is_empty() {
-                    warn!(
-                        "Some PR updates failed for {}: {} errors",
-                        repository.full_name(),
-                        result.errors.len()
-                    );
-                }
+            .get_open_prs_with_sha(installation_id, &repository.owner, &repository.name)
+            .await;
+        let outcome = match &prs {
+            Ok(prs) => {
+                let blocked = blocked_authors(prs, options.branch.as_deref(), is_exempt);
+                messages::freeze_dry_run(
+                    &repository.to_string(),
+                    &messages::format_duration_display(
+                        options.duration.unwrap_or(DEFAULT_FREEZE_DURATION),
+                    ),
+                    &messages::format_reason_display(reason),
+                    &messages::format_branch_display(options.branch.clone()),
+                    &blocked,
+                )
             }
             Err(e) => {
-                warn!(
-                    "Failed to refresh PRs for repository {}: {}",
+                error!(
+                    "Failed to list open PRs of {} for dry run: {}",
                     repository.full_name(),
                     e
                 );
-                // Don't fail the freeze operation if PR refresh fails
+                messages::freeze_error(&e.to_string())
             }
-        }
+        };
 
-        Ok(record)
+        self.notify_comment_issue(installation_id, repository, issue_nr, &outcome)
+            .await;
+        command_result(&prs)
     }
 
-    pub async fn freeze_all(
+    /// Finds the repository's own active freeze a new freeze would be merged into
+    async fn find_merge_target(
         &self,
-        installation_id: u64,
-        duration: Option<chrono::Duration>,
+        installation_id: InstallationId,
+        repository: &Repository,
+        branch: Option<&str>,
+    ) -> Result<Option<FreezeRecord>> {
+        let conn = self
+            .db
+            .get_connection()
+            .map_err(|e| anyhow!("Failed to get database connection: {}", e))?;
+
+        let records =
+            FreezeRecord::list_in_effect(conn, installation_id, &repository.full_name()).await?;
+
+        Ok(merge_target(&records, &repository.full_name(), branch)?.cloned())
+    }
+
+    /// Extends an active freeze to cover a new window and appends the new reason
+    async fn merge_freeze(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        mut record: FreezeRecord,
         reason: Option<String>,
-        initiated_by: String,
-        issue_nr: u64,
-        repos: Vec<String>,
-        branch: Option<String>,
-    ) {
-        // If specific repos are provided, filter to those repos only
-        if !repos.is_empty() {
-            self.freeze_repos(
-                installation_id,
-                duration,
-                reason,
-                initiated_by,
-                issue_nr,
-                repos,
-                branch,
-            )
-            .await;
-            return;
-        }
+        initiated_by: &str,
+        options: &FreezeOptions,
+    ) -> Result<FreezeRecord> {
+        let now = Utc::now();
+        let duration = options.duration.unwrap_or(DEFAULT_FREEZE_DURATION);
+        check_min_duration(duration, self.config.min_freeze_duration, options.can_override)?;
+        let reason = reason.map(|r| expand_reason(&r, &repository.full_name(), initiated_by, now));
 
-        // Get all repositories for this installation
-        let repositories = match self.get_installation_repositories(installation_id).await {
-            Ok(repos) => repos,
-            Err(e) => {
-                let _ = messages::freeze_error(&format!("Failed to get repositories: {}", e));
-                // We don't have a specific repository for this case, so we'll need to create a dummy one
-                // This is a limitation of the current architecture
-                error!(
-                    "Failed to get repositories for installation {}: {}",
-                    installation_id, e
-                );
-                return;
-            }
-        };
+        record.expires_at = merged_expiry(record.expires_at, now + duration);
+        record.reason = merged_reason(record.reason.as_deref(), reason.as_deref());
 
-        if repositories.is_empty() {
-            let _ = messages::freeze_error("No repositories accessible for this installation");
-            error!("No repositories found for installation {}", installation_id);
-            return;
+        let conn = self
+            .db
+            .get_connection()
+            .map_err(|e| anyhow!("Failed to get database connection: {}", e))?;
+        if !FreezeRecord::update_window(conn, &record.id, record.expires_at, record.reason.as_deref())
+            .await?
+        {
+            return Err(anyhow!(
+                "The freeze on {} ended before it could be extended",
+                repository.full_name()
+            ));
         }
+        self.cache.invalidate(installation_id, &record.repository);
 
-        let mut successful_freezes = 0;
-        let mut failed_freezes = 0;
-        let mut error_messages = Vec::new();
+        info!(
+            "{} merged a freeze into {} of {}",
+            initiated_by,
+            record.id,
+            repository.full_name()
+        );
 
-        for repo in &repositories {
-            let repository = Repository::new(&repo.owner.as_ref().unwrap().login, &repo.name);
+        // Refresh PRs so the check run details show the new end time
+        self.refresh_prs(installation_id, repository, Some(&record))
+            .await;
 
-            match self
-                .handle_freeze(
-                    installation_id,
-                    &repository,
-                    duration,
-                    reason.clone(),
-                    initiated_by.clone(),
-                    branch.clone(),
-                )
-                .await
-            {
-                Ok(_) => {
-                    successful_freezes += 1;
-                    info!("Successfully froze repository: {}", repository.full_name());
-                }
-                Err(e) => {
-                    failed_freezes += 1;
-                    let error = format!("Failed to freeze {}: {}", repository.full_name(), e);
-                    error_messages.push(error.clone());
-                    error!("{}", error);
-                }
-            }
+        Ok(record)
+    }
+
+    /// Freezes a repository for a stabilization window after a release.
+    ///
+    /// There is no comment to reply to, so nothing is posted. Repositories that
+    /// are already frozen keep their freeze. Returns the freeze created, if any.
+    pub async fn freeze_after_release(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        duration: chrono::Duration,
+        reason: String,
+        initiated_by: String,
+        branch: Option<String>,
+    ) -> Result<Option<FreezeRecord>> {
+        if let Some(existing) = self
+            .find_covering_freeze(installation_id, repository, branch.as_deref())
+            .await?
+        {
+            info!(
+                "Repository {} is already frozen by {}, skipping release freeze",
+                repository.full_name(),
+                existing.id
+            );
+            return Ok(None);
         }
 
-        let outcome = if failed_freezes == 0 {
-            messages::freeze_all_success(successful_freezes)
-        } else {
-            messages::freeze_all_partial_success(
-                successful_freezes,
-                failed_freezes,
-                &error_messages,
-            )
+        // The duration comes from the installation config, not from a user
+        let options = FreezeOptions {
+            duration: Some(duration),
+            branch,
+            can_override: true,
+            ..FreezeOptions::default()
         };
+        let record = self
+            .handle_freeze(installation_id, repository, Some(reason), initiated_by, &options)
+            .await?;
 
-        // For freeze_all, we need to pick a repository to comment on. Let's use the first one
-        if let Some(first_repo) = repositories.first() {
-            let repository =
-                Repository::new(&first_repo.owner.as_ref().unwrap().login, &first_repo.name);
-            self.notify_comment_issue(installation_id, &repository, issue_nr, &outcome)
-                .await;
-        }
+        Ok(Some(record))
     }
 
-    async fn freeze_repos(
+    /// Freezes a repository on behalf of automation, such as a repository dispatch.
+    ///
+    /// There is no comment to reply to, so nothing is posted and the outcome
+    /// is returned instead.
+    pub async fn freeze_from_dispatch(
         &self,
-        installation_id: u64,
+        installation_id: InstallationId,
+        repository: &Repository,
         duration: Option<chrono::Duration>,
         reason: Option<String>,
         initiated_by: String,
-        issue_nr: u64,
-        repo_names: Vec<String>,
         branch: Option<String>,
-    ) {
-        let mut successful_freezes = 0;
-        let mut failed_freezes = 0;
-        let mut error_messages = Vec::new();
-        let mut first_repository = None;
-
-        for repo_name in &repo_names {
-            // Parse repository name (expecting format "owner/repo" or just "repo")
-            let repository = if repo_name.contains('/') {
-                let parts: Vec<&str> = repo_name.split('/').collect();
-                if parts.len() != 2 {
-                    failed_freezes += 1;
-                    let error = format!("Invalid repository format: {}", repo_name);
-                    error_messages.push(error.clone());
-                    error!("{}", error);
-                    continue;
-                }
-                Repository::new(parts[0], parts[1])
-            } else {
-                // If no owner specified, we can't determine the repository
-                failed_freezes += 1;
-                let error = format!(
-                    "Invalid repository format '{}'. Expected 'owner/repo'",
-                    repo_name
-                );
-                error_messages.push(error.clone());
-                error!("{}", error);
-                continue;
-            };
+    ) -> Result<FreezeRecord> {
+        let options = FreezeOptions {
+            duration,
+            branch,
+            ..FreezeOptions::default()
+        };
+        self.handle_freeze(installation_id, repository, reason, initiated_by, &options)
+            .await
+    }
 
-            if first_repository.is_none() {
-                first_repository = Some(repository.clone());
-            }
+    /// Tells the author of a newly opened PR that its target branch is frozen.
+    ///
+    /// Returns `true` if the PR targets a frozen branch and was commented on.
+    pub async fn comment_on_frozen_pr(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        pr_number: u64,
+        author: &str,
+        base_ref: &str,
+    ) -> Result<bool> {
+        let records = self
+            .cached_freezes_in_effect(installation_id, repository)
+            .await?;
+        let Some(freeze) = covering_freeze(&records, Some(base_ref), Utc::now()) else {
+            return Ok(false);
+        };
 
-            match self
-                .handle_freeze(
-                    installation_id,
-                    &repository,
-                    duration,
-                    reason.clone(),
-                    initiated_by.clone(),
-                    branch.clone(),
-                )
-                .await
-            {
-                Ok(_) => {
-                    successful_freezes += 1;
-                    info!("Successfully froze repository: {}", repository.full_name());
-                }
-                Err(e) => {
-                    failed_freezes += 1;
-                    let error = format!("Failed to freeze {}: {}", repository.full_name(), e);
-                    error_messages.push(error.clone());
-                    error!("{}", error);
-                }
-            }
-        }
+        let msg = messages::new_pr_during_freeze(author, freeze);
+        self.notify_comment_issue(installation_id, repository, pr_number, &msg)
+            .await;
 
-        let outcome = if failed_freezes == 0 {
-            messages::freeze_all_success(successful_freezes)
-        } else {
-            messages::freeze_all_partial_success(
-                successful_freezes,
-                failed_freezes,
-                &error_messages,
-            )
-        };
+        Ok(true)
+    }
 
-        // Comment on the first repository (or the one that triggered the command)
-        if let Some(repository) = first_repository {
-            self.notify_comment_issue(installation_id, &repository, issue_nr, &outcome)
-                .await;
+    /// Returns the frozen branch if it does not exist in the repository.
+    ///
+    /// Lookup failures are logged and treated as an existing branch, so they
+    /// never fail the freeze itself.
+    async fn missing_branch<'a>(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        branch: Option<&'a str>,
+    ) -> Option<&'a str> {
+        let branch = branch?;
+        match self
+            .pr_refresh
+            .branch_exists(installation_id, &repository.owner, &repository.name, branch)
+            .await
+        {
+            Ok(true) => None,
+            Ok(false) => {
+                warn!(
+                    "Froze branch {} of {}, which does not exist",
+                    branch,
+                    repository.full_name()
+                );
+                Some(branch)
+            }
+            Err(e) => {
+                warn!("Could not check the frozen branch {}: {}", branch, e);
+                None
+            }
         }
     }
 
-    pub async fn unfreeze_all(&self, installation_id: u64, ended_by: String, issue_nr: u64) {
-        // Get all repositories for this installation
-        let repositories = match self.get_installation_repositories(installation_id).await {
-            Ok(repos) => repos,
+    /// Remember where a freeze was requested so its initiator can be reminded there
+    async fn track_reminder(&self, freeze_id: &str, repository: &Repository, issue_nr: u64) {
+        let conn = match self.db.get_connection() {
+            Ok(conn) => conn,
             Err(e) => {
-                error!(
-                    "Failed to get repositories for installation {}: {}",
-                    installation_id, e
-                );
+                error!("Failed to get database connection for freeze reminder: {}", e);
                 return;
             }
         };
 
-        if repositories.is_empty() {
-            error!("No repositories found for installation {}", installation_id);
-            return;
+        if let Err(e) =
+            FreezeReminder::track(conn, freeze_id, &repository.full_name(), issue_nr).await
+        {
+            error!("Failed to track reminders for freeze {}: {}", freeze_id, e);
         }
+    }
 
-        let mut successful_unfreezes = 0;
-        let mut failed_unfreezes = 0;
-        let mut error_messages = Vec::new();
+    /// Returns the active freeze that already covers the target, if any
+    async fn find_covering_freeze(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        branch: Option<&str>,
+    ) -> Result<Option<FreezeRecord>> {
+        let conn = self
+            .db
+            .get_connection()
+            .map_err(|e| anyhow!("Failed to get database connection: {}", e))?;
 
-        for repo in &repositories {
-            let repository = Repository::new(&repo.owner.as_ref().unwrap().login, &repo.name);
+        let records =
+            FreezeRecord::list_in_effect(conn, installation_id, &repository.full_name())
+                .await?;
 
-            match self
-                .handle_unfreeze(installation_id, &repository, ended_by.clone(), None)
-                .await
-            {
-                Ok(_) => {
-                    successful_unfreezes += 1;
-                    info!(
-                        "Successfully unfroze repository: {}",
-                        repository.full_name()
-                    );
-                }
-                Err(e) => {
-                    failed_unfreezes += 1;
-                    let error = format!("Failed to unfreeze {}: {}", repository.full_name(), e);
-                    error_messages.push(error.clone());
-                    error!("{}", error);
-                }
-            }
+        Ok(covering_freeze(&records, branch, Utc::now()).cloned())
+    }
+
+    /// Returns the freezes in effect for a repository, served from the freeze
+    /// cache while it is fresh
+    async fn cached_freezes_in_effect(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+    ) -> Result<Vec<FreezeRecord>> {
+        let repo = repository.full_name();
+        if let Some(records) = self.cache.get(installation_id, &repo) {
+            return Ok(records);
         }
 
-        let outcome = if failed_unfreezes == 0 {
-            messages::unfreeze_all_success(successful_unfreezes)
-        } else {
-            messages::unfreeze_all_partial_success(
-                successful_unfreezes,
-                failed_unfreezes,
-                &error_messages,
-            )
-        };
+        let conn = self
+            .db
+            .get_connection()
+            .map_err(|e| anyhow!("Failed to get database connection: {}", e))?;
+        let records = FreezeRecord::list_in_effect(conn, installation_id, &repo).await?;
+        self.cache.insert(installation_id, &repo, records.clone());
 
-        // For unfreeze_all, we need to pick a repository to comment on. Let's use the first one
-        if let Some(first_repo) = repositories.first() {
-            let repository =
-                Repository::new(&first_repo.owner.as_ref().unwrap().login, &first_repo.name);
-            self.notify_comment_issue(installation_id, &repository, issue_nr, &outcome)
-                .await;
-        }
+        Ok(records)
     }
 
-    async fn get_installation_repositories(
+    /// Checks whether the repository itself (not its organization) holds an
+    /// active freeze, optionally restricted to a branch
+    async fn has_own_freeze(
         &self,
-        installation_id: u64,
-    ) -> Result<Vec<octocrab::models::Repository>> {
-        self.github
-            .with_installation_async(installation_id, |client| async move {
-                // Use the manual HTTP approach for the installation repositories endpoint
-                let url = "/installation/repositories";
-                let response: serde_json::Value = client
-                    .get(url, None::<&()>)
-                    .await
-                    .map_err(|e| anyhow!("Failed to get installation repositories: {}", e))?;
-
-                let repositories = response
-                    .get("repositories")
-                    .and_then(|r| r.as_array())
-                    .ok_or_else(|| anyhow!("Invalid response format"))?;
+        installation_id: InstallationId,
+        repository: &Repository,
+        branch: &Option<String>,
+    ) -> bool {
+        let Ok(conn) = self.db.get_connection() else {
+            return false;
+        };
 
-                let mut repos = Vec::new();
-                for repo_value in repositories {
-                    let repo: octocrab::models::Repository =
-                        serde_json::from_value(repo_value.clone())
-                            .map_err(|e| anyhow!("Failed to deserialize repository: {}", e))?;
-                    repos.push(repo);
-                }
+        match FreezeRecord::list(
+            conn,
+            Some(installation_id),
+            Some(&repository.full_name()),
+            Some(true),
+        )
+        .await
+        {
+            Ok(records) => records
+                .iter()
+                .any(|r| branch.is_none() || r.branch == *branch),
+            Err(e) => {
+                warn!(
+                    "Failed to check freezes of {}: {}",
+                    repository.full_name(),
+                    e
+                );
+                false
+            }
+        }
+    }
 
-                Ok(repos)
-            })
+    /// Checks whether a bulk `--if-not-frozen` freeze can skip a repository
+    async fn is_already_frozen(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        branch: &Option<String>,
+    ) -> bool {
+        match self
+            .find_covering_freeze(installation_id, repository, branch.as_deref())
             .await
+        {
+            Ok(Some(_)) => {
+                info!(
+                    "Repository {} is already frozen, skipping",
+                    repository.full_name()
+                );
+                true
+            }
+            Ok(None) => false,
+            Err(e) => {
+                // Fall through to a regular freeze, which reports the error
+                warn!(
+                    "Failed to check existing freezes for {}: {}",
+                    repository.full_name(),
+                    e
+                );
+                false
+            }
+        }
     }
 
-    pub async fn get_status(
+    async fn handle_freeze(
         &self,
-        installation_id: u64,
-        repos: Vec<String>,
-        issue_nr: u64,
+        installation_id: InstallationId,
         repository: &Repository,
-    ) {
-        let mut status_entries = Vec::new();
+        reason: Option<String>,
+        initiated_by: String,
+        options: &FreezeOptions,
+    ) -> Result<FreezeRecord> {
+        self.freeze_and_refresh(installation_id, repository, reason, initiated_by, options)
+            .await
+            .map(|(record, _)| record)
+    }
 
-        if repos.is_empty() {
-            // If no specific repos requested, get all repositories for this installation
-            match self.get_installation_repositories(installation_id).await {
-                Ok(all_repos) => {
-                    for repo in all_repos {
-                        let repo_name =
-                            format!("{}/{}", repo.owner.as_ref().unwrap().login, repo.name);
-                        let repository =
-                            Repository::new(&repo.owner.as_ref().unwrap().login, &repo.name);
-                        let entry = self
-                            .get_repository_status(installation_id, &repository)
-                            .await;
-                        status_entries.push((repo_name, entry));
-                    }
-                }
-                Err(e) => {
-                    let error_msg =
-                        messages::status_error(&format!("Failed to get repositories: {}", e));
-                    self.notify_comment_issue(installation_id, repository, issue_nr, &error_msg)
-                        .await;
-                    return;
-                }
-            }
-        } else {
-            // Get status for specific repositories
-            for repo_name in repos {
-                let parts: Vec<&str> = repo_name.split('/').collect();
-                if parts.len() != 2 {
-                    status_entries.push((
-                        repo_name.clone(),
-                        StatusEntry::error("Invalid repository format"),
-                    ));
-                    continue;
-                }
+    /// Creates a freeze and refreshes the PRs it blocks, returning both.
+    async fn freeze_and_refresh(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        reason: Option<String>,
+        initiated_by: String,
+        options: &FreezeOptions,
+    ) -> Result<(FreezeRecord, RefreshResult)> {
+        self.ensure_not_archived(installation_id, repository)
+            .await?;
 
-                let repository = Repository::new(parts[0], parts[1]);
-                let entry = self
-                    .get_repository_status(installation_id, &repository)
-                    .await;
-                status_entries.push((repo_name, entry));
-            }
-        }
+        let record = self
+            .create_freeze(installation_id, repository, reason, initiated_by, options)
+            .await?;
 
-        let status_msg = messages::format_status_table(status_entries);
-        self.notify_comment_issue(installation_id, repository, issue_nr, &status_msg)
+        // Refresh PRs after creating freeze
+        let refreshed = self
+            .refresh_prs(installation_id, repository, Some(&record))
             .await;
+
+        Ok((record, refreshed))
     }
 
-    async fn get_repository_status(
+    /// Creates a freeze and refreshes the PRs it blocks in a background task.
+    async fn freeze_and_refresh_in_background(
         &self,
-        installation_id: u64,
+        installation_id: InstallationId,
         repository: &Repository,
-    ) -> StatusEntry {
-        let conn = match self.db.get_connection() {
-            Ok(conn) => conn,
-            Err(e) => return StatusEntry::error(&format!("Database error: {}", e)),
-        };
+        reason: Option<String>,
+        initiated_by: String,
+        options: &FreezeOptions,
+    ) -> Result<FreezeRecord> {
+        self.ensure_not_archived(installation_id, repository)
+            .await?;
 
-        match FreezeRecord::list(
-            conn,
-            Some(installation_id),
-            Some(&repository.full_name()),
-            Some(true),
-        )
-        .await
+        let record = self
+            .create_freeze(installation_id, repository, reason, initiated_by, options)
+            .await?;
+
+        let pr_refresh = self.pr_refresh.clone();
+        let target = repository.clone();
+        let frozen = record.clone();
+        tokio::spawn(async move {
+            // Don't fail the freeze operation if PR refresh fails
+            if let Err(e) = pr_refresh
+                .refresh_repository_prs(
+                    installation_id,
+                    target.owner(),
+                    target.name(),
+                    Some(&frozen),
+                )
+                .await
+            {
+                warn!(
+                    "Failed to refresh PRs for repository {}: {}",
+                    target.full_name(),
+                    e
+                );
+            }
+        });
+
+        Ok(record)
+    }
+
+    /// Fails when the repository is archived.
+    ///
+    /// Freezes are not blocked when the repository cannot be looked up; the
+    /// freeze itself reports any real problem with the repository.
+    async fn ensure_not_archived(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+    ) -> Result<()> {
+        match self
+            .pr_refresh
+            .get_repository(installation_id, &repository.owner, &repository.name)
+            .await
         {
-            Ok(records) => {
-                if records.is_empty() {
-                    StatusEntry::not_frozen()
-                } else {
-                    // Take the most recent active freeze
-                    let record = &records[0];
-                    StatusEntry::frozen(record)
-                }
+            Ok(repo) => check_not_archived(&repo),
+            Err(e) => {
+                warn!(
+                    "Could not check whether {} is archived: {}",
+                    repository.full_name(),
+                    e
+                );
+                Ok(())
             }
-            Err(e) => StatusEntry::error(&format!("Failed to get freeze records: {}", e)),
         }
     }
 
-    pub async fn schedule_freeze(
+    /// Stores a new active freeze and announces it, without touching any PR
+    async fn create_freeze(
         &self,
-        installation_id: u64,
+        installation_id: InstallationId,
         repository: &Repository,
-        start: DateTime<Utc>,
-        end: Option<DateTime<Utc>>,
-        duration: Option<chrono::Duration>,
         reason: Option<String>,
         initiated_by: String,
-        branch: Option<String>,
-    ) -> Result<()> {
-        let end_time = match (end, duration) {
-            (Some(end), _) => end,
-            (None, Some(dur)) => start + dur,
-            (None, None) => start + DEFAULT_FREEZE_DURATION,
+        options: &FreezeOptions,
+    ) -> Result<FreezeRecord> {
+        // Create the record
+        let start = Utc::now();
+        let duration = match options.duration {
+            Some(d) => d,
+            None => DEFAULT_FREEZE_DURATION,
         };
-
-        let record = FreezeRecord::new_scheduled(
+        check_min_duration(duration, self.config.min_freeze_duration, options.can_override)?;
+        let reason = reason
+            .map(|r| expand_reason(&r, &repository.full_name(), &initiated_by, start));
+        let record = FreezeRecord::new(
             repository.full_name(),
             installation_id,
             start,
-            Some(end_time),
+            Some(start + duration),
             reason,
             initiated_by,
-            branch,
-        );
+            options.branch.clone(),
+        )
+        .with_environment(options.environment.clone())
+        .with_soft(options.soft)
+        .with_category(options.category);
 
         let conn = self
             .db
             .get_connection()
             .map_err(|e| anyhow!("Failed to get database connection: {}", e))?;
 
-        FreezeRecord::create(conn, &record).await?;
-        Ok(())
+        // Save it to database
+        let record = FreezeRecord::create(conn, &record).await?;
+        self.cache.invalidate(installation_id, &record.repository);
+
+        self.notifier
+            .dispatch(
+                FreezeEvent::from_record(FreezeEventKind::Freeze, &record)
+                    .with_watchers(self.watchers_for(
+                        installation_id,
+                        &record.repository,
+                        &record.initiated_by,
+                    )),
+            )
+            .await;
+
+        Ok(record)
     }
 
-    pub async fn unfreeze(
+    /// Freezes deployments of a repository to a GitHub Environment.
+    ///
+    /// Pull requests are left untouched; deployments to the environment are
+    /// failed by the deployment handlers while the freeze is in effect.
+    pub async fn freeze_environment(
         &self,
-        installation_id: u64,
+        installation_id: InstallationId,
         repository: &Repository,
-        ended_by: String,
         reason: Option<String>,
+        initiated_by: String,
         issue_nr: u64,
-        branch: Option<String>,
-    ) {
-        let branch_for_display = branch.clone();
-        let outcome = match self
-            .handle_unfreeze(installation_id, repository, ended_by, branch)
-            .await
-        {
-            Ok(_) => {
-                let reason_str = messages::format_reason_display(reason);
-                let branch_str = messages::format_branch_display(branch_for_display);
-                messages::unfreeze_success(&repository.to_string(), &reason_str, &branch_str)
-            }
-            Err(e) => {
-                tracing::error!("Failed to unfreeze repository: {:?}", e);
-                messages::unfreeze_error(&e.to_string())
+        options: FreezeOptions,
+    ) -> (CommandResult, Option<String>) {
+        let environment = options.environment.clone().unwrap_or_default();
+        // Environment freezes leave pull requests alone, whatever their branch
+        let options = FreezeOptions {
+            branch: None,
+            soft: false,
+            ..options
+        };
+        let frozen = self
+            .create_freeze(installation_id, repository, reason, initiated_by, &options)
+            .await;
+        let outcome = match &frozen {
+            Ok(r) => {
+                self.track_reminder(&r.id, repository, issue_nr).await;
+
+                let duration = r
+                    .expires_at
+                    .map_or(DEFAULT_FREEZE_DURATION, |e| e - r.started_at);
+                let duration_str = messages::format_duration_display(duration);
+                let reason_str = messages::format_reason_display(r.reason.clone());
+                messages::environment_freeze_success(
+                    &repository.to_string(),
+                    &environment,
+                    &duration_str,
+                    &reason_str,
+                )
             }
+            Err(e) => messages::freeze_error(&e.to_string()),
         };
 
         self.notify_comment_issue(installation_id, repository, issue_nr, &outcome)
             .await;
+        command_result(&frozen)
     }
 
-    /// Unfreeze a repository
-    async fn handle_unfreeze(
+    /// Returns the freeze blocking deployments of a repository to an environment, if any
+    pub async fn find_environment_freeze(
         &self,
-        installation_id: u64,
+        installation_id: InstallationId,
         repository: &Repository,
-        ended_by: String,
-        branch: Option<String>,
-    ) -> Result<()> {
+        environment: &str,
+    ) -> Result<Option<FreezeRecord>> {
         let conn = self
             .db
             .get_connection()
             .map_err(|e| anyhow!("Failed to get database connection: {}", e))?;
 
-        let repo = repository.full_name();
-        // Get active freeze records for this repository
-        let mut freeze_records =
-            FreezeRecord::list(conn, Some(installation_id), Some(&repo), Some(true))
-                .await
-                .map_err(|e| anyhow!("Failed to get freeze records for repo {}: {}", repo, e))?;
-
-        // Filter by branch if specified
-        if let Some(ref target_branch) = branch {
-            freeze_records.retain(|r| r.branch.as_ref() == Some(target_branch));
-        }
-
-        if freeze_records.is_empty() {
-            let branch_msg = branch.map_or(String::new(), |b| format!(" for branch '{}'", b));
-            return Err(anyhow!("No active freeze found for repository: {}{}", repo, branch_msg));
-        }
+        let records = FreezeRecord::list_environment_in_effect(
+            conn,
+            installation_id,
+            &repository.full_name(),
+            environment,
+        )
+        .await?;
+        Ok(records.into_iter().next())
+    }
 
-        // End all matching active freezes for this repository
-        for record in freeze_records {
-            let record_id = record.id.clone();
-            FreezeRecord::update_status(
-                conn,
-                record.id,
-                crate::database::models::FreezeStatus::Ended,
-                Some(ended_by.clone()),
-            )
-            .await
-            .map_err(|e| anyhow!("Failed to end freeze record {}: {}", record_id, e))?;
-        }
+    /// Marks a deployment as failed because its environment is frozen
+    pub async fn fail_deployment(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        deployment_id: u64,
+        freeze: &FreezeRecord,
+    ) -> Result<()> {
+        let route = format!(
+            "/repos/{}/{}/deployments/{}/statuses",
+            repository.owner(),
+            repository.name(),
+            deployment_id
+        );
+        let body = serde_json::json!({
+            "state": "failure",
+            "description": messages::deployment_frozen(freeze),
+        });
 
-        // Refresh PRs after unfreezing
-        match self
-            .pr_refresh
-            .refresh_repository_prs(
-                installation_id,
-                repository.owner(),
-                repository.name(),
-                None, // Repository is now unfrozen - no freeze record
-            )
+        self.github
+            .app_client()
+            .installation(installation_id.into())?
+            .post::<_, serde_json::Value>(route, Some(&body))
             .await
-        {
-            Ok(result) => {
-                info!(
-                    "Successfully updated {} PRs for unfrozen repository {}",
-                    result.successful_updates,
-                    repository.full_name()
-                );
-                if !result.errors.is_empty() {
-                    warn!(
-                        "Some PR updates failed for {}: {} errors",
-                        repository.full_name(),
-                        result.errors.len()
-                    );
-                }
-            }
-            Err(e) => {
-                warn!(
-                    "Failed to refresh PRs for repository {}: {}",
-                    repository.full_name(),
-                    e
-                );
-                // Don't fail the unfreeze operation if PR refresh fails
-            }
-        }
+            .map_err(|e| anyhow!("Failed to create deployment status: {}", e))?;
 
         Ok(())
     }
-    /// Get the active freeze record for a repository, if one exists
-    async fn get_active_freeze(
+
+    /// Refreshes the check runs of a repository's open PRs.
+    ///
+    /// For the `owner/*` wildcard of an organization-wide freeze, every
+    /// installation repository of the owner is refreshed. Without a freeze
+    /// record, each repository is refreshed against the freeze still in effect
+    /// for it, if any. Failures are logged and never propagated.
+    ///
+    /// # Returns
+    ///
+    /// The PR updates of every refreshed repository, combined.
+    pub async fn refresh_prs(
         &self,
+        installation_id: InstallationId,
         repository: &Repository,
-        installation_id: i64,
-    ) -> Result<Option<FreezeRecord>> {
-        let conn = self
-            .db
-            .get_connection()
-            .map_err(|e| anyhow!("Failed to get database connection: {}", e))?;
+        freeze_record: Option<&FreezeRecord>,
+    ) -> RefreshResult {
+        let targets = if repository.is_org_wildcard() {
+            match self
+                .get_owner_repositories(installation_id, repository.owner())
+                .await
+            {
+                Ok(targets) => targets,
+                Err(e) => {
+                    warn!(
+                        "Failed to list repositories of {}: {}",
+                        repository.owner(),
+                        e
+                    );
+                    return RefreshResult::default();
+                }
+            }
+        } else {
+            vec![repository.clone()]
+        };
 
-        let repo = repository.full_name();
-        let freeze_record = FreezeRecord::get_active_freeze(conn, installation_id, &repo)
-            .await
-            .map_err(|e| anyhow!("Failed to get active freeze for repository {}: {}", repo, e))?;
+        let mut outcomes = Vec::new();
+        for target in &targets {
+            let remaining = match freeze_record {
+                Some(_) => None,
+                None => self
+                    .get_active_freeze(target, installation_id)
+                    .await
+                    .unwrap_or_else(|e| {
+                        warn!("{}", e);
+                        None
+                    }),
+            };
 
-        Ok(freeze_record)
+            match self
+                .pr_refresh
+                .refresh_repository_prs(
+                    installation_id,
+                    target.owner(),
+                    target.name(),
+                    freeze_record.or(remaining.as_ref()),
+                )
+                .await
+            {
+                Ok(result) => {
+                    info!(
+                        "Successfully updated {} PRs for repository {}",
+                        result.successful_updates,
+                        target.full_name()
+                    );
+                    if !result.errors.is_empty() {
+                        warn!(
+                            "Some PR updates failed for {}: {} errors",
+                            target.full_name(),
+                            result.errors.len()
+                        );
+                    }
+                    outcomes.extend(result.per_pr);
+                }
+                Err(e) => {
+                    // Don't fail the freeze operation if PR refresh fails
+                    warn!(
+                        "Failed to refresh PRs for repository {}: {}",
+                        target.full_name(),
+                        e
+                    );
+                }
+            }
+        }
+
+        RefreshResult::from_outcomes(outcomes)
     }
 
-    pub async fn unlock_pr(
+    /// Freezes every repository of the current repository's owner with a
+    /// single organization-wide record.
+    pub async fn freeze_org(
         &self,
-        installation_id: u64,
+        installation_id: InstallationId,
         repository: &Repository,
-        pr_number: u64,
-        author: String,
         reason: Option<String>,
+        initiated_by: String,
         issue_nr: u64,
-    ) {
-        let repo_name = repository.full_name();
+        options: FreezeOptions,
+    ) -> (CommandResult, Option<String>) {
+        let org = Repository::org_wildcard(repository.owner());
 
-        // Check if repository is currently frozen
-        match self
-            .get_active_freeze(repository, installation_id as i64)
-            .await
+        if options.if_not_frozen
+            && let Ok(Some(existing)) = self
+                .find_covering_freeze(installation_id, &org, options.branch.as_deref())
+                .await
         {
-            Ok(Some(_)) => {
-                // Repository is frozen, proceed with unlock
-                match UnlockedPr::unlock_pr(
-                    self.db.pool(),
-                    installation_id as i64,
-                    &repo_name,
-                    pr_number,
-                    &author,
+            let until_str = existing
+                .expires_at
+                .map(|e| e.format("%Y-%m-%d %H:%M:%S UTC").to_string());
+            let branch_str = messages::format_branch_display(existing.branch);
+            let outcome =
+                messages::already_frozen(&org.to_string(), until_str.as_deref(), &branch_str);
+            self.notify_comment_issue(installation_id, repository, issue_nr, &outcome)
+                .await;
+            return (CommandResult::Success, None);
+        }
+
+        let frozen = self
+            .handle_freeze(installation_id, &org, reason, initiated_by, &options)
+            .await;
+        let outcome = match &frozen {
+            Ok(r) => {
+                self.track_reminder(&r.id, repository, issue_nr).await;
+
+                let duration = r
+                    .expires_at
+                    .map_or(DEFAULT_FREEZE_DURATION, |e| e - r.started_at);
+                let duration_str = messages::format_duration_display(duration);
+                let reason_str = messages::format_reason_display(r.reason.clone());
+                let branch_str = messages::format_branch_display(r.branch.clone());
+                messages::org_freeze_success(org.owner(), &duration_str, &reason_str, &branch_str)
+            }
+            Err(e) => messages::freeze_error(&e.to_string()),
+        };
+
+        self.notify_comment_issue(installation_id, repository, issue_nr, &outcome)
+            .await;
+        command_result(&frozen)
+    }
+
+    pub async fn freeze_all(
+        &self,
+        installation_id: InstallationId,
+        reason: Option<String>,
+        initiated_by: String,
+        issue_nr: u64,
+        options: FreezeOptions,
+    ) -> (CommandResult, Option<String>) {
+        // If specific repos are provided, filter to those repos only
+        if !options.repos.is_empty() {
+            return self
+                .freeze_repos(installation_id, reason, initiated_by, issue_nr, &options)
+                .await;
+        }
+
+        // Get all repositories for this installation
+        let repositories = match self.get_installation_repositories(installation_id).await {
+            Ok(repos) => repos,
+            Err(e) => {
+                let _ = messages::freeze_error(&format!("Failed to get repositories: {}", e));
+                // We don't have a specific repository for this case, so we'll need to create a dummy one
+                // This is a limitation of the current architecture
+                error!(
+                    "Failed to get repositories for installation {}: {}",
+                    installation_id, e
+                );
+                return (
+                    CommandResult::Failure,
+                    Some(format!("Failed to get repositories: {}", e)),
+                );
+            }
+        };
+
+        if repositories.is_empty() {
+            let _ = messages::freeze_error("No repositories accessible for this installation");
+            error!("No repositories found for installation {}", installation_id);
+            return (
+                CommandResult::Failure,
+                Some("No repositories accessible for this installation".to_string()),
+            );
+        }
+
+        let (usable, skipped) = self
+            .freezable_repositories(installation_id, &repositories)
+            .await;
+        for note in &skipped {
+            info!("Skipping repository {}", note);
+        }
+
+        let mut successful_freezes = 0;
+        let mut failed_freezes = 0;
+        let mut error_messages = Vec::new();
+
+        for repo in usable {
+            let repository = Repository::new(&repo.owner.as_ref().unwrap().login, &repo.name);
+
+            if options.if_not_frozen
+                && self
+                    .is_already_frozen(installation_id, &repository, &options.branch)
+                    .await
+            {
+                successful_freezes += 1;
+                continue;
+            }
+
+            match self
+                .handle_freeze(
+                    installation_id,
+                    &repository,
+                    reason.clone(),
+                    initiated_by.clone(),
+                    &options,
                 )
                 .await
-                {
-                    Ok(_) => {
-                        let reason_str = messages::format_reason_display(reason);
-                        let success_msg = messages::pr_unlock_success(pr_number, &reason_str);
-                        self.notify_comment_issue(
-                            installation_id,
-                            repository,
-                            issue_nr,
-                            &success_msg,
-                        )
-                        .await;
-
-                        // Refresh the specific PR to update its status
-                        if let Err(e) = self
-                            .pr_refresh
-                            .refresh_single_pr(installation_id as i64, repository, pr_number)
-                            .await
-                        {
-                            error!("Failed to refresh PR {} after unlock: {}", pr_number, e);
-                        }
-                    }
-                    Err(e) => {
-                        error!("Failed to unlock PR {}: {}", pr_number, e);
-                        let error_msg = messages::pr_unlock_failed(pr_number, &e.to_string());
-                        self.notify_comment_issue(
-                            installation_id,
-                            repository,
-                            issue_nr,
-                            &error_msg,
-                        )
-                        .await;
-                    }
+            {
+                Ok(_) => {
+                    successful_freezes += 1;
+                    info!("Successfully froze repository: {}", repository.full_name());
+                }
+                Err(e) => {
+                    failed_freezes += 1;
+                    let error = format!("Failed to freeze {}: {}", repository.full_name(), e);
+                    error_messages.push(error.clone());
+                    error!("{}", error);
                 }
             }
-            Ok(None) => {
-                // Repository is not frozen
-                let error_msg = messages::pr_unlock_not_frozen(&repo_name);
-                self.notify_comment_issue(installation_id, repository, issue_nr, &error_msg)
-                    .await;
+        }
+
+        let skipped_str = messages::format_skipped_display(&skipped);
+        let mut outcome = if failed_freezes == 0 {
+            messages::freeze_all_success(successful_freezes, &skipped_str)
+        } else {
+            messages::freeze_all_partial_success(
+                successful_freezes,
+                failed_freezes,
+                &error_messages,
+                &skipped_str,
+            )
+        };
+        outcome.push_str(&self.ops_contacts_display(
+            installation_id,
+            failed_freezes,
+            &initiated_by,
+        ));
+
+        // For freeze_all, we need to pick a repository to comment on. Let's use the first one
+        if let Some(first_repo) = repositories.first() {
+            let repository =
+                Repository::new(&first_repo.owner.as_ref().unwrap().login, &first_repo.name);
+            self.notify_comment_issue(installation_id, &repository, issue_nr, &outcome)
+                .await;
+        }
+
+        multi_repo_result(successful_freezes, failed_freezes, &error_messages)
+    }
+
+    /// Asks the user to confirm a `/freeze-all` with a token before anything is frozen
+    pub async fn request_freeze_all_confirmation(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        requested_by: String,
+        issue_nr: u64,
+        command_line: String,
+        repos: &[String],
+        command_prefix: Option<&str>,
+    ) {
+        let outcome = match self
+            .create_freeze_all_confirmation(
+                installation_id,
+                repository,
+                requested_by,
+                command_line,
+                repos,
+                command_prefix,
+            )
+            .await
+        {
+            Ok(msg) => msg,
+            Err(e) => messages::freeze_error(&e.to_string()),
+        };
+
+        self.notify_comment_issue(installation_id, repository, issue_nr, &outcome)
+            .await;
+    }
+
+    async fn create_freeze_all_confirmation(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        requested_by: String,
+        command_line: String,
+        repos: &[String],
+        command_prefix: Option<&str>,
+    ) -> Result<String> {
+        let count = if repos.is_empty() {
+            let repositories = self.get_installation_repositories(installation_id).await?;
+            self.freezable_repositories(installation_id, &repositories)
+                .await
+                .0
+                .len()
+        } else {
+            repos.len()
+        };
+
+        let pending = PendingConfirmation::new(
+            installation_id,
+            repository.full_name(),
+            requested_by,
+            command_line,
+            FREEZE_ALL_CONFIRMATION_TTL,
+        );
+        let conn = self
+            .db
+            .get_connection()
+            .map_err(|e| anyhow!("Failed to get database connection: {}", e))?;
+        PendingConfirmation::create(conn, &pending).await?;
+
+        Ok(messages::freeze_all_confirmation_required(
+            count,
+            &confirm_command(command_prefix, &pending.token),
+            &messages::format_duration_display(FREEZE_ALL_CONFIRMATION_TTL),
+        ))
+    }
+
+    /// Holds a `/unfreeze` back until one of the installation's required approvers approves it
+    pub async fn request_unfreeze_approval(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        requested_by: String,
+        issue_nr: u64,
+        command_line: String,
+        approvers: Vec<String>,
+        command_prefix: Option<&str>,
+    ) {
+        let pending = PendingUnfreeze::new(
+            installation_id,
+            repository.full_name(),
+            requested_by,
+            command_line,
+            approvers,
+            UNFREEZE_APPROVAL_TTL,
+        );
+        let created = match self.db.get_connection() {
+            Ok(conn) => PendingUnfreeze::create(conn, &pending).await,
+            Err(e) => Err(anyhow!("Failed to get database connection: {}", e)),
+        };
+
+        let outcome = match created {
+            Ok(()) => {
+                info!(
+                    "Unfreeze of {} by {} awaits approval",
+                    repository.full_name(),
+                    pending.requested_by
+                );
+                messages::unfreeze_approval_required(
+                    &repository.full_name(),
+                    &pending.requested_by,
+                    &pending.approvers,
+                    &approve_unfreeze_command(command_prefix),
+                    &messages::format_duration_display(UNFREEZE_APPROVAL_TTL),
+                )
             }
             Err(e) => {
-                error!("Failed to check freeze status for {}: {}", repo_name, e);
-                let error_msg = messages::pr_unlock_failed(pr_number, &e.to_string());
-                self.notify_comment_issue(installation_id, repository, issue_nr, &error_msg)
-                    .await;
+                error!("Failed to request unfreeze approval: {}", e);
+                messages::unfreeze_error(&e.to_string())
             }
-        }
+        };
+
+        self.notify_comment_issue(installation_id, repository, issue_nr, &outcome)
+            .await;
+    }
+
+    /// Approves the pending unfreeze of a repository and returns the approved command
+    pub async fn approve_unfreeze(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        approver: &str,
+        command_prefix: Option<&str>,
+    ) -> Result<commands::Command> {
+        let conn = self
+            .db
+            .get_connection()
+            .map_err(|e| anyhow!("Failed to get database connection: {}", e))?;
+
+        let pending = take_unfreeze_approval(
+            conn,
+            installation_id,
+            &repository.full_name(),
+            approver,
+            Utc::now(),
+        )
+        .await?;
+        info!(
+            "{} approved the unfreeze of {} requested by {}",
+            approver,
+            repository.full_name(),
+            pending.requested_by
+        );
+
+        commands::parse_with_prefix(&pending.command_line, command_prefix)
+            .map(|parser| parser.command)
+            .map_err(|e| anyhow!("Failed to parse the approved command: {}", e))
+    }
+
+    /// Consumes a `/freeze-all` confirmation token and returns the confirmed command
+    pub async fn confirm_freeze_all(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        requested_by: &str,
+        token: &str,
+        command_prefix: Option<&str>,
+    ) -> Result<commands::Command> {
+        let conn = self
+            .db
+            .get_connection()
+            .map_err(|e| anyhow!("Failed to get database connection: {}", e))?;
+
+        let pending = PendingConfirmation::take(
+            conn,
+            installation_id,
+            &repository.full_name(),
+            requested_by,
+            token,
+            Utc::now(),
+        )
+        .await?
+        .ok_or_else(|| {
+            anyhow!(
+                "Unknown or expired confirmation token `{}`. Run `/freeze-all` again to get a new one.",
+                token.trim()
+            )
+        })?;
+
+        commands::parse_with_prefix(&pending.command_line, command_prefix)
+            .map(|parser| parser.command)
+            .map_err(|e| anyhow!("Failed to parse the confirmed command: {}", e))
+    }
+
+    async fn freeze_repos(
+        &self,
+        installation_id: InstallationId,
+        reason: Option<String>,
+        initiated_by: String,
+        issue_nr: u64,
+        options: &FreezeOptions,
+    ) -> (CommandResult, Option<String>) {
+        let (repositories, mut error_messages, duplicates) = parse_repositories(&options.repos);
+        for error in &error_messages {
+            error!("{}", error);
+        }
+        if !duplicates.is_empty() {
+            info!("Ignoring repeated repositories: {}", duplicates.join(", "));
+        }
+        let skipped_str = messages::format_skipped_display(&duplicates);
+
+        let mut successful_freezes = 0;
+        let mut failed_freezes = error_messages.len();
+
+        for repository in &repositories {
+            if options.if_not_frozen
+                && self
+                    .is_already_frozen(installation_id, repository, &options.branch)
+                    .await
+            {
+                successful_freezes += 1;
+                continue;
+            }
+
+            match self
+                .handle_freeze(
+                    installation_id,
+                    repository,
+                    reason.clone(),
+                    initiated_by.clone(),
+                    options,
+                )
+                .await
+            {
+                Ok(_) => {
+                    successful_freezes += 1;
+                    info!("Successfully froze repository: {}", repository.full_name());
+                }
+                Err(e) => {
+                    failed_freezes += 1;
+                    let error = format!("Failed to freeze {}: {}", repository.full_name(), e);
+                    error_messages.push(error.clone());
+                    error!("{}", error);
+                }
+            }
+        }
+
+        let mut outcome = if failed_freezes == 0 {
+            messages::freeze_all_success(successful_freezes, &skipped_str)
+        } else {
+            messages::freeze_all_partial_success(
+                successful_freezes,
+                failed_freezes,
+                &error_messages,
+                &skipped_str,
+            )
+        };
+        outcome.push_str(&self.ops_contacts_display(
+            installation_id,
+            failed_freezes,
+            &initiated_by,
+        ));
+
+        // Comment on the first repository (or the one that triggered the command)
+        if let Some(repository) = repositories.first() {
+            self.notify_comment_issue(installation_id, repository, issue_nr, &outcome)
+                .await;
+        }
+
+        multi_repo_result(successful_freezes, failed_freezes, &error_messages)
+    }
+
+    pub async fn unfreeze_all(
+        &self,
+        installation_id: InstallationId,
+        ended_by: String,
+        issue_nr: u64,
+        repos: Vec<String>,
+        branch: Option<String>,
+        reason: Option<String>,
+    ) -> (CommandResult, Option<String>) {
+        // If specific repos are provided, only unfreeze those
+        if !repos.is_empty() {
+            return self
+                .unfreeze_repos(installation_id, ended_by, issue_nr, repos, branch, reason)
+                .await;
+        }
+
+        // Get all repositories for this installation
+        let repositories = match self.get_installation_repositories(installation_id).await {
+            Ok(repos) => repos,
+            Err(e) => {
+                error!(
+                    "Failed to get repositories for installation {}: {}",
+                    installation_id, e
+                );
+                return (
+                    CommandResult::Failure,
+                    Some(format!("Failed to get repositories: {}", e)),
+                );
+            }
+        };
+
+        if repositories.is_empty() {
+            error!("No repositories found for installation {}", installation_id);
+            return (
+                CommandResult::Failure,
+                Some("No repositories accessible for this installation".to_string()),
+            );
+        }
+
+        let mut successful_unfreezes = 0;
+        let mut failed_unfreezes = 0;
+        let mut error_messages = Vec::new();
+
+        // Lift organization-wide freezes first
+        let mut owners: Vec<String> = Vec::new();
+        for repo in &repositories {
+            let owner = Repository::from(repo).owner;
+            if !owners.iter().any(|o| o.eq_ignore_ascii_case(&owner)) {
+                owners.push(owner);
+            }
+        }
+
+        let mut unfroze_org = false;
+        for owner in owners {
+            let org = Repository::org_wildcard(owner);
+            if !self.has_own_freeze(installation_id, &org, &branch).await {
+                continue;
+            }
+
+            match self
+                .handle_unfreeze(
+                    installation_id,
+                    &org,
+                    ended_by.clone(),
+                    branch.clone(),
+                    reason.clone(),
+                    None,
+                )
+                .await
+            {
+                Ok(_) => {
+                    successful_unfreezes += 1;
+                    unfroze_org = true;
+                    info!("Successfully unfroze organization: {}", org.owner());
+                }
+                Err(e) => {
+                    failed_unfreezes += 1;
+                    let error = format!("Failed to unfreeze {}: {}", org.full_name(), e);
+                    error_messages.push(error.clone());
+                    error!("{}", error);
+                }
+            }
+        }
+
+        for repo in &repositories {
+            let repository = Repository::new(&repo.owner.as_ref().unwrap().login, &repo.name);
+
+            // Repositories only covered by the lifted organization freeze are done
+            if unfroze_org && !self.has_own_freeze(installation_id, &repository, &branch).await {
+                continue;
+            }
+
+            match self
+                .handle_unfreeze(
+                    installation_id,
+                    &repository,
+                    ended_by.clone(),
+                    branch.clone(),
+                    reason.clone(),
+                    None,
+                )
+                .await
+            {
+                Ok(_) => {
+                    successful_unfreezes += 1;
+                    info!(
+                        "Successfully unfroze repository: {}",
+                        repository.full_name()
+                    );
+                }
+                Err(e) => {
+                    failed_unfreezes += 1;
+                    let error = format!("Failed to unfreeze {}: {}", repository.full_name(), e);
+                    error_messages.push(error.clone());
+                    error!("{}", error);
+                }
+            }
+        }
+
+        let reason_str = messages::format_reason_display(reason);
+        let mut outcome = if failed_unfreezes == 0 {
+            messages::unfreeze_all_success(successful_unfreezes, &reason_str)
+        } else {
+            messages::unfreeze_all_partial_success(
+                successful_unfreezes,
+                failed_unfreezes,
+                &error_messages,
+                &reason_str,
+            )
+        };
+        outcome.push_str(&self.ops_contacts_display(installation_id, failed_unfreezes, &ended_by));
+
+        // For unfreeze_all, we need to pick a repository to comment on. Let's use the first one
+        if let Some(first_repo) = repositories.first() {
+            let repository =
+                Repository::new(&first_repo.owner.as_ref().unwrap().login, &first_repo.name);
+            self.notify_comment_issue(installation_id, &repository, issue_nr, &outcome)
+                .await;
+        }
+
+        multi_repo_result(successful_unfreezes, failed_unfreezes, &error_messages)
+    }
+
+    async fn unfreeze_repos(
+        &self,
+        installation_id: InstallationId,
+        ended_by: String,
+        issue_nr: u64,
+        repo_names: Vec<String>,
+        branch: Option<String>,
+        reason: Option<String>,
+    ) -> (CommandResult, Option<String>) {
+        let (repositories, mut error_messages, duplicates) = parse_repositories(&repo_names);
+        for error in &error_messages {
+            error!("{}", error);
+        }
+        if !duplicates.is_empty() {
+            info!("Ignoring repeated repositories: {}", duplicates.join(", "));
+        }
+
+        let mut successful_unfreezes = 0;
+        let mut failed_unfreezes = error_messages.len();
+
+        for repository in &repositories {
+            match self
+                .handle_unfreeze(
+                    installation_id,
+                    repository,
+                    ended_by.clone(),
+                    branch.clone(),
+                    reason.clone(),
+                    None,
+                )
+                .await
+            {
+                Ok(_) => {
+                    successful_unfreezes += 1;
+                    info!(
+                        "Successfully unfroze repository: {}",
+                        repository.full_name()
+                    );
+                }
+                Err(e) => {
+                    failed_unfreezes += 1;
+                    let error = format!("Failed to unfreeze {}: {}", repository.full_name(), e);
+                    error_messages.push(error.clone());
+                    error!("{}", error);
+                }
+            }
+        }
+
+        let reason_str = messages::format_reason_display(reason);
+        let mut outcome = if failed_unfreezes == 0 {
+            messages::unfreeze_all_success(successful_unfreezes, &reason_str)
+        } else {
+            messages::unfreeze_all_partial_success(
+                successful_unfreezes,
+                failed_unfreezes,
+                &error_messages,
+                &reason_str,
+            )
+        };
+        outcome.push_str(&self.ops_contacts_display(installation_id, failed_unfreezes, &ended_by));
+
+        // Comment on the first valid repository
+        if let Some(repository) = repositories.first() {
+            self.notify_comment_issue(installation_id, repository, issue_nr, &outcome)
+                .await;
+        }
+
+        multi_repo_result(successful_unfreezes, failed_unfreezes, &error_messages)
+    }
+
+    /// Reads a freeze reason from a file of the repository's default branch
+    pub async fn read_reason_file(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        path: &str,
+    ) -> Result<String> {
+        let path = path.trim().trim_start_matches('/').to_string();
+        if path.is_empty() {
+            return Err(anyhow!("A path is required for --reason-file"));
+        }
+
+        let url = format!(
+            "/repos/{}/{}/contents/{}",
+            repository.owner, repository.name, path
+        );
+        let contents: serde_json::Value = self
+            .github
+            .with_installation_async(installation_id.as_u64(), |client| async move {
+                client
+                    .get(url, None::<&()>)
+                    .await
+                    .map_err(|e| anyhow!("Failed to read reason file: {}", e))
+            })
+            .await?;
+
+        reason_from_contents(&path, &contents)
+    }
+
+    async fn get_installation_repositories(
+        &self,
+        installation_id: InstallationId,
+    ) -> Result<Vec<octocrab::models::Repository>> {
+        self.github
+            .with_installation_async(installation_id.as_u64(), |client| async move {
+                // Use the manual HTTP approach for the installation repositories endpoint
+                let url = "/installation/repositories";
+                let response: serde_json::Value = client
+                    .get(url, None::<&()>)
+                    .await
+                    .map_err(|e| anyhow!("Failed to get installation repositories: {}", e))?;
+
+                let repositories = response
+                    .get("repositories")
+                    .and_then(|r| r.as_array())
+                    .ok_or_else(|| anyhow!("Invalid response format"))?;
+
+                let mut repos = Vec::new();
+                for repo_value in repositories {
+                    let repo: octocrab::models::Repository =
+                        serde_json::from_value(repo_value.clone())
+                            .map_err(|e| anyhow!("Failed to deserialize repository: {}", e))?;
+                    repos.push(repo);
+                }
+
+                Ok(repos)
+            })
+            .await
+    }
+
+    /// Splits installation repositories into freezable ones and notes for the
+    /// skipped ones, asking GitHub whether the remaining ones are empty.
+    ///
+    /// A repository whose emptiness cannot be checked is kept, so a failed
+    /// lookup never drops a repository from a freeze.
+    async fn freezable_repositories<'a>(
+        &self,
+        installation_id: InstallationId,
+        repositories: &'a [octocrab::models::Repository],
+    ) -> (Vec<&'a octocrab::models::Repository>, Vec<String>) {
+        let (candidates, mut skipped) = partition_freezable(repositories);
+
+        let mut usable = Vec::new();
+        for repo in candidates {
+            let repository = Repository::from(repo);
+            match self
+                .pr_refresh
+                .is_empty_repository(installation_id, repository.owner(), repository.name())
+                .await
+            {
+                Ok(true) => skipped.push(format!("`{}` (empty)", repository.full_name())),
+                Ok(false) => usable.push(repo),
+                Err(e) => {
+                    warn!("Could not check whether {} is empty: {}", repository.full_name(), e);
+                    usable.push(repo);
+                }
+            }
+        }
+
+        (usable, skipped)
+    }
+
+    /// Lists the freezable installation repositories belonging to `owner`
+    async fn get_owner_repositories(
+        &self,
+        installation_id: InstallationId,
+        owner: &str,
+    ) -> Result<Vec<Repository>> {
+        let repositories: Vec<_> = self
+            .get_installation_repositories(installation_id)
+            .await?
+            .into_iter()
+            .filter(|r| Repository::from(r).owner.eq_ignore_ascii_case(owner))
+            .collect();
+        let (usable, _) = self
+            .freezable_repositories(installation_id, &repositories)
+            .await;
+
+        Ok(usable.into_iter().map(Repository::from).collect())
+    }
+
+    /// Posts the most recent commands run in a repository as a Markdown table
+    pub async fn audit(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        limit: u32,
+        issue_nr: u64,
+    ) -> (CommandResult, Option<String>) {
+        let limit = limit.clamp(1, MAX_AUDIT_LIMIT);
+        let logs = match self.db.get_connection() {
+            Ok(conn) => {
+                CommandLog::list(conn, Some(installation_id), Some(&repository.full_name()), limit)
+                    .await
+            }
+            Err(e) => Err(anyhow!("Failed to get database connection: {}", e)),
+        };
+
+        let outcome = match &logs {
+            Ok(logs) => messages::format_audit_table(&repository.full_name(), logs),
+            Err(e) => {
+                error!("Failed to read command log: {}", e);
+                messages::audit_error(&e.to_string())
+            }
+        };
+
+        self.notify_comment_issue(installation_id, repository, issue_nr, &outcome)
+            .await;
+        command_result(&logs)
+    }
+
+    /// Posts the scheduled freezes of a repository that have not started yet
+    pub async fn list_scheduled(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        issue_nr: u64,
+    ) -> (CommandResult, Option<String>) {
+        let records = match self.db.get_connection() {
+            Ok(conn) => {
+                FreezeRecord::list_scheduled(conn, installation_id, &repository.full_name()).await
+            }
+            Err(e) => Err(anyhow!("Failed to get database connection: {}", e)),
+        };
+
+        let outcome = match &records {
+            Ok(records) => messages::format_scheduled_table(&repository.full_name(), records),
+            Err(e) => {
+                error!("Failed to list scheduled freezes: {}", e);
+                messages::scheduled_error(&e.to_string())
+            }
+        };
+
+        self.notify_comment_issue(installation_id, repository, issue_nr, &outcome)
+            .await;
+        command_result(&records)
+    }
+
+    /// Posts the freezes of the installation the scheduler will activate or
+    /// expire on its next run, without changing anything
+    pub async fn scheduler_preview(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        issue_nr: u64,
+    ) -> (CommandResult, Option<String>) {
+        let at = Utc::now()
+            + chrono::Duration::from_std(worker::SCHEDULER_INTERVAL)
+                .unwrap_or_else(|_| chrono::Duration::minutes(1));
+        let preview = match self.db.get_connection() {
+            Ok(conn) => worker::scheduler_preview(conn, installation_id, at).await,
+            Err(e) => Err(anyhow!("Failed to get database connection: {}", e)),
+        };
+
+        let outcome = match &preview {
+            Ok(preview) => messages::format_scheduler_preview(
+                &at.format("%Y-%m-%d %H:%M UTC").to_string(),
+                &preview.to_activate,
+                &preview.to_expire,
+            ),
+            Err(e) => {
+                error!("Failed to preview the scheduler: {}", e);
+                messages::scheduler_preview_error(&e.to_string())
+            }
+        };
+
+        self.notify_comment_issue(installation_id, repository, issue_nr, &outcome)
+            .await;
+        command_result(&preview)
+    }
+
+    /// Reports the open PRs whose Frezze check disagrees with their freeze state.
+    ///
+    /// Every freezable repository of the installation is checked unless
+    /// `repos` names some. With `fix`, the drifted checks are re-published.
+    pub async fn reconcile(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        repos: Vec<String>,
+        fix: bool,
+        issue_nr: u64,
+    ) -> (CommandResult, Option<String>) {
+        let (repositories, mut errors) = if repos.is_empty() {
+            match self.get_installation_repositories(installation_id).await {
+                Ok(all_repos) => {
+                    let (usable, _) = self
+                        .freezable_repositories(installation_id, &all_repos)
+                        .await;
+                    (
+                        usable.into_iter().map(Repository::from).collect(),
+                        Vec::new(),
+                    )
+                }
+                Err(e) => {
+                    error!("Failed to list repositories to reconcile: {}", e);
+                    let error_msg = messages::reconcile_error(&e.to_string());
+                    self.notify_comment_issue(installation_id, repository, issue_nr, &error_msg)
+                        .await;
+                    return (CommandResult::Failure, Some(e.to_string()));
+                }
+            }
+        } else {
+            let (repositories, errors, _) = parse_repositories(&repos);
+            (repositories, errors)
+        };
+
+        let mut results = Vec::new();
+        for repo in &repositories {
+            match self
+                .pr_refresh
+                .reconcile_repository(installation_id, repo, fix)
+                .await
+            {
+                Ok(drifts) => {
+                    if !drifts.is_empty() {
+                        info!(
+                            "Found {} drifted freeze checks in {}",
+                            drifts.len(),
+                            repo.full_name()
+                        );
+                    }
+                    results.push((repo.full_name(), drifts));
+                }
+                Err(e) => {
+                    warn!("Failed to reconcile {}: {}", repo.full_name(), e);
+                    errors.push(format!("`{}`: {}", repo.full_name(), e));
+                }
+            }
+        }
+
+        let outcome = messages::format_reconcile_report(results.len(), &results, &errors, fix);
+        self.notify_comment_issue(installation_id, repository, issue_nr, &outcome)
+            .await;
+
+        multi_repo_result(results.len(), errors.len(), &errors)
+    }
+
+    /// Cancels a scheduled freeze of a repository before it starts
+    pub async fn cancel_schedule(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        id: &str,
+        issue_nr: u64,
+    ) -> (CommandResult, Option<String>) {
+        let cancelled = match self.db.get_connection() {
+            Ok(conn) => {
+                FreezeRecord::cancel_scheduled(conn, installation_id, &repository.full_name(), id)
+                    .await
+            }
+            Err(e) => Err(anyhow!("Failed to get database connection: {}", e)),
+        }
+        .and_then(|cancelled| {
+            if cancelled {
+                Ok(())
+            } else {
+                Err(anyhow!(
+                    "No scheduled freeze with ID {} found for this repository",
+                    id.trim()
+                ))
+            }
+        });
+
+        let outcome = match &cancelled {
+            Ok(()) => {
+                info!(
+                    "Cancelled scheduled freeze {} of {}",
+                    id,
+                    repository.full_name()
+                );
+                messages::cancel_schedule_success(&repository.full_name(), id.trim())
+            }
+            Err(e) => {
+                error!("Failed to cancel scheduled freeze {}: {}", id, e);
+                messages::cancel_schedule_error(&e.to_string())
+            }
+        };
+
+        self.notify_comment_issue(installation_id, repository, issue_nr, &outcome)
+            .await;
+        command_result(&cancelled)
+    }
+
+    /// Posts bot messages rendered with sample data, every message type when none is given
+    pub async fn preview(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        message_type: Option<&str>,
+        issue_nr: u64,
+    ) -> (CommandResult, Option<String>) {
+        let outcome = match message_type {
+            Some(message_type) => messages::preview_message(message_type)
+                .unwrap_or_else(|| messages::preview_unknown(message_type)),
+            None => messages::PREVIEW_MESSAGE_TYPES
+                .iter()
+                .filter_map(|t| messages::preview_message(t))
+                .collect::<Vec<_>>()
+                .join("\n\n---\n\n"),
+        };
+
+        self.notify_comment_issue(installation_id, repository, issue_nr, &outcome)
+            .await;
+
+        match message_type.filter(|t| messages::preview_message(t).is_none()) {
+            Some(unknown) => (
+                CommandResult::Failure,
+                Some(format!("Unknown message type `{}`", unknown)),
+            ),
+            None => (CommandResult::Success, None),
+        }
+    }
+
+    pub async fn get_status(
+        &self,
+        installation_id: InstallationId,
+        repos: Vec<String>,
+        issue_nr: u64,
+        repository: &Repository,
+        as_check: bool,
+        pinned: bool,
+        debug: bool,
+        branch: Option<&str>,
+    ) -> (CommandResult, Option<String>) {
+        let mut status_entries = Vec::new();
+
+        if repos.is_empty() {
+            // If no specific repos requested, get all repositories for this installation
+            match self.get_installation_repositories(installation_id).await {
+                Ok(all_repos) => {
+                    for repo in all_repos {
+                        let repo_name =
+                            format!("{}/{}", repo.owner.as_ref().unwrap().login, repo.name);
+                        let repository =
+                            Repository::new(&repo.owner.as_ref().unwrap().login, &repo.name);
+                        let entries = self
+                            .get_repository_status(installation_id, &repository, branch)
+                            .await;
+                        status_entries
+                            .extend(entries.into_iter().map(|e| (repo_name.clone(), e)));
+                    }
+                }
+                Err(e) => {
+                    let error = format!("Failed to get repositories: {}", e);
+                    let error_msg = messages::status_error(&error);
+                    self.notify_comment_issue(installation_id, repository, issue_nr, &error_msg)
+                        .await;
+                    return (CommandResult::Failure, Some(error));
+                }
+            }
+        } else {
+            // Get status for specific repositories
+            for repo_name in repos {
+                let parts: Vec<&str> = repo_name.split('/').collect();
+                if parts.len() != 2 {
+                    status_entries.push((
+                        repo_name.clone(),
+                        StatusEntry::error("Invalid repository format"),
+                    ));
+                    continue;
+                }
+
+                let repository = Repository::new(parts[0], parts[1]);
+                let entries = self
+                    .get_repository_status(installation_id, &repository, branch)
+                    .await;
+                status_entries.extend(entries.into_iter().map(|e| (repo_name.clone(), e)));
+            }
+        }
+
+        let mut repo_names: Vec<String> = Vec::new();
+        if debug {
+            for (repo_name, _) in &status_entries {
+                if !repo_names.contains(repo_name) {
+                    repo_names.push(repo_name.clone());
+                }
+            }
+        }
+
+        let mut status_msg = messages::format_status_table(status_entries);
+        if debug {
+            status_msg.push_str(&self.refresh_debug(installation_id, repo_names).await);
+        }
+
+        if as_check {
+            let summary = self.config.message_theme.apply(&status_msg);
+            match self
+                .pr_refresh
+                .publish_status_check(installation_id, repository, issue_nr, &summary)
+                .await
+            {
+                Ok(()) => return (CommandResult::Success, None),
+                Err(e) => {
+                    // Not a PR or the check could not be created: fall back to a comment
+                    warn!(
+                        "Failed to publish status on check run for #{}: {}",
+                        issue_nr, e
+                    );
+                }
+            }
+        }
+
+        if pinned {
+            self.pin_status_comment(installation_id, repository, issue_nr, &status_msg)
+                .await;
+        } else {
+            self.notify_comment_issue(installation_id, repository, issue_nr, &status_msg)
+                .await;
+        }
+        (CommandResult::Success, None)
+    }
+
+    /// Renders the refresh statistics of the given repositories
+    async fn refresh_debug(&self, installation_id: InstallationId, repo_names: Vec<String>) -> String {
+        let mut entries = Vec::new();
+        for repo_name in repo_names {
+            let stats = RefreshStats::get(self.db.pool(), installation_id, &repo_name)
+                .await
+                .unwrap_or_else(|e| {
+                    warn!("Failed to get refresh stats for {}: {}", repo_name, e);
+                    None
+                });
+            entries.push((repo_name, stats));
+        }
+
+        messages::format_refresh_debug(entries)
+    }
+
+    async fn get_repository_status(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        branch: Option<&str>,
+    ) -> Vec<StatusEntry> {
+        let conn = match self.db.get_connection() {
+            Ok(conn) => conn,
+            Err(e) => return vec![StatusEntry::error(&format!("Database error: {}", e))],
+        };
+
+        match FreezeRecord::list_in_effect(conn, installation_id, &repository.full_name())
+            .await
+        {
+            Ok(records) => repository_status_entries(&records, branch),
+            Err(e) => vec![StatusEntry::error(&format!(
+                "Failed to get freeze records: {}",
+                e
+            ))],
+        }
+    }
+
+    pub async fn schedule_freeze(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        start: DateTime<Utc>,
+        end: Option<DateTime<Utc>>,
+        reason: Option<String>,
+        initiated_by: String,
+        options: FreezeOptions,
+    ) -> Result<()> {
+        let end_time = resolve_schedule_end(start, end, options.duration)?;
+        check_min_duration(
+            end_time - start,
+            self.config.min_freeze_duration,
+            options.can_override,
+        )?;
+
+        let reason = reason
+            .map(|r| expand_reason(&r, &repository.full_name(), &initiated_by, start));
+        let record = FreezeRecord::new_scheduled(
+            repository.full_name(),
+            installation_id,
+            start,
+            Some(end_time),
+            reason,
+            initiated_by,
+            options.branch,
+        );
+
+        let conn = self
+            .db
+            .get_connection()
+            .map_err(|e| anyhow!("Failed to get database connection: {}", e))?;
+
+        let record = FreezeRecord::create(conn, &record).await?;
+
+        self.notifier
+            .dispatch(
+                FreezeEvent::from_record(FreezeEventKind::Schedule, &record)
+                    .with_watchers(self.watchers_for(
+                        installation_id,
+                        &record.repository,
+                        &record.initiated_by,
+                    )),
+            )
+            .await;
+
+        Ok(())
+    }
+
+    pub async fn unfreeze(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        ended_by: String,
+        reason: Option<String>,
+        issue_nr: u64,
+        branch: Option<String>,
+        environment: Option<String>,
+    ) -> (CommandResult, Option<String>) {
+        let branch_for_display = branch.clone();
+        let environment_for_display = environment.clone();
+        let watchers = self.watchers_for(installation_id, &repository.full_name(), &ended_by);
+        let unfrozen = self
+            .handle_unfreeze(
+                installation_id,
+                repository,
+                ended_by,
+                branch,
+                reason.clone(),
+                environment,
+            )
+            .await;
+        let outcome = match &unfrozen {
+            Ok(_) => {
+                let reason_str = messages::format_reason_display(reason);
+                let branch_str = format!(
+                    "{}{}",
+                    messages::format_branch_display(branch_for_display),
+                    messages::format_environment_display(environment_for_display)
+                );
+                format!(
+                    "{}{}",
+                    messages::unfreeze_success(&repository.to_string(), &reason_str, &branch_str),
+                    messages::format_watchers_display(&watchers)
+                )
+            }
+            Err(e) => {
+                tracing::error!("Failed to unfreeze repository: {:?}", e);
+                messages::unfreeze_error(&e.to_string())
+            }
+        };
+
+        self.notify_comment_issue(installation_id, repository, issue_nr, &outcome)
+            .await;
+        command_result(&unfrozen)
+    }
+
+    /// Unfreeze a repository
+    async fn handle_unfreeze(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        ended_by: String,
+        branch: Option<String>,
+        end_reason: Option<String>,
+        environment: Option<String>,
+    ) -> Result<()> {
+        let conn = self
+            .db
+            .get_connection()
+            .map_err(|e| anyhow!("Failed to get database connection: {}", e))?;
+
+        let repo = repository.full_name();
+        // Get active freeze records for this repository
+        let mut freeze_records =
+            FreezeRecord::list(conn, Some(installation_id), Some(&repo), Some(true))
+                .await
+                .map_err(|e| anyhow!("Failed to get freeze records for repo {}: {}", repo, e))?;
+
+        // Filter by branch if specified
+        if let Some(ref target_branch) = branch {
+            freeze_records.retain(|r| r.branch.as_ref() == Some(target_branch));
+        }
+
+        // Filter by environment if specified
+        if let Some(ref target_environment) = environment {
+            freeze_records.retain(|r| {
+                r.environment
+                    .as_ref()
+                    .is_some_and(|e| e.eq_ignore_ascii_case(target_environment))
+            });
+        }
+
+        if freeze_records.is_empty() {
+            let branch_msg = branch.map_or(String::new(), |b| format!(" for branch '{}'", b));
+            let environment_msg =
+                environment.map_or(String::new(), |e| format!(" for environment '{}'", e));
+            return Err(anyhow!(
+                "No active freeze found for repository: {}{}{}",
+                repo,
+                branch_msg,
+                environment_msg
+            ));
+        }
+
+        // End all matching active freezes for this repository
+        let mut ended_records = Vec::new();
+        for record in freeze_records {
+            let record_id = record.id.clone();
+            let ended = FreezeRecord::update_status(
+                conn,
+                record.id,
+                crate::database::models::FreezeStatus::Ended,
+                Some(ended_by.clone()),
+                end_reason.clone(),
+            )
+            .await
+            .map_err(|e| anyhow!("Failed to end freeze record {}: {}", record_id, e))?;
+            if let Some(ref ended) = ended {
+                self.cache.invalidate(installation_id, &ended.repository);
+            }
+            ended_records.extend(ended);
+        }
+
+        for record in &ended_records {
+            self.notifier
+                .dispatch(
+                    FreezeEvent::from_record(FreezeEventKind::Unfreeze, record)
+                        .with_watchers(self.watchers_for(
+                            installation_id,
+                            &record.repository,
+                            &ended_by,
+                        )),
+                )
+                .await;
+        }
+
+        // Refresh PRs after unfreezing, keeping any freeze still in effect
+        self.refresh_prs(installation_id, repository, None).await;
+
+        Ok(())
+    }
+    /// Get the active freeze record for a repository, if one exists
+    async fn get_active_freeze(
+        &self,
+        repository: &Repository,
+        installation_id: InstallationId,
+    ) -> Result<Option<FreezeRecord>> {
+        let conn = self
+            .db
+            .get_connection()
+            .map_err(|e| anyhow!("Failed to get database connection: {}", e))?;
+
+        let repo = repository.full_name();
+        let freeze_record = FreezeRecord::get_active_freeze(
+            conn,
+            installation_id,
+            &repo,
+            self.config.max_freeze_age,
+        )
+        .await
+        .map_err(|e| anyhow!("Failed to get active freeze for repository {}: {}", repo, e))?;
+
+        Ok(freeze_record)
+    }
+
+    pub async fn unlock_pr(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        pr_number: u64,
+        author: String,
+        reason: Option<String>,
+        issue_nr: u64,
+    ) -> (CommandResult, Option<String>) {
+        let repo_name = repository.full_name();
+
+        // Check if repository is currently frozen
+        match self
+            .get_active_freeze(repository, installation_id)
+            .await
+        {
+            Ok(Some(freeze)) => {
+                // Repository is frozen, proceed with unlock
+                match UnlockedPr::unlock_pr(
+                    self.db.pool(),
+                    installation_id,
+                    &repo_name,
+                    pr_number,
+                    &author,
+                )
+                .await
+                {
+                    Ok(Some(unlocked_by)) => {
+                        let msg = messages::pr_already_unlocked(pr_number, &unlocked_by);
+                        self.notify_comment_issue(installation_id, repository, issue_nr, &msg)
+                            .await;
+                        (CommandResult::Success, None)
+                    }
+                    Ok(None) => {
+                        let reason_str = messages::format_reason_display(reason);
+                        let success_msg = messages::pr_unlock_success(pr_number, &reason_str);
+                        self.notify_comment_issue(
+                            installation_id,
+                            repository,
+                            issue_nr,
+                            &success_msg,
+                        )
+                        .await;
+
+                        // Make sure unlocks during a freeze do not go unnoticed
+                        if let Some(alert) = unlock_alert(
+                            self.config.alert_on_unlock_pr,
+                            &freeze,
+                            &self.watchers_for(installation_id, &repo_name, &author),
+                            &author,
+                            pr_number,
+                            &repository.pr_url(&self.config.github_url, pr_number),
+                            &reason_str,
+                        ) {
+                            self.notify_comment_issue(
+                                installation_id,
+                                repository,
+                                issue_nr,
+                                &alert,
+                            )
+                            .await;
+                        }
+
+                        // Refresh the specific PR to update its status
+                        if let Err(e) = self
+                            .pr_refresh
+                            .refresh_single_pr(installation_id, repository, pr_number)
+                            .await
+                        {
+                            error!("Failed to refresh PR {} after unlock: {}", pr_number, e);
+                        }
+                        (CommandResult::Success, None)
+                    }
+                    Err(e) => {
+                        error!("Failed to unlock PR {}: {}", pr_number, e);
+                        let error_msg = messages::pr_unlock_failed(pr_number, &e.to_string());
+                        self.notify_comment_issue(
+                            installation_id,
+                            repository,
+                            issue_nr,
+                            &error_msg,
+                        )
+                        .await;
+                        (CommandResult::Failure, Some(e.to_string()))
+                    }
+                }
+            }
+            Ok(None) => {
+                // Repository is not frozen
+                let error_msg = messages::pr_unlock_not_frozen(&repo_name);
+                self.notify_comment_issue(installation_id, repository, issue_nr, &error_msg)
+                    .await;
+                (
+                    CommandResult::Failure,
+                    Some(format!("{} is not frozen", repo_name)),
+                )
+            }
+            Err(e) => {
+                error!("Failed to check freeze status for {}: {}", repo_name, e);
+                let error_msg = messages::pr_unlock_failed(pr_number, &e.to_string());
+                self.notify_comment_issue(installation_id, repository, issue_nr, &error_msg)
+                    .await;
+                (CommandResult::Failure, Some(e.to_string()))
+            }
+        }
+    }
+
+    /// Unlock the pull request a `/freeze` was requested from.
+    ///
+    /// Only called once the freeze went through, so a rejected freeze
+    /// leaves no unlock behind.
+    pub async fn auto_unlock_pr(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        pr_number: u64,
+        author: String,
+    ) {
+        let repo_name = repository.full_name();
+
+        match UnlockedPr::unlock_pr(
+            self.db.pool(),
+            installation_id,
+            &repo_name,
+            pr_number,
+            &author,
+        )
+        .await
+        {
+            Ok(None) => {}
+            // Nothing new to announce
+            Ok(Some(_)) => return,
+            Err(e) => {
+                error!(
+                    "Failed to auto-unlock PR {} in {}: {}",
+                    pr_number, repo_name, e
+                );
+                return;
+            }
+        }
+
+        info!(
+            "Auto-unlocked PR {} in {} after freeze",
+            pr_number, repo_name
+        );
+        let msg = messages::pr_auto_unlocked(pr_number);
+        self.notify_comment_issue(installation_id, repository, pr_number, &msg)
+            .await;
+
+        if let Err(e) = self
+            .pr_refresh
+            .refresh_single_pr(installation_id, repository, pr_number)
+            .await
+        {
+            error!("Failed to refresh PR {} after unlock: {}", pr_number, e);
+        }
+    }
+
+    /// Record a comment command in the audit log before it is executed
+    pub async fn record_command(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        user_login: String,
+        command: String,
+        comment_id: u64,
+        result: CommandResult,
+    ) {
+        self.log_command(CommandLog::new(
+            installation_id,
+            repository.full_name(),
+            user_login,
+            command,
+            comment_id,
+            result,
+            None,
+        ))
+        .await;
+    }
+
+    /// Record the outcome of a comment command once it has run
+    pub async fn update_command_result(
+        &self,
+        installation_id: InstallationId,
+        comment_id: u64,
+        command: &str,
+        result: CommandResult,
+        error_message: Option<String>,
+    ) {
+        let conn = match self.db.get_connection() {
+            Ok(conn) => conn,
+            Err(e) => {
+                error!("Failed to get database connection for command log: {}", e);
+                return;
+            }
+        };
+
+        if let Err(e) = CommandLog::update_result(
+            conn,
+            installation_id,
+            comment_id,
+            command,
+            &result,
+            error_message.as_deref(),
+        )
+        .await
+        {
+            error!("Failed to update command log for {}: {}", command, e);
+        }
+    }
+
+    /// Check whether a comment already triggered the given command.
+    ///
+    /// Lookup failures are logged and treated as "not processed" so that a
+    /// database hiccup never swallows a command.
+    pub async fn is_command_processed(
+        &self,
+        installation_id: InstallationId,
+        comment_id: u64,
+        command: &str,
+    ) -> bool {
+        let conn = match self.db.get_connection() {
+            Ok(conn) => conn,
+            Err(e) => {
+                error!("Failed to get database connection for command log: {}", e);
+                return false;
+            }
+        };
+
+        CommandLog::exists_for_comment(conn, installation_id, comment_id, command)
+            .await
+            .unwrap_or_else(|e| {
+                error!("Failed to look up command log for comment {}: {}", comment_id, e);
+                false
+            })
+    }
+
+    /// Record a command in the audit log, logging (but not propagating) failures
+    async fn log_command(&self, log: CommandLog) {
+        let conn = match self.db.get_connection() {
+            Ok(conn) => conn,
+            Err(e) => {
+                error!("Failed to get database connection for command log: {}", e);
+                return;
+            }
+        };
+
+        if let Err(e) = CommandLog::create(conn, &log).await {
+            error!("Failed to record command log for {}: {}", log.command, e);
+        }
+    }
+
+    pub async fn reassign_freeze(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        new_owner: String,
+        requested_by: String,
+        requester_is_admin: bool,
+        branch: Option<String>,
+        issue_nr: u64,
+    ) -> (CommandResult, Option<String>) {
+        let reassigned = self
+            .handle_reassign(
+                installation_id,
+                repository,
+                &new_owner,
+                &requested_by,
+                requester_is_admin,
+                branch.as_deref(),
+            )
+            .await;
+        let outcome = match &reassigned {
+            Ok(previous_owners) => {
+                let branch_str = messages::format_branch_display(branch);
+                messages::freeze_reassign_success(
+                    &repository.to_string(),
+                    previous_owners,
+                    &new_owner,
+                    &branch_str,
+                )
+            }
+            Err(e) => {
+                error!("Failed to reassign freeze: {:?}", e);
+                messages::freeze_reassign_error(&e.to_string())
+            }
+        };
+
+        self.notify_comment_issue(installation_id, repository, issue_nr, &outcome)
+            .await;
+        match reassigned {
+            // Keep the previous owner in the audit trail
+            Ok(previous_owners) => (
+                CommandResult::Success,
+                Some(format!("previous owner: {}", previous_owners)),
+            ),
+            Err(e) => (CommandResult::Failure, Some(e.to_string())),
+        }
+    }
+
+    /// Transfer ownership of the active freezes of a repository.
+    ///
+    /// Returns the previous owner(s) of the reassigned freezes.
+    async fn handle_reassign(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        new_owner: &str,
+        requested_by: &str,
+        requester_is_admin: bool,
+        branch: Option<&str>,
+    ) -> Result<String> {
+        let conn = self
+            .db
+            .get_connection()
+            .map_err(|e| anyhow!("Failed to get database connection: {}", e))?;
+
+        let repo = repository.full_name();
+        let mut freeze_records =
+            FreezeRecord::list(conn, Some(installation_id), Some(&repo), Some(true))
+                .await
+                .map_err(|e| anyhow!("Failed to get freeze records for repo {}: {}", repo, e))?;
+
+        // Filter by branch if specified
+        if let Some(target_branch) = branch {
+            freeze_records.retain(|r| r.branch.as_deref() == Some(target_branch));
+        }
+
+        if freeze_records.is_empty() {
+            let branch_msg = branch.map_or(String::new(), |b| format!(" for branch '{}'", b));
+            return Err(anyhow!("No active freeze found for repository: {}{}", repo, branch_msg));
+        }
+
+        if let Some(record) = freeze_records
+            .iter()
+            .find(|r| !can_reassign(r, requested_by, requester_is_admin))
+        {
+            return Err(anyhow!(
+                "Freeze on {} is owned by @{}; only the owner or an admin can reassign it",
+                repo,
+                record.initiated_by
+            ));
+        }
+
+        let mut previous_owners: Vec<String> = Vec::new();
+        for record in &mut freeze_records {
+            FreezeRecord::update_initiated_by(conn, &record.id, new_owner)
+                .await
+                .map_err(|e| anyhow!("Failed to reassign freeze record {}: {}", record.id, e))?;
+            self.cache.invalidate(installation_id, &record.repository);
+
+            if !previous_owners.contains(&record.initiated_by) {
+                previous_owners.push(record.initiated_by.clone());
+            }
+            record.initiated_by = new_owner.to_string();
+        }
+
+        // Refresh PRs so the check run details show the new owner. With several
+        // branch freezes a single-record refresh would clear the other branches'
+        // checks, so only refresh when exactly one freeze was reassigned.
+        if let [record] = freeze_records.as_slice()
+            && let Err(e) = self
+                .pr_refresh
+                .refresh_repository_prs(
+                    installation_id,
+                    repository.owner(),
+                    repository.name(),
+                    Some(record),
+                )
+                .await
+        {
+            warn!(
+                "Failed to refresh PRs for repository {}: {}",
+                repository.full_name(),
+                e
+            );
+            // Don't fail the reassignment if PR refresh fails
+        }
+
+        Ok(previous_owners.join(", @"))
+    }
+
+    /// Returns the active freeze requested from an issue or PR, if any.
+    ///
+    /// Lookup failures are logged and treated as "no freeze" so commands fall
+    /// back to the repository they were issued in.
+    pub async fn thread_freeze(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        issue_nr: u64,
+    ) -> Option<FreezeRecord> {
+        let conn = match self.db.get_connection() {
+            Ok(conn) => conn,
+            Err(e) => {
+                error!("Failed to get database connection for freeze reminder: {}", e);
+                return None;
+            }
+        };
+
+        FreezeReminder::find_active_in_thread(
+            conn,
+            installation_id,
+            &repository.full_name(),
+            issue_nr,
+        )
+        .await
+        .unwrap_or_else(|e| {
+            error!(
+                "Failed to look up the freeze of {}#{}: {}",
+                repository.full_name(),
+                issue_nr,
+                e
+            );
+            None
+        })
+    }
+
+    /// Unfreezes the freeze requested from a reminder thread.
+    ///
+    /// Used when the freeze is for other repositories than the thread, such as
+    /// an organization-wide freeze, so `/unfreeze` does not have to name them.
+    pub async fn unfreeze_thread_freeze(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        record: FreezeRecord,
+        ended_by: String,
+        reason: Option<String>,
+        issue_nr: u64,
+    ) -> (CommandResult, Option<String>) {
+        let unfrozen = match Repository::parse(&record.repository) {
+            Some(target) => self
+                .handle_unfreeze(
+                    installation_id,
+                    &target,
+                    ended_by,
+                    record.branch.clone(),
+                    reason.clone(),
+                    None,
+                )
+                .await
+                .map(|_| target),
+            None => Err(anyhow!("Invalid repository format: {}", record.repository)),
+        };
+        let outcome = match &unfrozen {
+            Ok(target) => messages::unfreeze_success(
+                &target.to_string(),
+                &messages::format_reason_display(reason),
+                &messages::format_branch_display(record.branch),
+            ),
+            Err(e) => {
+                error!("Failed to unfreeze {}: {:?}", record.repository, e);
+                messages::unfreeze_error(&e.to_string())
+            }
+        };
+
+        self.notify_comment_issue(installation_id, repository, issue_nr, &outcome)
+            .await;
+        command_result(&unfrozen)
+    }
+
+    /// Extends the active freeze by a duration.
+    ///
+    /// In the thread a freeze was requested from, such as the one its reminders
+    /// are posted to, the freeze of that thread is extended. Elsewhere, the
+    /// active freeze of the current repository is.
+    pub async fn extend_freeze(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        duration: commands::FreezeDuration,
+        branch: Option<String>,
+        extended_by: String,
+        issue_nr: u64,
+    ) -> (CommandResult, Option<String>) {
+        let extended = self
+            .handle_extend(
+                installation_id,
+                repository,
+                &duration,
+                branch.as_deref(),
+                issue_nr,
+            )
+            .await;
+        let outcome = match &extended {
+            Ok(record) => {
+                info!(
+                    "{} extended freeze {} of {} until {:?}",
+                    extended_by, record.id, record.repository, record.expires_at
+                );
+                let until = record
+                    .expires_at
+                    .map(|e| e.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+                    .unwrap_or_default();
+                messages::freeze_extend_success(
+                    &record.repository,
+                    &until,
+                    &messages::format_branch_display(record.branch.clone()),
+                )
+            }
+            Err(e) => {
+                error!("Failed to extend freeze: {:?}", e);
+                messages::freeze_extend_error(&e.to_string())
+            }
+        };
+
+        self.notify_comment_issue(installation_id, repository, issue_nr, &outcome)
+            .await;
+        command_result(&extended)
+    }
+
+    /// Extend the freeze of a thread, or else the active freeze of a repository.
+    ///
+    /// Returns the extended freeze record.
+    async fn handle_extend(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        duration: &commands::FreezeDuration,
+        branch: Option<&str>,
+        issue_nr: u64,
+    ) -> Result<FreezeRecord> {
+        let conn = self
+            .db
+            .get_connection()
+            .map_err(|e| anyhow!("Failed to get database connection: {}", e))?;
+
+        let tracked = self
+            .thread_freeze(installation_id, repository, issue_nr)
+            .await
+            .filter(|r| branch.is_none_or(|b| r.branch.as_deref() == Some(b)));
+
+        let mut record = match tracked {
+            Some(record) => record,
+            None => {
+                let repo = repository.full_name();
+                let mut freeze_records =
+                    FreezeRecord::list(conn, Some(installation_id), Some(&repo), Some(true))
+                        .await
+                        .map_err(|e| {
+                            anyhow!("Failed to get freeze records for repo {}: {}", repo, e)
+                        })?;
+
+                if let Some(target_branch) = branch {
+                    freeze_records.retain(|r| r.branch.as_deref() == Some(target_branch));
+                }
+
+                match freeze_records.len() {
+                    0 => {
+                        let branch_msg =
+                            branch.map_or(String::new(), |b| format!(" for branch '{}'", b));
+                        return Err(anyhow!(
+                            "No active freeze found for repository: {}{}",
+                            repo,
+                            branch_msg
+                        ));
+                    }
+                    1 => freeze_records.remove(0),
+                    n => {
+                        return Err(anyhow!(
+                            "{} freezes are active for repository {}; pick one with --branch",
+                            n,
+                            repo
+                        ));
+                    }
+                }
+            }
+        };
+
+        record.expires_at = Some(extended_expiry(
+            record.expires_at,
+            Utc::now(),
+            duration,
+            &self.config.business_hours,
+        ));
+        if !FreezeRecord::update_window(conn, &record.id, record.expires_at, record.reason.as_deref())
+            .await?
+        {
+            return Err(anyhow!(
+                "The freeze on {} ended before it could be extended",
+                record.repository
+            ));
+        }
+        self.cache.invalidate(installation_id, &record.repository);
+
+        // Refresh PRs so the check run details show the new end time
+        if let Some(target) = Repository::parse(&record.repository) {
+            self.refresh_prs(installation_id, &target, Some(&record))
+                .await;
+        }
+
+        Ok(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_take_unfreeze_approval() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+        let pending = PendingUnfreeze::new(
+            12345.into(),
+            "owner/repo".to_string(),
+            "alice".to_string(),
+            "/unfreeze --reason \"release shipped\"".to_string(),
+            vec!["release-manager".to_string()],
+            UNFREEZE_APPROVAL_TTL,
+        );
+        PendingUnfreeze::create(pool, &pending).await.unwrap();
+
+        // Users outside the approvers cannot approve, not even the requester
+        for user in ["mallory", "alice"] {
+            let err = take_unfreeze_approval(pool, 12345.into(), "owner/repo", user, Utc::now())
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("Only @release-manager"));
+        }
+
+        // The rejected attempts left the request pending for an approver
+        let approved = take_unfreeze_approval(
+            pool,
+            12345.into(),
+            "owner/repo",
+            "Release-Manager",
+            Utc::now(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(approved.requested_by, "alice");
+        assert_eq!(approved.command_line, pending.command_line);
+
+        let err = take_unfreeze_approval(
+            pool,
+            12345.into(),
+            "owner/repo",
+            "release-manager",
+            Utc::now(),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("no pending unfreeze"));
+    }
+
+    #[test]
+    fn test_approve_unfreeze_command() {
+        assert_eq!(approve_unfreeze_command(None), "/approve-unfreeze");
+        assert_eq!(
+            approve_unfreeze_command(Some("frezze")),
+            "/frezze approve-unfreeze"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_status_comment_creates_then_updates() {
+        use std::sync::Mutex;
+
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+        let writes = Mutex::new(Vec::new());
+        let write = |comment_id: Option<u64>| -> CommentWrite {
+            writes.lock().unwrap().push(comment_id);
+            Box::pin(async move { Ok(comment_id.unwrap_or(1001)) })
+        };
+
+        // The first status creates the comment, later ones edit it
+        for _ in 0..2 {
+            let comment_id = write_status_comment(pool, 12345.into(), "owner/repo", 7, &write)
+                .await
+                .unwrap();
+            assert_eq!(comment_id, 1001);
+        }
+        assert_eq!(*writes.lock().unwrap(), vec![None, Some(1001)]);
+
+        // Other issues get their own comment
+        write_status_comment(pool, 12345.into(), "owner/repo", 8, &write)
+            .await
+            .unwrap();
+        assert_eq!(writes.lock().unwrap().last(), Some(&None));
+    }
+
+    #[tokio::test]
+    async fn test_write_status_comment_replaces_deleted_comment() {
+        use std::sync::Mutex;
+
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+        StatusComment::record(pool, 12345.into(), "owner/repo", 7, 1001, Utc::now())
+            .await
+            .unwrap();
+
+        let writes = Mutex::new(Vec::new());
+        let comment_id = write_status_comment(
+            pool,
+            12345.into(),
+            "owner/repo",
+            7,
+            |comment_id: Option<u64>| -> CommentWrite {
+                writes.lock().unwrap().push(comment_id);
+                Box::pin(async move {
+                    match comment_id {
+                        Some(_) => Err(anyhow!("Not Found")),
+                        None => Ok(1002),
+                    }
+                })
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(comment_id, 1002);
+        assert_eq!(*writes.lock().unwrap(), vec![Some(1001), None]);
+        assert_eq!(
+            StatusComment::find(pool, 12345.into(), "owner/repo", 7)
+                .await
+                .unwrap(),
+            Some(1002)
+        );
+    }
+
+    fn contents_response(text: &str) -> serde_json::Value {
+        use base64::Engine;
+
+        // GitHub wraps the encoded content every 60 characters
+        let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+        let wrapped: Vec<String> = encoded
+            .as_bytes()
+            .chunks(60)
+            .map(|c| String::from_utf8(c.to_vec()).unwrap())
+            .collect();
+        serde_json::json!({
+            "type": "file",
+            "encoding": "base64",
+            "name": "FREEZE.md",
+            "path": ".github/FREEZE.md",
+            "content": wrapped.join("\n"),
+        })
+    }
+
+    #[test]
+    fn test_reason_from_contents() {
+        let text = "\nRelease freeze for 2.0.\n\nSee the incident channel for details.\n";
+        let reason = reason_from_contents(".github/FREEZE.md", &contents_response(text)).unwrap();
+        assert_eq!(reason, "Release freeze for 2.0.\n\nSee the incident channel for details.");
+    }
+
+    #[test]
+    fn test_reason_from_contents_truncates() {
+        let text = "❄".repeat(MAX_REASON_FILE_LENGTH + 50);
+        let reason = reason_from_contents("FREEZE.md", &contents_response(&text)).unwrap();
+        assert_eq!(reason.chars().count(), MAX_REASON_FILE_LENGTH);
+        assert!(reason.chars().all(|c| c == '❄'));
+    }
+
+    #[test]
+    fn test_reason_from_contents_rejects_unusable_files() {
+        // Directories are listed as arrays
+        assert!(reason_from_contents("docs", &serde_json::json!([])).is_err());
+
+        // Files over 1 MB are returned without content
+        let large = serde_json::json!({"type": "file", "encoding": "none", "content": ""});
+        assert!(reason_from_contents("big.md", &large).is_err());
+
+        assert!(reason_from_contents("empty.md", &contents_response("  \n")).is_err());
+
+        let mut invalid = contents_response("text");
+        invalid["content"] = serde_json::json!("not base64!");
+        assert!(reason_from_contents("bad.md", &invalid).is_err());
+    }
+
+    fn record_owned_by(owner: &str) -> FreezeRecord {
+        let now = Utc::now();
+        FreezeRecord::new(
+            "owner/repo".to_string(),
+            12345.into(),
+            now,
+            Some(now + DEFAULT_FREEZE_DURATION),
+            None,
+            owner.to_string(),
+            None,
+        )
+    }
+
+    #[test]
+    fn test_expand_reason() {
+        use chrono::TimeZone;
+
+        let start = Utc.with_ymd_and_hms(2025, 3, 14, 9, 30, 0).unwrap();
+        assert_eq!(
+            expand_reason("Deploy of {repo} by {user} on {date}", "owner/repo", "alice", start),
+            "Deploy of owner/repo by alice on 2025-03-14"
+        );
+        assert_eq!(
+            expand_reason("{user}/{user}", "owner/repo", "alice", start),
+            "alice/alice"
+        );
+        assert_eq!(
+            expand_reason("No variables here", "owner/repo", "alice", start),
+            "No variables here"
+        );
+    }
+
+    #[test]
+    fn test_expand_reason_literal_braces() {
+        use chrono::TimeZone;
+
+        let start = Utc.with_ymd_and_hms(2025, 3, 14, 9, 30, 0).unwrap();
+        // Doubled braces escape a variable
+        assert_eq!(
+            expand_reason("{{repo}} is {repo}", "owner/repo", "alice", start),
+            "{repo} is owner/repo"
+        );
+        // Unknown variables and unbalanced braces are kept as typed
+        assert_eq!(
+            expand_reason("{team} {repo", "owner/repo", "alice", start),
+            "{team} {repo"
+        );
+        assert_eq!(
+            expand_reason("fix } and { both", "owner/repo", "alice", start),
+            "fix } and { both"
+        );
+        assert_eq!(expand_reason("{}", "owner/repo", "alice", start), "{}");
+    }
+
+    #[test]
+    fn test_confirm_command() {
+        assert_eq!(confirm_command(None, "1a2b3c4d"), "/freeze-all --confirm 1a2b3c4d");
+        assert_eq!(
+            confirm_command(Some("frezze"), "1a2b3c4d"),
+            "/frezze freeze-all --confirm 1a2b3c4d"
+        );
+    }
+
+    #[test]
+    fn test_failure_contacts() {
+        let contacts = vec![
+            "@oncall".to_string(),
+            "alice".to_string(),
+            "Oncall".to_string(),
+        ];
+
+        // Nobody is pinged when every repository was handled
+        assert!(failure_contacts(0, &contacts, "bob").is_empty());
+
+        // Failures ping each contact once, except the user who ran the command
+        assert_eq!(
+            failure_contacts(2, &contacts, "bob"),
+            vec!["oncall", "alice"]
+        );
+        assert_eq!(failure_contacts(1, &contacts, "Alice"), vec!["oncall"]);
+
+        // Installations without contacts ping nobody
+        assert!(failure_contacts(3, &[], "bob").is_empty());
+    }
+
+    #[test]
+    fn test_multi_repo_result() {
+        assert_eq!(multi_repo_result(4, 0, &[]), (CommandResult::Success, None));
+
+        // A partial freeze-all is logged with its counts and errors
+        let errors = vec![
+            "Failed to freeze owner/a: archived".to_string(),
+            "Failed to freeze owner/b: already frozen".to_string(),
+        ];
+        assert_eq!(
+            multi_repo_result(3, 2, &errors),
+            (
+                CommandResult::PartialSuccess {
+                    succeeded: 3,
+                    failed: 2
+                },
+                Some(
+                    "Failed to freeze owner/a: archived; Failed to freeze owner/b: already frozen"
+                        .to_string()
+                )
+            )
+        );
+
+        let (result, error_message) = multi_repo_result(0, 2, &errors);
+        assert_eq!(result, CommandResult::Failure);
+        assert!(error_message.is_some());
+    }
+
+    #[test]
+    fn test_refresh_note() {
+        use crate::freezer::pr_refresh::PrRefreshOutcome;
+
+        let outcome = |pr_number, error: Option<&str>| PrRefreshOutcome {
+            pr_number,
+            retries: 0,
+            error: error.map(str::to_string),
+        };
+        let refreshed = RefreshResult::from_outcomes(vec![
+            outcome(1, None),
+            outcome(2, None),
+            outcome(3, Some("rate limited")),
+        ]);
+
+        // Without `--wait` the comment does not mention the refresh
+        assert_eq!(refresh_note(None), "");
+
+        let note = refresh_note(Some(&refreshed));
+        assert!(note.contains("**2** of 3 open pull requests"));
+        assert!(refresh_note(Some(&RefreshResult::default())).contains("No open pull request"));
+    }
+
+    #[test]
+    fn test_can_reassign() {
+        let record = record_owned_by("alice");
+
+        // The current owner can hand the freeze off
+        assert!(can_reassign(&record, "alice", false));
+        assert!(can_reassign(&record, "Alice", false));
+
+        // Other non-admin users cannot
+        assert!(!can_reassign(&record, "mallory", false));
+
+        // Admins can always reassign
+        assert!(can_reassign(&record, "mallory", true));
+    }
+
+    #[test]
+    fn test_check_min_duration() {
+        let min = chrono::Duration::seconds(60);
+
+        // Exactly the minimum is allowed
+        assert!(check_min_duration(chrono::Duration::seconds(60), min, false).is_ok());
+        // Above the minimum is allowed
+        assert!(check_min_duration(chrono::Duration::seconds(61), min, false).is_ok());
+        // Below the minimum is rejected
+        let err = check_min_duration(chrono::Duration::seconds(59), min, false).unwrap_err();
+        assert!(err.to_string().contains("below the minimum of 60s"));
+        // Users with override can bypass the guard
+        assert!(check_min_duration(chrono::Duration::seconds(1), min, true).is_ok());
+    }
+
+    #[test]
+    fn test_resolve_schedule_end() {
+        let start = Utc::now();
+        let hour = chrono::Duration::hours(1);
+
+        // Consistent windows resolve to their end
+        assert_eq!(resolve_schedule_end(start, Some(start + hour), None).unwrap(), start + hour);
+        assert_eq!(
+            resolve_schedule_end(start, Some(start + hour), Some(hour)).unwrap(),
+            start + hour
+        );
+        assert_eq!(resolve_schedule_end(start, None, Some(hour)).unwrap(), start + hour);
+        assert_eq!(
+            resolve_schedule_end(start, None, None).unwrap(),
+            start + DEFAULT_FREEZE_DURATION
+        );
+
+        // Inverted and empty windows are rejected
+        let err = resolve_schedule_end(start, Some(start - hour), None).unwrap_err();
+        assert!(err.to_string().contains("must be after its start"));
+        assert!(resolve_schedule_end(start, Some(start), None).is_err());
+        assert!(resolve_schedule_end(start, None, Some(-hour)).is_err());
+
+        // --to and --duration must agree
+        let err = resolve_schedule_end(start, Some(start + hour), Some(hour * 2)).unwrap_err();
+        assert!(err.to_string().contains("does not match"));
+    }
+
+    #[test]
+    fn test_status_entry_frozen_includes_branch() {
+        let mut record = record_owned_by("alice");
+        assert_eq!(StatusEntry::frozen(&record).branch, None);
+
+        record.branch = Some("release".to_string());
+        let entry = StatusEntry::frozen(&record);
+        assert_eq!(entry.branch.as_deref(), Some("release"));
+        assert!(matches!(entry.freeze_status, FreezeStatus::Active));
+
+        assert_eq!(StatusEntry::not_frozen().branch, None);
+    }
+
+    #[test]
+    fn test_repository_status_entries_lists_every_branch() {
+        assert!(matches!(
+            repository_status_entries(&[], None)[0].freeze_status,
+            FreezeStatus::Off
+        ));
+
+        let mut release = record_owned_by("alice");
+        release.branch = Some("release".to_string());
+        let mut main = record_owned_by("bob");
+        main.branch = Some("main".to_string());
+
+        let entries = repository_status_entries(&[release, main], None);
+        let branches: Vec<_> = entries.iter().map(|e| e.branch.as_deref()).collect();
+        assert_eq!(branches, vec![Some("main"), Some("release")]);
+        assert!(
+            entries
+                .iter()
+                .all(|e| matches!(e.freeze_status, FreezeStatus::Active))
+        );
+    }
+
+    #[test]
+    fn test_repository_status_entries_for_branch() {
+        let repo_wide = record_owned_by("alice");
+        let mut release = record_owned_by("bob");
+        release.branch = Some("release".to_string());
+        let mut main = record_owned_by("carol");
+        main.branch = Some("main".to_string());
+        let records = [repo_wide, release.clone(), main];
+
+        // Repository-wide freezes affect every branch
+        let entries = repository_status_entries(&records, Some("main"));
+        let branches: Vec<_> = entries.iter().map(|e| e.branch.as_deref()).collect();
+        assert_eq!(branches, vec![None, Some("main")]);
+
+        let entries = repository_status_entries(&records, Some("develop"));
+        let branches: Vec<_> = entries.iter().map(|e| e.branch.as_deref()).collect();
+        assert_eq!(branches, vec![None]);
+
+        // Freezes of other branches leave the branch unfrozen
+        let entries = repository_status_entries(&[release], Some("main"));
+        assert_eq!(entries.len(), 1);
+        assert!(matches!(entries[0].freeze_status, FreezeStatus::Off));
+    }
+
+    #[test]
+    fn test_covering_freeze() {
+        let repo_wide = record_owned_by("alice");
+        let mut on_main = record_owned_by("alice");
+        on_main.branch = Some("main".to_string());
+        let mut elapsed = record_owned_by("alice");
+        // Taken after the records so they have all started by now
+        let now = Utc::now();
+        elapsed.expires_at = Some(now - chrono::Duration::minutes(1));
+
+        // Not yet frozen
+        assert!(covering_freeze(&[], None, now).is_none());
+        assert!(covering_freeze(std::slice::from_ref(&on_main), Some("develop"), now).is_none());
+        assert!(covering_freeze(std::slice::from_ref(&on_main), None, now).is_none());
+        assert!(covering_freeze(std::slice::from_ref(&elapsed), None, now).is_none());
+
+        // Already frozen
+        assert!(covering_freeze(std::slice::from_ref(&repo_wide), None, now).is_some());
+        assert!(covering_freeze(std::slice::from_ref(&repo_wide), Some("main"), now).is_some());
+        assert!(covering_freeze(std::slice::from_ref(&on_main), Some("main"), now).is_some());
+    }
+
+    #[test]
+    fn test_merged_expiry() {
+        let now = Utc::now();
+        let sooner = now + chrono::Duration::hours(1);
+        let later = now + chrono::Duration::hours(3);
+
+        // The union of both windows ends at the later end time
+        assert_eq!(merged_expiry(Some(sooner), later), Some(later));
+        assert_eq!(merged_expiry(Some(later), sooner), Some(later));
+
+        // An indefinite freeze stays indefinite
+        assert_eq!(merged_expiry(None, later), None);
+    }
+
+    #[test]
+    fn test_extended_expiry() {
+        let now = Utc::now();
+        let two_hours = commands::FreezeDuration::Wall(chrono::Duration::hours(2));
+        let business_hours = BusinessHours::default();
+
+        // A timed freeze is pushed back from its current end
+        let end = now + chrono::Duration::hours(1);
+        assert_eq!(
+            extended_expiry(Some(end), now, &two_hours, &business_hours),
+            end + chrono::Duration::hours(2)
+        );
+
+        // An open-ended freeze, or one already past its end, ends from now
+        assert_eq!(
+            extended_expiry(None, now, &two_hours, &business_hours),
+            now + chrono::Duration::hours(2)
+        );
+        assert_eq!(
+            extended_expiry(
+                Some(now - chrono::Duration::hours(1)),
+                now,
+                &two_hours,
+                &business_hours
+            ),
+            now + chrono::Duration::hours(2)
+        );
+    }
+
+    fn open_pr(number: u64, login: &str, base_ref: &str, is_bot: bool) -> PullRequestInfo {
+        PullRequestInfo {
+            number,
+            head_sha: format!("sha{}", number),
+            base_ref: base_ref.to_string(),
+            author: Some(login.to_string()),
+            is_bot,
+        }
+    }
+
+    #[test]
+    fn test_blocked_authors() {
+        let prs = vec![
+            open_pr(1, "carol", "main", false),
+            open_pr(2, "alice", "main", false),
+            open_pr(3, "Alice", "develop", false),
+            open_pr(4, "dependabot[bot]", "main", true),
+            open_pr(5, "admin_user", "main", false),
+        ];
+        let is_exempt = |login: &str| login == "admin_user";
+
+        // A repository-wide freeze blocks every branch
+        assert_eq!(
+            blocked_authors(&prs, None, is_exempt),
+            vec!["alice".to_string(), "carol".to_string()]
+        );
+        // A branch freeze only blocks PRs against that branch
+        assert_eq!(
+            blocked_authors(&prs, Some("develop"), is_exempt),
+            vec!["Alice".to_string()]
+        );
+        assert!(blocked_authors(&prs, Some("release"), is_exempt).is_empty());
+        // Without exemptions, only bots are left out
+        assert_eq!(
+            blocked_authors(&prs, Some("main"), |_| false),
+            vec![
+                "admin_user".to_string(),
+                "alice".to_string(),
+                "carol".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merged_reason() {
+        assert_eq!(
+            merged_reason(Some("release 1.2"), Some("hotfix rollout")).as_deref(),
+            Some("release 1.2; hotfix rollout")
+        );
+        assert_eq!(
+            merged_reason(Some("release 1.2"), Some("release 1.2")).as_deref(),
+            Some("release 1.2")
+        );
+        assert_eq!(
+            merged_reason(None, Some("hotfix rollout")).as_deref(),
+            Some("hotfix rollout")
+        );
+        assert_eq!(
+            merged_reason(Some("release 1.2"), None).as_deref(),
+            Some("release 1.2")
+        );
+        assert_eq!(merged_reason(None, None), None);
+    }
+
+    #[test]
+    fn test_merge_target() {
+        let repo_wide = record_owned_by("alice");
+        let mut on_main = record_owned_by("alice");
+        on_main.branch = Some("main".to_string());
+        let mut on_release = record_owned_by("alice");
+        on_release.branch = Some("release".to_string());
+        let mut org = record_owned_by("alice");
+        org.repository = "owner/*".to_string();
+
+        // Organization-wide freezes are not merged into
+        let records = [org.clone()];
+        assert!(merge_target(&records, "owner/repo", None).unwrap().is_none());
+
+        let records = [org.clone(), repo_wide.clone()];
+        let target = merge_target(&records, "Owner/Repo", Some("main")).unwrap();
+        assert_eq!(target.unwrap().id, repo_wide.id);
+
+        let records = [on_main.clone(), on_release.clone()];
+        let target = merge_target(&records, "owner/repo", Some("release")).unwrap();
+        assert_eq!(target.unwrap().id, on_release.id);
+        assert!(merge_target(&records, "owner/repo", Some("develop")).unwrap().is_none());
+
+        // A repository-wide freeze would overlap both branch freezes
+        assert!(merge_target(&records, "owner/repo", None).is_err());
+    }
+
+    #[test]
+    fn test_parse_repositories() {
+        let names = vec![
+            "owner/repo1".to_string(),
+            "repo2".to_string(),
+            "owner/repo3".to_string(),
+            "too/many/parts".to_string(),
+        ];
+
+        let (repositories, errors, duplicates) = parse_repositories(&names);
+        assert_eq!(
+            repositories,
+            vec![
+                Repository::new("owner", "repo1"),
+                Repository::new("owner", "repo3")
+            ]
+        );
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("'repo2'"));
+        assert!(errors[1].contains("'too/many/parts'"));
+        assert!(duplicates.is_empty());
+    }
+
+    #[test]
+    fn test_parse_repositories_accepts_urls() {
+        let names = vec![
+            "https://github.com/owner/repo1".to_string(),
+            "owner/repo2.git".to_string(),
+            "owner/repo1".to_string(),
+            "https://gitlab.com/owner/repo3".to_string(),
+        ];
+
+        let (repositories, errors, duplicates) = parse_repositories(&names);
+        assert_eq!(
+            repositories,
+            vec![
+                Repository::new("owner", "repo1"),
+                Repository::new("owner", "repo2")
+            ]
+        );
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("'https://gitlab.com/owner/repo3'"));
+        assert_eq!(duplicates, vec!["`owner/repo1` (duplicate)"]);
+    }
+
+    #[test]
+    fn test_parse_repositories_removes_duplicates() {
+        // Same repository from a comma list, a repeated flag and different casing
+        let names = vec![
+            "owner/repo1".to_string(),
+            "owner/repo2".to_string(),
+            "owner/repo1".to_string(),
+            "Owner/Repo1".to_string(),
+        ];
+
+        let (repositories, errors, duplicates) = parse_repositories(&names);
+        assert_eq!(
+            repositories,
+            vec![
+                Repository::new("owner", "repo1"),
+                Repository::new("owner", "repo2")
+            ]
+        );
+        assert!(errors.is_empty());
+        assert_eq!(duplicates, vec!["`owner/repo1` (duplicate)", "`Owner/Repo1` (duplicate)"]);
+    }
+
+    fn mock_repository(
+        name: &str,
+        archived: bool,
+        size: u32,
+        default_branch: Option<&str>,
+    ) -> octocrab::models::Repository {
+        serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "name": name,
+            "url": format!("https://api.github.com/repos/owner/{name}"),
+            "archived": archived,
+            "size": size,
+            "default_branch": default_branch,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_partition_freezable_skips_archived_and_empty() {
+        let repositories = vec![
+            mock_repository("active", false, 42, Some("main")),
+            mock_repository("old", true, 42, Some("main")),
+            mock_repository("blank", false, 0, None),
+            // GitHub reports a size of 0 for small repositories with commits
+            mock_repository("tiny", false, 0, Some("main")),
+        ];
+
+        assert_eq!(skip_reason(&repositories[0]), None);
+        assert_eq!(skip_reason(&repositories[1]), Some("archived"));
+        assert_eq!(skip_reason(&repositories[2]), Some("empty"));
+        assert_eq!(skip_reason(&repositories[3]), None);
+
+        let (usable, skipped) = partition_freezable(&repositories);
+        assert_eq!(usable.len(), 2);
+        assert_eq!(usable[0].name, "active");
+        assert_eq!(usable[1].name, "tiny");
+        assert_eq!(skipped.len(), 2);
+        assert!(skipped[0].contains("old") && skipped[0].ends_with("(archived)"));
+        assert!(skipped[1].contains("blank") && skipped[1].ends_with("(empty)"));
+    }
+
+    #[test]
+    fn test_check_not_archived() {
+        assert!(check_not_archived(&mock_repository("active", false, 42, Some("main"))).is_ok());
+
+        let err = check_not_archived(&mock_repository("old", true, 42, Some("main"))).unwrap_err();
+        assert!(err.to_string().contains("old"));
+        assert!(err.to_string().contains("is archived"));
+    }
+
+    const PR_URL: &str = "https://github.com/owner/repo/pull/42";
+
+    #[test]
+    fn test_unlock_alert() {
+        let freeze = FreezeRecord::new(
+            "owner/repo".to_string(),
+            12345.into(),
+            Utc::now(),
+            None,
+            Some("release".to_string()),
+            "alice".to_string(),
+            None,
+        );
+        let watchers = vec!["@sre-lead".to_string(), "Alice".to_string()];
+
+        let alert = unlock_alert(true, &freeze, &watchers, "bob", 42, PR_URL, "").unwrap();
+        assert!(alert.contains("[PR #42](https://github.com/owner/repo/pull/42)"));
+        assert!(alert.contains("@bob"));
+        assert!(alert.ends_with("cc @alice @sre-lead"));
+
+        // The user who unlocked the PR is not alerted about it
+        let alert = unlock_alert(true, &freeze, &watchers, "alice", 42, PR_URL, "").unwrap();
+        assert!(alert.ends_with("cc @sre-lead"));
+
+        // Nobody left to alert
+        assert!(unlock_alert(true, &freeze, &[], "alice", 42, PR_URL, "").is_none());
+    }
+
+    #[test]
+    fn test_unlock_alert_disabled() {
+        let freeze = FreezeRecord::new(
+            "owner/repo".to_string(),
+            12345.into(),
+            Utc::now(),
+            None,
+            None,
+            "alice".to_string(),
+            None,
+        );
+        let watchers = vec!["sre-lead".to_string()];
+        assert!(unlock_alert(false, &freeze, &watchers, "bob", 42, PR_URL, "").is_none());
+    }
+
+    #[test]
+    fn test_resolve_watchers_removes_duplicates_and_initiator() {
+        let watchers = vec![
+            "@release-manager".to_string(),
+            "oncall".to_string(),
+            "Release-Manager".to_string(),
+            "alice".to_string(),
+            " ".to_string(),
+        ];
+        assert_eq!(
+            resolve_watchers(&watchers, "Alice"),
+            vec!["release-manager".to_string(), "oncall".to_string()]
+        );
+        assert!(resolve_watchers(&[], "alice").is_empty());
     }
 }
@@ -4,6 +4,110 @@
 // with appropriate emojis and professional tone.
 //
 
+use std::str::FromStr;
+
+use crate::database::models::{CommandLog, FreezeRecord, RefreshStats};
+
+use super::pr_refresh::CheckDrift;
+
+/// Visual theme applied to outgoing bot messages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MessageTheme {
+    /// Decorate messages with emojis (default)
+    #[default]
+    Emoji,
+    /// Plain, professional messages without emoji decorations
+    Plain,
+}
+
+impl MessageTheme {
+    /// Renders a message according to the theme.
+    ///
+    /// The emoji theme returns the message untouched, while the plain theme
+    /// strips the emoji decorations of the message templates along with the
+    /// space that follows them. Emojis in user text, such as reasons, are kept.
+    pub fn apply(&self, msg: &str) -> String {
+        match self {
+            MessageTheme::Emoji => msg.to_string(),
+            MessageTheme::Plain => strip_emojis(msg),
+        }
+    }
+}
+
+impl FromStr for MessageTheme {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "emoji" => Ok(MessageTheme::Emoji),
+            "plain" => Ok(MessageTheme::Plain),
+            other => Err(anyhow::anyhow!(
+                "Invalid message theme '{}'. Expected 'emoji' or 'plain'",
+                other
+            )),
+        }
+    }
+}
+
+/// Emojis the message templates decorate headings, lines and table cells with
+const DECORATIONS: &[char] = &[
+    '⏰', '⏳', '⚠', '✅', '❄', '❌', '🔒', '🚨', '🔍', '👋', '❓', '🌞', '🎉', '🚫', '📊', '📜',
+    '🗓', '📅', '🔮', '⏹', '▶', '🩺', '🔓', '🗑', '🔧', '⏸', '🚧', '🔔', '📂', '🔁', '👤', '🔄',
+    'ℹ',
+];
+
+/// Variation selector some decorations are written with
+const VARIATION_SELECTOR: char = '\u{FE0F}';
+
+/// Length of the decoration and its trailing space at the start of `text`, if any
+fn decoration_len(text: &str) -> Option<usize> {
+    let mut chars = text.char_indices().peekable();
+    let (_, first) = chars.next()?;
+    if !DECORATIONS.contains(&first) {
+        return None;
+    }
+    chars.next_if(|&(_, c)| c == VARIATION_SELECTOR);
+    let (space, _) = chars.next_if(|&(_, c)| c == ' ')?;
+    Some(space + 1)
+}
+
+/// Removes the decoration starting a line or table cell, after any heading
+/// or quote marker
+fn strip_leading_decoration(segment: &str) -> String {
+    let marker = segment.len() - segment.trim_start_matches(['#', '>', ' ']).len();
+    let (prefix, rest) = segment.split_at(marker);
+    match decoration_len(rest) {
+        Some(len) => format!("{}{}", prefix, &rest[len..]),
+        None => segment.to_string(),
+    }
+}
+
+/// Removes the template decorations from a message.
+///
+/// Decorations only ever start a line or a table cell outside of code
+/// blocks, so emojis anywhere else, or outside the decoration set, come from
+/// user text and are kept.
+fn strip_emojis(msg: &str) -> String {
+    let mut in_code_block = false;
+    msg.split_inclusive('\n')
+        .map(|line| {
+            if line.starts_with("```") {
+                in_code_block = !in_code_block;
+                line.to_string()
+            } else if in_code_block {
+                line.to_string()
+            } else if line.starts_with('|') {
+                line.split('|')
+                    .map(strip_leading_decoration)
+                    .collect::<Vec<_>>()
+                    .join("|")
+            } else {
+                strip_leading_decoration(line)
+            }
+        })
+        .collect()
+}
+
 /// Success message for repository freeze operation
 pub fn freeze_success(repository: &str, duration_str: &str, reason_str: &str, branch_str: &str) -> String {
     format!(
@@ -14,18 +118,158 @@ pub fn freeze_success(repository: &str, duration_str: &str, reason_str: &str, br
     )
 }
 
+/// Preview of a `/freeze --dry-run`, listing the authors it would block
+pub fn freeze_dry_run(
+    repository: &str,
+    duration_str: &str,
+    reason_str: &str,
+    branch_str: &str,
+    blocked: &[String],
+) -> String {
+    let blocked_str = if blocked.is_empty() {
+        "No open pull request would be blocked.".to_string()
+    } else {
+        let authors: Vec<String> = blocked.iter().map(|a| format!("- @{}", a)).collect();
+        format!(
+            "These authors have open pull requests that would need `/unlock-pr` to be merged:\n\n{}",
+            authors.join("\n")
+        )
+    };
+    format!(
+        "## 🔍 Freeze Preview\n\n\
+        **Repository `{repository}` would be frozen**{branch_str}{duration_str}{reason_str}\n\n\
+        {blocked_str}\n\n\
+        *Nothing was frozen. Run the command again without `--dry-run` to freeze.*"
+    )
+}
+
+/// Success message for an organization-wide freeze
+pub fn org_freeze_success(owner: &str, duration_str: &str, reason_str: &str, branch_str: &str) -> String {
+    format!(
+        "## ❄️ Organization Frozen\n\n\
+        🔒 **All repositories of `{owner}` have been frozen**{branch_str}{duration_str}{reason_str}\n\n\
+        > 🚨 **Important**: All pull requests and pushes are now blocked in every repository of `{owner}` until the freeze is lifted.\n\n\
+        *Use `/unfreeze-all` to lift the freeze when ready.*"
+    )
+}
+
+/// Success message for a freeze of deployments to an environment
+pub fn environment_freeze_success(
+    repository: &str,
+    environment: &str,
+    duration_str: &str,
+    reason_str: &str,
+) -> String {
+    format!(
+        "## ❄️ Environment Frozen\n\n\
+        🔒 **Deployments of `{repository}` to `{environment}` have been frozen**{duration_str}{reason_str}\n\n\
+        > 🚨 **Important**: New deployments to `{environment}` will be failed until the freeze is lifted. Pull requests are not affected.\n\n\
+        *Use `/unfreeze --environment {environment}` to lift the freeze when ready.*"
+    )
+}
+
+/// Description of the failing deployment status for a frozen environment.
+///
+/// GitHub limits deployment status descriptions to 140 characters.
+pub fn deployment_frozen(freeze: &FreezeRecord) -> String {
+    let description = match freeze.reason.as_deref().map(str::trim) {
+        Some(reason) if !reason.is_empty() => {
+            format!("Frozen by {}: {}", freeze.initiated_by, reason)
+        }
+        _ => format!("Frozen by {}", freeze.initiated_by),
+    };
+    description.chars().take(140).collect()
+}
+
+/// Comment on a pull request opened while its target branch is frozen
+pub fn new_pr_during_freeze(author: &str, freeze: &FreezeRecord) -> String {
+    let until_str = freeze
+        .expires_at
+        .map(|e| format!(" until **{}**", e.format("%Y-%m-%d %H:%M UTC")))
+        .unwrap_or_else(|| " until it is lifted".to_string());
+    let branch_str = format_branch_display(freeze.branch.clone());
+    let reason_str = format_reason_display(freeze.reason.clone());
+    format!(
+        "## ❄️ Repository Frozen\n\n\
+        👋 @{author}, **repository `{}` is frozen**{branch_str}{until_str}{reason_str}\n\n\
+        *This pull request can be merged once the freeze is lifted.*",
+        freeze.repository
+    )
+}
+
+/// Message for a `--if-not-frozen` freeze whose target is already frozen
+pub fn already_frozen(repository: &str, until: Option<&str>, branch_str: &str) -> String {
+    let until_str = until
+        .map(|u| format!(" until **{u}**"))
+        .unwrap_or_else(|| " until it is lifted".to_string());
+    format!(
+        "## ❄️ Already Frozen\n\n\
+        🔒 **Repository `{repository}` is already frozen**{branch_str}{until_str}\n\n\
+        *No new freeze was created because `--if-not-frozen` was set.*"
+    )
+}
+
+/// Message for a `--merge` freeze that extended an existing freeze
+pub fn freeze_merged(
+    repository: &str,
+    until: Option<&str>,
+    reason_str: &str,
+    branch_str: &str,
+) -> String {
+    let until_str = until
+        .map(|u| format!(" until **{u}**"))
+        .unwrap_or_else(|| " until it is lifted".to_string());
+    format!(
+        "## ❄️ Freeze Extended
+
+        🔒 **Repository `{repository}` stays frozen**{branch_str}{until_str}{reason_str}
+
+        *The new window was merged into the existing freeze because `--merge` was set.*"
+    )
+}
+
+/// Reminder posted to the initiator of a long-running freeze
+pub fn freeze_reminder(
+    initiated_by: &str,
+    repository: &str,
+    duration_str: &str,
+    branch_str: &str,
+) -> String {
+    format!(
+        "## ⏳ Freeze Still Active\n\n\
+        👋 @{initiated_by}, **repository `{repository}` has been frozen**{branch_str}{duration_str}\n\n\
+        > ❓ **Is this freeze still needed?** Reply `/unfreeze` to lift it, or `/extend 2h` to keep it for a while.\n\n\
+        *You will be reminded again while the freeze remains active.*"
+    )
+}
+
 /// Success message for freeze-all operation
-pub fn freeze_all_success(count: usize) -> String {
+pub fn freeze_all_success(count: usize, skipped_str: &str) -> String {
     format!(
         "## ❄️ All Repositories Frozen\n\n\
-        🔒 **Successfully froze {count} repositories**\n\n\
+        🔒 **Successfully froze {count} repositories**{skipped_str}\n\n\
         > 🚨 **Important**: All pull requests and pushes are now blocked for all repositories until unfrozen.\n\n\
         *Use `/unfreeze-all` to lift all freezes when ready.*"
     )
 }
 
+/// Message asking the initiator of a freeze-all to confirm it with a token
+pub fn freeze_all_confirmation_required(count: usize, confirm_command: &str, ttl_str: &str) -> String {
+    format!(
+        "## ⚠️ Confirm Freeze-All\n\n\
+        🔒 **This would freeze {count} repositories**\n\n\
+        Reply with `{confirm_command}` to proceed. The token is valid{ttl_str}.\n\n\
+        *Nothing is frozen until the command is confirmed.*"
+    )
+}
+
 /// Partial success message for freeze-all operation
-pub fn freeze_all_partial_success(successful: usize, failed: usize, errors: &[String]) -> String {
+pub fn freeze_all_partial_success(
+    successful: usize,
+    failed: usize,
+    errors: &[String],
+    skipped_str: &str,
+) -> String {
     let error_list = if errors.len() <= 5 {
         errors.join("\n- ")
     } else {
@@ -39,7 +283,7 @@ pub fn freeze_all_partial_success(successful: usize, failed: usize, errors: &[St
     format!(
         "## ⚠️ Partial Freeze Success\n\n\
         ✅ **Successfully froze {successful} repositories**\n\
-        ❌ **Failed to freeze {failed} repositories**\n\n\
+        ❌ **Failed to freeze {failed} repositories**{skipped_str}\n\n\
         > 🚨 **Important**: Successfully frozen repositories are blocked until unfrozen.\n\n\
         **Errors encountered:**\n- {error_list}\n\n\
         *Use `/unfreeze-all` to lift all freezes when ready.*"
@@ -47,17 +291,22 @@ pub fn freeze_all_partial_success(successful: usize, failed: usize, errors: &[St
 }
 
 /// Success message for unfreeze-all operation
-pub fn unfreeze_all_success(count: usize) -> String {
+pub fn unfreeze_all_success(count: usize, reason_str: &str) -> String {
     format!(
         "## 🌞 All Repositories Unfrozen\n\n\
-        ✅ **Successfully unfroze {count} repositories**\n\n\
+        ✅ **Successfully unfroze {count} repositories**{reason_str}\n\n\
         > 🎉 **All systems go**: Pull requests and pushes are now allowed for all repositories.\n\n\
         *All freezes have been successfully lifted.*"
     )
 }
 
 /// Partial success message for unfreeze-all operation
-pub fn unfreeze_all_partial_success(successful: usize, failed: usize, errors: &[String]) -> String {
+pub fn unfreeze_all_partial_success(
+    successful: usize,
+    failed: usize,
+    errors: &[String],
+    reason_str: &str,
+) -> String {
     let error_list = if errors.len() <= 5 {
         errors.join("\n- ")
     } else {
@@ -71,7 +320,7 @@ pub fn unfreeze_all_partial_success(successful: usize, failed: usize, errors: &[
     format!(
         "## ⚠️ Partial Unfreeze Success\n\n\
         ✅ **Successfully unfroze {successful} repositories**\n\
-        ❌ **Failed to unfreeze {failed} repositories**\n\n\
+        ❌ **Failed to unfreeze {failed} repositories**{reason_str}\n\n\
         > 🎉 **Partially restored**: Some repositories are now accepting pull requests and pushes.\n\n\
         **Errors encountered:**\n- {error_list}\n\n\
         *Check repository statuses for details.*"
@@ -104,7 +353,10 @@ pub fn format_status_table(entries: Vec<(String, super::manager::StatusEntry)>)
             FreezeStatus::Error(ref err) => &format!("❌ Error: {}", err),
         };
 
-        let branch = entry.branch.unwrap_or_else(|| "All".to_string());
+        let branch = entry
+            .branch
+            .map(|b| format!("`{}`", b))
+            .unwrap_or_else(|| "all".to_string());
         let duration = entry.duration.unwrap_or_else(|| "-".to_string());
         let start = entry.start.unwrap_or_else(|| "-".to_string());
         let end = entry.end.unwrap_or_else(|| "-".to_string());
@@ -120,6 +372,283 @@ pub fn format_status_table(entries: Vec<(String, super::manager::StatusEntry)>)
     table
 }
 
+/// Format the recent commands of a repository for `/audit`
+pub fn format_audit_table(repository: &str, logs: &[CommandLog]) -> String {
+    let mut table = format!("## 📜 Recent Commands for `{repository}`\n\n");
+    if logs.is_empty() {
+        table.push_str("*No commands have been recorded for this repository yet.*");
+        return table;
+    }
+
+    table.push_str("| When | User | Command | Result |\n");
+    table.push_str("|------|------|---------|--------|\n");
+
+    for log in logs {
+        let command = log.command.replace('`', "'").replace('|', "\\|");
+        let result = match &log.error_message {
+            Some(err) if !err.trim().is_empty() => {
+                format!("{}: {}", log.result, err.trim().replace('|', "\\|"))
+            }
+            _ => log.result.to_string(),
+        };
+        table.push_str(&format!(
+            "| {} | @{} | `{}` | {} |\n",
+            log.created_at.format("%Y-%m-%d %H:%M:%S UTC"),
+            log.user_login,
+            command,
+            result
+        ));
+    }
+
+    table
+}
+
+/// Error message for a failed `/audit`
+pub fn audit_error(error: &str) -> String {
+    format!(
+        "## ❌ Audit Failed\n\n\
+        🚫 **Failed to read the command log**\n\n\
+        ```\n{error}\n```"
+    )
+}
+
+/// Format the scheduled freezes of a repository for `/scheduled`
+pub fn format_scheduled_table(repository: &str, records: &[FreezeRecord]) -> String {
+    let mut table = format!("## 🗓️ Scheduled Freezes for `{repository}`\n\n");
+    if records.is_empty() {
+        table.push_str("*No freezes are scheduled for this repository.*");
+        return table;
+    }
+
+    table.push_str("| ID | Starts | Ends | Branch | Scheduled By | Reason |\n");
+    table.push_str("|----|--------|------|--------|--------------|--------|\n");
+
+    for record in records {
+        let ends = record
+            .expires_at
+            .map(|e| e.format("%Y-%m-%d %H:%M UTC").to_string())
+            .unwrap_or_else(|| "-".to_string());
+        let reason = record
+            .reason
+            .as_deref()
+            .map(|r| r.trim().replace('|', "\\|"))
+            .filter(|r| !r.is_empty())
+            .unwrap_or_else(|| "-".to_string());
+        table.push_str(&format!(
+            "| `{}` | {} | {} | {} | @{} | {} |\n",
+            record.id,
+            record.started_at.format("%Y-%m-%d %H:%M UTC"),
+            ends,
+            record.branch.as_deref().unwrap_or("All branches"),
+            record.initiated_by,
+            reason
+        ));
+    }
+
+    table.push_str("\n*Use `/cancel-schedule --id <id>` to cancel a scheduled freeze.*");
+    table
+}
+
+/// Table of the freezes the scheduler will act on by `at`
+pub fn format_scheduler_preview(
+    at: &str,
+    to_activate: &[FreezeRecord],
+    to_expire: &[FreezeRecord],
+) -> String {
+    let mut message = format!(
+        "## 🔮 Scheduler Preview\n\n\
+        *Freezes the scheduler will act on by **{at}**. Nothing was changed.*\n\n"
+    );
+
+    type TimeOf = fn(&FreezeRecord) -> Option<chrono::DateTime<chrono::Utc>>;
+    let sections: [(&str, &str, &[FreezeRecord], TimeOf); 2] = [
+        ("▶️ To Activate", "Starts", to_activate, |r| Some(r.started_at)),
+        ("⏹️ To Expire", "Ends", to_expire, |r| r.expires_at),
+    ];
+    for (title, time_column, records, time_of) in sections {
+        message.push_str(&format!("### {title}\n\n"));
+        if records.is_empty() {
+            message.push_str("*None.*\n\n");
+            continue;
+        }
+
+        message.push_str(&format!("| ID | Repository | Branch | {time_column} |\n"));
+        message.push_str("|----|------------|--------|------|\n");
+        for record in records {
+            let time = time_of(record)
+                .map(|t| t.format("%Y-%m-%d %H:%M UTC").to_string())
+                .unwrap_or_else(|| "-".to_string());
+            message.push_str(&format!(
+                "| `{}` | `{}` | {} | {} |\n",
+                record.id,
+                record.repository,
+                record.branch.as_deref().unwrap_or("All branches"),
+                time
+            ));
+        }
+        message.push('\n');
+    }
+
+    message.trim_end().to_string()
+}
+
+/// Error message for a failed `/scheduler-preview`
+pub fn scheduler_preview_error(error: &str) -> String {
+    format!(
+        "## ❌ Scheduler Preview Failed\n\n\
+        🚫 **Failed to read the scheduler's candidates**\n\n\
+        ```\n{error}\n```"
+    )
+}
+
+/// Report of a `/reconcile`, listing the PRs whose Frezze check drifted
+pub fn format_reconcile_report(
+    checked: usize,
+    results: &[(String, Vec<CheckDrift>)],
+    errors: &[String],
+    fix: bool,
+) -> String {
+    let mut message = format!(
+        "## 🩺 Freeze Check Reconciliation\n\n\
+        *Compared the Frezze check of the open pull requests of **{checked}** repositories with their freeze state.*\n\n"
+    );
+
+    type Filter = fn(&CheckDrift) -> bool;
+    let sections: [(&str, Filter); 2] = [
+        ("🔒 Should Be Blocked", CheckDrift::should_block),
+        ("🔓 Should Pass", |d| !d.should_block()),
+    ];
+    let drifted = results.iter().any(|(_, drifts)| !drifts.is_empty());
+    if !drifted {
+        message.push_str("✅ **Every check matches the freeze state.**\n\n");
+    }
+    for (title, filter) in sections {
+        let rows: Vec<(&String, &CheckDrift)> = results
+            .iter()
+            .flat_map(|(repo, drifts)| drifts.iter().map(move |d| (repo, d)))
+            .filter(|(_, d)| filter(d))
+            .collect();
+        if rows.is_empty() {
+            continue;
+        }
+
+        message.push_str(&format!("### {title}\n\n"));
+        message.push_str("| Repository | PR | Check | Expected |");
+        message.push_str(if fix { " Fixed |\n" } else { "\n" });
+        message.push_str("|------------|----|-------|----------|");
+        message.push_str(if fix { "-------|\n" } else { "\n" });
+        for (repo, drift) in rows {
+            message.push_str(&format!(
+                "| `{}` | #{} | {} | {} |",
+                repo,
+                drift.pr_number,
+                drift.actual.as_deref().unwrap_or("missing"),
+                drift.expected
+            ));
+            if fix {
+                message.push_str(if drift.fixed { " ✅ |" } else { " ❌ |" });
+            }
+            message.push('\n');
+        }
+        message.push('\n');
+    }
+
+    if !errors.is_empty() {
+        message.push_str("### ⚠️ Errors\n\n");
+        for error in errors {
+            message.push_str(&format!("- {}\n", error));
+        }
+        message.push('\n');
+    }
+
+    if drifted && !fix {
+        message.push_str("*Run `/reconcile --fix` to re-publish these checks.*");
+    }
+
+    message.trim_end().to_string()
+}
+
+/// Error message for a failed `/reconcile`
+pub fn reconcile_error(error: &str) -> String {
+    format!(
+        "## ❌ Reconciliation Failed\n\n\
+        🚫 **Failed to compare the freeze checks**\n\n\
+        ```\n{error}\n```"
+    )
+}
+
+/// Success message for a scheduled freeze
+pub fn schedule_success(repository: &str, start: &str, end: &str, reason: &str) -> String {
+    format!(
+        "## ⏰ Freeze Scheduled\n\n\
+        📅 **Repository `{repository}` freeze has been scheduled**\n\n\
+        **Start**: {start}\n\
+        **End**: {end}\n\
+        **Reason**: {reason}\n\n\
+        > The freeze will automatically activate at the scheduled time."
+    )
+}
+
+/// Error message for a failed `/schedule-freeze`
+pub fn schedule_error(error: &str) -> String {
+    format!(
+        "## ❌ Schedule Failed\n\n\
+        🚫 **Failed to schedule freeze**\n\n\
+        ```\n{error}\n```\n\n\
+        *Please check your parameters and try again.*"
+    )
+}
+
+/// Error message for a failed `/scheduled`
+pub fn scheduled_error(error: &str) -> String {
+    format!(
+        "## ❌ Listing Scheduled Freezes Failed\n\n\
+        🚫 **Failed to read the scheduled freezes**\n\n\
+        ```\n{error}\n```"
+    )
+}
+
+/// Success message for a cancelled scheduled freeze
+pub fn cancel_schedule_success(repository: &str, id: &str) -> String {
+    format!(
+        "## 🗑️ Scheduled Freeze Cancelled\n\n\
+        ✅ **Scheduled freeze `{id}` of `{repository}` was cancelled**\n\n\
+        *It will not be activated.*"
+    )
+}
+
+/// Error message for a failed `/cancel-schedule`
+pub fn cancel_schedule_error(error: &str) -> String {
+    format!(
+        "## ❌ Cancel Failed\n\n\
+        🚫 **Failed to cancel the scheduled freeze**\n\n\
+        ```\n{error}\n```"
+    )
+}
+
+/// Format PR refresh statistics appended to `/status --debug`
+pub fn format_refresh_debug(entries: Vec<(String, Option<RefreshStats>)>) -> String {
+    let mut table = String::from("\n\n### 🔧 Refresh Debug\n\n");
+    table.push_str("| Repository | Last Refresh | Last Retries | Total Retries |\n");
+    table.push_str("|------------|--------------|--------------|---------------|\n");
+
+    for (repo_name, stats) in entries {
+        let row = match stats {
+            Some(stats) => format!(
+                "| {} | {} | {} | {} |\n",
+                repo_name,
+                stats.last_refreshed_at.format("%Y-%m-%d %H:%M:%S UTC"),
+                stats.last_retries,
+                stats.total_retries
+            ),
+            None => format!("| {} | never | - | - |\n", repo_name),
+        };
+        table.push_str(&row);
+    }
+
+    table
+}
+
 /// Error message for repository freeze operation failure
 pub fn freeze_error(error: &str) -> String {
     format!(
@@ -150,6 +679,27 @@ pub fn unfreeze_error(error: &str) -> String {
     )
 }
 
+/// Message for a `/unfreeze` waiting for one of the required approvers
+pub fn unfreeze_approval_required(
+    repository: &str,
+    requested_by: &str,
+    approvers: &[String],
+    approve_command: &str,
+    ttl_str: &str,
+) -> String {
+    let approvers_str = approvers
+        .iter()
+        .map(|a| format!("@{}", a))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "## ⏸️ Unfreeze Awaiting Approval\n\n\
+        🔒 **@{requested_by} asked to unfreeze `{repository}`**\n\n\
+        One of {approvers_str} has to reply with `{approve_command}` to proceed. The request is valid{ttl_str}.\n\n\
+        *The repository stays frozen until the unfreeze is approved.*"
+    )
+}
+
 /// Message for commands not yet implemented
 pub fn command_not_implemented() -> String {
     "## ⚠️ Command Not Available\n\n\
@@ -181,6 +731,56 @@ pub fn permission_denied(username: &str, reason: &str) -> String {
     )
 }
 
+/// Message displayed when a command is not enabled for the installation.
+///
+/// # Arguments
+///
+/// * `command` - Name of the disabled command (e.g. "unlock-pr")
+///
+/// # Returns
+///
+/// A formatted markdown message explaining that the command is disabled
+pub fn command_disabled(command: &str) -> String {
+    format!(
+        "## 🚫 Command Disabled\n\n\
+        **`/{}` is disabled for this installation**\n\n\
+        *Contact your administrator if you need this command enabled.*",
+        command
+    )
+}
+
+/// Message displayed when a pull request command is run on a plain issue.
+///
+/// # Arguments
+///
+/// * `command` - Command name without the leading slash (e.g. "unlock-pr")
+/// * `option` - Option naming the pull request (e.g. "--pr-number")
+///
+/// # Returns
+///
+/// A formatted markdown message explaining how to target a pull request
+pub fn pull_request_required(command: &str, option: &str) -> String {
+    format!(
+        "## ❌ Pull Request Required\n\n\
+        **`/{}` acts on a pull request, but this is an issue**\n\n\
+        *Comment it on the pull request, or name one with `{} <number>`.*",
+        command, option
+    )
+}
+
+/// Message displayed when a command is run with `--help`.
+///
+/// # Arguments
+///
+/// * `help` - Help text rendered for the command
+///
+/// # Returns
+///
+/// A formatted markdown message with the command's usage and options
+pub fn command_help(help: &str) -> String {
+    format!("## ℹ️ Command Help\n\n```text\n{}\n```", help.trim())
+}
+
 /// Message displayed when permission checking fails due to an error.
 ///
 /// # Arguments
@@ -209,6 +809,42 @@ pub fn pr_unlock_success(pr_number: u64, reason_str: &str) -> String {
     )
 }
 
+/// Alert mentioning the users who should know about a PR unlocked during a freeze
+pub fn pr_unlock_alert(
+    repository: &str,
+    pr_number: u64,
+    pr_url: &str,
+    unlocked_by: &str,
+    reason_str: &str,
+    recipients: &[String],
+) -> String {
+    format!(
+        "## 🔔 PR Unlocked During Freeze\n\n@{} unlocked **[PR #{}]({})**, so it can be merged while `{}` is frozen.{}{}",
+        unlocked_by,
+        pr_number,
+        pr_url,
+        repository,
+        reason_str,
+        format_watchers_display(recipients)
+    )
+}
+
+/// Message posted when a PR is unlocked again
+pub fn pr_already_unlocked(pr_number: u64, unlocked_by: &str) -> String {
+    format!(
+        "## ✅ PR Already Unlocked\n\n🔓 **PR #{} was already unlocked by @{}**\n\nThis PR can be merged despite the repository freeze. Nothing was changed.",
+        pr_number, unlocked_by
+    )
+}
+
+/// Message posted when a `/freeze` unlocks the pull request it was requested on
+pub fn pr_auto_unlocked(pr_number: u64) -> String {
+    format!(
+        "## 🔓 PR Unlocked\n\n**PR #{} has been unlocked automatically** because the freeze was requested from it.\n\nThis PR can still be merged while the repository is frozen. Use `/unfreeze` to lift the freeze for everyone.",
+        pr_number
+    )
+}
+
 /// Format error message for PR unlock failure
 pub fn pr_unlock_failed(pr_number: u64, error: &str) -> String {
     format!(
@@ -225,13 +861,67 @@ pub fn pr_unlock_not_frozen(repository: &str) -> String {
     )
 }
 
+/// Success message for freeze ownership reassignment
+pub fn freeze_reassign_success(
+    repository: &str,
+    previous_owner: &str,
+    new_owner: &str,
+    branch_str: &str,
+) -> String {
+    format!(
+        "## 🔁 Freeze Ownership Transferred\n\n\
+        👤 **Freeze on `{repository}` has been reassigned to @{new_owner}**{branch_str}\n\n\
+        **Previous owner**: @{previous_owner}\n\n\
+        *@{new_owner} is now the point of contact for this freeze.*"
+    )
+}
+
+/// Error message for freeze ownership reassignment failure
+pub fn freeze_reassign_error(error: &str) -> String {
+    format!(
+        "## ❌ Reassign Failed\n\n\
+        🚫 **Failed to reassign freeze ownership**\n\n\
+        ```\n{error}\n```\n\n\
+        *Only admins or the current freeze owner can reassign a freeze.*"
+    )
+}
+
+/// Success message for extending the freeze of a reminder thread
+pub fn freeze_extend_success(repository: &str, until: &str, branch_str: &str) -> String {
+    format!(
+        "## ⏳ Freeze Extended\n\n\
+        🔒 **Freeze on `{repository}` has been extended**{branch_str} until **{until}**\n\n\
+        *Reply `/extend` again to keep it longer, or `/unfreeze` to lift it.*"
+    )
+}
+
+/// Error message for freeze extension failure
+pub fn freeze_extend_error(error: &str) -> String {
+    format!(
+        "## ❌ Extend Failed\n\n\
+        🚫 **Failed to extend the freeze**\n\n\
+        ```\n{error}\n```\n\n\
+        *Only active freezes can be extended.*"
+    )
+}
+
 /// Helper function to format duration for display
 pub fn format_duration_display(duration: chrono::Duration) -> String {
     let total_seconds = duration.num_seconds();
     let hours = total_seconds / 3600;
     let minutes = (total_seconds % 3600) / 60;
 
-    if hours > 0 {
+    if hours >= 24 {
+        // Long freezes read better in weeks and days; zero parts are left out
+        let weeks = hours / (24 * 7);
+        let days = (hours / 24) % 7;
+        let parts: Vec<String> = [(weeks, "w"), (days, "d"), (hours % 24, "h"), (minutes, "m")]
+            .into_iter()
+            .filter(|(value, _)| *value > 0)
+            .map(|(value, unit)| format!("{}{}", value, unit))
+            .collect();
+        format!(" for **{}**", parts.join(" "))
+    } else if hours > 0 {
         format!(" for **{}h {}m**", hours, minutes)
     } else if minutes > 0 {
         format!(" for **{}m**", minutes)
@@ -240,14 +930,69 @@ pub fn format_duration_display(duration: chrono::Duration) -> String {
     }
 }
 
+/// Characters escaped in user-provided text so they are not read as Markdown or HTML
+const MARKDOWN_CONTROL_CHARS: &[char] = &['\\', '`', '*', '_', '[', ']', '<', '>', '|', '~'];
+
+/// Makes user-provided text, such as a freeze reason, safe to embed in
+/// Markdown comments and check runs.
+///
+/// Heading markers at the start of a line are dropped, Markdown control
+/// characters are escaped and mentions such as `@everyone` are broken up so
+/// they notify nobody. The text reads the same once rendered.
+pub fn sanitize_markdown(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            let unmarked = trimmed.trim_start_matches('#');
+            let line = if unmarked.len() < trimmed.len()
+                && (unmarked.is_empty() || unmarked.starts_with(char::is_whitespace))
+            {
+                unmarked.trim_start()
+            } else {
+                line
+            };
+
+            let mut sanitized = String::with_capacity(line.len());
+            // A line of `=` or `-` would turn the line above it into a heading
+            if !line.trim().is_empty() && line.trim().chars().all(|c| c == '=' || c == '-') {
+                sanitized.push('\\');
+            }
+            let mut previous = None;
+            for c in line.chars() {
+                if MARKDOWN_CONTROL_CHARS.contains(&c) {
+                    sanitized.push('\\');
+                }
+                sanitized.push(c);
+                if c == '@' && previous.is_none_or(|p: char| !p.is_alphanumeric()) {
+                    sanitized.push('\u{200B}');
+                }
+                previous = Some(c);
+            }
+            sanitized
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 /// Helper function to format reason for display
 pub fn format_reason_display(reason: Option<String>) -> String {
     match reason {
-        Some(r) if !r.trim().is_empty() => format!("\n\n**Reason**: _{}_", r.trim()),
+        Some(r) if !r.trim().is_empty() => {
+            format!("\n\n**Reason**: _{}_", sanitize_markdown(r.trim()))
+        }
         _ => String::new(),
     }
 }
 
+/// Helper function to format repositories skipped by a bulk operation
+pub fn format_skipped_display(skipped: &[String]) -> String {
+    if skipped.is_empty() {
+        String::new()
+    } else {
+        format!("\n\n**Skipped**: {}", skipped.join(", "))
+    }
+}
+
 /// Helper function to format branch for display
 pub fn format_branch_display(branch: Option<String>) -> String {
     match branch {
@@ -256,6 +1001,304 @@ pub fn format_branch_display(branch: Option<String>) -> String {
     }
 }
 
+/// Helper function to format environment for display
+pub fn format_environment_display(environment: Option<String>) -> String {
+    match environment {
+        Some(e) if !e.trim().is_empty() => format!(" for environment **`{}`**", e.trim()),
+        _ => String::new(),
+    }
+}
+
+/// Helper function to mention repository watchers
+pub fn format_watchers_display(watchers: &[String]) -> String {
+    if watchers.is_empty() {
+        return String::new();
+    }
+    let mentions: Vec<String> = watchers.iter().map(|w| format!("@{w}")).collect();
+    format!("\n\ncc {}", mentions.join(" "))
+}
+
+/// Helper function to ask the ops contacts to look into failed repositories
+pub fn format_ops_contacts_display(contacts: &[String]) -> String {
+    if contacts.is_empty() {
+        return String::new();
+    }
+    let mentions: Vec<String> = contacts.iter().map(|c| format!("@{c}")).collect();
+    format!(
+        "\n\n🚨 {} some repositories failed, please look into the errors above.",
+        mentions.join(" ")
+    )
+}
+
+/// Helper function to suggest the reasons recently used in a repository
+pub fn format_reason_suggestions(reasons: &[String]) -> String {
+    if reasons.is_empty() {
+        return String::new();
+    }
+    let suggestions: Vec<String> = reasons.iter().map(|r| format!("- `{r}`")).collect();
+    format!("\n\n**Recently used reasons**:\n{}", suggestions.join("\n"))
+}
+
+/// Helper function to warn that a frozen branch does not exist
+pub fn format_missing_branch_display(branch: Option<&str>) -> String {
+    match branch {
+        Some(branch) => format!(
+            "\n\n⚠️ **Branch `{branch}` does not exist in this repository.** \
+            No pull request is blocked until it does; check for a typo or a renamed branch."
+        ),
+        None => String::new(),
+    }
+}
+
+/// Helper function to report the PRs updated by the refresh of a `/freeze --wait`
+pub fn format_refresh_display(updated: usize, failed: usize, total: usize) -> String {
+    match total {
+        0 => "\n\n🔄 No open pull request needed to be updated.".to_string(),
+        _ if failed == 0 => {
+            format!("\n\n🔄 Updated the checks of all **{total}** open pull requests.")
+        }
+        _ => format!(
+            "\n\n🔄 Updated the checks of **{updated}** of {total} open pull requests; \
+            the **{failed}** that failed can be fixed with `/reconcile --fix`."
+        ),
+    }
+}
+
+/// Message types rendered by `/preview`, as typed in the command
+pub const PREVIEW_MESSAGE_TYPES: &[&str] = &[
+    "freeze-success",
+    "org-freeze-success",
+    "environment-freeze-success",
+    "deployment-frozen",
+    "already-frozen",
+    "freeze-merged",
+    "freeze-dry-run",
+    "new-pr-during-freeze",
+    "freeze-reminder",
+    "freeze-all-confirmation-required",
+    "freeze-all-success",
+    "freeze-all-partial-success",
+    "unfreeze-all-success",
+    "unfreeze-all-partial-success",
+    "unfreeze-success",
+    "freeze-error",
+    "unfreeze-error",
+    "unfreeze-approval-required",
+    "status-table",
+    "status-error",
+    "audit-table",
+    "audit-error",
+    "schedule-success",
+    "schedule-error",
+    "scheduled-table",
+    "scheduled-error",
+    "cancel-schedule-success",
+    "cancel-schedule-error",
+    "scheduler-preview",
+    "scheduler-preview-error",
+    "reconcile-report",
+    "reconcile-error",
+    "pr-unlock-success",
+    "pr-unlock-alert",
+    "pr-already-unlocked",
+    "pr-unlock-failed",
+    "pr-auto-unlocked",
+    "pr-unlock-not-frozen",
+    "freeze-reassign-success",
+    "freeze-reassign-error",
+    "freeze-extend-success",
+    "freeze-extend-error",
+    "permission-denied",
+    "permission-check-failed",
+    "command-disabled",
+    "command-help",
+    "pull-request-required",
+    "command-not-implemented",
+];
+
+/// Renders a message type with sample data.
+///
+/// Lets message and theme changes be reviewed without triggering real freezes.
+/// Returns `None` for message types not listed in [`PREVIEW_MESSAGE_TYPES`].
+pub fn preview_message(message_type: &str) -> Option<String> {
+    use chrono::{TimeZone, Utc};
+
+    use super::manager::StatusEntry;
+    use crate::database::models::CommandResult;
+
+    let repository = "octo-org/octo-repo";
+    let started_at = Utc.with_ymd_and_hms(2025, 1, 15, 10, 0, 0).unwrap();
+    let freeze = FreezeRecord::new(
+        repository.to_string(),
+        12345.into(),
+        started_at,
+        Some(started_at + chrono::Duration::hours(2)),
+        Some("Release 1.2 stabilization".to_string()),
+        "octocat".to_string(),
+        Some("main".to_string()),
+    );
+    let duration_str = format_duration_display(chrono::Duration::hours(2));
+    let reason_str = format_reason_display(freeze.reason.clone());
+    let branch_str = format_branch_display(freeze.branch.clone());
+    let skipped_str = format_skipped_display(&["octo-org/archived-repo".to_string()]);
+    let errors = vec!["octo-org/other-repo: Not Found".to_string()];
+
+    let message = match message_type {
+        "freeze-success" => freeze_success(repository, &duration_str, &reason_str, &branch_str),
+        "org-freeze-success" => {
+            org_freeze_success("octo-org", &duration_str, &reason_str, &branch_str)
+        }
+        "environment-freeze-success" => {
+            environment_freeze_success(repository, "production", &duration_str, &reason_str)
+        }
+        "deployment-frozen" => deployment_frozen(&freeze),
+        "already-frozen" => already_frozen(repository, Some("2025-01-15 12:00 UTC"), &branch_str),
+        "freeze-merged" => freeze_merged(
+            repository,
+            Some("2025-01-15 14:00 UTC"),
+            &reason_str,
+            &branch_str,
+        ),
+        "freeze-dry-run" => freeze_dry_run(
+            repository,
+            &duration_str,
+            &reason_str,
+            &branch_str,
+            &["hubot".to_string(), "octocat".to_string()],
+        ),
+        "new-pr-during-freeze" => new_pr_during_freeze("hubot", &freeze),
+        "freeze-reminder" => freeze_reminder("octocat", repository, &duration_str, &branch_str),
+        "freeze-all-confirmation-required" => freeze_all_confirmation_required(
+            3,
+            "/freeze-all --confirm 1a2b3c4d",
+            &format_duration_display(chrono::Duration::minutes(10)),
+        ),
+        "freeze-all-success" => freeze_all_success(3, &skipped_str),
+        "freeze-all-partial-success" => freeze_all_partial_success(2, 1, &errors, &skipped_str),
+        "unfreeze-all-success" => unfreeze_all_success(3, &reason_str),
+        "unfreeze-all-partial-success" => {
+            unfreeze_all_partial_success(2, 1, &errors, &reason_str)
+        }
+        "unfreeze-success" => unfreeze_success(repository, &reason_str, &branch_str),
+        "freeze-error" => freeze_error("Repository is already frozen"),
+        "unfreeze-error" => unfreeze_error("No active freeze found"),
+        "unfreeze-approval-required" => unfreeze_approval_required(
+            repository,
+            "octocat",
+            &["hubot".to_string()],
+            "/approve-unfreeze",
+            &format_duration_display(chrono::Duration::hours(24)),
+        ),
+        "status-table" => format_status_table(vec![
+            (repository.to_string(), StatusEntry::frozen(&freeze)),
+            ("octo-org/other-repo".to_string(), StatusEntry::not_frozen()),
+        ]),
+        "status-error" => status_error("Repository not found"),
+        "audit-table" => {
+            let mut log = CommandLog::new(
+                12345.into(),
+                repository.to_string(),
+                "octocat".to_string(),
+                "/freeze --duration 2h".to_string(),
+                1,
+                CommandResult::Success,
+                None,
+            );
+            log.created_at = started_at;
+            format_audit_table(repository, &[log])
+        }
+        "audit-error" => audit_error("Failed to get database connection"),
+        "schedule-success" => schedule_success(
+            repository,
+            "2025-01-15 10:00:00 UTC",
+            "2025-01-15 12:00:00 UTC",
+            "Release 1.2 stabilization",
+        ),
+        "schedule-error" => schedule_error("--to must be after --from"),
+        "scheduled-table" => format_scheduled_table(repository, std::slice::from_ref(&freeze)),
+        "scheduled-error" => scheduled_error("Failed to get database connection"),
+        "cancel-schedule-success" => cancel_schedule_success(repository, &freeze.id),
+        "cancel-schedule-error" => cancel_schedule_error("No scheduled freeze found"),
+        "scheduler-preview" => format_scheduler_preview(
+            "2025-01-15 10:01 UTC",
+            std::slice::from_ref(&freeze),
+            &[],
+        ),
+        "scheduler-preview-error" => scheduler_preview_error("Failed to get database connection"),
+        "reconcile-report" => format_reconcile_report(
+            2,
+            &[(
+                repository.to_string(),
+                vec![CheckDrift {
+                    pr_number: 42,
+                    expected: "failure",
+                    actual: Some("success".to_string()),
+                    fixed: false,
+                }],
+            )],
+            &[],
+            false,
+        ),
+        "reconcile-error" => reconcile_error("Failed to fetch open PRs"),
+        "pr-unlock-success" => pr_unlock_success(42, &reason_str),
+        "pr-unlock-alert" => pr_unlock_alert(
+            repository,
+            42,
+            "https://github.com/octo-org/octo-repo/pull/42",
+            "octocat",
+            &reason_str,
+            &["hubot".to_string()],
+        ),
+        "pr-already-unlocked" => pr_already_unlocked(42, "octocat"),
+        "pr-unlock-failed" => pr_unlock_failed(42, "Pull request not found"),
+        "pr-auto-unlocked" => pr_auto_unlocked(42),
+        "pr-unlock-not-frozen" => pr_unlock_not_frozen(repository),
+        "freeze-reassign-success" => {
+            freeze_reassign_success(repository, "octocat", "hubot", &branch_str)
+        }
+        "freeze-reassign-error" => freeze_reassign_error("No active freeze found"),
+        "freeze-extend-success" => {
+            freeze_extend_success(repository, "2025-01-15 14:00:00 UTC", &branch_str)
+        }
+        "freeze-extend-error" => freeze_extend_error("No active freeze found"),
+        "permission-denied" => permission_denied(
+            "hubot",
+            "User role 'contributor' does not have freeze permissions",
+        ),
+        "permission-check-failed" => permission_check_failed("hubot", "Configuration unavailable"),
+        "command-disabled" => command_disabled("unlock-pr"),
+        "command-help" => command_help(
+            "Unlock a specific PR during a freeze\n\n\
+             Usage: /unlock-pr [OPTIONS]\n\n\
+             Options:\n      \
+             --pr-number <PR_NUMBER>  PR number to unlock\n      \
+             --reason <REASON>        Reason for unlocking, optional\n  \
+             -h, --help                   Print help",
+        ),
+        "pull-request-required" => pull_request_required("unlock-pr", "--pr-number"),
+        "command-not-implemented" => command_not_implemented(),
+        _ => return None,
+    };
+
+    Some(message)
+}
+
+/// Message for a `/preview` of an unknown message type
+pub fn preview_unknown(message_type: &str) -> String {
+    format!(
+        "## ❌ Unknown Message Type\n\n\
+        🚫 **`{}` is not a previewable message type**\n\n\
+        **Available types**: {}\n\n\
+        *Use `/preview` without a type to render every message.*",
+        message_type,
+        PREVIEW_MESSAGE_TYPES
+            .iter()
+            .map(|t| format!("`{t}`"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -276,6 +1319,16 @@ mod tests {
         assert!(msg.contains("❄️"));
     }
 
+    #[test]
+    fn test_already_frozen_message() {
+        let msg = already_frozen("owner/repo", Some("2025-01-01 12:00:00 UTC"), "");
+        assert!(msg.contains("Already Frozen"));
+        assert!(msg.contains("already frozen** until **2025-01-01 12:00:00 UTC**"));
+
+        let msg = already_frozen("owner/repo", None, " on branch **`main`**");
+        assert!(msg.contains("on branch **`main`** until it is lifted"));
+    }
+
     #[test]
     fn test_freeze_error_message() {
         let msg = freeze_error("Permission denied");
@@ -321,6 +1374,88 @@ mod tests {
         assert!(msg.contains("⚠️"));
     }
 
+    #[test]
+    fn test_message_theme_from_str() {
+        assert_eq!("emoji".parse::<MessageTheme>().unwrap(), MessageTheme::Emoji);
+        assert_eq!("Plain".parse::<MessageTheme>().unwrap(), MessageTheme::Plain);
+        assert!("fancy".parse::<MessageTheme>().is_err());
+        assert_eq!(MessageTheme::default(), MessageTheme::Emoji);
+    }
+
+    #[test]
+    fn test_emoji_theme_keeps_message_unchanged() {
+        let msg = freeze_success("owner/repo", "", "", "");
+        assert_eq!(MessageTheme::Emoji.apply(&msg), msg);
+    }
+
+    /// Returns true for characters used as emoji decorations in messages
+    fn is_emoji(c: char) -> bool {
+        matches!(
+            c as u32,
+            0x1F000..=0x1FAFF   // Pictographs, emoticons, transport, symbols
+                | 0x2139          // Information source (ℹ)
+                | 0x2190..=0x21FF // Arrows
+                | 0x2300..=0x23FF // Miscellaneous technical (⏰, ⏳)
+                | 0x25A0..=0x25FF // Geometric shapes (▶)
+                | 0x2600..=0x27BF // Miscellaneous symbols and dingbats (⚠, ✅, ❄, ❌)
+                | 0x2B00..=0x2BFF // Miscellaneous symbols and arrows (⭐)
+                | 0xFE0F          // Variation selector
+                | 0x200D          // Zero width joiner
+        )
+    }
+
+    #[test]
+    fn test_plain_theme_strips_emojis() {
+        // Every message type of /preview, so new templates are covered once listed there
+        let mut messages: Vec<String> = PREVIEW_MESSAGE_TYPES
+            .iter()
+            .map(|t| preview_message(t).unwrap())
+            .collect();
+        messages.extend([
+            format_refresh_debug(vec![("owner/repo".to_string(), None)]),
+            preview_unknown("nope"),
+            freeze_success("owner/repo", " for **2h 30m**", "", " on branch **`main`**"),
+            org_freeze_success("owner", " for **2h 0m**", "", ""),
+            environment_freeze_success("owner/repo", "production", " for **2h 0m**", ""),
+            freeze_all_success(3, ""),
+            freeze_all_partial_success(1, 1, &["Error 1".to_string()], ""),
+            unfreeze_success("owner/repo", "", ""),
+            unfreeze_all_success(2, ""),
+            freeze_error("boom"),
+            unfreeze_error("boom"),
+            command_not_implemented(),
+            permission_denied("alice", "nope"),
+            command_disabled("unlock-pr"),
+            pr_unlock_success(42, ""),
+            freeze_reassign_success("owner/repo", "alice", "bob", ""),
+            freeze_reminder("alice", "owner/repo", " for **26h 0m**", ""),
+        ]);
+
+        for msg in messages {
+            let plain = MessageTheme::Plain.apply(&msg);
+            assert!(
+                !plain.chars().any(is_emoji),
+                "plain message still contains emojis: {plain}"
+            );
+        }
+
+        let plain = MessageTheme::Plain.apply(&freeze_success("owner/repo", "", "", ""));
+        assert!(plain.starts_with("## Repository Frozen"));
+        assert!(plain.contains("\n\n**Repository `owner/repo` has been frozen**"));
+        assert!(plain.contains("> **Important**"));
+    }
+
+    #[test]
+    fn test_plain_theme_keeps_user_emojis() {
+        let reason = format_reason_display(Some("🚀 launch day ❄️".to_string()));
+        let plain = MessageTheme::Plain.apply(&freeze_success("owner/repo", "", &reason, ""));
+        assert!(plain.starts_with("## Repository Frozen"));
+        assert!(plain.contains("**Reason**: _🚀 launch day ❄️_"));
+
+        let plain = MessageTheme::Plain.apply(&freeze_error("🔒 locked by ❌ policy"));
+        assert!(plain.contains("🔒 locked by ❌ policy"));
+    }
+
     #[test]
     fn test_format_duration_display() {
         assert_eq!(
@@ -335,6 +1470,65 @@ mod tests {
             format_duration_display(Duration::seconds(30)),
             " for a **short duration**"
         );
+        assert_eq!(
+            format_duration_display(Duration::hours(23) + Duration::minutes(59)),
+            " for **23h 59m**"
+        );
+    }
+
+    #[test]
+    fn test_format_duration_display_days() {
+        assert_eq!(format_duration_display(Duration::hours(24)), " for **1d**");
+        assert_eq!(format_duration_display(Duration::days(3)), " for **3d**");
+        assert_eq!(
+            format_duration_display(Duration::days(3) + Duration::hours(2) + Duration::minutes(30)),
+            " for **3d 2h 30m**"
+        );
+        assert_eq!(
+            format_duration_display(Duration::days(2) + Duration::minutes(15)),
+            " for **2d 15m**"
+        );
+    }
+
+    #[test]
+    fn test_format_duration_display_weeks() {
+        assert_eq!(format_duration_display(Duration::weeks(1)), " for **1w**");
+        assert_eq!(
+            format_duration_display(Duration::weeks(2) + Duration::days(3) + Duration::hours(4)),
+            " for **2w 3d 4h**"
+        );
+    }
+
+    #[test]
+    fn test_new_pr_during_freeze() {
+        use chrono::{TimeZone, Utc};
+
+        let started_at = Utc.with_ymd_and_hms(2025, 1, 15, 10, 0, 0).unwrap();
+        let freeze = FreezeRecord::new(
+            "owner/repo".to_string(),
+            12345.into(),
+            started_at,
+            Some(started_at + Duration::hours(2)),
+            Some("Release 1.2".to_string()),
+            "octocat".to_string(),
+            Some("main".to_string()),
+        );
+        let msg = new_pr_during_freeze("hubot", &freeze);
+        assert!(msg.contains("@hubot"));
+        assert!(msg.contains("on branch **`main`**"));
+        assert!(msg.contains("until **2025-01-15 12:00 UTC**"));
+        assert!(msg.contains("_Release 1.2_"));
+
+        let open_ended = FreezeRecord::new(
+            "owner/repo".to_string(),
+            12345.into(),
+            started_at,
+            None,
+            None,
+            "octocat".to_string(),
+            None,
+        );
+        assert!(new_pr_during_freeze("hubot", &open_ended).contains("until it is lifted"));
     }
 
     #[test]
@@ -349,17 +1543,275 @@ mod tests {
 
     #[test]
     fn test_freeze_all_success_message() {
-        let msg = freeze_all_success(5);
+        let msg = freeze_all_success(5, "");
         assert!(msg.contains("All Repositories Frozen"));
         assert!(msg.contains("5 repositories"));
         assert!(msg.contains("❄️"));
         assert!(msg.contains("/unfreeze-all"));
     }
 
+    #[test]
+    fn test_freeze_all_success_lists_skipped() {
+        let skipped_str = format_skipped_display(&["`owner/old` (archived)".to_string()]);
+        let msg = freeze_all_success(2, &skipped_str);
+        assert!(msg.contains("**Skipped**: `owner/old` (archived)"));
+        assert_eq!(format_skipped_display(&[]), "");
+    }
+
+    #[test]
+    fn test_deployment_frozen() {
+        let now = chrono::Utc::now();
+        let mut freeze = FreezeRecord::new(
+            "owner/repo".to_string(),
+            12345.into(),
+            now,
+            None,
+            Some("Release".to_string()),
+            "alice".to_string(),
+            None,
+        );
+        assert_eq!(deployment_frozen(&freeze), "Frozen by alice: Release");
+
+        freeze.reason = Some("x".repeat(200));
+        assert_eq!(deployment_frozen(&freeze).chars().count(), 140);
+
+        freeze.reason = None;
+        assert_eq!(deployment_frozen(&freeze), "Frozen by alice");
+    }
+
+    #[test]
+    fn test_format_scheduled_table() {
+        use chrono::{TimeZone, Utc};
+
+        let start = Utc.with_ymd_and_hms(2025, 2, 1, 8, 0, 0).unwrap();
+        let record = FreezeRecord::new_scheduled(
+            "owner/repo".to_string(),
+            12345.into(),
+            start,
+            Some(start + Duration::hours(4)),
+            Some("Migration | phase 1".to_string()),
+            "alice".to_string(),
+            Some("main".to_string()),
+        );
+
+        let table = format_scheduled_table("owner/repo", std::slice::from_ref(&record));
+        assert!(table.contains(&format!("| `{}` |", record.id)));
+        assert!(table.contains("| 2025-02-01 08:00 UTC | 2025-02-01 12:00 UTC | main | @alice |"));
+        assert!(table.contains("Migration \\| phase 1"));
+        assert!(table.contains("/cancel-schedule --id"));
+
+        let empty = format_scheduled_table("owner/repo", &[]);
+        assert!(empty.contains("No freezes are scheduled"));
+    }
+
+    #[test]
+    fn test_format_scheduler_preview() {
+        use chrono::{TimeZone, Utc};
+
+        let start = Utc.with_ymd_and_hms(2025, 2, 1, 8, 0, 0).unwrap();
+        let scheduled = FreezeRecord::new_scheduled(
+            "owner/repo".to_string(),
+            12345.into(),
+            start,
+            Some(start + Duration::hours(4)),
+            None,
+            "alice".to_string(),
+            Some("main".to_string()),
+        );
+
+        let message = format_scheduler_preview("2025-02-01 08:01 UTC", std::slice::from_ref(&scheduled), &[]);
+        assert!(message.contains("by **2025-02-01 08:01 UTC**"));
+        assert!(message.contains(&format!(
+            "| `{}` | `owner/repo` | main | 2025-02-01 08:00 UTC |",
+            scheduled.id
+        )));
+        let (_, expire_section) = message.split_once("To Expire").unwrap();
+        assert!(expire_section.contains("*None.*"));
+
+        let plain = MessageTheme::Plain.apply(&message);
+        assert!(plain.contains("### To Activate\n"));
+        assert!(plain.contains("### To Expire\n"));
+    }
+
+    #[test]
+    fn test_format_reconcile_report() {
+        let drift = |pr_number: u64, expected: &'static str, actual: Option<&str>| CheckDrift {
+            pr_number,
+            expected,
+            actual: actual.map(str::to_string),
+            fixed: pr_number != 8,
+        };
+        let results = vec![
+            (
+                "owner/repo".to_string(),
+                vec![
+                    drift(7, "failure", None),
+                    drift(8, "success", Some("failure")),
+                ],
+            ),
+            ("owner/other".to_string(), Vec::new()),
+        ];
+
+        let message = format_reconcile_report(2, &results, &[], false);
+        assert!(message.contains("**2** repositories"));
+        let (blocked, pass) = message.split_once("Should Pass").unwrap();
+        assert!(blocked.contains("| `owner/repo` | #7 | missing | failure |"));
+        assert!(pass.contains("| `owner/repo` | #8 | failure | success |"));
+        assert!(message.contains("/reconcile --fix"));
+
+        let message =
+            format_reconcile_report(2, &results, &["`owner/gone`: Not Found".to_string()], true);
+        assert!(message.contains("| `owner/repo` | #7 | missing | failure | ✅ |"));
+        assert!(message.contains("| `owner/repo` | #8 | failure | success | ❌ |"));
+        assert!(message.contains("- `owner/gone`: Not Found"));
+        assert!(!message.contains("/reconcile --fix"));
+
+        let message = format_reconcile_report(1, &results[1..], &[], false);
+        assert!(message.contains("Every check matches the freeze state"));
+        assert!(!message.contains("Should Be Blocked"));
+    }
+
+    #[test]
+    fn test_format_audit_table() {
+        use crate::database::models::CommandResult;
+        use chrono::TimeZone;
+
+        let mut denied = CommandLog::new(
+            12345.into(),
+            "owner/repo".to_string(),
+            "mallory".to_string(),
+            "/freeze --reason \"a | b\"".to_string(),
+            2,
+            CommandResult::Denied,
+            None,
+        );
+        denied.created_at = chrono::Utc.with_ymd_and_hms(2025, 1, 2, 9, 30, 0).unwrap();
+        let mut failed = CommandLog::new(
+            12345.into(),
+            "owner/repo".to_string(),
+            "alice".to_string(),
+            "/unfreeze".to_string(),
+            1,
+            CommandResult::Failure,
+            Some("No active freeze".to_string()),
+        );
+        failed.created_at = chrono::Utc.with_ymd_and_hms(2025, 1, 1, 10, 0, 0).unwrap();
+
+        let table = format_audit_table("owner/repo", &[denied, failed]);
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[0].contains("`owner/repo`"));
+        assert_eq!(lines[2], "| When | User | Command | Result |");
+        assert_eq!(
+            lines[4],
+            "| 2025-01-02 09:30:00 UTC | @mallory | `/freeze --reason \"a \\| b\"` | denied |"
+        );
+        assert_eq!(
+            lines[5],
+            "| 2025-01-01 10:00:00 UTC | @alice | `/unfreeze` | failure: No active freeze |"
+        );
+
+        let empty = format_audit_table("owner/repo", &[]);
+        assert!(empty.contains("No commands have been recorded"));
+        assert!(!empty.contains("| When |"));
+    }
+
+    #[test]
+    fn test_format_watchers_display() {
+        let watchers = vec!["alice".to_string(), "bob".to_string()];
+        assert_eq!(format_watchers_display(&watchers), "\n\ncc @alice @bob");
+        assert_eq!(format_watchers_display(&[]), "");
+
+        let contacts = format_ops_contacts_display(&watchers);
+        assert!(contacts.contains("@alice @bob"));
+        assert_eq!(format_ops_contacts_display(&[]), "");
+    }
+
+    #[test]
+    fn test_freeze_dry_run_message() {
+        let msg = freeze_dry_run(
+            "owner/repo",
+            " for **2h 0m**",
+            "",
+            "",
+            &["alice".to_string(), "bob".to_string()],
+        );
+        assert!(msg.contains("would be frozen"));
+        assert!(msg.contains("- @alice\n- @bob"));
+        assert!(msg.contains("Nothing was frozen"));
+
+        let msg = freeze_dry_run("owner/repo", " for **2h 0m**", "", "", &[]);
+        assert!(msg.contains("No open pull request would be blocked"));
+    }
+
+    #[test]
+    fn test_format_reason_suggestions() {
+        let suggestions =
+            format_reason_suggestions(&["release 1.2".to_string(), "incident".to_string()]);
+        assert_eq!(
+            suggestions,
+            "\n\n**Recently used reasons**:\n- `release 1.2`\n- `incident`"
+        );
+        assert_eq!(format_reason_suggestions(&[]), "");
+    }
+
+    #[test]
+    fn test_sanitize_markdown() {
+        // Heading markers are dropped, issue references are kept
+        assert_eq!(sanitize_markdown("# URGENT"), "URGENT");
+        assert_eq!(
+            sanitize_markdown("Release\n  ### Deploy"),
+            "Release\nDeploy"
+        );
+        assert_eq!(sanitize_markdown("Fix for #123"), "Fix for #123");
+        assert_eq!(sanitize_markdown("#123 regression"), "#123 regression");
+        assert_eq!(sanitize_markdown("Release\n==="), "Release\n\\===");
+
+        // Control characters are escaped
+        assert_eq!(sanitize_markdown("run `make`"), "run \\`make\\`");
+        assert_eq!(
+            sanitize_markdown("**bold** [link](https://x) <b>"),
+            "\\*\\*bold\\*\\* \\[link\\](https://x) \\<b\\>"
+        );
+
+        // Mentions notify nobody, email addresses are left alone
+        assert_eq!(sanitize_markdown("cc @everyone"), "cc @\u{200B}everyone");
+        assert_eq!(sanitize_markdown("@org/team"), "@\u{200B}org/team");
+        assert_eq!(sanitize_markdown("ops@example.com"), "ops@example.com");
+
+        assert_eq!(sanitize_markdown("Release v1.2.3"), "Release v1.2.3");
+    }
+
+    #[test]
+    fn test_format_reason_display_sanitizes() {
+        let reason_str = format_reason_display(Some("# Outage `db` @everyone".to_string()));
+        assert_eq!(
+            reason_str,
+            "\n\n**Reason**: _Outage \\`db\\` @\u{200B}everyone_"
+        );
+    }
+
+    #[test]
+    fn test_format_missing_branch_display() {
+        let warning = format_missing_branch_display(Some("release"));
+        assert!(warning.starts_with("\n\n⚠️"));
+        assert!(warning.contains("`release`"));
+        assert_eq!(format_missing_branch_display(None), "");
+    }
+
+    #[test]
+    fn test_format_refresh_display() {
+        assert!(format_refresh_display(0, 0, 0).contains("No open pull request"));
+        assert!(format_refresh_display(4, 0, 4).contains("all **4** open pull requests"));
+        let partial = format_refresh_display(3, 2, 5);
+        assert!(partial.starts_with("\n\n🔄"));
+        assert!(partial.contains("**3** of 5"));
+        assert!(partial.contains("the **2** that failed"));
+    }
+
     #[test]
     fn test_freeze_all_partial_success_message() {
         let errors = vec!["Error 1".to_string(), "Error 2".to_string()];
-        let msg = freeze_all_partial_success(3, 2, &errors);
+        let msg = freeze_all_partial_success(3, 2, &errors, "");
         assert!(msg.contains("Partial Freeze Success"));
         assert!(msg.contains("3 repositories"));
         assert!(msg.contains("2 repositories"));
@@ -370,13 +1822,35 @@ mod tests {
 
     #[test]
     fn test_unfreeze_all_success_message() {
-        let msg = unfreeze_all_success(3);
+        let msg = unfreeze_all_success(3, "");
         assert!(msg.contains("All Repositories Unfrozen"));
         assert!(msg.contains("3 repositories"));
         assert!(msg.contains("🌞"));
         assert!(msg.contains("All systems go"));
     }
 
+    #[test]
+    fn test_unfreeze_all_messages_include_reason() {
+        let reason_str = format_reason_display(Some("Release shipped".to_string()));
+
+        let msg = unfreeze_all_success(2, &reason_str);
+        assert!(msg.contains("**Reason**: _Release shipped_"));
+
+        let msg = unfreeze_all_partial_success(1, 1, &["Error 1".to_string()], &reason_str);
+        assert!(msg.contains("**Reason**: _Release shipped_"));
+    }
+
+    #[test]
+    fn test_freeze_reminder_message() {
+        let msg = freeze_reminder("alice", "owner/repo", " for **26h 0m**", " on branch **`main`**");
+        assert!(msg.contains("@alice"));
+        assert!(msg.contains("owner/repo"));
+        assert!(msg.contains("26h 0m"));
+        assert!(msg.contains("`main`"));
+        assert!(msg.contains("/unfreeze"));
+        assert!(msg.contains("/extend"));
+    }
+
     #[test]
     fn test_status_error_message() {
         let msg = status_error("Database error");
@@ -421,8 +1895,52 @@ mod tests {
         assert!(table.contains("🔒 Active"));
         assert!(table.contains("🌞 Off"));
         assert!(table.contains("maintenance"));
-        assert!(table.contains("main"));
-        assert!(table.contains("| Repository | Status |"));
+        assert!(table.contains("| `main` |"));
+        assert!(table.contains("| 🌞 Off | all |"));
+        assert!(table.contains("| Repository | Status | Branch |"));
+    }
+
+    #[test]
+    fn test_format_refresh_debug() {
+        use chrono::TimeZone;
+
+        let stats = RefreshStats {
+            installation_id: 12345,
+            repository: "owner/repo".to_string(),
+            last_refreshed_at: chrono::Utc.with_ymd_and_hms(2025, 1, 1, 10, 0, 0).unwrap(),
+            last_retries: 1,
+            total_retries: 4,
+        };
+        let debug = format_refresh_debug(vec![
+            ("owner/repo".to_string(), Some(stats)),
+            ("owner/new".to_string(), None),
+        ]);
+
+        assert!(debug.contains("| owner/repo | 2025-01-01 10:00:00 UTC | 1 | 4 |"));
+        assert!(debug.contains("| owner/new | never | - | - |"));
+    }
+
+    #[test]
+    fn test_format_status_table_multiple_freezes_per_repo() {
+        use crate::freezer::manager::{FreezeStatus, StatusEntry};
+
+        let branch_entry = |branch: &str| StatusEntry {
+            freeze_status: FreezeStatus::Active,
+            branch: Some(branch.to_string()),
+            duration: Some("2h".to_string()),
+            start: None,
+            end: None,
+            reason: None,
+        };
+        let entries = vec![
+            ("owner/repo".to_string(), branch_entry("main")),
+            ("owner/repo".to_string(), branch_entry("release")),
+        ];
+
+        let table = format_status_table(entries);
+        assert_eq!(table.matches("| owner/repo |").count(), 2);
+        assert!(table.contains("| `main` |"));
+        assert!(table.contains("| `release` |"));
     }
 
     #[test]
@@ -449,4 +1967,62 @@ mod tests {
         assert!(msg.contains("🚫"));
         assert!(msg.contains("try again later"));
     }
+
+    #[test]
+    fn test_freeze_reassign_messages() {
+        let msg = freeze_reassign_success("owner/repo", "alice", "bob", " on branch **`main`**");
+        assert!(msg.contains("Freeze Ownership Transferred"));
+        assert!(msg.contains("owner/repo"));
+        assert!(msg.contains("reassigned to @bob"));
+        assert!(msg.contains("**Previous owner**: @alice"));
+        assert!(msg.contains("main"));
+
+        let msg = freeze_reassign_error("No active freeze found");
+        assert!(msg.contains("Reassign Failed"));
+        assert!(msg.contains("No active freeze found"));
+        assert!(msg.contains("❌"));
+    }
+
+    #[test]
+    fn test_freeze_extend_messages() {
+        let msg = freeze_extend_success(
+            "owner/repo",
+            "2025-01-15 14:00:00 UTC",
+            " on branch **`main`**",
+        );
+        assert!(msg.contains("Freeze Extended"));
+        assert!(msg.contains("`owner/repo`"));
+        assert!(msg.contains("until **2025-01-15 14:00:00 UTC**"));
+        assert!(msg.contains("`main`"));
+
+        let msg = freeze_extend_error("No active freeze found");
+        assert!(msg.contains("Extend Failed"));
+        assert!(msg.contains("No active freeze found"));
+    }
+
+    #[test]
+    fn test_command_help() {
+        let msg = command_help("Usage: /status [OPTIONS]\n\n");
+        assert!(msg.contains("Command Help"));
+        assert!(msg.contains("```text\nUsage: /status [OPTIONS]\n```"));
+    }
+
+    #[test]
+    fn test_preview_message_renders_every_type() {
+        for message_type in PREVIEW_MESSAGE_TYPES {
+            let message = preview_message(message_type)
+                .unwrap_or_else(|| panic!("No preview for '{}'", message_type));
+            assert!(!message.trim().is_empty(), "Empty preview for '{}'", message_type);
+            assert!(!MessageTheme::Plain.apply(&message).trim().is_empty());
+        }
+
+        let freeze = preview_message("freeze-success").unwrap();
+        assert!(freeze.contains("`octo-org/octo-repo`"));
+        assert!(freeze.contains("Release 1.2 stabilization"));
+
+        assert!(preview_message("not-a-message").is_none());
+        let unknown = preview_unknown("not-a-message");
+        assert!(unknown.contains("`not-a-message`"));
+        assert!(unknown.contains("`freeze-success`"));
+    }
 }
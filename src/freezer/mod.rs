@@ -1,3 +1,5 @@
+pub mod business_hours;
+pub mod cache;
 pub mod commands;
 pub mod errors;
 pub mod manager;
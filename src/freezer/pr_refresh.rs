@@ -3,25 +3,40 @@
 //! This module provides functionality to efficiently update all open PRs with
 //! freeze check runs while respecting GitHub API rate limits.
 
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{collections::HashMap, str::FromStr, sync::Arc, time::Duration};
 
 use anyhow::{Result, anyhow};
+use chrono::Utc;
 use octofer::github::{GitHubClient, models::checks::CheckRun, pulls::PullRequest};
-use octofer::octocrab::params::checks::{CheckRunConclusion, CheckRunOutput, CheckRunStatus};
+use octofer::octocrab::{
+    self,
+    models::StatusState,
+    params::checks::{CheckRunConclusion, CheckRunOutput, CheckRunStatus},
+};
+use rand::Rng;
 use tracing::{error, info, warn};
 
 use crate::{
     database::{
         Database,
-        models::{FreezeRecord, UnlockedPr},
+        models::{FreezeRecord, RefreshStats, UnlockedPr},
     },
-    repository::Repository,
+    freezer::{errors::CheckRunError, messages},
+    installation::InstallationId,
+    repository::{DEFAULT_GITHUB_URL, ORG_WILDCARD, Repository},
 };
 
 const FREZZE_CHECK_RUN_NAME: &str = "Frezze";
 
-/// Format freeze information for check run output
-fn format_freeze_details(freeze_record: &FreezeRecord) -> CheckRunOutput {
+/// Format freeze information for check run output.
+///
+/// When the repository configures a `check_run_text_template`, it replaces the
+/// built-in details text.
+fn format_freeze_details(
+    freeze_record: &FreezeRecord,
+    template: Option<&str>,
+    github_url: &str,
+) -> CheckRunOutput {
     let start_time = freeze_record
         .started_at
         .format("%Y-%m-%d %H:%M:%S UTC")
@@ -30,10 +45,12 @@ fn format_freeze_details(freeze_record: &FreezeRecord) -> CheckRunOutput {
         .expires_at
         .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
         .unwrap_or_else(|| "No end time set".to_string());
+    // Reasons are user input rendered as Markdown in the check run
     let reason = freeze_record
         .reason
         .as_deref()
-        .unwrap_or("No reason provided");
+        .map(messages::sanitize_markdown)
+        .unwrap_or_else(|| "No reason provided".to_string());
     let author = &freeze_record.initiated_by;
     let branch = freeze_record
         .branch
@@ -41,6 +58,10 @@ fn format_freeze_details(freeze_record: &FreezeRecord) -> CheckRunOutput {
         .map(|b| format!(" for branch '{}'", b))
         .unwrap_or_else(|| " for all branches".to_string());
 
+    let repository_url = Repository::parse(&freeze_record.repository)
+        .map(|r| r.html_url(github_url))
+        .unwrap_or_default();
+
     let title = format!("Repository is frozen{} by {}", branch, author);
     let summary = format!("This repository is currently under a freeze restriction{}", branch);
 
@@ -50,16 +71,33 @@ fn format_freeze_details(freeze_record: &FreezeRecord) -> CheckRunOutput {
         .map(|b| format!("- **Branch**: {}\n", b))
         .unwrap_or_else(|| "- **Branch**: All branches\n".to_string());
 
-    let text = format!(
-        "**Repository Freeze Details**\n\n\
-        {}\
-        - **Author**: {}\n\
-        - **Start**: {}\n\
-        - **End**: {}\n\
-        - **Reason**: {}\n\n\
-        This PR cannot be merged while the repository is frozen. Please wait for the freeze to end or contact the freeze author.",
-        branch_text, author, start_time, end_time, reason
-    );
+    let text = match template {
+        Some(template) => template
+            .replace("{reason}", &reason)
+            .replace("{end}", &end_time)
+            .replace("{author}", author)
+            .replace("{start}", &start_time)
+            .replace("{branch}", freeze_record.branch.as_deref().unwrap_or("All branches"))
+            .replace("{repository_url}", &repository_url)
+            .replace("{repository}", &freeze_record.repository),
+        None => format!(
+            "**Repository Freeze Details**\n\n\
+            - **Repository**: [{}]({})\n\
+            {}\
+            - **Author**: {}\n\
+            - **Start**: {}\n\
+            - **End**: {}\n\
+            - **Reason**: {}\n\n\
+            This PR cannot be merged while the repository is frozen. Please wait for the freeze to end or contact the freeze author.",
+            freeze_record.repository,
+            repository_url,
+            branch_text,
+            author,
+            start_time,
+            end_time,
+            reason
+        ),
+    };
 
     CheckRunOutput {
         title,
@@ -81,21 +119,171 @@ fn format_success_output() -> CheckRunOutput {
     }
 }
 
+/// Check run of a commit waiting to be published
+struct CheckRunRequest<'a> {
+    installation_id: InstallationId,
+    owner: &'a str,
+    repo: &'a str,
+    head_sha: &'a str,
+    conclusion: CheckRunConclusion,
+    /// Freeze covering the commit, `None` when it is not frozen
+    freeze_record: Option<&'a FreezeRecord>,
+    /// `check_run_text_template` of the repository, if it configures one
+    template: Option<&'a str>,
+    github_url: &'a str,
+}
+
+impl CheckRunRequest<'_> {
+    /// Output of the check run, with the freeze details when the commit is frozen
+    fn output(&self) -> CheckRunOutput {
+        match self.freeze_record {
+            Some(freeze) => format_freeze_details(freeze, self.template, self.github_url),
+            None => format_success_output(),
+        }
+    }
+}
+
+/// Check run conclusion published on PRs that are frozen.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FrozenConclusion {
+    /// Mark frozen PRs as failed (default)
+    #[default]
+    Failure,
+    /// Mark frozen PRs as requiring action, shown distinctly from failures
+    ActionRequired,
+    /// Mark frozen PRs as cancelled
+    Cancelled,
+}
+
+impl FrozenConclusion {
+    /// The check run conclusion this setting maps to
+    pub fn conclusion(&self) -> CheckRunConclusion {
+        match self {
+            FrozenConclusion::Failure => CheckRunConclusion::Failure,
+            FrozenConclusion::ActionRequired => CheckRunConclusion::ActionRequired,
+            FrozenConclusion::Cancelled => CheckRunConclusion::Cancelled,
+        }
+    }
+
+    /// The check run conclusion of PRs blocked by the given freeze.
+    ///
+    /// Soft freezes always require action, whatever the configured conclusion.
+    pub fn conclusion_for(&self, freeze: &FreezeRecord) -> CheckRunConclusion {
+        if freeze.soft {
+            CheckRunConclusion::ActionRequired
+        } else {
+            self.conclusion()
+        }
+    }
+}
+
+impl FromStr for FrozenConclusion {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "failure" => Ok(FrozenConclusion::Failure),
+            "action_required" => Ok(FrozenConclusion::ActionRequired),
+            "cancelled" => Ok(FrozenConclusion::Cancelled),
+            other => Err(anyhow!(
+                "Invalid frozen conclusion '{}'. Expected 'failure', 'action_required' or 'cancelled'",
+                other
+            )),
+        }
+    }
+}
+
 /// Information about a pull request needed for check run updates
 #[derive(Debug, Clone)]
 pub struct PullRequestInfo {
     pub number: u64,
     pub head_sha: String,
     pub base_ref: String,
+    /// Login of the PR author, unless their account was deleted
+    pub author: Option<String>,
+    /// Whether the PR was opened by a bot account
+    pub is_bot: bool,
+}
+
+impl From<PullRequest> for PullRequestInfo {
+    fn from(pr: PullRequest) -> Self {
+        let is_bot = pr.user.as_ref().is_some_and(|user| user.r#type == "Bot");
+        PullRequestInfo {
+            number: pr.number,
+            head_sha: pr.head.sha,
+            base_ref: pr.base.ref_field,
+            author: pr.user.map(|user| user.login),
+            is_bot,
+        }
+    }
+}
+
+/// Outcome of updating the freeze check of a single PR
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrRefreshOutcome {
+    pub pr_number: u64,
+    /// Retries needed by the update
+    pub retries: usize,
+    /// Why the update failed, `None` if it succeeded
+    pub error: Option<String>,
+}
+
+impl PrRefreshOutcome {
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Open PR whose Frezze check disagrees with the freeze state of its branch
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckDrift {
+    pub pr_number: u64,
+    /// Conclusion the check should have (e.g. "failure" while frozen)
+    pub expected: &'static str,
+    /// Conclusion of the PR's latest Frezze check, `None` if it has none
+    pub actual: Option<String>,
+    /// Whether the check was re-published with the expected conclusion
+    pub fixed: bool,
+}
+
+impl CheckDrift {
+    /// Whether the PR should be blocked but its check lets it through
+    pub fn should_block(&self) -> bool {
+        self.expected != conclusion_name(CheckRunConclusion::Success)
+    }
 }
 
 /// Results of a PR refresh operation
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct RefreshResult {
     pub total_prs: usize,
     pub successful_updates: usize,
     pub failed_updates: usize,
     pub errors: Vec<String>,
+    /// Retries needed by the PR updates, counting every retry of failed updates
+    pub retries: usize,
+    /// Outcome of every PR update, in the order the PRs were listed
+    pub per_pr: Vec<PrRefreshOutcome>,
+}
+
+impl RefreshResult {
+    /// Aggregates the outcomes of the PR updates of a refresh
+    pub fn from_outcomes(per_pr: Vec<PrRefreshOutcome>) -> Self {
+        let successful_updates = per_pr.iter().filter(|o| o.is_success()).count();
+        let errors = per_pr
+            .iter()
+            .filter_map(|o| o.error.as_ref().map(|e| format!("PR #{}: {}", o.pr_number, e)))
+            .collect();
+
+        RefreshResult {
+            total_prs: per_pr.len(),
+            successful_updates,
+            failed_updates: per_pr.len() - successful_updates,
+            errors,
+            retries: per_pr.iter().map(|o| o.retries).sum(),
+            per_pr,
+        }
+    }
 }
 
 /// Configuration for PR refresh operations
@@ -109,6 +297,16 @@ pub struct RefreshConfig {
     pub max_retries: usize,
     /// Base delay for exponential backoff in ms
     pub base_retry_delay_ms: u64,
+    /// Fraction of each retry delay randomly added or removed (0.2 for ±20%)
+    pub retry_jitter: f64,
+    /// Publish a legacy commit status when the installation may not create check runs
+    pub commit_status_fallback: bool,
+    /// Custom frozen check run text keyed by installation and lowercase repository ("owner/repo")
+    pub check_run_text_templates: HashMap<(i64, String), String>,
+    /// Conclusion of the check run published on frozen PRs
+    pub frozen_conclusion: FrozenConclusion,
+    /// Web address of the GitHub instance used in check run links
+    pub github_url: String,
 }
 
 impl Default for RefreshConfig {
@@ -118,11 +316,44 @@ impl Default for RefreshConfig {
             batch_delay_ms: 100,
             max_retries: 3,
             base_retry_delay_ms: 1000,
+            retry_jitter: 0.2,
+            commit_status_fallback: false,
+            check_run_text_templates: HashMap::new(),
+            frozen_conclusion: FrozenConclusion::default(),
+            github_url: DEFAULT_GITHUB_URL.to_string(),
         }
     }
 }
 
+impl RefreshConfig {
+    /// Custom frozen check run text of an installation's repository, if any
+    pub fn check_run_text_template(
+        &self,
+        installation_id: InstallationId,
+        repository: &str,
+    ) -> Option<&String> {
+        self.check_run_text_templates
+            .get(&(installation_id.get(), repository.to_lowercase()))
+    }
+}
+
+/// Delay before the given retry (starting at 1).
+///
+/// Backs off exponentially from `base_ms`, then moves the delay randomly by up
+/// to `jitter` of itself so PRs failing together do not all retry at once.
+fn retry_delay(base_ms: u64, attempt: usize, jitter: f64) -> Duration {
+    let backoff = base_ms.saturating_mul(2_u64.saturating_pow(attempt.saturating_sub(1) as u32));
+    let jitter = jitter.clamp(0.0, 1.0);
+    if jitter == 0.0 {
+        return Duration::from_millis(backoff);
+    }
+
+    let factor = 1.0 + rand::thread_rng().gen_range(-jitter..=jitter);
+    Duration::from_millis((backoff as f64 * factor).round() as u64)
+}
+
 /// Service for managing PR refresh operations
+#[derive(Clone)]
 pub struct PrRefreshService {
     github: Arc<GitHubClient>,
     db: Arc<Database>,
@@ -154,7 +385,7 @@ impl PrRefreshService {
     /// - If there's no freeze, all PRs get success status
     pub async fn refresh_repository_prs(
         &self,
-        installation_id: u64,
+        installation_id: InstallationId,
         owner: &str,
         repo: &str,
         freeze_record: Option<&FreezeRecord>,
@@ -170,27 +401,42 @@ impl PrRefreshService {
             .get_open_prs_with_sha(installation_id, owner, repo)
             .await?;
 
-        if prs.is_empty() {
+        let result = if prs.is_empty() {
             info!("No open PRs found for repository {}/{}", owner, repo);
-            return Ok(RefreshResult {
-                total_prs: 0,
-                successful_updates: 0,
-                failed_updates: 0,
-                errors: Vec::new(),
-            });
-        }
+            RefreshResult::default()
+        } else {
+            info!("Found {} open PRs to update", prs.len());
 
-        info!("Found {} open PRs to update", prs.len());
+            // Update all PRs - each PR will be checked individually to determine if it's frozen
+            self.update_prs_in_batches(
+                installation_id,
+                owner,
+                repo,
+                &prs,
+                freeze_record,
+            )
+            .await?
+        };
+
+        self.record_refresh(installation_id, &format!("{}/{}", owner, repo), result.retries)
+            .await;
+
+        Ok(result)
+    }
 
-        // Update all PRs - each PR will be checked individually to determine if it's frozen
-        self.update_prs_in_batches(
+    /// Remember when a repository was refreshed and how many retries it took
+    async fn record_refresh(&self, installation_id: InstallationId, repository: &str, retries: usize) {
+        if let Err(e) = RefreshStats::record(
+            self.db.pool(),
             installation_id,
-            owner,
-            repo,
-            &prs,
-            freeze_record,
+            repository,
+            Utc::now(),
+            retries,
         )
         .await
+        {
+            warn!("Failed to record refresh stats for {}: {}", repository, e);
+        }
     }
 
     /// Refresh check runs for all repositories with active freezes
@@ -214,6 +460,11 @@ impl PrRefreshService {
         let mut results = HashMap::new();
 
         for freeze in active_freezes {
+            // Environment freezes block deployments, not pull requests
+            if freeze.environment.is_some() {
+                continue;
+            }
+
             // Parse repository owner/name
             let parts: Vec<&str> = freeze.repository.split('/').collect();
             if parts.len() != 2 {
@@ -222,9 +473,30 @@ impl PrRefreshService {
             }
             let (owner, repo) = (parts[0], parts[1]);
 
+            // Organization-wide freezes are refreshed per repository by the freeze manager
+            if repo == ORG_WILDCARD {
+                info!("Skipping organization-wide freeze {}", freeze.repository);
+                continue;
+            }
+
+            if let Some(ref branch) = freeze.branch {
+                match self
+                    .branch_exists(freeze.installation_id.into(), owner, repo, branch)
+                    .await
+                {
+                    Ok(true) => {}
+                    Ok(false) => warn!(
+                        "Freeze {} of {} targets branch {} which does not exist, no PR is blocked; \
+                        it may have been deleted or renamed",
+                        freeze.id, freeze.repository, branch
+                    ),
+                    Err(e) => warn!("Could not check the frozen branch of {}: {}", freeze.id, e),
+                }
+            }
+
             match self
                 .refresh_repository_prs(
-                    freeze.installation_id as u64,
+                    freeze.installation_id.into(),
                     owner,
                     repo,
                     Some(&freeze), // Pass the freeze record
@@ -243,10 +515,8 @@ impl PrRefreshService {
                     results.insert(
                         freeze.repository.clone(),
                         RefreshResult {
-                            total_prs: 0,
-                            successful_updates: 0,
-                            failed_updates: 0,
                             errors: vec![format!("Repository refresh failed: {}", e)],
+                            ..RefreshResult::default()
                         },
                     );
                 }
@@ -259,15 +529,18 @@ impl PrRefreshService {
         Ok(results)
     }
 
-    /// Get open PRs for a repository with their head SHAs
-    async fn get_open_prs_with_sha(
+    /// Get open PRs for a repository with their head SHAs and authors.
+    ///
+    /// Every page of open PRs is read, so repositories with more than 100 open
+    /// PRs are fully covered.
+    pub async fn get_open_prs_with_sha(
         &self,
-        installation_id: u64,
+        installation_id: InstallationId,
         owner: &str,
         repo: &str,
     ) -> Result<Vec<PullRequestInfo>> {
         self.github
-            .with_installation_async(installation_id, |client| async move {
+            .with_installation_async(installation_id.as_u64(), |client| async move {
                 let page = client
                     .pulls(owner, repo)
                     .list()
@@ -279,31 +552,139 @@ impl PrRefreshService {
                         error!("Failed to fetch open PRs: {:?}", e);
                         anyhow!("Failed to fetch open PRs: {}", e)
                     })?;
+                let prs = client.all_pages(page).await.map_err(|e| {
+                    error!("Failed to fetch open PRs: {:?}", e);
+                    anyhow!("Failed to fetch open PRs: {}", e)
+                })?;
+
+                Ok(prs.into_iter().map(PullRequestInfo::from).collect())
+            })
+            .await
+    }
+
+    /// Lists the check runs of a commit
+    async fn list_check_runs(
+        &self,
+        installation_id: InstallationId,
+        owner: &str,
+        repo: &str,
+        head_sha: &str,
+    ) -> Result<Vec<CheckRun>> {
+        self.github
+            .with_installation_async(installation_id.as_u64(), |client| async move {
+                let runs = client
+                    .checks(owner, repo)
+                    .list_check_runs_for_git_ref(octocrab::params::repos::Commitish(
+                        head_sha.to_string(),
+                    ))
+                    .per_page(100u8)
+                    .send()
+                    .await
+                    .map_err(|e| anyhow!("Failed to fetch check runs of {}: {}", head_sha, e))?;
+
+                Ok(runs.check_runs)
+            })
+            .await
+    }
 
-                let prs = page
-                    .items
-                    .into_iter()
-                    .map(|pr| PullRequestInfo {
-                        number: pr.number,
-                        head_sha: pr.head.sha,
-                        base_ref: pr.base.ref_field,
-                    })
-                    .collect();
-
-                Ok(prs)
+    /// Fetches the metadata of a repository, such as whether it is archived.
+    pub async fn get_repository(
+        &self,
+        installation_id: InstallationId,
+        owner: &str,
+        repo: &str,
+    ) -> Result<octocrab::models::Repository> {
+        self.github
+            .with_installation_async(installation_id.as_u64(), |client| async move {
+                client
+                    .repos(owner, repo)
+                    .get()
+                    .await
+                    .map_err(|e| anyhow!("Failed to get repository {}/{}: {}", owner, repo, e))
+            })
+            .await
+    }
+
+    /// Checks whether a branch exists in a repository.
+    ///
+    /// A branch freeze only blocks PRs whose base branch has the frozen name,
+    /// so a freeze on a deleted or renamed branch silently lets every PR through.
+    pub async fn branch_exists(
+        &self,
+        installation_id: InstallationId,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+    ) -> Result<bool> {
+        let url = format!("/repos/{}/{}/branches/{}", owner, repo, branch);
+        self.github
+            .with_installation_async(installation_id.as_u64(), |client| async move {
+                match client.get::<serde_json::Value, _, ()>(url, None::<&()>).await {
+                    Ok(_) => Ok(true),
+                    Err(e) => {
+                        let status_code = match &e {
+                            octocrab::Error::GitHub { source, .. } => {
+                                Some(source.status_code.as_u16())
+                            }
+                            _ => None,
+                        };
+                        if is_missing_branch(status_code) {
+                            Ok(false)
+                        } else {
+                            Err(anyhow!("Failed to look up branch {}: {}", branch, e))
+                        }
+                    }
+                }
+            })
+            .await
+    }
+
+    /// Checks whether a repository has no commits yet.
+    ///
+    /// GitHub answers commit listings of empty repositories with a conflict,
+    /// which is the only reliable sign: their size and default branch are set
+    /// like for any other repository.
+    pub async fn is_empty_repository(
+        &self,
+        installation_id: InstallationId,
+        owner: &str,
+        repo: &str,
+    ) -> Result<bool> {
+        let url = format!("/repos/{}/{}/commits", owner, repo);
+        self.github
+            .with_installation_async(installation_id.as_u64(), |client| async move {
+                match client
+                    .get::<serde_json::Value, _, _>(url, Some(&[("per_page", "1")]))
+                    .await
+                {
+                    Ok(_) => Ok(false),
+                    Err(e) => {
+                        let status_code = match &e {
+                            octocrab::Error::GitHub { source, .. } => {
+                                Some(source.status_code.as_u16())
+                            }
+                            _ => None,
+                        };
+                        if is_empty_repository_status(status_code) {
+                            Ok(true)
+                        } else {
+                            Err(anyhow!("Failed to list commits of {}/{}: {}", owner, repo, e))
+                        }
+                    }
+                }
             })
             .await
     }
 
     async fn get_pr(
         &self,
-        installation_id: u64,
+        installation_id: InstallationId,
         owner: &str,
         repo: &str,
         pr_number: u64,
     ) -> Result<PullRequest> {
         self.github
-            .with_installation_async(installation_id, |client| async move {
+            .with_installation_async(installation_id.as_u64(), |client| async move {
                 let pr = client
                     .pulls(owner, repo)
                     .get(pr_number)
@@ -321,25 +702,23 @@ impl PrRefreshService {
     /// Update PRs in batches with proper rate limiting and error handling
     async fn update_prs_in_batches(
         &self,
-        installation_id: u64,
+        installation_id: InstallationId,
         owner: &str,
         repo: &str,
         prs: &[PullRequestInfo],
         freeze_record: Option<&FreezeRecord>,
     ) -> Result<RefreshResult> {
-        let mut successful_updates = 0;
-        let mut failed_updates = 0;
-        let mut errors = Vec::new();
+        let mut outcomes = Vec::with_capacity(prs.len());
 
         // Process PRs in chunks to respect concurrent request limits
         for chunk in prs.chunks(self.config.max_concurrent_requests) {
             let mut handles = Vec::new();
 
             for pr in chunk {
+                let pr_number = pr.number;
                 let github = self.github.clone();
                 let pr = pr.clone();
-                let owner = owner.to_string();
-                let repo = repo.to_string();
+                let repository = Repository::new(owner, repo);
                 let config = self.config.clone();
                 let freeze_record = freeze_record.cloned();
 
@@ -358,17 +737,17 @@ impl PrRefreshService {
                         false
                     };
 
-                    let conclusion = if is_pr_frozen {
-                        CheckRunConclusion::Failure
-                    } else {
-                        CheckRunConclusion::Success
+                    let conclusion = match freeze_record {
+                        Some(ref freeze) if is_pr_frozen => {
+                            config.frozen_conclusion.conclusion_for(freeze)
+                        }
+                        _ => CheckRunConclusion::Success,
                     };
 
                     Self::update_pr_with_retry(
                         github,
                         installation_id,
-                        &owner,
-                        &repo,
+                        &repository,
                         &pr,
                         conclusion,
                         freeze_record.as_ref(),
@@ -377,22 +756,21 @@ impl PrRefreshService {
                     .await
                 });
 
-                handles.push(handle);
+                handles.push((pr_number, handle));
             }
 
             // Wait for all updates in this batch to complete
-            for handle in handles {
-                match handle.await {
-                    Ok(Ok(_)) => successful_updates += 1,
-                    Ok(Err(e)) => {
-                        failed_updates += 1;
-                        errors.push(e.to_string());
-                    }
-                    Err(e) => {
-                        failed_updates += 1;
-                        errors.push(format!("Task join error: {}", e));
-                    }
-                }
+            for (pr_number, handle) in handles {
+                let (retries, error) = match handle.await {
+                    Ok(Ok(pr_retries)) => (pr_retries, None),
+                    Ok(Err(e)) => (self.config.max_retries, Some(e.to_string())),
+                    Err(e) => (0, Some(format!("Task join error: {}", e))),
+                };
+                outcomes.push(PrRefreshOutcome {
+                    pr_number,
+                    retries,
+                    error,
+                });
             }
 
             // Add delay between batches
@@ -401,40 +779,36 @@ impl PrRefreshService {
             }
         }
 
-        Ok(RefreshResult {
-            total_prs: prs.len(),
-            successful_updates,
-            failed_updates,
-            errors,
-        })
+        Ok(RefreshResult::from_outcomes(outcomes))
     }
 
     /// Update a single PR with retry logic
     async fn update_pr_with_retry(
         github: Arc<GitHubClient>,
-        installation_id: u64,
-        owner: &str,
-        repo: &str,
+        installation_id: InstallationId,
+        repository: &Repository,
         pr: &PullRequestInfo,
         conclusion: CheckRunConclusion,
         freeze_record: Option<&FreezeRecord>,
         config: RefreshConfig,
-    ) -> Result<()> {
+    ) -> Result<usize> {
         let mut attempt = 0;
 
+        let request = CheckRunRequest {
+            installation_id,
+            owner: &repository.owner,
+            repo: &repository.name,
+            head_sha: &pr.head_sha,
+            conclusion,
+            freeze_record,
+            template: config
+                .check_run_text_template(installation_id, &repository.full_name())
+                .map(String::as_str),
+            github_url: &config.github_url,
+        };
+
         while attempt <= config.max_retries {
-            match create_check_run(
-                &github,
-                owner,
-                repo,
-                &pr.head_sha,
-                CheckRunStatus::Completed,
-                conclusion,
-                installation_id,
-                freeze_record,
-            )
-            .await
-            {
+            match publish_pr_status(&github, &request, config.commit_status_fallback).await {
                 Ok(_) => {
                     if attempt > 0 {
                         info!(
@@ -442,17 +816,21 @@ impl PrRefreshService {
                             pr.number, attempt
                         );
                     }
-                    return Ok(());
+                    return Ok(attempt);
                 }
                 Err(e) => {
                     attempt += 1;
                     if attempt <= config.max_retries {
-                        let delay = config.base_retry_delay_ms * 2_u64.pow((attempt - 1) as u32);
+                        let delay =
+                            retry_delay(config.base_retry_delay_ms, attempt, config.retry_jitter);
                         warn!(
                             "Failed to update PR #{} (attempt {}), retrying in {}ms: {}",
-                            pr.number, attempt, delay, e
+                            pr.number,
+                            attempt,
+                            delay.as_millis(),
+                            e
                         );
-                        tokio::time::sleep(Duration::from_millis(delay)).await;
+                        tokio::time::sleep(delay).await;
                     } else {
                         error!(
                             "Failed to update PR #{} after {} attempts: {}",
@@ -470,17 +848,20 @@ impl PrRefreshService {
         ))
     }
 
-    /// Refresh a single PR's check run status
-    pub async fn refresh_single_pr(
+    /// Determine the freeze state of a single PR.
+    ///
+    /// Returns the PR details, the active freeze of its repository (if any) and
+    /// the check run conclusion that reflects whether the PR may be merged.
+    async fn evaluate_pr(
         &self,
-        installation_id: i64,
+        installation_id: InstallationId,
         repository: &Repository,
         pr_number: u64,
-    ) -> Result<()> {
+    ) -> Result<(PullRequestInfo, Option<FreezeRecord>, CheckRunConclusion)> {
         // Get PR info
         let pr = self
             .get_pr(
-                installation_id as u64,
+                installation_id,
                 &repository.owner,
                 &repository.name,
                 pr_number,
@@ -489,25 +870,16 @@ impl PrRefreshService {
 
         let pr_base_ref = pr.base.ref_field.clone();
 
-        // Check freeze status - get active freeze for this repo
+        // Check freeze status - organization-wide freezes come first, then the repo's own
         let repo_name = repository.full_name();
         let conn = self.db.get_connection()
             .map_err(|e| anyhow!("Failed to get database connection: {}", e))?;
         
-        let freeze_record = FreezeRecord::get_active_freeze(conn, installation_id, &repo_name).await?;
-        
-        // Check if this PR is affected by the freeze
-        let is_frozen = if let Some(ref freeze) = freeze_record {
-            // If freeze has a specific branch, check if it matches this PR's base branch
-            if let Some(ref freeze_branch) = freeze.branch {
-                freeze_branch == &pr_base_ref
-            } else {
-                // No branch specified, freeze applies to all branches
-                true
-            }
-        } else {
-            false
-        };
+        let freeze_record = freeze_for_branch(
+            FreezeRecord::list_in_effect(conn, installation_id, &repo_name).await?,
+            &pr_base_ref,
+        );
+        let is_frozen = freeze_record.is_some();
 
         let is_unlocked = if is_frozen {
             UnlockedPr::is_pr_unlocked(self.db.pool(), installation_id, &repo_name, pr_number)
@@ -516,64 +888,427 @@ impl PrRefreshService {
             false
         };
 
-        let pr_info = PullRequestInfo {
-            number: pr.number,
-            head_sha: pr.head.sha,
-            base_ref: pr_base_ref,
-        };
+        let pr_info = PullRequestInfo::from(pr);
 
         // Determine check run conclusion based on freeze status
-        let conclusion = if is_frozen && !is_unlocked {
-            CheckRunConclusion::Failure
-        } else {
-            CheckRunConclusion::Success
+        let conclusion = match freeze_record {
+            Some(ref freeze) if !is_unlocked => {
+                self.config.frozen_conclusion.conclusion_for(freeze)
+            }
+            _ => CheckRunConclusion::Success,
         };
 
-        Self::update_pr_with_retry(
+        Ok((pr_info, freeze_record, conclusion))
+    }
+
+    /// Refresh a single PR's check run status
+    pub async fn refresh_single_pr(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        pr_number: u64,
+    ) -> Result<()> {
+        let (pr_info, freeze_record, conclusion) = self
+            .evaluate_pr(installation_id, repository, pr_number)
+            .await?;
+
+        let retries = Self::update_pr_with_retry(
             self.github.clone(),
-            installation_id as u64,
-            &repository.owner,
-            &repository.name,
+            installation_id,
+            repository,
             &pr_info,
             conclusion,
             freeze_record.as_ref(),
-            RefreshConfig::default(),
+            self.config.clone(),
+        )
+        .await?;
+
+        self.record_refresh(installation_id, &repository.full_name(), retries)
+            .await;
+
+        Ok(())
+    }
+
+    /// Re-create a PR's check run if the branch it targets is frozen.
+    ///
+    /// Reviews can make GitHub recompute mergeability, so a stale failing check
+    /// is replaced with a fresh one for the PR's current head SHA. PRs that are
+    /// not frozen are left untouched. Returns `true` if the check was re-created.
+    pub async fn recheck_frozen_pr(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        pr_number: u64,
+        base_ref: &str,
+    ) -> Result<bool> {
+        let conn = self.db.get_connection()
+            .map_err(|e| anyhow!("Failed to get database connection: {}", e))?;
+        let freezes =
+            FreezeRecord::list_in_effect(conn, installation_id, &repository.full_name()).await?;
+        if freeze_for_branch(freezes, base_ref).is_none() {
+            return Ok(false);
+        }
+
+        self.refresh_single_pr(installation_id, repository, pr_number)
+            .await?;
+        Ok(true)
+    }
+
+    /// Publish the Frezze check of a merge queue entry.
+    ///
+    /// The entry is blocked while its base branch is frozen, unless the PR it
+    /// was created for is unlocked. Returns the published conclusion.
+    pub async fn refresh_merge_group(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        head_sha: &str,
+        base_ref: &str,
+        pr_number: Option<u64>,
+    ) -> Result<CheckRunConclusion> {
+        let repo_name = repository.full_name();
+        let conn = self.db.get_connection()
+            .map_err(|e| anyhow!("Failed to get database connection: {}", e))?;
+        let freeze_record = freeze_for_branch(
+            FreezeRecord::list_in_effect(conn, installation_id, &repo_name).await?,
+            base_ref,
+        );
+
+        let is_unlocked = match (&freeze_record, pr_number) {
+            (Some(_), Some(pr_number)) => {
+                UnlockedPr::is_pr_unlocked(self.db.pool(), installation_id, &repo_name, pr_number)
+                    .await?
+            }
+            _ => false,
+        };
+        let conclusion = match freeze_record {
+            Some(ref freeze) if !is_unlocked => {
+                self.config.frozen_conclusion.conclusion_for(freeze)
+            }
+            _ => CheckRunConclusion::Success,
+        };
+
+        let group = PullRequestInfo {
+            number: pr_number.unwrap_or_default(),
+            head_sha: head_sha.to_string(),
+            base_ref: base_ref.to_string(),
+            author: None,
+            is_bot: false,
+        };
+        let retries = Self::update_pr_with_retry(
+            self.github.clone(),
+            installation_id,
+            repository,
+            &group,
+            conclusion,
+            freeze_record.as_ref(),
+            self.config.clone(),
         )
         .await?;
 
+        self.record_refresh(installation_id, &repo_name, retries)
+            .await;
+
+        Ok(conclusion)
+    }
+
+    /// Compare the Frezze check of every open PR of a repository with its freeze state.
+    ///
+    /// Returns the PRs whose latest check disagrees with the conclusion the
+    /// freezes of their base branch call for. With `fix`, their checks are
+    /// re-published with the expected conclusion.
+    pub async fn reconcile_repository(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        fix: bool,
+    ) -> Result<Vec<CheckDrift>> {
+        let repo_name = repository.full_name();
+        let prs = self
+            .get_open_prs_with_sha(installation_id, &repository.owner, &repository.name)
+            .await?;
+        let conn = self
+            .db
+            .get_connection()
+            .map_err(|e| anyhow!("Failed to get database connection: {}", e))?;
+        let freezes = FreezeRecord::list_in_effect(conn, installation_id, &repo_name).await?;
+
+        let mut drifts = Vec::new();
+        for pr in &prs {
+            let freeze_record = freeze_for_branch(freezes.clone(), &pr.base_ref);
+            let is_unlocked = match freeze_record {
+                Some(_) => {
+                    UnlockedPr::is_pr_unlocked(
+                        self.db.pool(),
+                        installation_id,
+                        &repo_name,
+                        pr.number,
+                    )
+                    .await?
+                }
+                None => false,
+            };
+            let expected = match freeze_record {
+                Some(ref freeze) if !is_unlocked => {
+                    self.config.frozen_conclusion.conclusion_for(freeze)
+                }
+                _ => CheckRunConclusion::Success,
+            };
+
+            let check_runs = self
+                .list_check_runs(
+                    installation_id,
+                    &repository.owner,
+                    &repository.name,
+                    &pr.head_sha,
+                )
+                .await?;
+            let actual = latest_frezze_conclusion(&check_runs);
+            let Some(mut drift) = detect_drift(pr.number, expected, actual) else {
+                continue;
+            };
+
+            if fix {
+                match Self::update_pr_with_retry(
+                    self.github.clone(),
+                    installation_id,
+                    repository,
+                    pr,
+                    expected,
+                    freeze_record.as_ref(),
+                    self.config.clone(),
+                )
+                .await
+                {
+                    Ok(_) => drift.fixed = true,
+                    Err(e) => warn!(
+                        "Failed to fix the check of {}#{}: {}",
+                        repo_name, pr.number, e
+                    ),
+                }
+            }
+            drifts.push(drift);
+        }
+
+        Ok(drifts)
+    }
+
+    /// Publish a status summary on a PR's Frezze check run.
+    ///
+    /// The check run keeps the conclusion matching the PR's freeze state; the
+    /// summary is appended to its details instead of being posted as a comment.
+    pub async fn publish_status_check(
+        &self,
+        installation_id: InstallationId,
+        repository: &Repository,
+        pr_number: u64,
+        status_summary: &str,
+    ) -> Result<()> {
+        let (pr_info, freeze_record, conclusion) = self
+            .evaluate_pr(installation_id, repository, pr_number)
+            .await?;
+
+        let request = CheckRunRequest {
+            installation_id,
+            owner: &repository.owner,
+            repo: &repository.name,
+            head_sha: &pr_info.head_sha,
+            conclusion,
+            freeze_record: freeze_record.as_ref(),
+            template: self
+                .config
+                .check_run_text_template(installation_id, &repository.full_name())
+                .map(String::as_str),
+            github_url: &self.config.github_url,
+        };
+        let output = status_check_output(request.output(), status_summary);
+
+        send_check_run(&self.github, &request, CheckRunStatus::Completed, output).await?;
+
         Ok(())
     }
 }
 
+/// Returns `true` if a failed branch lookup means the branch does not exist
+fn is_missing_branch(status_code: Option<u16>) -> bool {
+    status_code == Some(404)
+}
+
+/// Returns `true` if a failed commit listing means the repository is empty
+fn is_empty_repository_status(status_code: Option<u16>) -> bool {
+    status_code == Some(409)
+}
+
+/// Picks the freeze that affects a PR targeting `base_ref`.
+///
+/// A freeze affects the PR if it has no branch or targets the PR's base branch.
+fn freeze_for_branch(freezes: Vec<FreezeRecord>, base_ref: &str) -> Option<FreezeRecord> {
+    freezes
+        .into_iter()
+        .find(|freeze| freeze.branch.as_deref().is_none_or(|b| b == base_ref))
+}
+
+/// Name of a check run conclusion as reported by GitHub
+fn conclusion_name(conclusion: CheckRunConclusion) -> &'static str {
+    match conclusion {
+        CheckRunConclusion::Success => "success",
+        CheckRunConclusion::Failure => "failure",
+        CheckRunConclusion::Neutral => "neutral",
+        CheckRunConclusion::Cancelled => "cancelled",
+        CheckRunConclusion::TimedOut => "timed_out",
+        CheckRunConclusion::Skipped => "skipped",
+        CheckRunConclusion::Stale => "stale",
+        CheckRunConclusion::ActionRequired => "action_required",
+    }
+}
+
+/// Conclusion of the most recent Frezze check among a commit's check runs.
+///
+/// Checks still in progress are reported as "pending".
+fn latest_frezze_conclusion(check_runs: &[CheckRun]) -> Option<String> {
+    check_runs
+        .iter()
+        .filter(|run| run.name == FREZZE_CHECK_RUN_NAME)
+        .max_by_key(|run| (run.started_at, run.id))
+        .map(|run| {
+            run.conclusion
+                .clone()
+                .unwrap_or_else(|| "pending".to_string())
+        })
+}
+
+/// Compares the Frezze check of a PR with the conclusion its freeze state calls for
+fn detect_drift(
+    pr_number: u64,
+    expected: CheckRunConclusion,
+    actual: Option<String>,
+) -> Option<CheckDrift> {
+    let expected = conclusion_name(expected);
+    if actual.as_deref() == Some(expected) {
+        return None;
+    }
+
+    Some(CheckDrift {
+        pr_number,
+        expected,
+        actual,
+        fixed: false,
+    })
+}
+
+/// Appends a status summary to the details of a check run output
+fn status_check_output(base: CheckRunOutput, status_summary: &str) -> CheckRunOutput {
+    let text = match base.text {
+        Some(text) => format!("{}\n\n---\n\n{}", text, status_summary),
+        None => status_summary.to_string(),
+    };
+
+    CheckRunOutput {
+        text: Some(text),
+        ..base
+    }
+}
+
+/// Maps a check run conclusion onto the equivalent commit status state
+fn commit_status_state(conclusion: CheckRunConclusion) -> StatusState {
+    match conclusion {
+        CheckRunConclusion::Success | CheckRunConclusion::Neutral | CheckRunConclusion::Skipped => {
+            StatusState::Success
+        }
+        _ => StatusState::Failure,
+    }
+}
+
+/// Checks whether a failed check run should be replaced by a commit status
+fn needs_status_fallback(error: &CheckRunError, fallback_enabled: bool) -> bool {
+    fallback_enabled && matches!(error, CheckRunError::Forbidden(_))
+}
+
+/// Publishes the freeze status of a commit, preferring check runs.
+///
+/// When the installation lacks the `checks:write` permission and the fallback
+/// is enabled, a legacy commit status with the same outcome is created instead.
+async fn publish_pr_status(
+    client: &GitHubClient,
+    request: &CheckRunRequest<'_>,
+    commit_status_fallback: bool,
+) -> Result<()> {
+    let error = match create_check_run(client, request, CheckRunStatus::Completed).await {
+        Ok(_) => return Ok(()),
+        Err(e) => e,
+    };
+
+    if !needs_status_fallback(&error, commit_status_fallback) {
+        return Err(error.into());
+    }
+
+    warn!(
+        "Check runs are not permitted for {}/{}, falling back to a commit status: {}",
+        request.owner, request.repo, error
+    );
+    create_commit_status(client, request).await
+}
+
+async fn create_commit_status(client: &GitHubClient, request: &CheckRunRequest<'_>) -> Result<()> {
+    // Commit statuses only carry the title, so the text template does not apply
+    let output = CheckRunRequest {
+        template: None,
+        ..*request
+    }
+    .output();
+
+    client
+        .app_client()
+        .installation(request.installation_id.into())?
+        .repos(request.owner, request.repo)
+        .create_status(
+            request.head_sha.to_string(),
+            commit_status_state(request.conclusion),
+        )
+        .context(FREZZE_CHECK_RUN_NAME.to_string())
+        .description(output.title)
+        .send()
+        .await
+        .map_err(|e| {
+            error!("Failed to create commit status: {:?}", e);
+            anyhow!("Failed to create commit status: {}", e)
+        })?;
+
+    Ok(())
+}
+
 async fn create_check_run(
     client: &GitHubClient,
-    owner: &str,
-    repo: &str,
-    head_sha: &str,
+    request: &CheckRunRequest<'_>,
     status: CheckRunStatus,
-    conclusion: CheckRunConclusion,
-    installation_id: u64,
-    freeze_record: Option<&FreezeRecord>,
-) -> Result<CheckRun> {
-    let output = if let Some(freeze) = freeze_record {
-        format_freeze_details(freeze)
-    } else {
-        format_success_output()
-    };
+) -> std::result::Result<CheckRun, CheckRunError> {
+    send_check_run(client, request, status, request.output()).await
+}
 
+async fn send_check_run(
+    client: &GitHubClient,
+    request: &CheckRunRequest<'_>,
+    status: CheckRunStatus,
+    output: CheckRunOutput,
+) -> std::result::Result<CheckRun, CheckRunError> {
     let result = client
         .app_client()
-        .installation(installation_id.into())?
-        .checks(owner, repo)
-        .create_check_run(FREZZE_CHECK_RUN_NAME, head_sha)
+        .installation(request.installation_id.into())
+        .map_err(|e| CheckRunError::Other(e.to_string()))?
+        .checks(request.owner, request.repo)
+        .create_check_run(FREZZE_CHECK_RUN_NAME, request.head_sha)
         .status(status)
-        .conclusion(conclusion)
+        .conclusion(request.conclusion)
         .output(output)
         .send()
         .await
         .map_err(|e| {
             error!("Failed to create check run: {:?}", e);
-            anyhow!("Failed to create check run: {}", e)
+            let status_code = match &e {
+                octocrab::Error::GitHub { source, .. } => Some(source.status_code.as_u16()),
+                _ => None,
+            };
+            CheckRunError::from_status(status_code, e.to_string())
         })?;
 
     Ok(result)
@@ -590,6 +1325,10 @@ mod tests {
         assert_eq!(config.batch_delay_ms, 100);
         assert_eq!(config.max_retries, 3);
         assert_eq!(config.base_retry_delay_ms, 1000);
+        assert_eq!(config.retry_jitter, 0.2);
+        assert!(!config.commit_status_fallback);
+        assert!(config.check_run_text_templates.is_empty());
+        assert_eq!(config.frozen_conclusion, FrozenConclusion::Failure);
     }
 
     #[test]
@@ -598,6 +1337,8 @@ mod tests {
             number: 42,
             head_sha: "abc123def456".to_string(),
             base_ref: "main".to_string(),
+            author: Some("alice".to_string()),
+            is_bot: false,
         };
 
         assert_eq!(pr_info.number, 42);
@@ -612,6 +1353,8 @@ mod tests {
             successful_updates: 4,
             failed_updates: 1,
             errors: vec!["Error updating PR #3".to_string()],
+            retries: 2,
+            per_pr: Vec::new(),
         };
 
         assert_eq!(result.total_prs, 5);
@@ -619,6 +1362,79 @@ mod tests {
         assert_eq!(result.failed_updates, 1);
         assert_eq!(result.errors.len(), 1);
         assert_eq!(result.errors[0], "Error updating PR #3");
+        assert_eq!(result.retries, 2);
+    }
+
+    #[test]
+    fn test_refresh_result_from_outcomes() {
+        let outcomes = vec![
+            PrRefreshOutcome {
+                pr_number: 1,
+                retries: 0,
+                error: None,
+            },
+            PrRefreshOutcome {
+                pr_number: 3,
+                retries: 3,
+                error: Some("Not Found".to_string()),
+            },
+            PrRefreshOutcome {
+                pr_number: 7,
+                retries: 1,
+                error: None,
+            },
+        ];
+
+        let result = RefreshResult::from_outcomes(outcomes.clone());
+        assert_eq!(result.total_prs, 3);
+        assert_eq!(result.successful_updates, 2);
+        assert_eq!(result.failed_updates, 1);
+        assert_eq!(result.retries, 4);
+        assert_eq!(result.errors, vec!["PR #3: Not Found"]);
+        assert_eq!(result.per_pr, outcomes);
+
+        // The per-PR outcomes add up to the aggregate counts
+        let failed: Vec<u64> = result
+            .per_pr
+            .iter()
+            .filter(|o| !o.is_success())
+            .map(|o| o.pr_number)
+            .collect();
+        assert_eq!(failed, vec![3]);
+        assert_eq!(
+            result.per_pr.iter().filter(|o| o.is_success()).count(),
+            result.successful_updates
+        );
+
+        let empty = RefreshResult::from_outcomes(Vec::new());
+        assert_eq!(empty.total_prs, 0);
+        assert!(empty.errors.is_empty());
+    }
+
+    #[test]
+    fn test_retry_delay_jitter() {
+        // Delays stay within ±20% of the exponential backoff
+        for attempt in 1..=3 {
+            let backoff = 1000 * 2_u64.pow(attempt as u32 - 1);
+            let mut delays = Vec::new();
+            for _ in 0..200 {
+                let delay = retry_delay(1000, attempt, 0.2).as_millis() as u64;
+                assert!(
+                    (backoff * 8 / 10..=backoff * 12 / 10).contains(&delay),
+                    "delay {}ms outside the jittered range of {}ms",
+                    delay,
+                    backoff
+                );
+                delays.push(delay);
+            }
+            // Retries of different PRs are spread out
+            assert!(delays.iter().any(|d| *d != delays[0]));
+        }
+
+        // Without jitter the backoff is deterministic
+        assert_eq!(retry_delay(1000, 1, 0.0), Duration::from_millis(1000));
+        assert_eq!(retry_delay(1000, 3, 0.0), Duration::from_millis(4000));
+        assert_eq!(retry_delay(1000, 2, -1.0), Duration::from_millis(2000));
     }
 
     #[test]
@@ -628,18 +1444,101 @@ mod tests {
             batch_delay_ms: 200,
             max_retries: 5,
             base_retry_delay_ms: 500,
+            retry_jitter: 0.0,
+            commit_status_fallback: true,
+            check_run_text_templates: HashMap::new(),
+            frozen_conclusion: FrozenConclusion::ActionRequired,
+            github_url: "https://github.example.com".to_string(),
         };
 
         assert_eq!(config.max_concurrent_requests, 5);
         assert_eq!(config.batch_delay_ms, 200);
         assert_eq!(config.max_retries, 5);
         assert_eq!(config.base_retry_delay_ms, 500);
+        assert!(config.commit_status_fallback);
+    }
+
+    #[test]
+    fn test_frozen_conclusion_mapping() {
+        let parse = |value: &str| value.parse::<FrozenConclusion>().unwrap().conclusion();
+        assert!(matches!(parse("failure"), CheckRunConclusion::Failure));
+        assert!(matches!(parse("action_required"), CheckRunConclusion::ActionRequired));
+        assert!(matches!(parse("cancelled"), CheckRunConclusion::Cancelled));
+        assert!(matches!(parse(" Action_Required "), CheckRunConclusion::ActionRequired));
+        assert!(matches!(
+            FrozenConclusion::default().conclusion(),
+            CheckRunConclusion::Failure
+        ));
+
+        assert!("success".parse::<FrozenConclusion>().is_err());
+        assert!("neutral".parse::<FrozenConclusion>().is_err());
+        assert!("".parse::<FrozenConclusion>().is_err());
+    }
+
+    #[test]
+    fn test_soft_freeze_conclusion() {
+        let freeze = FreezeRecord::new(
+            "owner/repo".to_string(),
+            12345.into(),
+            Utc::now(),
+            None,
+            None,
+            "alice".to_string(),
+            None,
+        );
+        assert!(matches!(
+            FrozenConclusion::Failure.conclusion_for(&freeze),
+            CheckRunConclusion::Failure
+        ));
+        assert!(matches!(
+            FrozenConclusion::Cancelled.conclusion_for(&freeze),
+            CheckRunConclusion::Cancelled
+        ));
+
+        // `--soft` freezes require action whatever the configured conclusion
+        let soft = freeze.with_soft(true);
+        for configured in [
+            FrozenConclusion::Failure,
+            FrozenConclusion::ActionRequired,
+            FrozenConclusion::Cancelled,
+        ] {
+            assert!(matches!(
+                configured.conclusion_for(&soft),
+                CheckRunConclusion::ActionRequired
+            ));
+        }
+    }
+
+    #[test]
+    fn test_commit_status_fallback_on_forbidden_check_run() {
+        // Checks API rejects the request because checks:write is missing
+        let forbidden = CheckRunError::from_status(Some(403), "Resource not accessible".into());
+        assert!(matches!(forbidden, CheckRunError::Forbidden(_)));
+        assert!(needs_status_fallback(&forbidden, true));
+        assert!(!needs_status_fallback(&forbidden, false));
+
+        // Other failures are retried as check runs and never fall back
+        let server_error = CheckRunError::from_status(Some(500), "Server error".into());
+        assert!(!needs_status_fallback(&server_error, true));
+        let network_error = CheckRunError::from_status(None, "timeout".into());
+        assert!(!needs_status_fallback(&network_error, true));
+    }
+
+    #[test]
+    fn test_commit_status_state() {
+        assert_eq!(
+            commit_status_state(CheckRunConclusion::Success),
+            StatusState::Success
+        );
+        assert_eq!(
+            commit_status_state(CheckRunConclusion::Failure),
+            StatusState::Failure
+        );
     }
 
     #[test]
     fn test_format_freeze_details() {
         use crate::database::models::{FreezeRecord, FreezeStatus};
-        use chrono::Utc;
 
         let freeze_record = FreezeRecord {
             id: "test-id".to_string(),
@@ -653,10 +1552,15 @@ mod tests {
             ended_by: None,
             status: FreezeStatus::Active,
             branch: None,
+            end_reason: None,
+            environment: None,
+            soft: false,
+            category: None,
+            deleted_at: None,
             created_at: Utc::now(),
         };
 
-        let output = format_freeze_details(&freeze_record);
+        let output = format_freeze_details(&freeze_record, None, DEFAULT_GITHUB_URL);
 
         assert_eq!(output.title, "Repository is frozen for all branches by test-user");
         assert_eq!(
@@ -669,6 +1573,7 @@ mod tests {
         assert!(text.contains("test-user"));
         assert!(text.contains("Emergency maintenance"));
         assert!(text.contains("All branches"));
+        assert!(text.contains("[owner/repo](https://github.com/owner/repo)"));
         assert!(text.contains("This PR cannot be merged while the repository is frozen"));
         assert_eq!(output.annotations.len(), 0);
         assert_eq!(output.images.len(), 0);
@@ -677,7 +1582,6 @@ mod tests {
     #[test]
     fn test_format_freeze_details_no_reason() {
         use crate::database::models::{FreezeRecord, FreezeStatus};
-        use chrono::Utc;
 
         let freeze_record = FreezeRecord {
             id: "test-id".to_string(),
@@ -691,10 +1595,15 @@ mod tests {
             ended_by: None,
             status: FreezeStatus::Active,
             branch: None,
+            end_reason: None,
+            environment: None,
+            soft: false,
+            category: None,
+            deleted_at: None,
             created_at: Utc::now(),
         };
 
-        let output = format_freeze_details(&freeze_record);
+        let output = format_freeze_details(&freeze_record, None, DEFAULT_GITHUB_URL);
 
         assert_eq!(output.title, "Repository is frozen for all branches by test-user");
         assert!(output.text.is_some());
@@ -705,6 +1614,246 @@ mod tests {
         assert!(text.contains("All branches"));
     }
 
+    #[test]
+    fn test_format_freeze_details_with_template() {
+        use crate::database::models::{FreezeRecord, FreezeStatus};
+        use chrono::{TimeZone, Utc};
+
+        let freeze_record = FreezeRecord {
+            id: "test-id".to_string(),
+            repository: "owner/repo".to_string(),
+            installation_id: 12345,
+            started_at: Utc.with_ymd_and_hms(2025, 1, 1, 10, 0, 0).unwrap(),
+            expires_at: Some(Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap()),
+            ended_at: None,
+            reason: Some("Release v1.2.3".to_string()),
+            initiated_by: "test-user".to_string(),
+            ended_by: None,
+            status: FreezeStatus::Active,
+            branch: Some("main".to_string()),
+            end_reason: None,
+            environment: None,
+            soft: false,
+            category: None,
+            deleted_at: None,
+            created_at: Utc::now(),
+        };
+
+        let output = format_freeze_details(
+            &freeze_record,
+            Some("Frozen by {author} until {end}: {reason}. See the team runbook."),
+            DEFAULT_GITHUB_URL,
+        );
+
+        // Title and summary keep the built-in wording
+        assert_eq!(output.title, "Repository is frozen for branch 'main' by test-user");
+        assert_eq!(
+            output.text.unwrap(),
+            "Frozen by test-user until 2025-01-01 12:00:00 UTC: Release v1.2.3. See the team runbook."
+        );
+
+        // Missing values fall back to the same defaults as the built-in text
+        let freeze_record = FreezeRecord {
+            reason: None,
+            expires_at: None,
+            ..freeze_record
+        };
+        let output = format_freeze_details(
+            &freeze_record,
+            Some("{reason} / {end} / {unknown}"),
+            DEFAULT_GITHUB_URL,
+        );
+        assert_eq!(
+            output.text.unwrap(),
+            "No reason provided / No end time set / {unknown}"
+        );
+
+        // Links follow the configured GitHub instance
+        let output = format_freeze_details(
+            &freeze_record,
+            Some("{repository}: {repository_url}"),
+            "https://github.example.com/",
+        );
+        assert_eq!(
+            output.text.unwrap(),
+            "owner/repo: https://github.example.com/owner/repo"
+        );
+    }
+
+    #[test]
+    fn test_format_freeze_details_sanitizes_reason() {
+        let freeze_record = FreezeRecord::new(
+            "owner/repo".to_string(),
+            12345.into(),
+            Utc::now(),
+            None,
+            Some("## Outage\nrun `rollback` @everyone".to_string()),
+            "test-user".to_string(),
+            None,
+        );
+
+        let output = format_freeze_details(&freeze_record, None, DEFAULT_GITHUB_URL);
+        let text = output.text.unwrap();
+        assert!(text.contains("- **Reason**: Outage\nrun \\`rollback\\` @\u{200B}everyone"));
+        assert!(!text.contains("## Outage"));
+
+        let output = format_freeze_details(&freeze_record, Some("{reason}"), DEFAULT_GITHUB_URL);
+        assert_eq!(
+            output.text.unwrap(),
+            "Outage\nrun \\`rollback\\` @\u{200B}everyone"
+        );
+    }
+
+    #[test]
+    fn test_status_check_output() {
+        use crate::freezer::manager::StatusEntry;
+        use crate::freezer::messages;
+
+        let summary = messages::format_status_table(vec![(
+            "owner/repo".to_string(),
+            StatusEntry::not_frozen(),
+        )]);
+
+        let output = status_check_output(format_success_output(), &summary);
+
+        // Title and summary still describe the PR's freeze state
+        assert_eq!(output.title, "Repository is not frozen");
+        let text = output.text.unwrap();
+        assert!(text.starts_with("PRs can be merged normally."));
+        assert!(text.contains("Repository Freeze Status"));
+        assert!(text.contains("owner/repo"));
+    }
+
+    #[test]
+    fn test_is_missing_branch() {
+        assert!(is_missing_branch(Some(404)));
+
+        // Other failures say nothing about the branch
+        assert!(!is_missing_branch(Some(403)));
+        assert!(!is_missing_branch(Some(500)));
+        assert!(!is_missing_branch(None));
+    }
+
+    #[test]
+    fn test_is_empty_repository_status() {
+        assert!(is_empty_repository_status(Some(409)));
+
+        assert!(!is_empty_repository_status(Some(404)));
+        assert!(!is_empty_repository_status(Some(500)));
+        assert!(!is_empty_repository_status(None));
+    }
+
+    #[test]
+    fn test_freeze_for_branch() {
+        let freeze = |id: &str, branch: Option<&str>| {
+            let mut record = FreezeRecord::new(
+                "owner/repo".to_string(),
+                12345.into(),
+                Utc::now(),
+                None,
+                None,
+                "test-user".to_string(),
+                branch.map(str::to_string),
+            );
+            record.id = id.to_string();
+            record
+        };
+
+        let freezes = vec![freeze("release", Some("release")), freeze("all", None)];
+        assert_eq!(freeze_for_branch(freezes.clone(), "release").unwrap().id, "release");
+        assert_eq!(freeze_for_branch(freezes, "main").unwrap().id, "all");
+
+        let freezes = vec![freeze("release", Some("release"))];
+        assert!(freeze_for_branch(freezes, "main").is_none());
+        assert!(freeze_for_branch(Vec::new(), "main").is_none());
+    }
+
+    #[test]
+    fn test_latest_frezze_conclusion() {
+        let run = |id: u64, name: &str, started_at: &str, conclusion: Option<&str>| -> CheckRun {
+            serde_json::from_value(serde_json::json!({
+                "id": id,
+                "node_id": "CR_node",
+                "details_url": null,
+                "head_sha": "abc123",
+                "url": "https://api.github.com/repos/owner/repo/check-runs/1",
+                "html_url": null,
+                "conclusion": conclusion,
+                "output": {
+                    "title": null,
+                    "summary": null,
+                    "text": null,
+                    "annotations_count": 0,
+                    "annotations_url": "https://api.github.com/repos/owner/repo/check-runs/1/annotations",
+                },
+                "started_at": started_at,
+                "completed_at": null,
+                "name": name,
+                "pull_requests": [],
+            }))
+            .unwrap()
+        };
+
+        // The most recent Frezze check wins, other checks are ignored
+        let runs = vec![
+            run(1, "Frezze", "2025-01-15T10:00:00Z", Some("failure")),
+            run(2, "Frezze", "2025-01-15T12:00:00Z", Some("success")),
+            run(3, "CI", "2025-01-15T13:00:00Z", Some("failure")),
+        ];
+        assert_eq!(latest_frezze_conclusion(&runs).as_deref(), Some("success"));
+
+        let runs = vec![run(1, "Frezze", "2025-01-15T10:00:00Z", None)];
+        assert_eq!(latest_frezze_conclusion(&runs).as_deref(), Some("pending"));
+
+        let runs = vec![run(3, "CI", "2025-01-15T13:00:00Z", Some("success"))];
+        assert!(latest_frezze_conclusion(&runs).is_none());
+    }
+
+    #[test]
+    fn test_detect_drift() {
+        // Checks matching the freeze state are fine
+        assert!(
+            detect_drift(1, CheckRunConclusion::Failure, Some("failure".to_string())).is_none()
+        );
+        assert!(
+            detect_drift(1, CheckRunConclusion::Success, Some("success".to_string())).is_none()
+        );
+
+        // Frozen PRs whose check lets them through
+        for actual in [
+            Some("success".to_string()),
+            Some("pending".to_string()),
+            None,
+        ] {
+            let drift = detect_drift(7, CheckRunConclusion::Failure, actual.clone()).unwrap();
+            assert_eq!(
+                drift,
+                CheckDrift {
+                    pr_number: 7,
+                    expected: "failure",
+                    actual,
+                    fixed: false,
+                }
+            );
+            assert!(drift.should_block());
+        }
+
+        // PRs still blocked after their freeze ended
+        let drift =
+            detect_drift(8, CheckRunConclusion::Success, Some("failure".to_string())).unwrap();
+        assert_eq!(drift.expected, "success");
+        assert!(!drift.should_block());
+
+        // A changed frozen conclusion leaves the old one behind
+        let drift = detect_drift(
+            9,
+            CheckRunConclusion::ActionRequired,
+            Some("failure".to_string()),
+        )
+        .unwrap();
+        assert!(drift.should_block());
+    }
+
     #[test]
     fn test_format_success_output() {
         let output = format_success_output();
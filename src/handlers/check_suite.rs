@@ -0,0 +1,169 @@
+//! Check suite handler (re)creating freeze checks for new commits.
+//!
+//! GitHub requests a check suite whenever commits are pushed, and again when a
+//! user re-runs all checks. Both are the canonical moments to publish checks,
+//! so the Frezze check of every pull request whose head is the suite's commit
+//! is re-evaluated and re-created for it.
+
+use std::sync::Arc;
+
+use octofer::octocrab::models::webhook_events::{
+    WebhookEventPayload, payload::CheckSuiteWebhookEventAction,
+};
+use tracing::{error, info, warn};
+
+use crate::{AppState, freezer, installation::InstallationId, repository::Repository};
+
+use super::require_client;
+
+/// Returns `true` for check suite actions asking for checks to be created
+fn needs_checks(action: &CheckSuiteWebhookEventAction) -> bool {
+    matches!(
+        action,
+        CheckSuiteWebhookEventAction::Requested | CheckSuiteWebhookEventAction::Rerequested
+    )
+}
+
+/// Resolves the pull requests a check suite belongs to.
+///
+/// GitHub lists the open PRs whose head is the suite's commit. PRs from forks
+/// are not listed, so their checks keep being handled by the periodic refresh.
+fn suite_pull_requests(check_suite: &serde_json::Value) -> Vec<u64> {
+    let head_sha = check_suite["head_sha"].as_str();
+    let mut numbers: Vec<u64> = check_suite["pull_requests"]
+        .as_array()
+        .map(|prs| {
+            prs.iter()
+                .filter(|pr| {
+                    // Skip PRs that moved on to another commit in the meantime
+                    match (head_sha, pr["head"]["sha"].as_str()) {
+                        (Some(suite), Some(pr)) => suite == pr,
+                        _ => true,
+                    }
+                })
+                .filter_map(|pr| pr["number"].as_u64())
+                .collect()
+        })
+        .unwrap_or_default();
+    numbers.sort_unstable();
+    numbers.dedup();
+    numbers
+}
+
+pub async fn check_suite_handler(
+    context: octofer::Context,
+    extra: Arc<AppState>,
+) -> anyhow::Result<()> {
+    info!("Check suite event received!");
+
+    let Some(e) = context.event else {
+        warn!("Check suite event has no payload... skipping");
+        return Ok(());
+    };
+    let WebhookEventPayload::CheckSuite(payload) = &e.specific else {
+        return Err(anyhow::anyhow!("Expected a check suite payload"));
+    };
+    if !needs_checks(&payload.action) {
+        return Ok(());
+    }
+    let pr_numbers = suite_pull_requests(&payload.check_suite);
+    if pr_numbers.is_empty() {
+        return Ok(());
+    }
+
+    let client = require_client(context.github_client)?;
+    let installation_id = InstallationId::try_from(
+        context
+            .installation_id
+            .ok_or(anyhow::anyhow!("Cannot get installation_id"))?,
+    )?;
+    let repository: Repository = e
+        .repository
+        .ok_or(anyhow::anyhow!("Cannot get repository from event"))?
+        .into();
+
+    let mng = freezer::manager::FreezeManager::with_config(
+        extra.database.clone(),
+        client,
+        extra.config.clone(),
+    )
+    .with_cache(extra.freeze_cache.clone());
+
+    let mut failed = 0;
+    for pr_number in pr_numbers {
+        match mng
+            .pr_refresh
+            .refresh_single_pr(installation_id, &repository, pr_number)
+            .await
+        {
+            Ok(()) => info!(
+                "Updated freeze check for PR #{} in {} for requested check suite",
+                pr_number,
+                repository.full_name()
+            ),
+            Err(e) => {
+                error!(
+                    "Failed to update freeze check for PR #{} in {}: {}",
+                    pr_number,
+                    repository.full_name(),
+                    e
+                );
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(anyhow::anyhow!(
+            "Failed to update the freeze check of {} PR(s) in {}",
+            failed,
+            repository.full_name()
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_needs_checks() {
+        assert!(needs_checks(&CheckSuiteWebhookEventAction::Requested));
+        assert!(needs_checks(&CheckSuiteWebhookEventAction::Rerequested));
+        assert!(!needs_checks(&CheckSuiteWebhookEventAction::Completed));
+    }
+
+    #[test]
+    fn test_suite_pull_requests() {
+        let suite = serde_json::json!({
+            "head_sha": "abc123",
+            "pull_requests": [
+                {"number": 7, "head": {"sha": "abc123"}, "base": {"ref": "main"}},
+                {"number": 3, "head": {"sha": "abc123"}, "base": {"ref": "release"}},
+                {"number": 7, "head": {"sha": "abc123"}, "base": {"ref": "main"}},
+                // Already pushed past the suite's commit
+                {"number": 9, "head": {"sha": "def456"}, "base": {"ref": "main"}}
+            ]
+        });
+        assert_eq!(suite_pull_requests(&suite), vec![3, 7]);
+    }
+
+    #[test]
+    fn test_suite_pull_requests_without_prs() {
+        // Pushes to branches without a PR, or PRs from forks
+        let suite = serde_json::json!({"head_sha": "abc123", "pull_requests": []});
+        assert!(suite_pull_requests(&suite).is_empty());
+
+        let suite = serde_json::json!({"head_sha": "abc123"});
+        assert!(suite_pull_requests(&suite).is_empty());
+
+        // Entries without a usable number are ignored
+        let suite = serde_json::json!({
+            "head_sha": "abc123",
+            "pull_requests": [{"head": {"sha": "abc123"}}, {"number": 4}]
+        });
+        assert_eq!(suite_pull_requests(&suite), vec![4]);
+    }
+}
@@ -0,0 +1,178 @@
+//! Deployment event handlers enforcing environment freezes.
+//!
+//! A freeze created with `/freeze --environment <name>` does not touch pull
+//! requests. Instead, every deployment to that environment is marked as failed
+//! while the freeze is in effect: new deployments when they are created, and
+//! deployments started before the freeze as soon as they report progress.
+
+use std::sync::Arc;
+
+use octofer::octocrab::models::webhook_events::WebhookEventPayload;
+use tracing::{error, info, warn};
+
+use crate::{AppState, freezer, installation::InstallationId, repository::Repository};
+
+use super::require_client;
+
+/// Deployment referenced by a deployment event
+#[derive(Debug, PartialEq)]
+struct DeploymentTarget {
+    id: u64,
+    environment: String,
+}
+
+/// Extracts the deployment id and environment from a deployment object
+fn deployment_target(deployment: &serde_json::Value) -> Option<DeploymentTarget> {
+    Some(DeploymentTarget {
+        id: deployment["id"].as_u64()?,
+        environment: deployment["environment"].as_str()?.to_string(),
+    })
+}
+
+/// Returns `true` for deployment states of a deployment that is still going ahead
+fn is_pending_state(state: &str) -> bool {
+    matches!(state, "queued" | "pending" | "in_progress")
+}
+
+pub async fn deployment_handler(
+    context: octofer::Context,
+    extra: Arc<AppState>,
+) -> anyhow::Result<()> {
+    info!("Deployment event received!");
+
+    let Some(e) = context.event else {
+        warn!("Deployment event has no payload... skipping");
+        return Ok(());
+    };
+    let WebhookEventPayload::Deployment(payload) = &e.specific else {
+        return Err(anyhow::anyhow!("Expected a deployment payload"));
+    };
+    let Some(target) = deployment_target(&payload.deployment) else {
+        warn!("Deployment event without id or environment... skipping");
+        return Ok(());
+    };
+
+    let repository = e
+        .repository
+        .ok_or(anyhow::anyhow!("Cannot get repository from event"))?;
+    enforce_environment_freeze(
+        context.github_client,
+        context.installation_id,
+        extra,
+        repository.into(),
+        target,
+    )
+    .await
+}
+
+pub async fn deployment_status_handler(
+    context: octofer::Context,
+    extra: Arc<AppState>,
+) -> anyhow::Result<()> {
+    info!("Deployment status event received!");
+
+    let Some(e) = context.event else {
+        warn!("Deployment status event has no payload... skipping");
+        return Ok(());
+    };
+    let WebhookEventPayload::DeploymentStatus(payload) = &e.specific else {
+        return Err(anyhow::anyhow!("Expected a deployment status payload"));
+    };
+
+    // Finished deployments, including the ones failed by us, need no action
+    let state = payload.deployment_status["state"].as_str().unwrap_or_default();
+    if !is_pending_state(state) {
+        return Ok(());
+    }
+    let Some(target) = deployment_target(&payload.deployment) else {
+        warn!("Deployment status event without id or environment... skipping");
+        return Ok(());
+    };
+
+    let repository = e
+        .repository
+        .ok_or(anyhow::anyhow!("Cannot get repository from event"))?;
+    enforce_environment_freeze(
+        context.github_client,
+        context.installation_id,
+        extra,
+        repository.into(),
+        target,
+    )
+    .await
+}
+
+/// Fails the deployment if its environment is frozen
+async fn enforce_environment_freeze(
+    client: Option<Arc<octofer::github::GitHubClient>>,
+    installation_id: Option<u64>,
+    extra: Arc<AppState>,
+    repository: Repository,
+    target: DeploymentTarget,
+) -> anyhow::Result<()> {
+    let client = require_client(client)?;
+    let installation_id = InstallationId::try_from(
+        installation_id.ok_or(anyhow::anyhow!("Cannot get installation_id"))?,
+    )?;
+
+    let mng = freezer::manager::FreezeManager::with_config(
+        extra.database.clone(),
+        client,
+        extra.config.clone(),
+    )
+    .with_cache(extra.freeze_cache.clone());
+
+    let Some(freeze) = mng
+        .find_environment_freeze(installation_id, &repository, &target.environment)
+        .await?
+    else {
+        return Ok(());
+    };
+
+    info!(
+        "Environment {} of {} is frozen, failing deployment {}",
+        target.environment,
+        repository.full_name(),
+        target.id
+    );
+    if let Err(e) = mng
+        .fail_deployment(installation_id, &repository, target.id, &freeze)
+        .await
+    {
+        error!("Failed to block deployment {}: {}", target.id, e);
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_deployment_target() {
+        let deployment = json!({"id": 42, "environment": "production", "ref": "main"});
+        assert_eq!(
+            deployment_target(&deployment),
+            Some(DeploymentTarget {
+                id: 42,
+                environment: "production".to_string()
+            })
+        );
+        assert_eq!(deployment_target(&json!({"id": 42})), None);
+        assert_eq!(deployment_target(&json!({"environment": "production"})), None);
+    }
+
+    #[test]
+    fn test_is_pending_state() {
+        assert!(is_pending_state("queued"));
+        assert!(is_pending_state("pending"));
+        assert!(is_pending_state("in_progress"));
+        assert!(!is_pending_state("success"));
+        assert!(!is_pending_state("failure"));
+        assert!(!is_pending_state("error"));
+        assert!(!is_pending_state(""));
+    }
+}
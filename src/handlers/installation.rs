@@ -0,0 +1,283 @@
+//! Installation handlers backfilling freeze checks for existing pull requests.
+//!
+//! Pull requests opened before Frezze was installed on a repository have no
+//! Frezze check until something happens on them. When the app is installed,
+//! or repositories are added to an installation, the open PRs of each new
+//! repository get their check right away, reflecting any freeze in effect.
+//!
+//! When the app is uninstalled, the freezes of the installation are ended and,
+//! with `PRUNE_DELETED_INSTALLATIONS`, every row stored for it is deleted.
+
+use std::sync::Arc;
+
+use octofer::octocrab::models::webhook_events::{
+    InstallationEventRepository, WebhookEventPayload,
+    payload::{InstallationRepositoriesWebhookEventAction, InstallationWebhookEventAction},
+};
+use tracing::{error, info, warn};
+
+use crate::{
+    AppState, database::installation as installation_db, freezer, installation::InstallationId,
+    repository::Repository,
+};
+
+use super::require_client;
+
+/// Parses the repositories listed by an installation event
+fn event_repositories(repositories: &[InstallationEventRepository]) -> Vec<Repository> {
+    repositories
+        .iter()
+        .filter_map(|repo| {
+            let repository = Repository::parse(&repo.full_name);
+            if repository.is_none() {
+                warn!("Ignoring repository with invalid name: {}", repo.full_name);
+            }
+            repository
+        })
+        .collect()
+}
+
+/// Repositories to backfill after an `installation` event.
+///
+/// Only a new installation has PRs without a check; the repositories it was
+/// granted access to are listed in the event.
+fn installation_backfill(
+    action: &InstallationWebhookEventAction,
+    repositories: Option<&[InstallationEventRepository]>,
+) -> Vec<Repository> {
+    match (action, repositories) {
+        (InstallationWebhookEventAction::Created, Some(repositories)) => {
+            event_repositories(repositories)
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Repositories to backfill after an `installation_repositories` event
+fn repositories_backfill(
+    action: &InstallationRepositoriesWebhookEventAction,
+    added: &[InstallationEventRepository],
+) -> Vec<Repository> {
+    match action {
+        InstallationRepositoriesWebhookEventAction::Added => event_repositories(added),
+        _ => Vec::new(),
+    }
+}
+
+/// Returns `true` for installation actions removing the app
+fn is_uninstall(action: &InstallationWebhookEventAction) -> bool {
+    matches!(action, InstallationWebhookEventAction::Deleted)
+}
+
+/// Ends the freezes of a deleted installation and drops what is cached for it.
+///
+/// The installation's token is revoked, so there is no client to update PR
+/// checks with; only local state is cleaned up.
+async fn cleanup(
+    extra: &AppState,
+    installation_id: InstallationId,
+    deleted_by: Option<&str>,
+) -> anyhow::Result<()> {
+    let conn = extra
+        .database
+        .get_connection()
+        .map_err(|e| anyhow::anyhow!("Failed to get database connection: {}", e))?;
+
+    let stopped = installation_db::end_installation_freezes(
+        conn,
+        installation_id,
+        deleted_by,
+        chrono::Utc::now(),
+    )
+    .await?;
+    info!(
+        "Installation {} was deleted: ended {} freezes and cancelled {} scheduled freezes",
+        installation_id, stopped.ended, stopped.cancelled
+    );
+
+    if extra.config.prune_deleted_installations {
+        match installation_db::prune_installation(conn, installation_id).await {
+            Ok(deleted) => info!(
+                "Pruned {} rows of deleted installation {}",
+                deleted, installation_id
+            ),
+            Err(e) => error!(
+                "Failed to prune rows of deleted installation {}: {}",
+                installation_id, e
+            ),
+        }
+    }
+
+    extra.freeze_cache.invalidate_installation(installation_id);
+
+    Ok(())
+}
+
+/// Creates the freeze check of every open PR of the given repositories
+async fn backfill(
+    context: octofer::Context,
+    extra: Arc<AppState>,
+    repositories: Vec<Repository>,
+) -> anyhow::Result<()> {
+    if repositories.is_empty() {
+        return Ok(());
+    }
+
+    let client = require_client(context.github_client)?;
+    let installation_id = InstallationId::try_from(
+        context
+            .installation_id
+            .ok_or(anyhow::anyhow!("Cannot get installation_id"))?,
+    )?;
+
+    let mng = freezer::manager::FreezeManager::with_config(
+        extra.database.clone(),
+        client,
+        extra.config.clone(),
+    )
+    .with_cache(extra.freeze_cache.clone());
+
+    for repository in &repositories {
+        info!(
+            "Backfilling freeze checks of {} for installation {}",
+            repository.full_name(),
+            installation_id
+        );
+        // Refreshes against the freeze in effect, if any, and logs failures
+        mng.refresh_prs(installation_id, repository, None).await;
+    }
+
+    Ok(())
+}
+
+pub async fn installation_handler(
+    context: octofer::Context,
+    extra: Arc<AppState>,
+) -> anyhow::Result<()> {
+    info!("Installation event received!");
+
+    let Some(ref e) = context.event else {
+        warn!("Installation event has no payload... skipping");
+        return Ok(());
+    };
+    let WebhookEventPayload::Installation(payload) = &e.specific else {
+        return Err(anyhow::anyhow!("Expected an installation payload"));
+    };
+    if is_uninstall(&payload.action) {
+        let installation_id = InstallationId::try_from(
+            context
+                .installation_id
+                .ok_or(anyhow::anyhow!("Cannot get installation_id"))?,
+        )?;
+        let deleted_by = e.sender.as_ref().map(|s| s.login.as_str());
+        return cleanup(&extra, installation_id, deleted_by).await;
+    }
+    let repositories = installation_backfill(&payload.action, payload.repositories.as_deref());
+
+    backfill(context, extra, repositories).await
+}
+
+pub async fn installation_repositories_handler(
+    context: octofer::Context,
+    extra: Arc<AppState>,
+) -> anyhow::Result<()> {
+    info!("Installation repositories event received!");
+
+    let Some(ref e) = context.event else {
+        warn!("Installation repositories event has no payload... skipping");
+        return Ok(());
+    };
+    let WebhookEventPayload::InstallationRepositories(payload) = &e.specific else {
+        return Err(anyhow::anyhow!(
+            "Expected an installation repositories payload"
+        ));
+    };
+    let repositories = repositories_backfill(&payload.action, &payload.repositories_added);
+
+    backfill(context, extra, repositories).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use octofer::octocrab::models::webhook_events::payload::{
+        InstallationRepositoriesWebhookEventPayload, InstallationWebhookEventPayload,
+    };
+
+    fn repositories_json() -> serde_json::Value {
+        serde_json::json!([
+            {"id": 1, "node_id": "R_1", "name": "api", "full_name": "owner/api", "private": false},
+            {"id": 2, "node_id": "R_2", "name": "web", "full_name": "owner/web", "private": true}
+        ])
+    }
+
+    #[test]
+    fn test_installation_backfill() {
+        let payload: InstallationWebhookEventPayload = serde_json::from_value(serde_json::json!({
+            "action": "created",
+            "repositories": repositories_json(),
+        }))
+        .unwrap();
+
+        let repositories = installation_backfill(&payload.action, payload.repositories.as_deref());
+        let names: Vec<String> = repositories.iter().map(|r| r.full_name()).collect();
+        assert_eq!(names, vec!["owner/api", "owner/web"]);
+
+        // Other installation actions have nothing to backfill
+        for action in [
+            InstallationWebhookEventAction::Deleted,
+            InstallationWebhookEventAction::Suspend,
+            InstallationWebhookEventAction::NewPermissionsAccepted,
+        ] {
+            assert!(installation_backfill(&action, payload.repositories.as_deref()).is_empty());
+        }
+        assert!(installation_backfill(&InstallationWebhookEventAction::Created, None).is_empty());
+    }
+
+    #[test]
+    fn test_is_uninstall() {
+        assert!(is_uninstall(&InstallationWebhookEventAction::Deleted));
+        assert!(!is_uninstall(&InstallationWebhookEventAction::Created));
+        assert!(!is_uninstall(&InstallationWebhookEventAction::Suspend));
+    }
+
+    #[test]
+    fn test_repositories_backfill() {
+        let payload: InstallationRepositoriesWebhookEventPayload =
+            serde_json::from_value(serde_json::json!({
+                "action": "added",
+                "repositories_added": repositories_json(),
+                "repositories_removed": [],
+                "repository_selection": "selected",
+            }))
+            .unwrap();
+
+        let repositories = repositories_backfill(&payload.action, &payload.repositories_added);
+        assert_eq!(repositories.len(), 2);
+        assert_eq!(repositories[1].full_name(), "owner/web");
+
+        assert!(
+            repositories_backfill(
+                &InstallationRepositoriesWebhookEventAction::Removed,
+                &payload.repositories_added,
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_event_repositories_skips_invalid_names() {
+        let repositories: Vec<InstallationEventRepository> =
+            serde_json::from_value(serde_json::json!([
+                {"id": 1, "node_id": "R_1", "name": "api", "full_name": "api", "private": false},
+                {"id": 2, "node_id": "R_2", "name": "web", "full_name": "owner/web", "private": false}
+            ]))
+            .unwrap();
+
+        let names: Vec<String> = event_repositories(&repositories)
+            .iter()
+            .map(|r| r.full_name())
+            .collect();
+        assert_eq!(names, vec!["owner/web"]);
+    }
+}
@@ -0,0 +1,227 @@
+//! Issue label handler freezing repositories without typing commands.
+//!
+//! When `FREEZE_LABEL` is configured, adding that label to an issue freezes the
+//! repository for the default duration with the issue title as the reason, and
+//! removing it lifts the freeze again. The user applying the label needs the
+//! same permissions as for `/freeze` and `/unfreeze`.
+
+use std::sync::Arc;
+
+use octofer::octocrab::models::webhook_events::{
+    WebhookEventPayload, payload::IssuesWebhookEventAction,
+};
+use tracing::{info, warn};
+
+use crate::{
+    AppState,
+    database::models::CommandResult,
+    freezer::{self, commands, manager::FreezeOptions},
+    installation::InstallationId,
+    repository::Repository,
+};
+
+use super::{check_command_allowed, freeze_branch, require_client};
+
+/// Freeze change requested by labeling an issue
+#[derive(Debug, PartialEq)]
+enum LabelAction {
+    /// The freeze label was added
+    Freeze,
+    /// The freeze label was removed
+    Unfreeze,
+}
+
+impl LabelAction {
+    /// Command whose permissions the user applying the label needs
+    fn command_line(&self) -> &'static str {
+        match self {
+            LabelAction::Freeze => "/freeze",
+            LabelAction::Unfreeze => "/unfreeze",
+        }
+    }
+}
+
+/// Maps an issue label event onto a freeze change, if it concerns the freeze label
+fn label_action(
+    action: &IssuesWebhookEventAction,
+    label: Option<&str>,
+    freeze_label: &str,
+) -> Option<LabelAction> {
+    // GitHub label names are case-insensitive
+    if !label.is_some_and(|l| l.trim().eq_ignore_ascii_case(freeze_label.trim())) {
+        return None;
+    }
+    match action {
+        IssuesWebhookEventAction::Labeled => Some(LabelAction::Freeze),
+        IssuesWebhookEventAction::Unlabeled => Some(LabelAction::Unfreeze),
+        _ => None,
+    }
+}
+
+pub async fn issues_handler(context: octofer::Context, extra: Arc<AppState>) -> anyhow::Result<()> {
+    info!("Issues event received!");
+
+    let Some(freeze_label) = extra.config.freeze_label.as_deref() else {
+        return Ok(());
+    };
+    let Some(e) = context.event else {
+        warn!("Issues event has no payload... skipping");
+        return Ok(());
+    };
+    let WebhookEventPayload::Issues(payload) = &e.specific else {
+        return Err(anyhow::anyhow!("Expected an issues payload"));
+    };
+    let label = payload.label.as_ref().map(|l| l.name.as_str());
+    let Some(action) = label_action(&payload.action, label, freeze_label) else {
+        return Ok(());
+    };
+
+    let client = require_client(context.github_client)?;
+    let installation_id = InstallationId::try_from(
+        context
+            .installation_id
+            .ok_or(anyhow::anyhow!("Cannot get installation_id"))?,
+    )?;
+    let author = e
+        .sender
+        .as_ref()
+        .map(|s| s.login.clone())
+        .ok_or(anyhow::anyhow!("Cannot get sender from event"))?;
+    let repo = e
+        .repository
+        .ok_or(anyhow::anyhow!("Cannot get repository from event"))?;
+    let repository: Repository = repo.clone().into();
+    let issue_nr = payload.issue.number;
+
+    let mng = freezer::manager::FreezeManager::with_config(
+        extra.database.clone(),
+        client,
+        extra.config.clone(),
+    )
+    .with_cache(extra.freeze_cache.clone());
+
+    // Labels need the same permissions as the equivalent command
+    let command_line = action.command_line();
+    let command = commands::parse(command_line)?.command;
+    let allowed =
+        check_command_allowed(&extra, installation_id, &repository, &author, &command).await;
+    let result = match allowed {
+        Ok(()) => CommandResult::Success,
+        Err(_) => CommandResult::Denied,
+    };
+    // Label events carry no comment to deduplicate by
+    mng.record_command(
+        installation_id,
+        &repository,
+        author.clone(),
+        command_line.to_string(),
+        0,
+        result,
+    )
+    .await;
+    if let Err(rejection) = allowed {
+        let error_msg = rejection.message(&author);
+        mng.notify_comment_issue(installation_id, &repository, issue_nr, &error_msg)
+            .await;
+        return Ok(());
+    }
+
+    let default_branch_only = extra
+        .user_config
+        .as_ref()
+        .is_some_and(|c| c.is_default_branch_only(installation_id.get(), &repository.full_name()));
+    let branch = freeze_branch(None, default_branch_only, &repo);
+
+    info!(
+        "Label '{}' {:?} on {}#{} by {}",
+        freeze_label,
+        action,
+        repository.full_name(),
+        issue_nr,
+        author
+    );
+    match action {
+        LabelAction::Freeze => {
+            // A label that is re-applied should not stack freezes
+            let options = FreezeOptions {
+                branch,
+                if_not_frozen: true,
+                ..FreezeOptions::default()
+            };
+            mng.freeze(
+                installation_id,
+                &repository,
+                Some(payload.issue.title.clone()),
+                author,
+                issue_nr,
+                options,
+            )
+            .await;
+        }
+        LabelAction::Unfreeze => {
+            mng.unfreeze(
+                installation_id,
+                &repository,
+                author,
+                None,
+                issue_nr,
+                branch,
+                None,
+            )
+            .await;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_label_action() {
+        assert_eq!(
+            label_action(&IssuesWebhookEventAction::Labeled, Some("freeze"), "freeze"),
+            Some(LabelAction::Freeze)
+        );
+        assert_eq!(
+            label_action(&IssuesWebhookEventAction::Unlabeled, Some("freeze"), "freeze"),
+            Some(LabelAction::Unfreeze)
+        );
+        // Label names are matched case-insensitively
+        assert_eq!(
+            label_action(&IssuesWebhookEventAction::Labeled, Some("Code Freeze"), "code freeze"),
+            Some(LabelAction::Freeze)
+        );
+
+        // Other labels and other actions are ignored
+        assert_eq!(
+            label_action(&IssuesWebhookEventAction::Labeled, Some("bug"), "freeze"),
+            None
+        );
+        assert_eq!(
+            label_action(&IssuesWebhookEventAction::Labeled, None, "freeze"),
+            None
+        );
+        assert_eq!(
+            label_action(&IssuesWebhookEventAction::Opened, Some("freeze"), "freeze"),
+            None
+        );
+        assert_eq!(
+            label_action(&IssuesWebhookEventAction::Closed, Some("freeze"), "freeze"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_label_action_command() {
+        for action in [LabelAction::Freeze, LabelAction::Unfreeze] {
+            let command = commands::parse(action.command_line()).unwrap().command;
+            match action {
+                LabelAction::Freeze => assert_eq!(command.name(), "freeze"),
+                LabelAction::Unfreeze => assert_eq!(command.name(), "unfreeze"),
+            }
+        }
+    }
+}
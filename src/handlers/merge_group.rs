@@ -0,0 +1,206 @@
+//! Merge group handler publishing freeze checks for merge queue entries.
+//!
+//! Repositories using a merge queue only merge a queued PR once the required
+//! checks pass on a temporary commit combining it with its base branch. That
+//! commit needs the Frezze check as well, otherwise queued PRs would merge
+//! during a freeze.
+
+use std::sync::Arc;
+
+use octofer::octocrab::models::webhook_events::{
+    WebhookEventPayload, payload::MergeGroupWebhookEventAction,
+};
+use tracing::{error, info, warn};
+
+use crate::{AppState, freezer, installation::InstallationId, repository::Repository};
+
+use super::require_client;
+
+/// Prefix of the temporary branches GitHub creates for merge queue entries
+const MERGE_QUEUE_BRANCH_PREFIX: &str = "refs/heads/gh-readonly-queue/";
+
+/// Merge queue entry that needs a freeze check
+#[derive(Debug, PartialEq)]
+struct MergeGroupTarget {
+    /// Commit the queue runs its checks on
+    head_sha: String,
+    /// Branch the entry will be merged into
+    base_ref: String,
+    /// PR the entry was created for, if it can be told from the branch name
+    pr_number: Option<u64>,
+}
+
+/// Extracts the PR number from a merge queue branch.
+///
+/// Queue branches are named `gh-readonly-queue/<base>/pr-<number>-<sha>`,
+/// where the base branch may itself contain slashes.
+fn queued_pr_number(head_ref: &str) -> Option<u64> {
+    let entry = head_ref
+        .strip_prefix(MERGE_QUEUE_BRANCH_PREFIX)?
+        .rsplit('/')
+        .next()?;
+    entry.strip_prefix("pr-")?.split('-').next()?.parse().ok()
+}
+
+/// Maps a merge group event onto the commit to publish the freeze check on
+fn merge_group_target(
+    action: &MergeGroupWebhookEventAction,
+    merge_group: &serde_json::Value,
+) -> Option<MergeGroupTarget> {
+    if !matches!(action, MergeGroupWebhookEventAction::ChecksRequested) {
+        return None;
+    }
+    let head_sha = merge_group["head_sha"].as_str()?;
+    let base_ref = merge_group["base_ref"].as_str()?;
+
+    Some(MergeGroupTarget {
+        head_sha: head_sha.to_string(),
+        base_ref: base_ref
+            .strip_prefix("refs/heads/")
+            .unwrap_or(base_ref)
+            .to_string(),
+        pr_number: merge_group["head_ref"].as_str().and_then(queued_pr_number),
+    })
+}
+
+pub async fn merge_group_handler(
+    context: octofer::Context,
+    extra: Arc<AppState>,
+) -> anyhow::Result<()> {
+    info!("Merge group event received!");
+
+    let Some(e) = context.event else {
+        warn!("Merge group event has no payload... skipping");
+        return Ok(());
+    };
+    let WebhookEventPayload::MergeGroup(payload) = &e.specific else {
+        return Err(anyhow::anyhow!("Expected a merge group payload"));
+    };
+    let Some(target) = merge_group_target(&payload.action, &payload.merge_group) else {
+        return Ok(());
+    };
+
+    let client = require_client(context.github_client)?;
+    let installation_id = InstallationId::try_from(
+        context
+            .installation_id
+            .ok_or(anyhow::anyhow!("Cannot get installation_id"))?,
+    )?;
+    let repository: Repository = e
+        .repository
+        .ok_or(anyhow::anyhow!("Cannot get repository from event"))?
+        .into();
+
+    let mng = freezer::manager::FreezeManager::with_config(
+        extra.database.clone(),
+        client,
+        extra.config.clone(),
+    )
+    .with_cache(extra.freeze_cache.clone());
+
+    match mng
+        .pr_refresh
+        .refresh_merge_group(
+            installation_id,
+            &repository,
+            &target.head_sha,
+            &target.base_ref,
+            target.pr_number,
+        )
+        .await
+    {
+        Ok(conclusion) => info!(
+            "Published freeze check ({:?}) for merge group {} into {} of {}",
+            conclusion,
+            target.head_sha,
+            target.base_ref,
+            repository.full_name()
+        ),
+        Err(e) => {
+            error!(
+                "Failed to publish freeze check for merge group {} of {}: {}",
+                target.head_sha,
+                repository.full_name(),
+                e
+            );
+            return Err(e);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queued_pr_number() {
+        assert_eq!(
+            queued_pr_number(
+                "refs/heads/gh-readonly-queue/main/pr-42-ec26c3e57ca3a959ca5aad62de7213c562f8c821"
+            ),
+            Some(42)
+        );
+        // Base branches may contain slashes
+        assert_eq!(
+            queued_pr_number("refs/heads/gh-readonly-queue/release/1.2/pr-7-abc123"),
+            Some(7)
+        );
+        assert_eq!(queued_pr_number("refs/heads/main"), None);
+        assert_eq!(
+            queued_pr_number("refs/heads/gh-readonly-queue/main/other"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_merge_group_target() {
+        let merge_group = serde_json::json!({
+            "head_sha": "ec26c3e57ca3a959ca5aad62de7213c562f8c821",
+            "head_ref": "refs/heads/gh-readonly-queue/main/pr-42-ec26c3e57ca3a959ca5aad62de7213c562f8c821",
+            "base_sha": "380387e1c6b2e6fe7de9d1d8e2d8d0b1b3f1a2c4",
+            "base_ref": "refs/heads/main"
+        });
+
+        assert_eq!(
+            merge_group_target(&MergeGroupWebhookEventAction::ChecksRequested, &merge_group),
+            Some(MergeGroupTarget {
+                head_sha: "ec26c3e57ca3a959ca5aad62de7213c562f8c821".to_string(),
+                base_ref: "main".to_string(),
+                pr_number: Some(42),
+            })
+        );
+    }
+
+    #[test]
+    fn test_merge_group_target_ignored() {
+        let merge_group = serde_json::json!({
+            "head_sha": "abc123",
+            "head_ref": "refs/heads/gh-readonly-queue/main/pr-42-abc123",
+            "base_ref": "refs/heads/main"
+        });
+
+        // Destroyed groups have nothing left to check
+        assert_eq!(
+            merge_group_target(&MergeGroupWebhookEventAction::Destroyed, &merge_group),
+            None
+        );
+
+        // Groups without a commit cannot be checked
+        let incomplete = serde_json::json!({"base_ref": "refs/heads/main"});
+        assert_eq!(
+            merge_group_target(&MergeGroupWebhookEventAction::ChecksRequested, &incomplete),
+            None
+        );
+
+        // Unknown queue branch names still get a check, without a PR to unlock
+        let unnamed = serde_json::json!({"head_sha": "abc123", "base_ref": "refs/heads/main"});
+        assert_eq!(
+            merge_group_target(&MergeGroupWebhookEventAction::ChecksRequested, &unnamed)
+                .unwrap()
+                .pr_number,
+            None
+        );
+    }
+}
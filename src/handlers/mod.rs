@@ -1,14 +1,219 @@
 use std::sync::Arc;
 
-use octofer::octocrab::models::webhook_events::WebhookEventPayload;
-use tracing::{error, info};
+use octofer::github::GitHubClient;
+use octofer::octocrab::models::webhook_events::{
+    WebhookEventPayload,
+    payload::{IssueCommentWebhookEventAction, IssueCommentWebhookEventPayload},
+};
+use tracing::{error, info, warn};
+
+pub mod check_suite;
+pub mod deployment;
+pub mod installation;
+pub mod issues;
+pub mod merge_group;
+pub mod pull_request;
+pub mod pull_request_review;
+pub mod pull_request_review_comment;
+pub mod release;
+pub mod repository_dispatch;
 
 use crate::{
     AppState,
-    freezer::{self, commands, errors::ParsingError, messages},
+    database::models::{CommandResult, FreezeCategory},
+    freezer::{self, commands, errors::ParsingError, manager::FreezeOptions, messages},
+    installation::InstallationId,
     permissions::{PermissionResult, PermissionService},
+    repository::Repository,
 };
 
+/// Kind of comment event that may carry a command
+#[derive(Debug, PartialEq)]
+enum CommentEvent {
+    /// A newly posted comment
+    Created,
+    /// An edited comment, which may already have run its command
+    Edited,
+}
+
+/// Determines whether a comment action should be parsed for commands
+fn comment_event(action: &IssueCommentWebhookEventAction) -> Option<CommentEvent> {
+    match action {
+        IssueCommentWebhookEventAction::Created => Some(CommentEvent::Created),
+        IssueCommentWebhookEventAction::Edited => Some(CommentEvent::Edited),
+        _ => None,
+    }
+}
+
+/// Where a comment command was posted
+#[derive(Debug, PartialEq)]
+enum CommandOrigin {
+    /// A comment on a plain issue
+    Issue,
+    /// A comment on a pull request
+    PullRequest,
+}
+
+impl CommandOrigin {
+    fn of(issue: &octofer::octocrab::models::issues::Issue) -> Self {
+        if issue.pull_request.is_some() {
+            CommandOrigin::PullRequest
+        } else {
+            CommandOrigin::Issue
+        }
+    }
+}
+
+/// Command comment, posted on an issue, a pull request or a review thread
+struct CommentCommand {
+    event: CommentEvent,
+    author: String,
+    /// Issue or pull request the comment belongs to, where replies are posted
+    issue_nr: u64,
+    origin: CommandOrigin,
+    comment_id: u64,
+    body: String,
+    repo: octofer::octocrab::models::Repository,
+}
+
+/// Picks the pull request to unlock after a `/freeze`, if any.
+///
+/// Installations enabling `auto_unlock_initiating_pr` unlock the pull request
+/// the freeze was requested from, so the change the freeze is made for can
+/// still land. Freezes requested from issues unlock nothing.
+fn auto_unlock_target(enabled: bool, origin: &CommandOrigin, issue_nr: u64) -> Option<u64> {
+    (enabled && *origin == CommandOrigin::PullRequest).then_some(issue_nr)
+}
+
+/// Picks the pull request a PR-only command such as `/unlock-pr` acts on.
+///
+/// An explicit pull request number always wins. Otherwise the command acts on
+/// the pull request it was commented on; commented on a plain issue, there is
+/// no pull request to act on.
+fn pull_request_target(
+    explicit: Option<u64>,
+    origin: &CommandOrigin,
+    issue_nr: u64,
+) -> Option<u64> {
+    explicit.or((*origin == CommandOrigin::PullRequest).then_some(issue_nr))
+}
+
+/// Returns the GitHub client of an event, failing cleanly when it is missing
+fn require_client<T>(client: Option<T>) -> anyhow::Result<T> {
+    client.ok_or_else(|| anyhow::anyhow!("No GitHub client available for this event"))
+}
+
+/// Extracts the issue comment payload, rejecting any other kind of event
+fn issue_comment_payload(
+    payload: &WebhookEventPayload,
+) -> anyhow::Result<&IssueCommentWebhookEventPayload> {
+    match payload {
+        WebhookEventPayload::IssueComment(comment) => Ok(comment),
+        _ => Err(anyhow::anyhow!("Expected an issue comment payload")),
+    }
+}
+
+/// Resolves the branch a single-repository `/freeze` applies to.
+///
+/// An explicit `--branch` always wins. Otherwise repositories with
+/// `default_branch_only` freeze just their default branch, falling back to
+/// all branches when it is unknown.
+fn freeze_branch(
+    branch: Option<String>,
+    default_branch_only: bool,
+    repository: &octofer::octocrab::models::Repository,
+) -> Option<String> {
+    if branch.is_some() || !default_branch_only {
+        return branch;
+    }
+    repository.default_branch.clone()
+}
+
+/// Rejects `--repo` lists naming more repositories than the installation allows
+fn repo_limit_problem(repos: usize, max_repos: usize) -> Option<String> {
+    (repos > max_repos).then(|| {
+        format!(
+            "Too many repositories: {} given, at most {} per command. \
+             Use `/freeze-all` to freeze every repository of the installation",
+            repos, max_repos
+        )
+    })
+}
+
+/// Why a user may not run a command
+#[derive(Debug, PartialEq)]
+enum CommandRejection {
+    /// The installation has not enabled the command
+    Disabled(&'static str),
+    /// The user lacks the permissions for the command
+    Denied(String),
+    /// The permissions of the user could not be checked
+    CheckFailed(String),
+}
+
+impl CommandRejection {
+    /// Reply explaining the rejection to the user
+    fn message(&self, author: &str) -> String {
+        match self {
+            CommandRejection::Disabled(name) => messages::command_disabled(name),
+            CommandRejection::Denied(reason) => messages::permission_denied(author, reason),
+            CommandRejection::CheckFailed(e) => messages::permission_check_failed(author, e),
+        }
+    }
+}
+
+impl std::fmt::Display for CommandRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CommandRejection::Disabled(name) => write!(f, "The `{}` command is disabled", name),
+            CommandRejection::Denied(reason) => write!(f, "{}", reason),
+            CommandRejection::CheckFailed(e) => write!(f, "Failed to check permissions: {}", e),
+        }
+    }
+}
+
+/// Checks that the installation enabled a command and that the user may run it.
+///
+/// Without a permission configuration, only the commands of `NO_CONFIG_MODE`
+/// are allowed.
+async fn check_command_allowed(
+    extra: &AppState,
+    installation_id: InstallationId,
+    repo: &Repository,
+    author: &str,
+    command: &commands::Command,
+) -> Result<(), CommandRejection> {
+    let Some(ref user_config) = extra.user_config else {
+        if extra.config.no_config_mode.allows(command) {
+            return Ok(());
+        }
+        return Err(CommandRejection::Denied(
+            "No permission configuration file loaded. Contact your administrator.".to_string(),
+        ));
+    };
+
+    if !user_config.is_command_enabled(installation_id.get(), command.name()) {
+        info!(
+            "Command {} is disabled for installation {}",
+            command.name(),
+            installation_id
+        );
+        return Err(CommandRejection::Disabled(command.name()));
+    }
+
+    match PermissionService::new(user_config.clone())
+        .check_permission(installation_id.get(), &repo.full_name(), author, command)
+        .await
+    {
+        Ok(PermissionResult::Allowed) => Ok(()),
+        Ok(PermissionResult::Denied(reason)) => Err(CommandRejection::Denied(reason)),
+        Err(e) => {
+            error!("Error checking permissions for user {}: {}", author, e);
+            Err(CommandRejection::CheckFailed(e.to_string()))
+        }
+    }
+}
+
 pub async fn issue_comment_handler(
     context: octofer::Context,
     extra: Arc<AppState>,
@@ -17,240 +222,858 @@ pub async fn issue_comment_handler(
     info!("Event type: {}", context.kind());
     info!("Installation ID: {:?}", context.installation_id());
 
-    let client = match context.github_client {
-        Some(c) => c,
-        None => panic!(),
+    let client = require_client(context.github_client)?;
+    let installation_id = InstallationId::try_from(
+        context
+            .installation_id
+            .ok_or(anyhow::anyhow!("Cannot get installation_id"))?,
+    )?;
+
+    let Some(e) = context.event else {
+        // Nothing to act on, but a malformed delivery must not take the handler down
+        warn!(
+            "Issue comment event for installation {} has no payload... skipping",
+            installation_id
+        );
+        return Ok(());
     };
-    let installation_id = context
-        .installation_id
-        .ok_or(anyhow::anyhow!("Cannot get installation_id"))?;
-
-    let mng = freezer::manager::FreezeManager::new(extra.database.clone(), client);
-
-    if let Some(e) = context.event {
-        let WebhookEventPayload::IssueComment(comment) = &e.specific else {
-            panic!();
-        };
-
-        let author = comment.comment.user.login.clone();
-        let issue_nr = comment.issue.number;
-        let repo = e
-            .repository
-            .ok_or(anyhow::anyhow!("Cannot get repository from event"))?;
-
-        if let Some(body) = comment.comment.body.clone() {
-            // Parse just the first line
-            let parser = match commands::parse(body.lines().next().unwrap_or(&body)) {
-                Ok(p) => p,
-                Err(e) => {
-                    if let ParsingError::NotACommand = e {
-                        info!("Not a command... skipping");
-                        return Ok(());
-                    } else {
-                        error!("Error parsing command: {e}");
-                        return Err(e.into());
-                    }
-                }
-            };
+    let comment = issue_comment_payload(&e.specific)?;
 
-            // Check permissions before executing command
-            if let Some(ref user_config) = extra.user_config {
-                let permission_service = PermissionService::new(user_config.clone());
-                let repository: crate::repository::Repository = repo.clone().into();
-                let repo_name = repository.full_name();
+    let Some(comment_event) = comment_event(&comment.action) else {
+        info!("Ignoring {:?} comment event", comment.action);
+        return Ok(());
+    };
+    let Some(body) = comment.comment.body.clone() else {
+        return Ok(());
+    };
+    let repo = e
+        .repository
+        .ok_or(anyhow::anyhow!("Cannot get repository from event"))?;
 
-                match permission_service
-                    .check_permission(installation_id as i64, &repo_name, &author, &parser.command)
-                    .await
-                {
-                    Ok(PermissionResult::Allowed) => {
-                        // Permission granted, proceed with command execution
-                    }
-                    Ok(PermissionResult::Denied(reason)) => {
-                        let error_msg = messages::permission_denied(&author, &reason);
-                        mng.notify_comment_issue(
-                            installation_id,
-                            &repo.into(),
-                            issue_nr,
-                            &error_msg,
-                        )
-                        .await;
-                        return Ok(());
-                    }
-                    Err(e) => {
-                        error!("Error checking permissions for user {}: {}", author, e);
-                        let error_msg = messages::permission_check_failed(&author, &e.to_string());
-                        mng.notify_comment_issue(
-                            installation_id,
-                            &repo.into(),
-                            issue_nr,
-                            &error_msg,
-                        )
-                        .await;
-                        return Ok(());
-                    }
-                }
+    let command = CommentCommand {
+        event: comment_event,
+        author: comment.comment.user.login.clone(),
+        issue_nr: comment.issue.number,
+        origin: CommandOrigin::of(&comment.issue),
+        comment_id: comment.comment.id.into_inner(),
+        body,
+        repo,
+    };
+    run_comment_command(client, installation_id, &extra, command).await
+}
+
+/// Parses the command of a comment and runs it, replying on the comment's
+/// issue or pull request.
+async fn run_comment_command(
+    client: Arc<GitHubClient>,
+    installation_id: InstallationId,
+    extra: &AppState,
+    comment: CommentCommand,
+) -> anyhow::Result<()> {
+    let mng = freezer::manager::FreezeManager::with_config(
+        extra.database.clone(),
+        client,
+        extra.config.clone(),
+    )
+    .with_cache(extra.freeze_cache.clone());
+
+    let CommentCommand {
+        event: comment_event,
+        author,
+        issue_nr,
+        origin,
+        comment_id,
+        body,
+        repo,
+    } = comment;
+
+    // Parse just the first line
+    let command_line = body.lines().next().unwrap_or(&body).to_string();
+    let prefix = extra
+        .user_config
+        .as_ref()
+        .and_then(|c| c.command_prefix(installation_id.get()));
+    let parser = match commands::parse_with_prefix(&command_line, prefix) {
+        Ok(p) => p,
+        Err(e) => {
+            if let ParsingError::NotACommand = e {
+                info!("Not a command... skipping");
+                return Ok(());
+            } else if let ParsingError::HelpRequested(help) = e {
+                let help_msg = messages::command_help(&help);
+                mng.notify_comment_issue(installation_id, &repo.into(), issue_nr, &help_msg)
+                    .await;
+                return Ok(());
             } else {
-                // No user config provided - deny all commands except status
-                match parser.command {
-                    commands::Command::Status(_) => {
-                        // Status is always allowed when no config is provided
-                    }
-                    _ => {
-                        let error_msg = messages::permission_denied(
-                            &author,
-                            "No permission configuration file loaded. Contact your administrator.",
-                        );
-                        mng.notify_comment_issue(
-                            installation_id,
-                            &repo.into(),
-                            issue_nr,
-                            &error_msg,
-                        )
+                error!("Error parsing command: {e}");
+                return Err(e.into());
+            }
+        }
+    };
+
+    // An edit that leaves the command untouched must not run it again
+    if comment_event == CommentEvent::Edited
+        && mng
+            .is_command_processed(installation_id, comment_id, &command_line)
+            .await
+    {
+        info!(
+            "Command of edited comment {} already processed... skipping",
+            comment_id
+        );
+        return Ok(());
+    }
+
+    // Reject commands the installation has not enabled or the user may not run
+    if let Err(rejection) = check_command_allowed(
+        extra,
+        installation_id,
+        &(&repo).into(),
+        &author,
+        &parser.command,
+    )
+    .await
+    {
+        mng.record_command(
+            installation_id,
+            &repo.clone().into(),
+            author.clone(),
+            command_line,
+            comment_id,
+            CommandResult::Denied,
+        )
+        .await;
+        let error_msg = rejection.message(&author);
+        mng.notify_comment_issue(installation_id, &repo.into(), issue_nr, &error_msg)
+            .await;
+        return Ok(());
+    }
+
+    // Record the command before running it so later edits of this
+    // comment can be recognised as duplicates
+    mng.record_command(
+        installation_id,
+        &repo.clone().into(),
+        author.clone(),
+        command_line.clone(),
+        comment_id,
+        CommandResult::Pending,
+    )
+    .await;
+
+    let comment = CommentCommand {
+        event: comment_event,
+        author,
+        issue_nr,
+        origin,
+        comment_id,
+        body,
+        repo,
+    };
+    let (result, error_message) = execute_command(
+        &mng,
+        extra,
+        installation_id,
+        comment,
+        command_line.clone(),
+        parser.command,
+    )
+    .await;
+    mng.update_command_result(installation_id, comment_id, &command_line, result, error_message)
+        .await;
+
+    Ok(())
+}
+
+/// Runs a command that passed the permission checks, replying on the
+/// comment's issue or pull request.
+///
+/// Returns the outcome of the command for the audit log.
+async fn execute_command(
+    mng: &freezer::manager::FreezeManager,
+    extra: &AppState,
+    installation_id: InstallationId,
+    comment: CommentCommand,
+    command_line: String,
+    mut command: commands::Command,
+) -> (CommandResult, Option<String>) {
+    let CommentCommand {
+        author,
+        issue_nr,
+        origin,
+        repo,
+        ..
+    } = comment;
+    let auto_unlock = extra
+        .user_config
+        .as_ref()
+        .is_some_and(|c| c.auto_unlocks_initiating_pr(installation_id.get()));
+    let prefix = extra
+        .user_config
+        .as_ref()
+        .and_then(|c| c.command_prefix(installation_id.get()));
+
+    // Users with emergency override may bypass safety guards
+    let can_override = extra.user_config.as_ref().is_some_and(|c| {
+        let repository: Repository = repo.clone().into();
+        PermissionService::new(c.clone()).can_override(
+            installation_id.get(),
+            &repository.full_name(),
+            &author,
+        )
+    });
+
+    // Business-hour durations are resolved against the time the command runs
+    let now = chrono::Utc::now();
+    let resolve_duration = |duration: Option<commands::FreezeDuration>| {
+        duration.map(|d| d.resolve(now, &extra.config.business_hours))
+    };
+
+    // Long `--repo` lists would flood the GitHub API with a single command
+    let max_repos = extra
+        .user_config
+        .as_ref()
+        .map_or(crate::config::DEFAULT_MAX_REPOS_PER_COMMAND, |c| {
+            c.max_repos_per_command(installation_id.get())
+        });
+    if let commands::Command::Freeze(freeze_args) | commands::Command::FreezeAll(freeze_args) =
+        &command
+        && let Some(problem) = repo_limit_problem(freeze_args.repos.len(), max_repos)
+    {
+        let error_msg = messages::freeze_error(&problem);
+        mng.notify_comment_issue(installation_id, &repo.into(), issue_nr, &error_msg)
+            .await;
+        return (CommandResult::Failure, Some(problem));
+    }
+
+    // Installations may require `/freeze-all` to be confirmed with a token
+    if let commands::Command::FreezeAll(freeze_args) = &mut command
+        && freeze_args.environment.is_none()
+    {
+        let repository: Repository = repo.clone().into();
+        if let Some(token) = freeze_args.confirm.take() {
+            match mng
+                .confirm_freeze_all(installation_id, &repository, &author, &token, prefix)
+                .await
+            {
+                Ok(confirmed) => command = confirmed,
+                Err(e) => {
+                    let error_msg = messages::freeze_error(&e.to_string());
+                    mng.notify_comment_issue(installation_id, &repository, issue_nr, &error_msg)
                         .await;
-                        return Ok(());
-                    }
+                    return (CommandResult::Failure, Some(e.to_string()));
                 }
             }
+        } else if extra
+            .user_config
+            .as_ref()
+            .is_some_and(|c| c.requires_freeze_all_confirmation(installation_id.get()))
+        {
+            mng.request_freeze_all_confirmation(
+                installation_id,
+                &repository,
+                author,
+                issue_nr,
+                command_line,
+                &freeze_args.repos,
+                prefix,
+            )
+            .await;
+            return (CommandResult::Success, None);
+        }
+    }
 
-            match parser.command {
-                commands::Command::Freeze(freeze_args) => {
-                    mng.freeze(
-                        installation_id,
-                        &repo.into(),
-                        freeze_args.duration,
-                        freeze_args.reason,
-                        author,
-                        issue_nr,
-                        freeze_args.repos,
-                        freeze_args.branch,
-                    )
+    // Installations may require unfreezes to be approved by one of a set of users
+    if let commands::Command::Unfreeze(_) = &command {
+        let approvers = extra
+            .user_config
+            .as_ref()
+            .map(|c| c.unfreeze_approvers(installation_id.get()))
+            .unwrap_or_default();
+        if !approvers.is_empty() && !approvers.iter().any(|a| a.eq_ignore_ascii_case(&author)) {
+            mng.request_unfreeze_approval(
+                installation_id,
+                &repo.into(),
+                author,
+                issue_nr,
+                command_line,
+                approvers,
+                prefix,
+            )
+            .await;
+            return (CommandResult::Success, None);
+        }
+    }
+    if let commands::Command::ApproveUnfreeze = &command {
+        match mng
+            .approve_unfreeze(installation_id, &repo.clone().into(), &author, prefix)
+            .await
+        {
+            Ok(approved) => command = approved,
+            Err(e) => {
+                let error_msg = messages::unfreeze_error(&e.to_string());
+                mng.notify_comment_issue(installation_id, &repo.into(), issue_nr, &error_msg)
+                    .await;
+                return (CommandResult::Failure, Some(e.to_string()));
+            }
+        }
+    }
+
+    // Long reasons may be kept in a file of the repository
+    if let commands::Command::Freeze(freeze_args) | commands::Command::FreezeAll(freeze_args) =
+        &mut command
+        && let Some(path) = freeze_args.reason_file.take()
+    {
+        match mng
+            .read_reason_file(installation_id, &repo.clone().into(), &path)
+            .await
+        {
+            Ok(reason) => freeze_args.reason = Some(reason),
+            Err(e) => {
+                let error_msg = messages::freeze_error(&e.to_string());
+                mng.notify_comment_issue(installation_id, &repo.into(), issue_nr, &error_msg)
                     .await;
+                return (CommandResult::Failure, Some(e.to_string()));
+            }
+        }
+    }
+
+    // Installations may require a meaningful reason for freezes
+    if let commands::Command::Freeze(freeze_args) | commands::Command::FreezeAll(freeze_args) =
+        &command
+        && let Some(problem) = extra
+            .user_config
+            .as_ref()
+            .and_then(|c| c.reason_problem(installation_id.get(), freeze_args.reason.as_deref()))
+    {
+        let repository: Repository = repo.into();
+        let mut error_msg = messages::freeze_error(&problem);
+        // Point users without a reason at the ones used before
+        if freeze_args
+            .reason
+            .as_deref()
+            .is_none_or(|r| r.trim().is_empty())
+        {
+            let recent = mng.recent_reasons(installation_id, &repository).await;
+            error_msg.push_str(&messages::format_reason_suggestions(&recent));
+        }
+        mng.notify_comment_issue(installation_id, &repository, issue_nr, &error_msg)
+            .await;
+        return (CommandResult::Failure, Some(problem));
+    }
+
+    // Categories come from a fixed set so freezes can be grouped by them
+    let category = match &command {
+        commands::Command::Freeze(freeze_args) | commands::Command::FreezeAll(freeze_args) => {
+            match freeze_args
+                .category
+                .as_deref()
+                .map(str::parse::<FreezeCategory>)
+                .transpose()
+            {
+                Ok(category) => category,
+                Err(problem) => {
+                    let error_msg = messages::freeze_error(&problem);
+                    mng.notify_comment_issue(installation_id, &repo.into(), issue_nr, &error_msg)
+                        .await;
+                    return (CommandResult::Failure, Some(problem));
                 }
-                commands::Command::FreezeAll(freeze_args) => {
-                    mng.freeze_all(
+            }
+        }
+        _ => None,
+    };
+
+    // The branch depends on the command and the repository, so it is resolved by the caller
+    let freeze_options = |freeze_args: &commands::FreezeArgs, branch: Option<String>| {
+        FreezeOptions {
+            duration: resolve_duration(freeze_args.duration),
+            repos: freeze_args.repos.clone(),
+            branch,
+            environment: freeze_args.environment.clone(),
+            can_override,
+            if_not_frozen: freeze_args.if_not_frozen,
+            soft: freeze_args.soft,
+            category,
+            wait: freeze_args.wait,
+        }
+    };
+
+    match command {
+        commands::Command::Freeze(freeze_args) if freeze_args.confirm.is_some() => {
+            let problem = "`--confirm` is only supported by `/freeze-all`";
+            reject_command(mng, installation_id, &repo.into(), issue_nr, problem).await
+        }
+        commands::Command::Freeze(freeze_args) if freeze_args.environment.is_some() => {
+            let options = freeze_options(&freeze_args, None);
+            mng.freeze_environment(
+                installation_id,
+                &repo.into(),
+                freeze_args.reason,
+                author,
+                issue_nr,
+                options,
+            )
+            .await
+        }
+        commands::Command::FreezeAll(freeze_args) if freeze_args.environment.is_some() => {
+            let problem = "`--environment` is only supported by `/freeze`";
+            reject_command(mng, installation_id, &repo.into(), issue_nr, problem).await
+        }
+        commands::Command::FreezeAll(freeze_args) if freeze_args.merge => {
+            let problem = "`--merge` is only supported by `/freeze`";
+            reject_command(mng, installation_id, &repo.into(), issue_nr, problem).await
+        }
+        commands::Command::FreezeAll(freeze_args) if freeze_args.dry_run => {
+            let problem = "`--dry-run` is only supported by `/freeze`";
+            reject_command(mng, installation_id, &repo.into(), issue_nr, problem).await
+        }
+        commands::Command::FreezeAll(freeze_args) if freeze_args.wait => {
+            let problem = "`--wait` is only supported by `/freeze`";
+            reject_command(mng, installation_id, &repo.into(), issue_nr, problem).await
+        }
+        commands::Command::Freeze(freeze_args) if freeze_args.dry_run => {
+            let repository: Repository = (&repo).into();
+            let default_branch_only = extra.user_config.as_ref().is_some_and(|c| {
+                c.is_default_branch_only(installation_id.get(), &repository.full_name())
+            });
+            let branch = freeze_branch(freeze_args.branch.clone(), default_branch_only, &repo);
+            let options = freeze_options(&freeze_args, branch);
+            let permissions = extra.user_config.clone().map(PermissionService::new);
+            let repo_name = repository.full_name();
+            mng.freeze_dry_run(
+                installation_id,
+                &repository,
+                freeze_args.reason,
+                issue_nr,
+                &options,
+                |login| {
+                    permissions
+                        .as_ref()
+                        .is_some_and(|p| p.can_override(installation_id.get(), &repo_name, login))
+                },
+            )
+            .await
+        }
+        commands::Command::Freeze(freeze_args) if freeze_args.merge => {
+            let repository: Repository = (&repo).into();
+            let default_branch_only = extra.user_config.as_ref().is_some_and(|c| {
+                c.is_default_branch_only(installation_id.get(), &repository.full_name())
+            });
+            let branch = freeze_branch(freeze_args.branch.clone(), default_branch_only, &repo);
+            let options = freeze_options(&freeze_args, branch);
+            let outcome = mng
+                .freeze_merge(
+                    installation_id,
+                    &repository,
+                    freeze_args.reason,
+                    author.clone(),
+                    issue_nr,
+                    options,
+                )
+                .await;
+            let unlock_pr = auto_unlock_target(auto_unlock, &origin, issue_nr)
+                .filter(|_| outcome.0 == CommandResult::Success);
+            if let Some(pr_number) = unlock_pr {
+                mng.auto_unlock_pr(installation_id, &repository, pr_number, author)
+                    .await;
+            }
+            outcome
+        }
+        commands::Command::Freeze(freeze_args) | commands::Command::FreezeAll(freeze_args)
+            if freeze_args.org =>
+        {
+            let options = freeze_options(&freeze_args, freeze_args.branch.clone());
+            mng.freeze_org(
+                installation_id,
+                &repo.into(),
+                freeze_args.reason,
+                author,
+                issue_nr,
+                options,
+            )
+            .await
+        }
+        commands::Command::Freeze(freeze_args) => {
+            let repository: Repository = (&repo).into();
+            let default_branch_only = freeze_args.repos.is_empty()
+                && extra.user_config.as_ref().is_some_and(|c| {
+                    c.is_default_branch_only(installation_id.get(), &repository.full_name())
+                });
+            let branch = freeze_branch(freeze_args.branch.clone(), default_branch_only, &repo);
+            let options = freeze_options(&freeze_args, branch);
+            // Multi-repo freezes may not cover the repository of the PR
+            let unlock_pr = auto_unlock_target(
+                auto_unlock && freeze_args.repos.is_empty(),
+                &origin,
+                issue_nr,
+            );
+            let outcome = mng
+                .freeze(
+                    installation_id,
+                    &repository,
+                    freeze_args.reason,
+                    author.clone(),
+                    issue_nr,
+                    options,
+                )
+                .await;
+            // Only unlock for a freeze that went through
+            if let Some(pr_number) = unlock_pr.filter(|_| outcome.0 == CommandResult::Success) {
+                mng.auto_unlock_pr(installation_id, &repository, pr_number, author)
+                    .await;
+            }
+            outcome
+        }
+        commands::Command::FreezeAll(freeze_args) => {
+            let options = freeze_options(&freeze_args, freeze_args.branch.clone());
+            mng.freeze_all(installation_id, freeze_args.reason, author, issue_nr, options)
+                .await
+        }
+        commands::Command::Unfreeze(unfreeze_args) => {
+            let repository: Repository = repo.into();
+            // In the thread of a freeze on other repositories, such as an
+            // organization-wide freeze, `/unfreeze` lifts that freeze
+            let thread_freeze = match (&unfreeze_args.branch, &unfreeze_args.environment) {
+                (None, None) => mng
+                    .thread_freeze(installation_id, &repository, issue_nr)
+                    .await
+                    .filter(|r| !r.repository.eq_ignore_ascii_case(&repository.full_name())),
+                _ => None,
+            };
+
+            match thread_freeze {
+                Some(record) => {
+                    mng.unfreeze_thread_freeze(
                         installation_id,
-                        freeze_args.duration,
-                        freeze_args.reason,
+                        &repository,
+                        record,
                         author,
+                        unfreeze_args.reason,
                         issue_nr,
-                        freeze_args.repos,
-                        freeze_args.branch,
                     )
-                    .await;
+                    .await
                 }
-                commands::Command::Unfreeze(unfreeze_args) => {
+                None => {
                     mng.unfreeze(
                         installation_id,
-                        &repo.into(),
+                        &repository,
                         author,
                         unfreeze_args.reason,
                         issue_nr,
                         unfreeze_args.branch,
+                        unfreeze_args.environment,
                     )
-                    .await;
-                }
-                commands::Command::UnfreezeAll => {
-                    mng.unfreeze_all(installation_id, author, issue_nr).await;
-                }
-                commands::Command::Status(status_args) => {
-                    mng.get_status(installation_id, status_args.repos, issue_nr, &repo.into())
-                        .await;
-                }
-                commands::Command::ScheduleFreeze(schedule_freeze_args) => {
-                    let repository = repo.clone().into();
-                    let reason_for_display = schedule_freeze_args.reason.clone();
-                    match mng
-                        .schedule_freeze(
-                            installation_id,
-                            &repository,
-                            schedule_freeze_args.from,
-                            schedule_freeze_args.to,
-                            schedule_freeze_args.duration,
-                            schedule_freeze_args.reason,
-                            author.clone(),
-                            schedule_freeze_args.branch,
-                        )
-                        .await
-                    {
-                        Ok(_) => {
-                            let start_str =
-                                schedule_freeze_args.from.format("%Y-%m-%d %H:%M:%S UTC");
-                            let end_str = schedule_freeze_args
-                                .to
-                                .or_else(|| {
-                                    schedule_freeze_args
-                                        .duration
-                                        .map(|d| schedule_freeze_args.from + d)
-                                })
-                                .unwrap_or_else(|| {
-                                    schedule_freeze_args.from
-                                        + crate::freezer::manager::DEFAULT_FREEZE_DURATION
-                                })
-                                .format("%Y-%m-%d %H:%M:%S UTC");
-
-                            let success_msg = format!(
-                                "## ⏰ Freeze Scheduled\n\n\
-                                📅 **Repository `{}` freeze has been scheduled**\n\n\
-                                **Start**: {}\n\
-                                **End**: {}\n\
-                                **Reason**: {}\n\n\
-                                > The freeze will automatically activate at the scheduled time.",
-                                repository.full_name(),
-                                start_str,
-                                end_str,
-                                reason_for_display
-                                    .unwrap_or_else(|| "No reason provided".to_string())
-                            );
-                            mng.notify_comment_issue(
-                                installation_id,
-                                &repository,
-                                issue_nr,
-                                &success_msg,
-                            )
-                            .await;
-                        }
-                        Err(e) => {
-                            let error_msg = format!(
-                                "## ❌ Schedule Failed\n\n\
-                                🚫 **Failed to schedule freeze**\n\n\
-                                ```\n{}\n```\n\n\
-                                *Please check your parameters and try again.*",
-                                e
-                            );
-                            mng.notify_comment_issue(
-                                installation_id,
-                                &repository,
-                                issue_nr,
-                                &error_msg,
-                            )
-                            .await;
-                        }
-                    }
+                    .await
                 }
-                commands::Command::UnlockPr(unlock_pr_args) => {
-                    let pr_number = if let Some(pr) = unlock_pr_args.pr_number {
-                        pr
-                    } else {
-                        issue_nr
+            }
+        }
+        commands::Command::UnfreezeAll(unfreeze_all_args) => {
+            mng.unfreeze_all(
+                installation_id,
+                author,
+                issue_nr,
+                unfreeze_all_args.repos,
+                unfreeze_all_args.branch,
+                unfreeze_all_args.reason,
+            )
+            .await
+        }
+        commands::Command::Status(status_args) => {
+            mng.get_status(
+                installation_id,
+                status_args.repos,
+                issue_nr,
+                &repo.into(),
+                status_args.as_check,
+                status_args.pinned,
+                status_args.debug,
+                status_args.branch.as_deref(),
+            )
+            .await
+        }
+        commands::Command::ScheduleFreeze(schedule_freeze_args) => {
+            let repository = repo.clone().into();
+            let reason_for_display = schedule_freeze_args.reason.clone();
+            let scheduled = match schedule_freeze_args.resolve_window() {
+                Ok((from, to)) => {
+                    // Business-hour durations count from the scheduled start
+                    let duration = schedule_freeze_args
+                        .duration
+                        .map(|d| d.resolve(from, &extra.config.business_hours));
+                    let options = FreezeOptions {
+                        duration,
+                        branch: schedule_freeze_args.branch,
+                        can_override,
+                        ..FreezeOptions::default()
                     };
-
-                    let repository = repo.clone();
-                    mng.unlock_pr(
+                    mng.schedule_freeze(
                         installation_id,
-                        &repository.into(),
-                        pr_number,
-                        author,
-                        unlock_pr_args.reason,
-                        issue_nr,
+                        &repository,
+                        from,
+                        to,
+                        schedule_freeze_args.reason,
+                        author.clone(),
+                        options,
                     )
-                    .await;
+                    .await
+                    .map(|_| (from, to, duration))
+                }
+                Err(e) => Err(anyhow::anyhow!(e)),
+            };
+
+            match scheduled {
+                Ok((from, to, duration)) => {
+                    let start_str = from.format("%Y-%m-%d %H:%M:%S UTC").to_string();
+                    let end_str = to
+                        .or_else(|| duration.map(|d| from + d))
+                        .unwrap_or_else(|| from + crate::freezer::manager::DEFAULT_FREEZE_DURATION)
+                        .format("%Y-%m-%d %H:%M:%S UTC")
+                        .to_string();
+
+                    let success_msg = messages::schedule_success(
+                        &repository.full_name(),
+                        &start_str,
+                        &end_str,
+                        &reason_for_display.unwrap_or_else(|| "No reason provided".to_string()),
+                    );
+                    mng.notify_comment_issue(installation_id, &repository, issue_nr, &success_msg)
+                        .await;
+                    (CommandResult::Success, None)
+                }
+                Err(e) => {
+                    let error_msg = messages::schedule_error(&e.to_string());
+                    mng.notify_comment_issue(installation_id, &repository, issue_nr, &error_msg)
+                        .await;
+                    (CommandResult::Failure, Some(e.to_string()))
                 }
             }
         }
-    } else {
-        todo!()
+        commands::Command::UnlockPr(unlock_pr_args) => {
+            let Some(pr_number) = pull_request_target(unlock_pr_args.pr_number, &origin, issue_nr)
+            else {
+                let error_msg = messages::pull_request_required("unlock-pr", "--pr-number");
+                mng.notify_comment_issue(installation_id, &repo.into(), issue_nr, &error_msg)
+                    .await;
+                return (
+                    CommandResult::Failure,
+                    Some("No pull request to unlock".to_string()),
+                );
+            };
+
+            let repository = repo.clone();
+            mng.unlock_pr(
+                installation_id,
+                &repository.into(),
+                pr_number,
+                author,
+                unlock_pr_args.reason,
+                issue_nr,
+            )
+            .await
+        }
+        commands::Command::FreezeReassign(reassign_args) => {
+            let repository: Repository = repo.into();
+            let requester_is_admin = extra.user_config.as_ref().is_some_and(|c| {
+                PermissionService::new(c.clone()).is_admin_user(
+                    installation_id.get(),
+                    &repository.full_name(),
+                    &author,
+                )
+            });
+
+            mng.reassign_freeze(
+                installation_id,
+                &repository,
+                reassign_args.to,
+                author,
+                requester_is_admin,
+                reassign_args.branch,
+                issue_nr,
+            )
+            .await
+        }
+        commands::Command::Extend(extend_args) => {
+            mng.extend_freeze(
+                installation_id,
+                &repo.into(),
+                extend_args.duration,
+                extend_args.branch,
+                author,
+                issue_nr,
+            )
+            .await
+        }
+        commands::Command::Audit(audit_args) => {
+            mng.audit(installation_id, &repo.into(), audit_args.limit, issue_nr)
+                .await
+        }
+        commands::Command::Preview(preview_args) => {
+            mng.preview(
+                installation_id,
+                &repo.into(),
+                preview_args.message_type.as_deref(),
+                issue_nr,
+            )
+            .await
+        }
+        commands::Command::SchedulerPreview => {
+            mng.scheduler_preview(installation_id, &repo.into(), issue_nr)
+                .await
+        }
+        // Approvals were replaced by the approved `/unfreeze` above
+        commands::Command::ApproveUnfreeze => (CommandResult::Success, None),
+        commands::Command::Reconcile(reconcile_args) => {
+            mng.reconcile(
+                installation_id,
+                &repo.into(),
+                reconcile_args.repos,
+                reconcile_args.fix,
+                issue_nr,
+            )
+            .await
+        }
+        commands::Command::Scheduled => {
+            mng.list_scheduled(installation_id, &repo.into(), issue_nr)
+                .await
+        }
+        commands::Command::CancelSchedule(cancel_args) => {
+            mng.cancel_schedule(installation_id, &repo.into(), &cancel_args.id, issue_nr)
+                .await
+        }
     }
+}
 
-    Ok(())
+/// Replies that a command cannot run as given, failing it
+async fn reject_command(
+    mng: &freezer::manager::FreezeManager,
+    installation_id: InstallationId,
+    repository: &Repository,
+    issue_nr: u64,
+    problem: &str,
+) -> (CommandResult, Option<String>) {
+    let error_msg = messages::freeze_error(problem);
+    mng.notify_comment_issue(installation_id, repository, issue_nr, &error_msg)
+        .await;
+    (CommandResult::Failure, Some(problem.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comment_event() {
+        assert_eq!(
+            comment_event(&IssueCommentWebhookEventAction::Created),
+            Some(CommentEvent::Created)
+        );
+        assert_eq!(
+            comment_event(&IssueCommentWebhookEventAction::Edited),
+            Some(CommentEvent::Edited)
+        );
+        assert_eq!(
+            comment_event(&IssueCommentWebhookEventAction::Deleted),
+            None
+        );
+    }
+
+    #[test]
+    fn test_repo_limit_problem() {
+        assert_eq!(repo_limit_problem(0, 25), None);
+        assert_eq!(repo_limit_problem(25, 25), None);
+
+        let problem = repo_limit_problem(26, 25).unwrap();
+        assert!(problem.contains("26 given, at most 25"));
+        assert!(problem.contains("/freeze-all"));
+
+        // Installations may raise the cap
+        assert_eq!(repo_limit_problem(26, 100), None);
+    }
+
+    #[test]
+    fn test_pull_request_target() {
+        // On a pull request, PR-only commands default to that pull request
+        assert_eq!(
+            pull_request_target(None, &CommandOrigin::PullRequest, 42),
+            Some(42)
+        );
+        assert_eq!(
+            pull_request_target(Some(7), &CommandOrigin::PullRequest, 42),
+            Some(7)
+        );
+
+        // On a plain issue the pull request has to be named
+        assert_eq!(pull_request_target(None, &CommandOrigin::Issue, 42), None);
+        assert_eq!(
+            pull_request_target(Some(7), &CommandOrigin::Issue, 42),
+            Some(7)
+        );
+    }
+
+    #[test]
+    fn test_require_client() {
+        assert_eq!(require_client(Some(1)).unwrap(), 1);
+        let err = require_client::<u32>(None).unwrap_err();
+        assert!(err.to_string().contains("No GitHub client"));
+    }
+
+    #[test]
+    fn test_issue_comment_payload_rejects_other_events() {
+        use octofer::octocrab::models::webhook_events::WebhookEvent;
+
+        let event = WebhookEvent::try_from_header_and_body(
+            "ping",
+            r#"{"zen": "Keep it logically awesome.", "hook_id": 1}"#,
+        )
+        .unwrap();
+        let err = issue_comment_payload(&event.specific).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Expected an issue comment payload")
+        );
+    }
+
+    #[test]
+    fn test_auto_unlock_target() {
+        // Freezes requested on a PR unlock it when enabled
+        assert_eq!(
+            auto_unlock_target(true, &CommandOrigin::PullRequest, 42),
+            Some(42)
+        );
+        // Off by default
+        assert_eq!(
+            auto_unlock_target(false, &CommandOrigin::PullRequest, 42),
+            None
+        );
+        // Issues have nothing to unlock
+        assert_eq!(auto_unlock_target(true, &CommandOrigin::Issue, 42), None);
+        assert_eq!(auto_unlock_target(false, &CommandOrigin::Issue, 42), None);
+    }
+
+    fn mock_repository(default_branch: Option<&str>) -> octofer::octocrab::models::Repository {
+        serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "name": "repo",
+            "url": "https://api.github.com/repos/owner/repo",
+            "default_branch": default_branch,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_freeze_branch() {
+        let repo = mock_repository(Some("main"));
+
+        // Disabled: a bare freeze covers all branches
+        assert_eq!(freeze_branch(None, false, &repo), None);
+        // Enabled: a bare freeze covers only the default branch
+        assert_eq!(freeze_branch(None, true, &repo), Some("main".to_string()));
+        // An explicit branch always wins
+        assert_eq!(
+            freeze_branch(Some("develop".to_string()), true, &repo),
+            Some("develop".to_string())
+        );
+        // Unknown default branch falls back to all branches
+        assert_eq!(freeze_branch(None, true, &mock_repository(None)), None);
+    }
 }
@@ -0,0 +1,149 @@
+//! Pull request handler telling authors about freezes.
+//!
+//! With `COMMENT_ON_NEW_PR_DURING_FREEZE` enabled, a PR opened against a
+//! frozen branch gets a comment with the freeze reason and end time, so its
+//! author knows to wait instead of chasing the failing check. Bots and users
+//! allowed to override freezes are not commented on.
+
+use std::sync::Arc;
+
+use octofer::octocrab::models::webhook_events::{
+    WebhookEventPayload, payload::PullRequestWebhookEventAction,
+};
+use tracing::{error, info, warn};
+
+use crate::{
+    AppState, freezer, installation::InstallationId, permissions::PermissionService,
+    repository::Repository,
+};
+
+use super::require_client;
+
+/// Decides whether a pull request event warrants a freeze comment
+fn should_comment(
+    action: &PullRequestWebhookEventAction,
+    enabled: bool,
+    author_is_bot: bool,
+    author_is_exempt: bool,
+) -> bool {
+    enabled
+        && matches!(action, PullRequestWebhookEventAction::Opened)
+        && !author_is_bot
+        && !author_is_exempt
+}
+
+pub async fn pull_request_handler(
+    context: octofer::Context,
+    extra: Arc<AppState>,
+) -> anyhow::Result<()> {
+    info!("Pull request event received!");
+
+    if !extra.config.comment_on_new_pr_during_freeze {
+        return Ok(());
+    }
+    let Some(e) = context.event else {
+        warn!("Pull request event has no payload... skipping");
+        return Ok(());
+    };
+    let WebhookEventPayload::PullRequest(payload) = &e.specific else {
+        return Err(anyhow::anyhow!("Expected a pull request payload"));
+    };
+    let Some(ref author) = payload.pull_request.user else {
+        warn!("Pull request without author... skipping");
+        return Ok(());
+    };
+
+    let installation_id = InstallationId::try_from(
+        context
+            .installation_id
+            .ok_or(anyhow::anyhow!("Cannot get installation_id"))?,
+    )?;
+    let repository: Repository = e
+        .repository
+        .ok_or(anyhow::anyhow!("Cannot get repository from event"))?
+        .into();
+
+    // Users who may override freezes can merge anyway
+    let author_is_exempt = extra.user_config.as_ref().is_some_and(|c| {
+        PermissionService::new(c.clone()).can_override(
+            installation_id.get(),
+            &repository.full_name(),
+            &author.login,
+        )
+    });
+    if !should_comment(
+        &payload.action,
+        extra.config.comment_on_new_pr_during_freeze,
+        author.r#type == "Bot",
+        author_is_exempt,
+    ) {
+        return Ok(());
+    }
+
+    let client = require_client(context.github_client)?;
+    let mng = freezer::manager::FreezeManager::with_config(
+        extra.database.clone(),
+        client,
+        extra.config.clone(),
+    )
+    .with_cache(extra.freeze_cache.clone());
+
+    let pr_number = payload.pull_request.number;
+    match mng
+        .comment_on_frozen_pr(
+            installation_id,
+            &repository,
+            pr_number,
+            &author.login,
+            &payload.pull_request.base.ref_field,
+        )
+        .await
+    {
+        Ok(true) => info!(
+            "Told {} that PR #{} in {} was opened during a freeze",
+            author.login,
+            pr_number,
+            repository.full_name()
+        ),
+        Ok(false) => {}
+        Err(e) => {
+            error!(
+                "Failed to check freezes for PR #{} in {}: {}",
+                pr_number,
+                repository.full_name(),
+                e
+            );
+            return Err(e);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_comment() {
+        let opened = PullRequestWebhookEventAction::Opened;
+        assert!(should_comment(&opened, true, false, false));
+
+        // Disabled by configuration
+        assert!(!should_comment(&opened, false, false, false));
+
+        // Bots and users who may override freezes are left alone
+        assert!(!should_comment(&opened, true, true, false));
+        assert!(!should_comment(&opened, true, false, true));
+
+        // Only newly opened PRs are commented on
+        for action in [
+            PullRequestWebhookEventAction::Synchronize,
+            PullRequestWebhookEventAction::Reopened,
+            PullRequestWebhookEventAction::Edited,
+            PullRequestWebhookEventAction::Closed,
+        ] {
+            assert!(!should_comment(&action, true, false, false));
+        }
+    }
+}
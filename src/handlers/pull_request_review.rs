@@ -0,0 +1,104 @@
+//! Pull request review handler keeping the freeze check current.
+//!
+//! Submitting or dismissing a review can make GitHub recompute a PR's
+//! mergeability. If the PR targets a frozen branch, its Frezze check is
+//! re-created for the current head SHA so a freshly approved PR never looks
+//! mergeable because of a stale check.
+
+use std::sync::Arc;
+
+use octofer::octocrab::models::webhook_events::{
+    WebhookEventPayload, payload::PullRequestReviewWebhookEventAction,
+};
+use tracing::{error, info, warn};
+
+use crate::{AppState, freezer, installation::InstallationId, repository::Repository};
+
+use super::require_client;
+
+/// Returns `true` for review actions that may change a PR's mergeability
+fn needs_recheck(action: &PullRequestReviewWebhookEventAction) -> bool {
+    matches!(
+        action,
+        PullRequestReviewWebhookEventAction::Submitted
+            | PullRequestReviewWebhookEventAction::Dismissed
+    )
+}
+
+pub async fn pull_request_review_handler(
+    context: octofer::Context,
+    extra: Arc<AppState>,
+) -> anyhow::Result<()> {
+    info!("Pull request review event received!");
+
+    let Some(e) = context.event else {
+        warn!("Pull request review event has no payload... skipping");
+        return Ok(());
+    };
+    let WebhookEventPayload::PullRequestReview(payload) = &e.specific else {
+        return Err(anyhow::anyhow!("Expected a pull request review payload"));
+    };
+    if !needs_recheck(&payload.action) {
+        return Ok(());
+    }
+
+    let client = require_client(context.github_client)?;
+    let installation_id = InstallationId::try_from(
+        context
+            .installation_id
+            .ok_or(anyhow::anyhow!("Cannot get installation_id"))?,
+    )?;
+    let repository: Repository = e
+        .repository
+        .ok_or(anyhow::anyhow!("Cannot get repository from event"))?
+        .into();
+    let pr_number = payload.pull_request.number;
+
+    let mng = freezer::manager::FreezeManager::with_config(
+        extra.database.clone(),
+        client,
+        extra.config.clone(),
+    )
+    .with_cache(extra.freeze_cache.clone());
+
+    match mng
+        .pr_refresh
+        .recheck_frozen_pr(
+            installation_id,
+            &repository,
+            pr_number,
+            &payload.pull_request.base.ref_field,
+        )
+        .await
+    {
+        Ok(true) => info!(
+            "Re-created freeze check for PR #{} in {} after review",
+            pr_number,
+            repository.full_name()
+        ),
+        Ok(false) => {}
+        Err(e) => {
+            error!(
+                "Failed to re-check PR #{} in {}: {}",
+                pr_number,
+                repository.full_name(),
+                e
+            );
+            return Err(e);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_needs_recheck() {
+        assert!(needs_recheck(&PullRequestReviewWebhookEventAction::Submitted));
+        assert!(needs_recheck(&PullRequestReviewWebhookEventAction::Dismissed));
+        assert!(!needs_recheck(&PullRequestReviewWebhookEventAction::Edited));
+    }
+}
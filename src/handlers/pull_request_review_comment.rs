@@ -0,0 +1,151 @@
+//! Pull request review comment handler running commands from review threads.
+//!
+//! Commands typed in a code review thread arrive as review comments rather
+//! than issue comments. They are parsed and run exactly like issue comments,
+//! with replies posted on the pull request's conversation.
+
+use std::sync::Arc;
+
+use octofer::octocrab::models::webhook_events::{
+    WebhookEventPayload, payload::PullRequestReviewCommentWebhookEventAction,
+};
+use tracing::{info, warn};
+
+use crate::{AppState, installation::InstallationId};
+
+use super::{CommandOrigin, CommentCommand, CommentEvent, require_client, run_comment_command};
+
+/// Builds the command of a newly posted review comment.
+///
+/// Edited and deleted review comments never run commands.
+fn review_comment_command(
+    action: &PullRequestReviewCommentWebhookEventAction,
+    author: Option<String>,
+    body: String,
+    comment_id: u64,
+    pr_number: u64,
+    repo: octofer::octocrab::models::Repository,
+) -> Option<CommentCommand> {
+    if !matches!(action, PullRequestReviewCommentWebhookEventAction::Created) {
+        return None;
+    }
+
+    Some(CommentCommand {
+        event: CommentEvent::Created,
+        author: author?,
+        issue_nr: pr_number,
+        origin: CommandOrigin::PullRequest,
+        comment_id,
+        body,
+        repo,
+    })
+}
+
+pub async fn pull_request_review_comment_handler(
+    context: octofer::Context,
+    extra: Arc<AppState>,
+) -> anyhow::Result<()> {
+    info!("Pull request review comment event received!");
+
+    let Some(e) = context.event else {
+        warn!("Pull request review comment event has no payload... skipping");
+        return Ok(());
+    };
+    let WebhookEventPayload::PullRequestReviewComment(payload) = &e.specific else {
+        return Err(anyhow::anyhow!(
+            "Expected a pull request review comment payload"
+        ));
+    };
+    let repo = e
+        .repository
+        .ok_or(anyhow::anyhow!("Cannot get repository from event"))?;
+
+    let Some(command) = review_comment_command(
+        &payload.action,
+        payload.comment.user.as_ref().map(|u| u.login.clone()),
+        payload.comment.body.clone(),
+        payload.comment.id.into_inner(),
+        payload.pull_request.number,
+        repo,
+    ) else {
+        info!("Ignoring {:?} review comment event", payload.action);
+        return Ok(());
+    };
+
+    let client = require_client(context.github_client)?;
+    let installation_id = InstallationId::try_from(
+        context
+            .installation_id
+            .ok_or(anyhow::anyhow!("Cannot get installation_id"))?,
+    )?;
+
+    run_comment_command(client, installation_id, &extra, command).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mock_repository() -> octofer::octocrab::models::Repository {
+        serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "name": "repo",
+            "url": "https://api.github.com/repos/owner/repo",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_review_comment_command() {
+        let command = review_comment_command(
+            &PullRequestReviewCommentWebhookEventAction::Created,
+            Some("alice".to_string()),
+            "/freeze --duration 2h\nwhile we cut the release".to_string(),
+            987,
+            42,
+            mock_repository(),
+        )
+        .unwrap();
+
+        // Replies go to the pull request the review thread belongs to
+        assert_eq!(command.issue_nr, 42);
+        assert_eq!(command.origin, CommandOrigin::PullRequest);
+        assert_eq!(command.event, CommentEvent::Created);
+        assert_eq!(command.author, "alice");
+        assert_eq!(command.comment_id, 987);
+        assert!(command.body.starts_with("/freeze --duration 2h"));
+    }
+
+    #[test]
+    fn test_review_comment_command_ignored() {
+        for action in [
+            PullRequestReviewCommentWebhookEventAction::Edited,
+            PullRequestReviewCommentWebhookEventAction::Deleted,
+        ] {
+            assert!(
+                review_comment_command(
+                    &action,
+                    Some("alice".to_string()),
+                    "/freeze".to_string(),
+                    987,
+                    42,
+                    mock_repository(),
+                )
+                .is_none()
+            );
+        }
+
+        // Comments without a known author cannot be permission-checked
+        assert!(
+            review_comment_command(
+                &PullRequestReviewCommentWebhookEventAction::Created,
+                None,
+                "/freeze".to_string(),
+                987,
+                42,
+                mock_repository(),
+            )
+            .is_none()
+        );
+    }
+}
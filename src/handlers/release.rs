@@ -0,0 +1,176 @@
+//! Release handler freezing repositories after a release goes out.
+//!
+//! Installations that set `release_freeze_hours` get a stabilization freeze
+//! whenever a release is published: the repository is frozen for that many
+//! hours with the release tag in the reason, unless it is already frozen.
+
+use std::sync::Arc;
+
+use octofer::octocrab::models::webhook_events::{
+    WebhookEventPayload, payload::ReleaseWebhookEventAction,
+};
+use tracing::{error, info, warn};
+
+use crate::{AppState, freezer, installation::InstallationId, repository::Repository};
+
+use super::{freeze_branch, require_client};
+
+/// Freeze requested by a published release
+#[derive(Debug, PartialEq)]
+struct ReleaseFreeze {
+    duration: chrono::Duration,
+    reason: String,
+}
+
+/// Reason recorded for freezes created after a release
+fn release_freeze_reason(tag: &str) -> String {
+    format!("post-release stabilization for {}", tag)
+}
+
+/// Maps a release event onto a freeze, if the installation enables release freezes
+fn release_freeze(
+    action: &ReleaseWebhookEventAction,
+    release: &serde_json::Value,
+    duration: Option<chrono::Duration>,
+) -> Option<ReleaseFreeze> {
+    if !matches!(action, ReleaseWebhookEventAction::Published) {
+        return None;
+    }
+    let duration = duration?;
+    let tag = release["tag_name"].as_str()?.trim();
+    if tag.is_empty() {
+        return None;
+    }
+
+    Some(ReleaseFreeze {
+        duration,
+        reason: release_freeze_reason(tag),
+    })
+}
+
+pub async fn release_handler(context: octofer::Context, extra: Arc<AppState>) -> anyhow::Result<()> {
+    info!("Release event received!");
+
+    let Some(ref user_config) = extra.user_config else {
+        return Ok(());
+    };
+    let installation_id = InstallationId::try_from(
+        context
+            .installation_id
+            .ok_or(anyhow::anyhow!("Cannot get installation_id"))?,
+    )?;
+    let Some(e) = context.event else {
+        warn!("Release event has no payload... skipping");
+        return Ok(());
+    };
+    let WebhookEventPayload::Release(payload) = &e.specific else {
+        return Err(anyhow::anyhow!("Expected a release payload"));
+    };
+    let Some(freeze) = release_freeze(
+        &payload.action,
+        &payload.release,
+        user_config.release_freeze_duration(installation_id.get()),
+    ) else {
+        return Ok(());
+    };
+
+    let client = require_client(context.github_client)?;
+    let author = e
+        .sender
+        .as_ref()
+        .map(|s| s.login.clone())
+        .ok_or(anyhow::anyhow!("Cannot get sender from event"))?;
+    let repo = e
+        .repository
+        .ok_or(anyhow::anyhow!("Cannot get repository from event"))?;
+    let repository: Repository = repo.clone().into();
+    let branch = freeze_branch(
+        None,
+        user_config.is_default_branch_only(installation_id.get(), &repository.full_name()),
+        &repo,
+    );
+
+    let mng = freezer::manager::FreezeManager::with_config(
+        extra.database.clone(),
+        client,
+        extra.config.clone(),
+    )
+    .with_cache(extra.freeze_cache.clone());
+
+    match mng
+        .freeze_after_release(
+            installation_id,
+            &repository,
+            freeze.duration,
+            freeze.reason,
+            author,
+            branch,
+        )
+        .await
+    {
+        Ok(Some(record)) => info!(
+            "Froze {} after release: {}",
+            repository.full_name(),
+            record.reason.unwrap_or_default()
+        ),
+        Ok(None) => {}
+        Err(e) => {
+            error!(
+                "Failed to freeze {} after release: {}",
+                repository.full_name(),
+                e
+            );
+            return Err(e);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_release_freeze() {
+        let release = serde_json::json!({"tag_name": "v1.4.0", "draft": false});
+        let duration = Some(chrono::Duration::hours(24));
+
+        assert_eq!(
+            release_freeze(&ReleaseWebhookEventAction::Published, &release, duration),
+            Some(ReleaseFreeze {
+                duration: chrono::Duration::hours(24),
+                reason: "post-release stabilization for v1.4.0".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_release_freeze_ignored() {
+        let release = serde_json::json!({"tag_name": "v1.4.0"});
+        let duration = Some(chrono::Duration::hours(24));
+
+        // Release freezes are not enabled for the installation
+        assert_eq!(
+            release_freeze(&ReleaseWebhookEventAction::Published, &release, None),
+            None
+        );
+
+        // Only publishing a release freezes
+        for action in [
+            ReleaseWebhookEventAction::Created,
+            ReleaseWebhookEventAction::Edited,
+            ReleaseWebhookEventAction::Released,
+            ReleaseWebhookEventAction::Deleted,
+        ] {
+            assert_eq!(release_freeze(&action, &release, duration), None);
+        }
+
+        // Releases without a tag cannot be named in the reason
+        let untagged = serde_json::json!({"tag_name": " "});
+        assert_eq!(
+            release_freeze(&ReleaseWebhookEventAction::Published, &untagged, duration),
+            None
+        );
+    }
+}
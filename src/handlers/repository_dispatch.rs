@@ -0,0 +1,234 @@
+//! Repository dispatch handler freezing repositories from automation.
+//!
+//! When `FREEZE_DISPATCH_EVENT_TYPE` is configured, a `repository_dispatch`
+//! event of that type freezes the repository it was sent to. Workflows pick
+//! the freeze through the client payload:
+//!
+//! ```json
+//! {"event_type": "frezze_freeze", "client_payload": {"duration": "2h", "reason": "Release", "branch": "main"}}
+//! ```
+//!
+//! Every field is optional. The user whose token sent the event needs the
+//! same permissions as for `/freeze`.
+
+use std::sync::Arc;
+
+use octofer::octocrab::models::webhook_events::WebhookEventPayload;
+use tracing::{error, info, warn};
+
+use crate::{
+    AppState,
+    freezer::{
+        self,
+        commands::{self, FreezeDuration},
+    },
+    installation::InstallationId,
+    repository::Repository,
+};
+
+use super::{check_command_allowed, freeze_branch, require_client};
+
+/// Freeze requested by a repository dispatch
+#[derive(Debug, PartialEq)]
+struct DispatchFreeze {
+    duration: Option<FreezeDuration>,
+    reason: Option<String>,
+    branch: Option<String>,
+}
+
+/// Reads an optional, non-empty string field of the client payload
+fn payload_str(client_payload: &serde_json::Value, field: &str) -> anyhow::Result<Option<String>> {
+    match &client_payload[field] {
+        serde_json::Value::Null => Ok(None),
+        serde_json::Value::String(s) if s.trim().is_empty() => Ok(None),
+        serde_json::Value::String(s) => Ok(Some(s.trim().to_string())),
+        other => Err(anyhow::anyhow!(
+            "Expected `{}` to be a string, got {}",
+            field,
+            other
+        )),
+    }
+}
+
+/// Maps a repository dispatch onto a freeze, if it has the configured event type
+fn dispatch_freeze(
+    action: &str,
+    client_payload: &serde_json::Value,
+    event_type: &str,
+) -> anyhow::Result<Option<DispatchFreeze>> {
+    if action.trim() != event_type.trim() {
+        return Ok(None);
+    }
+
+    let duration = payload_str(client_payload, "duration")?
+        .map(|d| commands::parse_duration_2(&d))
+        .transpose()
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+    Ok(Some(DispatchFreeze {
+        duration,
+        reason: payload_str(client_payload, "reason")?,
+        branch: payload_str(client_payload, "branch")?,
+    }))
+}
+
+pub async fn repository_dispatch_handler(
+    context: octofer::Context,
+    extra: Arc<AppState>,
+) -> anyhow::Result<()> {
+    info!("Repository dispatch event received!");
+
+    let Some(event_type) = extra.config.freeze_dispatch_event_type.as_deref() else {
+        return Ok(());
+    };
+    let Some(e) = context.event else {
+        warn!("Repository dispatch event has no payload... skipping");
+        return Ok(());
+    };
+    let WebhookEventPayload::RepositoryDispatch(payload) = &e.specific else {
+        return Err(anyhow::anyhow!("Expected a repository dispatch payload"));
+    };
+    let Some(freeze) = dispatch_freeze(&payload.action, &payload.client_payload, event_type)?
+    else {
+        return Ok(());
+    };
+
+    let client = require_client(context.github_client)?;
+    let installation_id = InstallationId::try_from(
+        context
+            .installation_id
+            .ok_or(anyhow::anyhow!("Cannot get installation_id"))?,
+    )?;
+    let author = e
+        .sender
+        .as_ref()
+        .map(|s| s.login.clone())
+        .ok_or(anyhow::anyhow!("Cannot get sender from event"))?;
+    let repo = e
+        .repository
+        .ok_or(anyhow::anyhow!("Cannot get repository from event"))?;
+    let repository: Repository = repo.clone().into();
+
+    // Dispatches need the same permissions as the equivalent command
+    let command = commands::parse("/freeze")?.command;
+    if let Err(rejection) =
+        check_command_allowed(&extra, installation_id, &repository, &author, &command).await
+    {
+        warn!(
+            "Ignoring freeze dispatched to {} by {}: {}",
+            repository.full_name(),
+            author,
+            rejection
+        );
+        return Ok(());
+    }
+
+    let default_branch_only = extra
+        .user_config
+        .as_ref()
+        .is_some_and(|c| c.is_default_branch_only(installation_id.get(), &repository.full_name()));
+    let branch = freeze_branch(freeze.branch, default_branch_only, &repo);
+    let duration = freeze
+        .duration
+        .map(|d| d.resolve(chrono::Utc::now(), &extra.config.business_hours));
+
+    let mng = freezer::manager::FreezeManager::with_config(
+        extra.database.clone(),
+        client,
+        extra.config.clone(),
+    )
+    .with_cache(extra.freeze_cache.clone());
+
+    match mng
+        .freeze_from_dispatch(
+            installation_id,
+            &repository,
+            duration,
+            freeze.reason,
+            author.clone(),
+            branch,
+        )
+        .await
+    {
+        Ok(record) => info!(
+            "Froze {} from a repository dispatch by {} until {:?}",
+            repository.full_name(),
+            author,
+            record.expires_at
+        ),
+        Err(e) => {
+            error!(
+                "Failed to freeze {} from a repository dispatch: {}",
+                repository.full_name(),
+                e
+            );
+            return Err(e);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dispatch_freeze() {
+        let client_payload = serde_json::json!({
+            "duration": "2h",
+            "reason": "Release 1.4",
+            "branch": "main",
+        });
+
+        assert_eq!(
+            dispatch_freeze("frezze_freeze", &client_payload, "frezze_freeze").unwrap(),
+            Some(DispatchFreeze {
+                duration: Some(FreezeDuration::Wall(chrono::Duration::hours(2))),
+                reason: Some("Release 1.4".to_string()),
+                branch: Some("main".to_string()),
+            })
+        );
+
+        // Every field is optional
+        for client_payload in [serde_json::json!({}), serde_json::Value::Null] {
+            assert_eq!(
+                dispatch_freeze("frezze_freeze", &client_payload, "frezze_freeze").unwrap(),
+                Some(DispatchFreeze {
+                    duration: None,
+                    reason: None,
+                    branch: None,
+                })
+            );
+        }
+
+        // Business-hour durations are accepted as in comments
+        let client_payload = serde_json::json!({"duration": "8bh", "reason": " "});
+        let freeze = dispatch_freeze("frezze_freeze", &client_payload, "frezze_freeze")
+            .unwrap()
+            .unwrap();
+        assert_eq!(freeze.duration, Some(FreezeDuration::BusinessHours(8)));
+        assert!(freeze.reason.is_none());
+    }
+
+    #[test]
+    fn test_dispatch_freeze_ignored_or_invalid() {
+        let client_payload = serde_json::json!({"duration": "2h"});
+
+        // Other event types belong to other automation
+        assert!(
+            dispatch_freeze("deploy", &client_payload, "frezze_freeze")
+                .unwrap()
+                .is_none()
+        );
+
+        // Malformed payloads are reported instead of freezing with defaults
+        for client_payload in [
+            serde_json::json!({"duration": "soon"}),
+            serde_json::json!({"duration": 2}),
+            serde_json::json!({"branch": ["main"]}),
+        ] {
+            assert!(dispatch_freeze("frezze_freeze", &client_payload, "frezze_freeze").is_err());
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use anyhow::anyhow;
+use octofer::octocrab;
+
+/// Identifies a GitHub App installation.
+///
+/// GitHub hands out installation ids as `u64`, while the database stores them
+/// as SQLite integers (`i64`). This type keeps the id in its stored form and
+/// checks the conversion from GitHub ids once, so values never silently wrap
+/// around when moving between the two.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InstallationId(i64);
+
+impl InstallationId {
+    /// Returns the id as stored in the database.
+    pub fn get(self) -> i64 {
+        self.0
+    }
+
+    /// Returns the id as used by the GitHub API.
+    pub fn as_u64(self) -> u64 {
+        self.0 as u64
+    }
+}
+
+/// Ids read back from the database, which were written from checked GitHub ids.
+impl From<i64> for InstallationId {
+    fn from(id: i64) -> Self {
+        Self(id)
+    }
+}
+
+impl TryFrom<u64> for InstallationId {
+    type Error = anyhow::Error;
+
+    fn try_from(id: u64) -> Result<Self, Self::Error> {
+        i64::try_from(id)
+            .map(Self)
+            .map_err(|_| anyhow!("Installation id {} is out of range", id))
+    }
+}
+
+impl TryFrom<octocrab::models::InstallationId> for InstallationId {
+    type Error = anyhow::Error;
+
+    fn try_from(id: octocrab::models::InstallationId) -> Result<Self, Self::Error> {
+        Self::try_from(id.into_inner())
+    }
+}
+
+impl From<InstallationId> for octocrab::models::InstallationId {
+    fn from(id: InstallationId) -> Self {
+        octocrab::models::InstallationId(id.as_u64())
+    }
+}
+
+impl std::fmt::Display for InstallationId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_github_id() {
+        let id = InstallationId::try_from(12345u64).unwrap();
+        assert_eq!(id.get(), 12345);
+        assert_eq!(id.as_u64(), 12345);
+
+        let id = InstallationId::try_from(octocrab::models::InstallationId(67890)).unwrap();
+        assert_eq!(id, InstallationId::from(67890));
+    }
+
+    #[test]
+    fn test_from_github_id_out_of_range() {
+        assert!(InstallationId::try_from(i64::MAX as u64).is_ok());
+        assert!(InstallationId::try_from(i64::MAX as u64 + 1).is_err());
+        assert!(InstallationId::try_from(u64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let id = InstallationId::from(12345);
+        assert_eq!(InstallationId::try_from(id.as_u64()).unwrap(), id);
+        assert_eq!(InstallationId::from(id.get()), id);
+        assert_eq!(id.to_string(), "12345");
+    }
+}
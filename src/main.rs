@@ -1,11 +1,14 @@
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
-use tracing::info;
+use clap::{Parser, Subcommand};
+use tracing::{info, warn};
 
 mod config;
 mod database;
 mod freezer;
 mod handlers;
+mod installation;
+mod notifications;
 mod permissions;
 mod repository;
 mod worker;
@@ -16,11 +19,33 @@ use octofer::{
     github::{GitHubAuth, GitHubClient},
 };
 
-use crate::{config::UserPermissionsConfig, database::Database};
+use crate::{
+    config::{AppConfig, NoConfigMode, UserPermissionsConfig},
+    database::{Database, dump::Dump},
+    freezer::cache::FreezeCache,
+};
+
+/// Runs the GitHub App, or a maintenance command when one is given
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<CliCommand>,
+}
+
+#[derive(Subcommand)]
+enum CliCommand {
+    /// Write every freeze, command log entry, unlocked PR and permission record to a JSON file
+    Export { path: PathBuf },
+    /// Load a JSON file written by `export`, skipping rows that already exist
+    Import { path: PathBuf },
+}
 
 struct AppState {
+    config: Arc<AppConfig>,
     database: Arc<Database>,
     user_config: Option<Arc<UserPermissionsConfig>>,
+    freeze_cache: Arc<FreezeCache>,
 }
 
 #[tokio::main]
@@ -28,8 +53,47 @@ async fn main() -> Result<(), anyhow::Error> {
     // Load environment variables from .env file
     dotenv::dotenv().ok();
 
-    // Start the application
-    start().await?;
+    match Cli::parse().command {
+        Some(command) => run_command(command).await?,
+        // Start the application
+        None => start().await?,
+    }
+    Ok(())
+}
+
+/// Opens and migrates the database configured in the environment
+async fn open_database() -> Result<Database, anyhow::Error> {
+    let database_url =
+        std::env::var("DATABASE_URL").unwrap_or_else(|_| "sqlite:frezze.db".to_string());
+    let migrations_path =
+        std::env::var("MIGRATIONS_PATH").unwrap_or_else(|_| "migrations".to_string());
+
+    Database::new(&database_url, &migrations_path, 10)
+        .connect()
+        .await?
+        .migrate()
+        .await
+}
+
+async fn run_command(command: CliCommand) -> Result<(), anyhow::Error> {
+    let db = open_database().await?;
+    match command {
+        CliCommand::Export { path } => {
+            let dump = Dump::export(db.pool()).await?;
+            std::fs::write(&path, serde_json::to_vec_pretty(&dump)?)?;
+            println!("Exported {} rows to {}", dump.row_count(), path.display());
+        }
+        CliCommand::Import { path } => {
+            let dump: Dump = serde_json::from_slice(&std::fs::read(&path)?)?;
+            let summary = dump.import(db.pool()).await?;
+            println!(
+                "Imported {} rows from {}, skipped {} existing rows",
+                summary.imported,
+                path.display(),
+                summary.skipped
+            );
+        }
+    }
     Ok(())
 }
 
@@ -46,17 +110,7 @@ async fn start() -> Result<(), anyhow::Error> {
         // Create a new Octofer app with the configuration
         let mut app = Octofer::new(config).await?;
 
-        // Get database URL from environment
-        let database_url =
-            std::env::var("DATABASE_URL").unwrap_or_else(|_| "sqlite:frezze.db".to_string());
-        let migrations_path =
-            std::env::var("MIGRATIONS_PATH").unwrap_or_else(|_| "migrations".to_string());
-
-        let db = Database::new(&database_url, &migrations_path, 10)
-            .connect()
-            .await?
-            .migrate()
-            .await?;
+        let db = open_database().await?;
 
         let permissions_path =
             std::env::var("PERMISSIONS_PATH").unwrap_or_else(|_| "users.yaml".to_string());
@@ -69,22 +123,99 @@ async fn start() -> Result<(), anyhow::Error> {
                 None
             });
 
+        let mut app_config = AppConfig::from_env()?;
+        info!("Using message theme: {:?}", app_config.message_theme);
+        if conf.is_none() && app_config.no_config_mode == NoConfigMode::AllowAll {
+            warn!(
+                "NO_CONFIG_MODE=allow-all: every user can run every command, including freezes of any repository. Use this for testing only!"
+            );
+        }
+        if let Some(ref conf) = conf {
+            app_config.check_run_text_templates = conf.check_run_text_templates();
+            app_config.repository_watchers = conf.repository_watchers();
+            app_config.ops_contacts = conf.ops_contacts();
+            app_config.quiet_hours = conf.quiet_hours();
+        }
+
         let state = AppState {
+            config: Arc::new(app_config),
             database: Arc::new(db),
             user_config: conf.map(Arc::new),
+            freeze_cache: Arc::new(FreezeCache::default()),
         };
 
         // Start the worker that refreshes PRs status checks in the bg
         let worker_db = state.database.clone();
+        let worker_config = state.config.clone();
+        let worker_cache = state.freeze_cache.clone();
         tokio::spawn(async move {
             // Start the freeze scheduler worker
-            worker(worker_db).await;
+            worker(worker_db, worker_config, worker_cache).await;
         });
 
+        let state = Arc::new(state);
+
         // Attach on the issue_comment handler
-        app.on_issue_comment(handlers::issue_comment_handler, Arc::new(state))
+        app.on_issue_comment(handlers::issue_comment_handler, state.clone())
+            .await;
+
+        // Attach the deployment handlers enforcing environment freezes
+        app.on_deployment(handlers::deployment::deployment_handler, state.clone())
+            .await;
+        app.on_deployment_status(
+            handlers::deployment::deployment_status_handler,
+            state.clone(),
+        )
+        .await;
+
+        // Attach the pull request handler commenting on PRs opened during a freeze
+        app.on_pull_request(handlers::pull_request::pull_request_handler, state.clone())
+            .await;
+
+        // Attach the review handler keeping freeze checks current
+        app.on_pull_request_review(
+            handlers::pull_request_review::pull_request_review_handler,
+            state.clone(),
+        )
+        .await;
+
+        // Attach the review comment handler running commands from review threads
+        app.on_pull_request_review_comment(
+            handlers::pull_request_review_comment::pull_request_review_comment_handler,
+            state.clone(),
+        )
+        .await;
+
+        // Attach the check suite handler creating freeze checks for new commits
+        app.on_check_suite(handlers::check_suite::check_suite_handler, state.clone())
             .await;
 
+        // Attach the merge group handler creating freeze checks for merge queues
+        app.on_merge_group(handlers::merge_group::merge_group_handler, state.clone())
+            .await;
+
+        // Attach the installation handlers creating freeze checks for existing PRs
+        app.on_installation(handlers::installation::installation_handler, state.clone())
+            .await;
+        app.on_installation_repositories(
+            handlers::installation::installation_repositories_handler,
+            state.clone(),
+        )
+        .await;
+
+        // Attach the issues handler freezing repositories through labels
+        app.on_issue(handlers::issues::issues_handler, state.clone()).await;
+
+        // Attach the repository dispatch handler freezing repositories from automation
+        app.on_repository_dispatch(
+            handlers::repository_dispatch::repository_dispatch_handler,
+            state.clone(),
+        )
+        .await;
+
+        // Attach the release handler freezing repositories after releases
+        app.on_release(handlers::release::release_handler, state).await;
+
         app.start().await
     });
 
@@ -96,12 +227,12 @@ async fn start() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
-async fn worker(db: Arc<Database>) {
+async fn worker(db: Arc<Database>, config: Arc<AppConfig>, cache: Arc<FreezeCache>) {
     let gh_cfg = GitHubConfig::from_env().expect("Unable to load github cfg");
     let gh_auth = GitHubAuth::from_config(&gh_cfg);
     let gh = GitHubClient::new(gh_auth)
         .await
         .expect("Unable to start github client");
-    let worker = worker::FreezeSchedulerWorker::new(db, gh.into());
+    let worker = worker::FreezeSchedulerWorker::new(db, gh.into(), config, cache);
     worker.start().await;
 }
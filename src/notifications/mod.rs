@@ -0,0 +1,252 @@
+//! Outbound notifications for freeze lifecycle events.
+//!
+//! Notifiers forward freeze events (freeze, unfreeze, schedule, activate,
+//! expire) to external systems. Every notifier implements the [`Notifier`]
+//! trait and is driven by a [`NotificationDispatcher`], which fans each event
+//! out to all configured notifiers without failing the triggering operation.
+//! Events of installations inside their [`quiet_hours::QuietHours`] are
+//! dropped by the dispatcher instead.
+//!
+//! # Modules
+//!
+//! - [`webhook`] - Generic JSON webhook signed with HMAC-SHA256
+//! - [`stdout`] - NDJSON lines on stdout for log-based integrations
+//! - [`quiet_hours`] - Daily windows during which events are not sent
+
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tracing::{error, info};
+
+use crate::{
+    config::AppConfig,
+    database::models::{FreezeCategory, FreezeRecord},
+};
+
+pub mod quiet_hours;
+pub mod stdout;
+pub mod webhook;
+
+use quiet_hours::QuietHours;
+
+/// Kind of freeze lifecycle event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FreezeEventKind {
+    /// A freeze was applied immediately
+    Freeze,
+    /// A freeze was lifted manually
+    Unfreeze,
+    /// A freeze was scheduled for later
+    Schedule,
+    /// A scheduled freeze became active
+    Activate,
+    /// A freeze reached its expiration time
+    Expire,
+}
+
+/// Payload describing a freeze lifecycle event.
+#[derive(Debug, Clone, Serialize)]
+pub struct FreezeEvent {
+    pub event: FreezeEventKind,
+    pub repository: String,
+    pub installation_id: i64,
+    pub initiated_by: String,
+    pub started_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub reason: Option<String>,
+    /// Structured category given with `--category`, if any
+    pub category: Option<FreezeCategory>,
+    /// Users watching the repository, excluding the one who triggered the event
+    pub watchers: Vec<String>,
+}
+
+impl FreezeEvent {
+    /// Builds an event from the freeze record it refers to.
+    pub fn from_record(event: FreezeEventKind, record: &FreezeRecord) -> Self {
+        Self {
+            event,
+            repository: record.repository.clone(),
+            installation_id: record.installation_id,
+            initiated_by: record.initiated_by.clone(),
+            started_at: record.started_at,
+            expires_at: record.expires_at,
+            reason: record.reason.clone(),
+            category: record.category,
+            watchers: Vec::new(),
+        }
+    }
+
+    /// Attaches the repository watchers to the event.
+    pub fn with_watchers(mut self, watchers: Vec<String>) -> Self {
+        self.watchers = watchers;
+        self
+    }
+}
+
+/// Destination for freeze lifecycle events.
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// Human readable name used in logs
+    fn name(&self) -> &str;
+
+    /// Delivers a single event.
+    async fn notify(&self, event: &FreezeEvent) -> Result<()>;
+}
+
+/// Fans freeze events out to every configured notifier.
+#[derive(Clone, Default)]
+pub struct NotificationDispatcher {
+    notifiers: Vec<Arc<dyn Notifier>>,
+    /// Quiet hours keyed by installation ID
+    quiet_hours: HashMap<i64, QuietHours>,
+}
+
+impl NotificationDispatcher {
+    pub fn new(notifiers: Vec<Arc<dyn Notifier>>) -> Self {
+        Self {
+            notifiers,
+            quiet_hours: HashMap::new(),
+        }
+    }
+
+    /// Suppresses the events of installations during their quiet hours.
+    pub fn with_quiet_hours(mut self, quiet_hours: HashMap<i64, QuietHours>) -> Self {
+        self.quiet_hours = quiet_hours;
+        self
+    }
+
+    /// Builds the dispatcher from the notifiers enabled in the application config.
+    pub fn from_config(config: &AppConfig) -> Self {
+        let mut notifiers: Vec<Arc<dyn Notifier>> = Vec::new();
+
+        if let Some(url) = &config.freeze_event_webhook_url {
+            notifiers.push(Arc::new(webhook::WebhookNotifier::new(
+                url.clone(),
+                config.freeze_event_webhook_secret.clone(),
+            )));
+        }
+
+        if config.emit_events_stdout {
+            notifiers.push(Arc::new(stdout::StdoutEventNotifier::new()));
+        }
+
+        Self::new(notifiers).with_quiet_hours(config.quiet_hours.clone())
+    }
+
+    /// Sends an event to all notifiers.
+    ///
+    /// Delivery runs in the background and failures are only logged, so a
+    /// misbehaving receiver cannot block freeze operations.
+    pub async fn dispatch(&self, event: FreezeEvent) {
+        if self.notifiers.is_empty() {
+            return;
+        }
+
+        let dispatcher = self.clone();
+        let now = Utc::now();
+        tokio::spawn(async move { dispatcher.dispatch_at(event, now).await });
+    }
+
+    /// Sends an event to all notifiers, unless its installation is in its
+    /// quiet hours at `now`.
+    async fn dispatch_at(&self, event: FreezeEvent, now: DateTime<Utc>) {
+        if self
+            .quiet_hours
+            .get(&event.installation_id)
+            .is_some_and(|quiet| quiet.contains(now))
+        {
+            info!(
+                "Suppressing {:?} event for {} during the quiet hours of installation {}",
+                event.event, event.repository, event.installation_id
+            );
+            return;
+        }
+
+        for notifier in &self.notifiers {
+            match notifier.notify(&event).await {
+                Ok(_) => info!(
+                    "Delivered {:?} event for {} via {}",
+                    event.event,
+                    event.repository,
+                    notifier.name()
+                ),
+                Err(e) => error!(
+                    "Failed to deliver {:?} event for {} via {}: {}",
+                    event.event,
+                    event.repository,
+                    notifier.name(),
+                    e
+                ),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::sync::Mutex;
+
+    /// Notifier remembering the repositories of the events it received
+    #[derive(Default)]
+    struct RecordingNotifier {
+        received: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl Notifier for RecordingNotifier {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        async fn notify(&self, event: &FreezeEvent) -> Result<()> {
+            self.received.lock().unwrap().push(event.repository.clone());
+            Ok(())
+        }
+    }
+
+    fn event(installation_id: i64) -> FreezeEvent {
+        FreezeEvent {
+            event: FreezeEventKind::Freeze,
+            repository: format!("owner/repo-{installation_id}"),
+            installation_id,
+            initiated_by: "alice".to_string(),
+            started_at: Utc.with_ymd_and_hms(2025, 1, 15, 10, 0, 0).unwrap(),
+            expires_at: None,
+            reason: None,
+            category: None,
+            watchers: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_quiet_hours_suppress_events() {
+        let notifier = Arc::new(RecordingNotifier::default());
+        let notifiers: Vec<Arc<dyn Notifier>> = vec![notifier.clone()];
+        let quiet_hours = QuietHours::parse("22:00", "07:00", None).unwrap();
+        let dispatcher = NotificationDispatcher::new(notifiers)
+            .with_quiet_hours(HashMap::from([(12345, quiet_hours)]));
+
+        // Inside the window, on both sides of midnight
+        for hour in [23, 3] {
+            let night = Utc.with_ymd_and_hms(2025, 1, 15, hour, 0, 0).unwrap();
+            dispatcher.dispatch_at(event(12345), night).await;
+        }
+        assert!(notifier.received.lock().unwrap().is_empty());
+
+        // Outside the window, and for installations without quiet hours
+        let day = Utc.with_ymd_and_hms(2025, 1, 15, 10, 0, 0).unwrap();
+        dispatcher.dispatch_at(event(12345), day).await;
+        let night = Utc.with_ymd_and_hms(2025, 1, 15, 23, 0, 0).unwrap();
+        dispatcher.dispatch_at(event(67890), night).await;
+        assert_eq!(
+            *notifier.received.lock().unwrap(),
+            vec!["owner/repo-12345", "owner/repo-67890"]
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! Quiet hours during which freeze events are not sent out.
+//!
+//! Installations may configure a daily window, in their own timezone, during
+//! which the dispatcher drops freeze events instead of delivering them, so
+//! nobody gets paged at night. Comments on GitHub are not affected.
+
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, NaiveTime, Utc};
+use chrono_tz::Tz;
+
+/// Daily window, in a given timezone, during which notifications are suppressed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuietHours {
+    /// Local time at which the quiet hours start
+    pub start: NaiveTime,
+    /// Local time at which the quiet hours end, exclusive
+    pub end: NaiveTime,
+    /// Timezone the window is expressed in
+    pub timezone: Tz,
+}
+
+impl QuietHours {
+    /// Builds quiet hours from their textual settings.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - Local start time as `HH:MM` (e.g. "22:00")
+    /// * `end` - Local end time as `HH:MM` (e.g. "07:00"), before `start` for a window spanning midnight
+    /// * `timezone` - IANA timezone of the window (e.g. "Europe/Rome"), UTC when unset
+    pub fn parse(start: &str, end: &str, timezone: Option<&str>) -> Result<Self> {
+        let parse_time = |t: &str| {
+            NaiveTime::parse_from_str(t.trim(), "%H:%M")
+                .map_err(|_| anyhow!("Invalid quiet hours time '{}', expected 'HH:MM'", t.trim()))
+        };
+        let start = parse_time(start)?;
+        let end = parse_time(end)?;
+        if start == end {
+            return Err(anyhow!("Quiet hours must start and end at different times"));
+        }
+
+        let timezone = match timezone {
+            Some(tz) => tz
+                .trim()
+                .parse::<Tz>()
+                .map_err(|_| anyhow!("Invalid quiet hours timezone '{}'", tz.trim()))?,
+            None => Tz::UTC,
+        };
+
+        Ok(Self {
+            start,
+            end,
+            timezone,
+        })
+    }
+
+    /// Returns whether an instant falls inside the quiet hours.
+    pub fn contains(&self, now: DateTime<Utc>) -> bool {
+        let local = now.with_timezone(&self.timezone).time();
+        if self.start < self.end {
+            self.start <= local && local < self.end
+        } else {
+            // The window spans midnight
+            local >= self.start || local < self.end
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 15, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_window_within_a_day() {
+        let quiet = QuietHours::parse("12:00", "14:30", None).unwrap();
+
+        assert!(!quiet.contains(at(11, 59)));
+        assert!(quiet.contains(at(12, 0)));
+        assert!(quiet.contains(at(14, 29)));
+        assert!(!quiet.contains(at(14, 30)));
+    }
+
+    #[test]
+    fn test_window_spanning_midnight() {
+        let quiet = QuietHours::parse("22:00", "07:00", None).unwrap();
+
+        assert!(quiet.contains(at(22, 0)));
+        assert!(quiet.contains(at(23, 59)));
+        assert!(quiet.contains(at(0, 0)));
+        assert!(quiet.contains(at(6, 59)));
+        assert!(!quiet.contains(at(7, 0)));
+        assert!(!quiet.contains(at(21, 59)));
+    }
+
+    #[test]
+    fn test_window_in_timezone() {
+        // 22:00-07:00 in Rome is 21:00-06:00 UTC in winter
+        let quiet = QuietHours::parse("22:00", "07:00", Some("Europe/Rome")).unwrap();
+
+        assert!(quiet.contains(at(21, 30)));
+        assert!(quiet.contains(at(5, 59)));
+        assert!(!quiet.contains(at(6, 30)));
+    }
+
+    #[test]
+    fn test_invalid_settings() {
+        assert!(QuietHours::parse("22", "07:00", None).is_err());
+        assert!(QuietHours::parse("22:00", "25:00", None).is_err());
+        assert!(QuietHours::parse("22:00", "22:00", None).is_err());
+        assert!(QuietHours::parse("22:00", "07:00", Some("Mars/Olympus")).is_err());
+    }
+}
@@ -0,0 +1,124 @@
+//! NDJSON stdout notifier.
+//!
+//! Writes each freeze event as a single JSON line prefixed with
+//! [`EVENT_LINE_PREFIX`], so pipelines that only tail container logs can pick
+//! the events out with `grep` and parse the remainder as JSON.
+
+use std::io::Write;
+use std::sync::Mutex;
+
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+
+use super::{FreezeEvent, Notifier};
+
+/// Prefix marking freeze event lines in the log output
+pub const EVENT_LINE_PREFIX: &str = "FREEZE_EVENT ";
+
+/// Notifier that prints freeze events to stdout as NDJSON.
+pub struct StdoutEventNotifier {
+    writer: Mutex<Box<dyn Write + Send>>,
+}
+
+impl StdoutEventNotifier {
+    pub fn new() -> Self {
+        Self::with_writer(Box::new(std::io::stdout()))
+    }
+
+    /// Creates a notifier writing to an arbitrary sink instead of stdout.
+    pub fn with_writer(writer: Box<dyn Write + Send>) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+        }
+    }
+
+    /// Renders the event as a single prefixed JSON line.
+    fn line(event: &FreezeEvent) -> Result<String> {
+        let json =
+            serde_json::to_string(event).map_err(|e| anyhow!("Failed to serialize event: {}", e))?;
+        Ok(format!("{EVENT_LINE_PREFIX}{json}\n"))
+    }
+}
+
+impl Default for StdoutEventNotifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Notifier for StdoutEventNotifier {
+    fn name(&self) -> &str {
+        "stdout"
+    }
+
+    async fn notify(&self, event: &FreezeEvent) -> Result<()> {
+        let line = Self::line(event)?;
+
+        let mut writer = self
+            .writer
+            .lock()
+            .map_err(|_| anyhow!("Event writer lock poisoned"))?;
+        writer.write_all(line.as_bytes())?;
+        writer.flush()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notifications::FreezeEventKind;
+    use chrono::{TimeZone, Utc};
+    use std::sync::Arc;
+
+    /// Writer that keeps everything written to it for inspection
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_emits_prefixed_json_line() {
+        let buffer = SharedBuffer::default();
+        let notifier = StdoutEventNotifier::with_writer(Box::new(buffer.clone()));
+
+        let event = FreezeEvent {
+            event: FreezeEventKind::Freeze,
+            repository: "owner/repo".to_string(),
+            installation_id: 12345,
+            initiated_by: "alice".to_string(),
+            started_at: Utc.with_ymd_and_hms(2025, 1, 1, 10, 0, 0).unwrap(),
+            expires_at: None,
+            reason: Some("Release".to_string()),
+            category: None,
+            watchers: Vec::new(),
+        };
+        notifier.notify(&event).await.unwrap();
+        notifier.notify(&event).await.unwrap();
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+
+        let json = lines[0].strip_prefix(EVENT_LINE_PREFIX).unwrap();
+        let json: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(json["event"], "freeze");
+        assert_eq!(json["repository"], "owner/repo");
+        assert_eq!(json["installation_id"], 12345);
+        assert_eq!(json["initiated_by"], "alice");
+        assert_eq!(json["started_at"], "2025-01-01T10:00:00Z");
+        assert!(json["expires_at"].is_null());
+        assert_eq!(json["reason"], "Release");
+    }
+}
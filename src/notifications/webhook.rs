@@ -0,0 +1,168 @@
+//! Generic JSON webhook notifier.
+//!
+//! Each freeze event is POSTed as JSON to the configured URL. When a secret is
+//! configured the body is signed with HMAC-SHA256 and the signature is sent in
+//! the [`SIGNATURE_HEADER`] header as `sha256=<hex digest>`, mirroring the way
+//! GitHub signs its own webhooks. Requests give up after [`REQUEST_TIMEOUT`]
+//! so an unresponsive receiver cannot hold on to a delivery.
+
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use super::{FreezeEvent, Notifier};
+
+/// Header carrying the HMAC signature of the request body
+pub const SIGNATURE_HEADER: &str = "X-Frezze-Signature-256";
+
+/// How long a single delivery may take before it is abandoned
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Computes the `sha256=<hex digest>` signature of a payload.
+pub fn sign_payload(secret: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Notifier that POSTs freeze events to an arbitrary HTTP endpoint.
+pub struct WebhookNotifier {
+    url: String,
+    secret: Option<String>,
+    client: reqwest::Client,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: String, secret: Option<String>) -> Self {
+        Self {
+            url,
+            secret,
+            client: reqwest::Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .expect("HTTP client builds with a timeout"),
+        }
+    }
+
+    /// Serializes the event into the request body.
+    fn payload(event: &FreezeEvent) -> Result<Vec<u8>> {
+        serde_json::to_vec(event).map_err(|e| anyhow!("Failed to serialize event: {}", e))
+    }
+
+    /// Returns the signature header for a body, if a secret is configured.
+    fn signature_header(&self, body: &[u8]) -> Option<(&'static str, String)> {
+        self.secret
+            .as_deref()
+            .map(|secret| (SIGNATURE_HEADER, sign_payload(secret, body)))
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    async fn notify(&self, event: &FreezeEvent) -> Result<()> {
+        let body = Self::payload(event)?;
+
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+
+        if let Some((name, value)) = self.signature_header(&body) {
+            request = request.header(name, value);
+        }
+
+        let response = request
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| anyhow!("Webhook request failed: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "Webhook responded with status {}",
+                response.status()
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notifications::FreezeEventKind;
+    use chrono::{TimeZone, Utc};
+
+    fn sample_event() -> FreezeEvent {
+        FreezeEvent {
+            event: FreezeEventKind::Freeze,
+            repository: "owner/repo".to_string(),
+            installation_id: 12345,
+            initiated_by: "alice".to_string(),
+            started_at: Utc.with_ymd_and_hms(2025, 1, 1, 10, 0, 0).unwrap(),
+            expires_at: Some(Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap()),
+            reason: Some("Release".to_string()),
+            category: None,
+            watchers: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_payload_shape() {
+        let body = WebhookNotifier::payload(&sample_event()).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(json["event"], "freeze");
+        assert_eq!(json["repository"], "owner/repo");
+        assert_eq!(json["installation_id"], 12345);
+        assert_eq!(json["initiated_by"], "alice");
+        assert_eq!(json["started_at"], "2025-01-01T10:00:00Z");
+        assert_eq!(json["expires_at"], "2025-01-01T12:00:00Z");
+        assert_eq!(json["reason"], "Release");
+        assert!(json["category"].is_null());
+        assert_eq!(json["watchers"], serde_json::json!([]));
+
+        let mut event = sample_event();
+        event.event = FreezeEventKind::Expire;
+        event.expires_at = None;
+        let json: serde_json::Value =
+            serde_json::from_slice(&WebhookNotifier::payload(&event).unwrap()).unwrap();
+        assert_eq!(json["event"], "expire");
+        assert!(json["expires_at"].is_null());
+
+        let event = sample_event().with_watchers(vec!["bob".to_string()]);
+        let json: serde_json::Value =
+            serde_json::from_slice(&WebhookNotifier::payload(&event).unwrap()).unwrap();
+        assert_eq!(json["watchers"], serde_json::json!(["bob"]));
+    }
+
+    #[test]
+    fn test_sign_payload() {
+        assert_eq!(
+            sign_payload("topsecret", br#"{"event":"freeze"}"#),
+            "sha256=f46be4c02c9fb379171b7cb12fd05d02494995c0f6673badfc1d76b103d8bced"
+        );
+    }
+
+    #[test]
+    fn test_signature_header() {
+        let body = br#"{"event":"freeze"}"#;
+
+        let signed = WebhookNotifier::new("http://localhost".to_string(), Some("topsecret".into()));
+        let (name, value) = signed.signature_header(body).unwrap();
+        assert_eq!(name, SIGNATURE_HEADER);
+        assert_eq!(value, sign_payload("topsecret", body));
+
+        let unsigned = WebhookNotifier::new("http://localhost".to_string(), None);
+        assert!(unsigned.signature_header(body).is_none());
+    }
+}
@@ -98,6 +98,42 @@ impl PermissionService {
         matches!(role, Role::Admin)
     }
 
+    /// Checks whether a user resolves to the admin role.
+    ///
+    /// Used by commands that apply additional ownership rules on top of the
+    /// regular permission check, where admins bypass those rules.
+    ///
+    /// # Arguments
+    ///
+    /// * `installation_id` - GitHub App installation ID
+    /// * `repository` - Repository name in "owner/repo" format
+    /// * `user_login` - GitHub username
+    pub fn is_admin_user(&self, installation_id: i64, repository: &str, user_login: &str) -> bool {
+        self.user_config
+            .get_user_permissions(installation_id, repository, user_login)
+            .and_then(|perms| perms.to_role().ok())
+            .is_some_and(|role| self.is_admin(&role))
+    }
+
+    /// Checks whether a user is allowed to perform emergency overrides.
+    ///
+    /// Only admins that have `can_emergency_override` set may bypass
+    /// safety guards such as the minimum freeze duration.
+    ///
+    /// # Arguments
+    ///
+    /// * `installation_id` - GitHub App installation ID
+    /// * `repository` - Repository name in "owner/repo" format
+    /// * `user_login` - GitHub username
+    pub fn can_override(&self, installation_id: i64, repository: &str, user_login: &str) -> bool {
+        self.user_config
+            .get_user_permissions(installation_id, repository, user_login)
+            .is_some_and(|perms| {
+                perms.can_emergency_override
+                    && perms.to_role().is_ok_and(|role| self.is_admin(&role))
+            })
+    }
+
     /// Checks if a user has permission to execute a specific command.
     ///
     /// This method evaluates permissions using the hierarchical system:
@@ -235,7 +271,7 @@ impl PermissionService {
                     ))
                 }
             }
-            Command::UnfreezeAll => {
+            Command::UnfreezeAll(_) => {
                 if self.can_unfreeze_all(&role, user_permissions) {
                     PermissionResult::Allowed
                 } else {
@@ -275,6 +311,97 @@ impl PermissionService {
                     ))
                 }
             }
+            Command::FreezeReassign(_) => {
+                if self.can_reassign_freeze(&role, user_permissions) {
+                    PermissionResult::Allowed
+                } else {
+                    PermissionResult::Denied(format!(
+                        "User role '{}' does not have freeze reassign permissions",
+                        role
+                    ))
+                }
+            }
+            Command::Extend(_) => {
+                if self.can_freeze(&role, user_permissions) {
+                    PermissionResult::Allowed
+                } else {
+                    PermissionResult::Denied(format!(
+                        "User role '{}' does not have freeze permissions",
+                        role
+                    ))
+                }
+            }
+            Command::Audit(_) => {
+                if self.is_admin(&role) {
+                    PermissionResult::Allowed
+                } else {
+                    PermissionResult::Denied(format!(
+                        "User role '{}' does not have audit permissions",
+                        role
+                    ))
+                }
+            }
+            Command::Preview(_) => {
+                if self.is_admin(&role) {
+                    PermissionResult::Allowed
+                } else {
+                    PermissionResult::Denied(format!(
+                        "User role '{}' does not have preview permissions",
+                        role
+                    ))
+                }
+            }
+            Command::SchedulerPreview => {
+                if self.is_admin(&role) {
+                    PermissionResult::Allowed
+                } else {
+                    PermissionResult::Denied(format!(
+                        "User role '{}' does not have scheduler preview permissions",
+                        role
+                    ))
+                }
+            }
+            // Who may approve is decided by the installation's `unfreeze_approvers`
+            Command::ApproveUnfreeze => {
+                if self.can_view_status(&role) {
+                    PermissionResult::Allowed
+                } else {
+                    PermissionResult::Denied(format!(
+                        "User role '{}' does not have unfreeze approval permissions",
+                        role
+                    ))
+                }
+            }
+            Command::Reconcile(_) => {
+                if self.is_admin(&role) {
+                    PermissionResult::Allowed
+                } else {
+                    PermissionResult::Denied(format!(
+                        "User role '{}' does not have reconcile permissions",
+                        role
+                    ))
+                }
+            }
+            Command::Scheduled => {
+                if self.can_view_status(&role) {
+                    PermissionResult::Allowed
+                } else {
+                    PermissionResult::Denied(format!(
+                        "User role '{}' does not have status viewing permissions",
+                        role
+                    ))
+                }
+            }
+            Command::CancelSchedule(_) => {
+                if self.can_schedule_freeze(&role, user_permissions) {
+                    PermissionResult::Allowed
+                } else {
+                    PermissionResult::Denied(format!(
+                        "User role '{}' does not have schedule freeze permissions",
+                        role
+                    ))
+                }
+            }
         };
 
         Ok(result)
@@ -385,6 +512,27 @@ impl PermissionService {
             Role::Contributor => false,
         }
     }
+
+    /// Checks if the user can reassign freeze ownership.
+    ///
+    /// Non-admin users must additionally be the current owner of the freeze,
+    /// which is verified by the freeze manager against the active record.
+    ///
+    /// # Arguments
+    ///
+    /// * `role` - The user's role
+    /// * `permissions` - The user's specific permissions
+    ///
+    /// # Returns
+    ///
+    /// `true` if the user can reassign freezes, `false` otherwise.
+    fn can_reassign_freeze(&self, role: &Role, permissions: &UserPermissions) -> bool {
+        match role {
+            Role::Admin => true,
+            Role::Maintainer => permissions.can_freeze,
+            Role::Contributor => false,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -418,6 +566,7 @@ mod tests {
         assert!(service.can_unfreeze_all(&Role::Admin, &permissions));
         assert!(service.can_view_status(&Role::Admin));
         assert!(service.can_schedule_freeze(&Role::Admin, &permissions));
+        assert!(service.can_reassign_freeze(&Role::Admin, &permissions));
     }
 
     #[test]
@@ -432,6 +581,7 @@ mod tests {
         assert!(service.can_unfreeze_all(&Role::Maintainer, &permissions_with_freeze));
         assert!(service.can_view_status(&Role::Maintainer));
         assert!(service.can_schedule_freeze(&Role::Maintainer, &permissions_with_freeze));
+        assert!(service.can_reassign_freeze(&Role::Maintainer, &permissions_with_freeze));
 
         // Maintainer without freeze permissions
         let permissions_without_freeze = create_test_permissions("maintainer", false, false);
@@ -441,6 +591,7 @@ mod tests {
         assert!(!service.can_unfreeze_all(&Role::Maintainer, &permissions_without_freeze));
         assert!(service.can_view_status(&Role::Maintainer));
         assert!(!service.can_schedule_freeze(&Role::Maintainer, &permissions_without_freeze));
+        assert!(!service.can_reassign_freeze(&Role::Maintainer, &permissions_without_freeze));
     }
 
     #[test]
@@ -455,6 +606,176 @@ mod tests {
         assert!(!service.can_unfreeze_all(&Role::Contributor, &permissions));
         assert!(service.can_view_status(&Role::Contributor));
         assert!(!service.can_schedule_freeze(&Role::Contributor, &permissions));
+        assert!(!service.can_reassign_freeze(&Role::Contributor, &permissions));
+    }
+
+    #[test]
+    fn test_is_admin_user() {
+        let service = create_test_service();
+
+        assert!(service.is_admin_user(12345, "owner/repo", "admin_user"));
+        assert!(!service.is_admin_user(12345, "owner/repo", "maintainer_user"));
+        assert!(!service.is_admin_user(12345, "owner/repo", "unknown_user"));
+        assert!(!service.is_admin_user(99999, "owner/repo", "admin_user"));
+    }
+
+    #[test]
+    fn test_can_override() {
+        let service = create_test_service();
+
+        assert!(service.can_override(12345, "owner/repo", "admin_user"));
+        assert!(!service.can_override(12345, "owner/repo", "maintainer_user"));
+        assert!(!service.can_override(12345, "owner/repo", "unknown_user"));
+    }
+
+    #[test]
+    fn test_audit_is_admin_only() {
+        use crate::freezer::commands::AuditArgs;
+
+        let service = create_test_service();
+        let audit = Command::Audit(AuditArgs { limit: 20 });
+
+        let admin = create_test_permissions("admin", false, false);
+        assert!(matches!(
+            service.check_command_permission(&admin, &audit).unwrap(),
+            PermissionResult::Allowed
+        ));
+
+        let maintainer = create_test_permissions("maintainer", true, true);
+        assert!(matches!(
+            service.check_command_permission(&maintainer, &audit).unwrap(),
+            PermissionResult::Denied(_)
+        ));
+    }
+
+    #[test]
+    fn test_preview_is_admin_only() {
+        use crate::freezer::commands::PreviewArgs;
+
+        let service = create_test_service();
+        let preview = Command::Preview(PreviewArgs { message_type: None });
+
+        let admin = create_test_permissions("admin", false, false);
+        assert!(matches!(
+            service.check_command_permission(&admin, &preview).unwrap(),
+            PermissionResult::Allowed
+        ));
+
+        let maintainer = create_test_permissions("maintainer", true, true);
+        assert!(matches!(
+            service.check_command_permission(&maintainer, &preview).unwrap(),
+            PermissionResult::Denied(_)
+        ));
+    }
+
+    #[test]
+    fn test_scheduler_preview_is_admin_only() {
+        let service = create_test_service();
+
+        let admin = create_test_permissions("admin", false, false);
+        assert!(matches!(
+            service
+                .check_command_permission(&admin, &Command::SchedulerPreview)
+                .unwrap(),
+            PermissionResult::Allowed
+        ));
+
+        let maintainer = create_test_permissions("maintainer", true, true);
+        assert!(matches!(
+            service
+                .check_command_permission(&maintainer, &Command::SchedulerPreview)
+                .unwrap(),
+            PermissionResult::Denied(_)
+        ));
+    }
+
+    #[test]
+    fn test_reconcile_is_admin_only() {
+        use crate::freezer::commands::ReconcileArgs;
+
+        let service = create_test_service();
+        let reconcile = Command::Reconcile(ReconcileArgs {
+            repos: Vec::new(),
+            fix: true,
+        });
+
+        let admin = create_test_permissions("admin", false, false);
+        assert!(matches!(
+            service
+                .check_command_permission(&admin, &reconcile)
+                .unwrap(),
+            PermissionResult::Allowed
+        ));
+
+        let maintainer = create_test_permissions("maintainer", true, true);
+        assert!(matches!(
+            service
+                .check_command_permission(&maintainer, &reconcile)
+                .unwrap(),
+            PermissionResult::Denied(_)
+        ));
+    }
+
+    #[test]
+    fn test_scheduled_commands_permissions() {
+        use crate::freezer::commands::CancelScheduleArgs;
+
+        let service = create_test_service();
+        let cancel = Command::CancelSchedule(CancelScheduleArgs { id: "id".to_string() });
+
+        // Listing follows status permissions, cancelling follows scheduling permissions
+        let contributor = create_test_permissions("contributor", false, false);
+        assert!(matches!(
+            service.check_command_permission(&contributor, &Command::Scheduled).unwrap(),
+            PermissionResult::Allowed
+        ));
+        assert!(matches!(
+            service.check_command_permission(&contributor, &cancel).unwrap(),
+            PermissionResult::Denied(_)
+        ));
+
+        let maintainer = create_test_permissions("maintainer", true, true);
+        assert!(matches!(
+            service.check_command_permission(&maintainer, &cancel).unwrap(),
+            PermissionResult::Allowed
+        ));
+    }
+
+    #[test]
+    fn test_approve_unfreeze_follows_status_permissions() {
+        let service = create_test_service();
+
+        // The installation's approvers decide who may approve, not the role
+        let contributor = create_test_permissions("contributor", false, false);
+        assert!(matches!(
+            service
+                .check_command_permission(&contributor, &Command::ApproveUnfreeze)
+                .unwrap(),
+            PermissionResult::Allowed
+        ));
+    }
+
+    #[test]
+    fn test_extend_follows_freeze_permissions() {
+        use crate::freezer::commands::{ExtendArgs, FreezeDuration};
+
+        let service = create_test_service();
+        let extend = Command::Extend(ExtendArgs {
+            duration: FreezeDuration::Wall(chrono::Duration::hours(2)),
+            branch: None,
+        });
+
+        let maintainer = create_test_permissions("maintainer", true, false);
+        assert!(matches!(
+            service.check_command_permission(&maintainer, &extend).unwrap(),
+            PermissionResult::Allowed
+        ));
+
+        let contributor = create_test_permissions("contributor", false, false);
+        assert!(matches!(
+            service.check_command_permission(&contributor, &extend).unwrap(),
+            PermissionResult::Denied(_)
+        ));
     }
 
     fn create_test_service() -> PermissionService {
@@ -1,11 +1,25 @@
 use octofer::octocrab;
 
+/// Repository name standing for every repository of an owner.
+///
+/// Organization-wide freezes are stored as a single record for `owner/*`.
+pub const ORG_WILDCARD: &str = "*";
+
+/// Host accepted in repository URLs given by users.
+const GITHUB_HOST: &str = "github.com";
+
+/// Web address of GitHub, used in links unless an Enterprise Server address is configured.
+pub const DEFAULT_GITHUB_URL: &str = "https://github.com";
+
 /// Represents a GitHub repository with owner and name components.
 ///
 /// This struct ensures consistent handling of repository identifiers throughout
 /// the application, providing utilities to construct and deconstruct the
 /// "owner/repo" format used by the database.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Like on GitHub, equality ignores ASCII case, so `owner/Repo` and
+/// `owner/repo` refer to the same repository.
+#[derive(Debug, Clone)]
 pub struct Repository {
     pub owner: String,
     pub name: String,
@@ -66,6 +80,75 @@ impl Repository {
         }
     }
 
+    /// Parses a repository typed by a user, tolerating pasted URLs.
+    ///
+    /// Besides "owner/repo", this accepts a `.git` suffix, GitHub URLs such as
+    /// `https://github.com/owner/repo` (further path segments like `/pull/1` are
+    /// ignored) and SSH remotes like `git@github.com:owner/repo.git`. URLs of
+    /// other hosts are rejected. Internal callers should use [`Repository::parse`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frezze::repository::Repository;
+    ///
+    /// let repo = Repository::parse_flexible("https://github.com/octocat/Hello-World").unwrap();
+    /// assert_eq!(repo.full_name(), "octocat/Hello-World");
+    ///
+    /// assert!(Repository::parse_flexible("https://gitlab.com/octocat/Hello-World").is_none());
+    /// ```
+    pub fn parse_flexible(input: &str) -> Option<Self> {
+        let input = input.trim();
+
+        let (host, path) = if let Some(remote) = input.strip_prefix("git@") {
+            let (host, path) = remote.split_once(':')?;
+            (Some(host), path)
+        } else {
+            let rest = input
+                .strip_prefix("https://")
+                .or_else(|| input.strip_prefix("http://"));
+            match rest {
+                Some(rest) => {
+                    let (host, path) = rest.split_once('/')?;
+                    (Some(host), path)
+                }
+                // GitHub logins cannot contain dots, so a dotted first segment is a host
+                None => match input.split_once('/') {
+                    Some((host, path)) if host.contains('.') => (Some(host), path),
+                    _ => (None, input),
+                },
+            }
+        };
+
+        let Some(host) = host else {
+            return Self::parse(path.strip_suffix(".git").unwrap_or(path));
+        };
+        let host = host.strip_prefix("www.").unwrap_or(host);
+        if !host.eq_ignore_ascii_case(GITHUB_HOST) {
+            return None;
+        }
+
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        let mut segments = path.split('/').filter(|s| !s.is_empty());
+        let owner = segments.next()?;
+        let name = segments.next()?;
+        let name = name.strip_suffix(".git").unwrap_or(name);
+        if name.is_empty() || name == ORG_WILDCARD {
+            return None;
+        }
+        Some(Self::new(owner, name))
+    }
+
+    /// Returns the wildcard repository covering every repository of `owner`.
+    pub fn org_wildcard(owner: impl Into<String>) -> Self {
+        Self::new(owner, ORG_WILDCARD)
+    }
+
+    /// Returns `true` if this is the `owner/*` wildcard of an organization.
+    pub fn is_org_wildcard(&self) -> bool {
+        self.name == ORG_WILDCARD
+    }
+
     /// Returns the owner component.
     pub fn owner(&self) -> &str {
         &self.owner
@@ -75,6 +158,48 @@ impl Repository {
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    /// Returns the web page of the repository on the GitHub instance at `base_url`.
+    ///
+    /// The `owner/*` wildcard of an organization-wide freeze links to the
+    /// owner's page.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frezze::repository::{DEFAULT_GITHUB_URL, Repository};
+    ///
+    /// let repo = Repository::new("octocat", "Hello-World");
+    /// assert_eq!(repo.html_url(DEFAULT_GITHUB_URL), "https://github.com/octocat/Hello-World");
+    /// ```
+    pub fn html_url(&self, base_url: &str) -> String {
+        let base_url = base_url.trim_end_matches('/');
+        if self.is_org_wildcard() {
+            format!("{}/{}", base_url, self.owner)
+        } else {
+            format!("{}/{}/{}", base_url, self.owner, self.name)
+        }
+    }
+
+    /// Returns the web page of a pull request of the repository.
+    pub fn pr_url(&self, base_url: &str, pr_number: u64) -> String {
+        format!("{}/pull/{}", self.html_url(base_url), pr_number)
+    }
+}
+
+impl PartialEq for Repository {
+    fn eq(&self, other: &Self) -> bool {
+        self.owner.eq_ignore_ascii_case(&other.owner) && self.name.eq_ignore_ascii_case(&other.name)
+    }
+}
+
+impl Eq for Repository {}
+
+impl std::hash::Hash for Repository {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.owner.to_ascii_lowercase().hash(state);
+        self.name.to_ascii_lowercase().hash(state);
+    }
 }
 
 impl std::fmt::Display for Repository {
@@ -165,6 +290,82 @@ mod tests {
         assert!(Repository::parse("owner//repo").is_none());
     }
 
+    #[test]
+    fn test_parse_flexible_plain() {
+        let repo = Repository::parse_flexible("octocat/Hello-World").unwrap();
+        assert_eq!(repo.full_name(), "octocat/Hello-World");
+
+        let repo = Repository::parse_flexible(" octocat/Hello-World.git ").unwrap();
+        assert_eq!(repo.full_name(), "octocat/Hello-World");
+
+        // Dots inside the name are kept
+        let repo = Repository::parse_flexible("my-org/some-repo.test").unwrap();
+        assert_eq!(repo.name, "some-repo.test");
+
+        assert!(Repository::parse_flexible("invalid").is_none());
+        assert!(Repository::parse_flexible("too/many/parts").is_none());
+        assert!(Repository::parse_flexible("owner/.git").is_none());
+    }
+
+    #[test]
+    fn test_parse_flexible_urls() {
+        for input in [
+            "https://github.com/octocat/Hello-World",
+            "https://github.com/octocat/Hello-World/",
+            "https://github.com/octocat/Hello-World.git",
+            "http://www.github.com/octocat/Hello-World",
+            "https://GitHub.com/octocat/Hello-World/pull/42",
+            "https://github.com/octocat/Hello-World?tab=readme#usage",
+            "github.com/octocat/Hello-World",
+            "git@github.com:octocat/Hello-World.git",
+        ] {
+            let repo = Repository::parse_flexible(input)
+                .unwrap_or_else(|| panic!("Failed to parse '{}'", input));
+            assert_eq!(repo.full_name(), "octocat/Hello-World", "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn test_parse_flexible_rejects_other_hosts() {
+        assert!(Repository::parse_flexible("https://gitlab.com/octocat/Hello-World").is_none());
+        assert!(Repository::parse_flexible("https://github.example.com/octocat/repo").is_none());
+        assert!(Repository::parse_flexible("git@bitbucket.org:octocat/Hello-World.git").is_none());
+        assert!(Repository::parse_flexible("example.com/octocat/Hello-World").is_none());
+
+        // GitHub URLs must point to a repository
+        assert!(Repository::parse_flexible("https://github.com/octocat").is_none());
+        assert!(Repository::parse_flexible("https://github.com/").is_none());
+        assert!(Repository::parse_flexible("https://github.com/octocat/*").is_none());
+
+        // The strict parser keeps rejecting URLs
+        assert!(Repository::parse("https://github.com/octocat/Hello-World").is_none());
+    }
+
+    #[test]
+    fn test_eq_ignores_case() {
+        use std::collections::HashSet;
+
+        let lower = Repository::new("octocat", "hello-world");
+        let mixed = Repository::parse("OctoCat/Hello-World").unwrap();
+        assert_eq!(lower, mixed);
+        assert_ne!(lower, Repository::new("octocat", "other"));
+
+        let set: HashSet<_> = [lower, mixed.clone()].into_iter().collect();
+        assert_eq!(set.len(), 1);
+
+        // The original spelling is kept for display and API calls
+        assert_eq!(mixed.full_name(), "OctoCat/Hello-World");
+    }
+
+    #[test]
+    fn test_org_wildcard() {
+        let org = Repository::org_wildcard("octocat");
+        assert_eq!(org.full_name(), "octocat/*");
+        assert!(org.is_org_wildcard());
+        assert!(Repository::parse("octocat/*").unwrap().is_org_wildcard());
+        assert!(!Repository::new("octocat", "Hello-World").is_org_wildcard());
+    }
+
     #[test]
     fn test_display() {
         let repo = Repository::new("octocat", "Hello-World");
@@ -206,4 +407,38 @@ mod tests {
         assert_eq!(parsed.owner(), github_owner);
         assert_eq!(parsed.name(), github_repo);
     }
+
+    #[test]
+    fn test_html_url() {
+        let repo = Repository::new("octocat", "Hello-World");
+        assert_eq!(
+            repo.html_url(DEFAULT_GITHUB_URL),
+            "https://github.com/octocat/Hello-World"
+        );
+        assert_eq!(
+            repo.pr_url(DEFAULT_GITHUB_URL, 42),
+            "https://github.com/octocat/Hello-World/pull/42"
+        );
+
+        // Organization-wide freezes link to the owner
+        assert_eq!(
+            Repository::org_wildcard("octocat").html_url(DEFAULT_GITHUB_URL),
+            "https://github.com/octocat"
+        );
+    }
+
+    #[test]
+    fn test_html_url_enterprise() {
+        let repo = Repository::new("platform", "api");
+        for base_url in ["https://github.example.com", "https://github.example.com/"] {
+            assert_eq!(
+                repo.html_url(base_url),
+                "https://github.example.com/platform/api"
+            );
+            assert_eq!(
+                repo.pr_url(base_url, 7),
+                "https://github.example.com/platform/api/pull/7"
+            );
+        }
+    }
 }
@@ -2,50 +2,86 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
-use tokio::time::interval;
+use tokio::time::{MissedTickBehavior, interval};
 use tracing::{error, info, warn};
 
 use crate::{
+    config::AppConfig,
     database::{
         Database,
-        models::{FreezeRecord, FreezeStatus},
+        models::{FreezeRecord, FreezeReminder, FreezeStatus},
     },
-    freezer::manager::FreezeManager,
+    freezer::{cache::FreezeCache, manager::FreezeManager, messages},
+    installation::InstallationId,
+    notifications::{FreezeEvent, FreezeEventKind},
     repository::Repository,
 };
 
-/// Helper function to parse SQLite datetime string to DateTime<Utc>
-fn parse_datetime(datetime_str: &str) -> anyhow::Result<DateTime<Utc>> {
-    datetime_str
-        .parse::<DateTime<Utc>>()
-        .map_err(|e| anyhow::anyhow!("Failed to parse datetime: {}", e))
+use octofer::github::GitHubClient;
+
+/// How often the worker checks for freezes to activate or expire
+pub const SCHEDULER_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Freezes the worker will act on at a given time
+#[derive(Debug, Default)]
+pub struct SchedulerPreview {
+    /// Scheduled freezes that will be activated
+    pub to_activate: Vec<FreezeRecord>,
+    /// Active freezes that will be marked as expired
+    pub to_expire: Vec<FreezeRecord>,
 }
 
-/// Helper function to parse optional SQLite datetime string to Option<DateTime<Utc>>
-fn parse_optional_datetime(datetime_str: Option<String>) -> anyhow::Result<Option<DateTime<Utc>>> {
-    match datetime_str {
-        Some(s) => Ok(Some(parse_datetime(&s)?)),
-        None => Ok(None),
-    }
+/// Runs the worker's selection queries for a tick at `at` without changing anything.
+///
+/// Only freezes of `installation_id` are returned.
+pub async fn scheduler_preview(
+    pool: &sqlx::SqlitePool,
+    installation_id: InstallationId,
+    at: DateTime<Utc>,
+) -> anyhow::Result<SchedulerPreview> {
+    let of_installation = |records: Vec<FreezeRecord>| -> Vec<FreezeRecord> {
+        records
+            .into_iter()
+            .filter(|r| r.installation_id == installation_id.get())
+            .collect()
+    };
+
+    Ok(SchedulerPreview {
+        to_activate: of_installation(FreezeRecord::get_scheduled_freezes_to_activate(pool, at).await?),
+        to_expire: of_installation(FreezeRecord::get_expired_freezes(pool, at).await?),
+    })
 }
-use octofer::github::GitHubClient;
 
 /// Worker that checks for scheduled freezes and activates them when their time comes
 pub struct FreezeSchedulerWorker {
     db: Arc<Database>,
     github: Arc<GitHubClient>,
+    config: Arc<AppConfig>,
+    cache: Arc<FreezeCache>,
 }
 
 impl FreezeSchedulerWorker {
-    pub fn new(db: Arc<Database>, github: Arc<GitHubClient>) -> Self {
-        Self { db, github }
+    pub fn new(
+        db: Arc<Database>,
+        github: Arc<GitHubClient>,
+        config: Arc<AppConfig>,
+        cache: Arc<FreezeCache>,
+    ) -> Self {
+        Self {
+            db,
+            github,
+            config,
+            cache,
+        }
     }
 
     /// Start the worker that checks for scheduled freezes every minute
     pub async fn start(&self) {
         info!("Starting freeze scheduler worker");
 
-        let mut interval = interval(Duration::from_secs(60)); // Check every minute
+        let mut interval = interval(SCHEDULER_INTERVAL);
+        // A slow run delays the next one instead of triggering a burst of catch-up ticks
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
 
         loop {
             interval.tick().await;
@@ -53,6 +89,18 @@ impl FreezeSchedulerWorker {
             if let Err(e) = self.check_and_activate_scheduled_freezes().await {
                 error!("Error checking scheduled freezes: {}", e);
             }
+
+            if let Err(e) = self.expire_elapsed_freezes().await {
+                error!("Error expiring elapsed freezes: {}", e);
+            }
+
+            if let Err(e) = self.remind_long_running_freezes().await {
+                error!("Error reminding about long-running freezes: {}", e);
+            }
+
+            if let Err(e) = self.apply_freeze_retention().await {
+                error!("Error applying freeze retention: {}", e);
+            }
         }
     }
 
@@ -77,12 +125,13 @@ impl FreezeSchedulerWorker {
 
         for freeze_record in scheduled_freezes {
             match self.activate_scheduled_freeze(&freeze_record).await {
-                Ok(_) => {
+                Ok(true) => {
                     info!(
                         "Successfully activated scheduled freeze for repository: {}",
                         freeze_record.repository
                     );
                 }
+                Ok(false) => {}
                 Err(e) => {
                     error!(
                         "Failed to activate scheduled freeze for repository {}: {}",
@@ -95,58 +144,212 @@ impl FreezeSchedulerWorker {
         Ok(())
     }
 
+    /// Mark active freezes whose end time has passed, or that exceed the
+    /// maximum freeze age, as expired
+    async fn expire_elapsed_freezes(&self) -> anyhow::Result<()> {
+        let conn = self
+            .db
+            .get_connection()
+            .map_err(|e| anyhow::anyhow!("Failed to get database connection: {}", e))?;
+
+        let now = Utc::now();
+        let mut elapsed_freezes = FreezeRecord::get_expired_freezes(conn, now).await?;
+
+        // Freezes left on for longer than the maximum age are most likely forgotten
+        if let Some(max_age) = self.config.max_freeze_age {
+            for stale in FreezeRecord::get_stale_freezes(conn, now, max_age).await? {
+                if elapsed_freezes.iter().all(|r| r.id != stale.id) {
+                    warn!(
+                        "Expiring freeze {} of {} active since {}, it is older than the maximum freeze age",
+                        stale.id, stale.repository, stale.started_at
+                    );
+                    elapsed_freezes.push(stale);
+                }
+            }
+        }
+
+        if elapsed_freezes.is_empty() {
+            return Ok(());
+        }
+
+        info!("Found {} elapsed freezes to expire", elapsed_freezes.len());
+
+        let freeze_manager =
+            FreezeManager::with_config(self.db.clone(), self.github.clone(), self.config.clone())
+                .with_cache(self.cache.clone());
+
+        for freeze_record in elapsed_freezes {
+            let Some(expired) = FreezeRecord::update_status(
+                conn,
+                freeze_record.id.clone(),
+                FreezeStatus::Expired,
+                None,
+                None,
+            )
+            .await?
+            else {
+                continue;
+            };
+
+            self.cache
+                .invalidate(expired.installation_id.into(), &expired.repository);
+            info!("Freeze for repository {} has expired", expired.repository);
+
+            freeze_manager
+                .notifier
+                .dispatch(
+                    FreezeEvent::from_record(FreezeEventKind::Expire, &expired).with_watchers(
+                        freeze_manager.watchers_for(
+                            expired.installation_id.into(),
+                            &expired.repository,
+                            &expired.initiated_by,
+                        ),
+                    ),
+                )
+                .await;
+
+            // Clear the freeze check runs now that the freeze is over
+            let Some(repository) = Repository::parse(&expired.repository) else {
+                warn!("Invalid repository format: {}", expired.repository);
+                continue;
+            };
+            freeze_manager
+                .refresh_prs(expired.installation_id.into(), &repository, None)
+                .await;
+        }
+
+        Ok(())
+    }
+
+    /// Remind initiators about freezes that have been left on for too long
+    async fn remind_long_running_freezes(&self) -> anyhow::Result<()> {
+        let Some(threshold) = self.config.freeze_reminder_after else {
+            return Ok(());
+        };
+
+        let conn = self
+            .db
+            .get_connection()
+            .map_err(|e| anyhow::anyhow!("Failed to get database connection: {}", e))?;
+
+        let now = Utc::now();
+        let due: Vec<_> = FreezeReminder::list_active(conn)
+            .await?
+            .into_iter()
+            .filter(|(record, reminder)| {
+                needs_reminder(
+                    record,
+                    reminder.last_reminded_at,
+                    now,
+                    threshold,
+                    self.config.freeze_reminder_interval,
+                )
+            })
+            .collect();
+
+        if due.is_empty() {
+            return Ok(());
+        }
+
+        info!("Reminding initiators of {} long-running freezes", due.len());
+
+        let freeze_manager =
+            FreezeManager::with_config(self.db.clone(), self.github.clone(), self.config.clone())
+                .with_cache(self.cache.clone());
+
+        for (record, reminder) in due {
+            let thread = reminder.thread_repository(&record);
+            let Some(repository) = Repository::parse(thread) else {
+                warn!("Invalid repository format: {}", thread);
+                continue;
+            };
+
+            let msg = messages::freeze_reminder(
+                &record.initiated_by,
+                &record.repository,
+                &messages::format_duration_display(now - record.started_at),
+                &messages::format_branch_display(record.branch.clone()),
+            );
+            freeze_manager
+                .notify_comment_issue(
+                    record.installation_id.into(),
+                    &repository,
+                    reminder.issue_number as u64,
+                    &msg,
+                )
+                .await;
+
+            FreezeReminder::mark_reminded(conn, &record.id, now).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Soft-delete finished freezes past the retention period, and purge the
+    /// ones soft-deleted for longer than the grace period
+    async fn apply_freeze_retention(&self) -> anyhow::Result<()> {
+        let Some(retention) = self.config.freeze_retention else {
+            return Ok(());
+        };
+
+        let conn = self
+            .db
+            .get_connection()
+            .map_err(|e| anyhow::anyhow!("Failed to get database connection: {}", e))?;
+
+        let now = Utc::now();
+        let deleted = FreezeRecord::soft_delete_before(conn, now - retention, now).await?;
+        if deleted > 0 {
+            info!("Soft-deleted {} freezes past the retention period", deleted);
+        }
+
+        if let Some(grace) = self.config.freeze_retention_grace {
+            let purged = FreezeRecord::purge_deleted(conn, now - grace).await?;
+            if purged > 0 {
+                info!("Purged {} soft-deleted freezes", purged);
+            }
+        }
+
+        Ok(())
+    }
+
     /// Get scheduled freezes that should be activated now
     async fn get_scheduled_freezes_to_activate(
         &self,
         conn: &sqlx::SqlitePool,
     ) -> anyhow::Result<Vec<FreezeRecord>> {
-        let now = Utc::now();
-
-        let rows = sqlx::query!(
-            r#"
-            SELECT * FROM freeze_records 
-            WHERE status = 'scheduled' 
-            AND started_at <= $1
-            ORDER BY started_at ASC
-            "#,
-            now
-        )
-        .fetch_all(conn)
-        .await
-        .map_err(|e| anyhow::anyhow!("Failed to query scheduled freezes: {}", e))?;
-
-        let mut records = Vec::new();
-        for row in rows {
-            records.push(FreezeRecord {
-                id: row.id.unwrap_or_default(),
-                repository: row.repository,
-                installation_id: row.installation_id,
-                started_at: parse_datetime(&row.started_at).unwrap_or_else(|_| Utc::now()),
-                expires_at: parse_optional_datetime(row.expires_at).unwrap_or(None),
-                ended_at: parse_optional_datetime(row.ended_at).unwrap_or(None),
-                reason: row.reason,
-                initiated_by: row.initiated_by,
-                ended_by: row.ended_by,
-                status: FreezeStatus::from(row.status.as_str()),
-                branch: row.branch,
-                created_at: parse_datetime(&row.created_at).unwrap_or_else(|_| Utc::now()),
-            });
-        }
-
-        Ok(records)
+        FreezeRecord::get_scheduled_freezes_to_activate(conn, Utc::now())
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to query scheduled freezes: {}", e))
     }
 
-    /// Activate a scheduled freeze by updating its status and applying the freeze
-    async fn activate_scheduled_freeze(&self, freeze_record: &FreezeRecord) -> anyhow::Result<()> {
+    /// Activate a scheduled freeze by updating its status and applying the freeze.
+    ///
+    /// Returns `false` if the freeze was cancelled instead because another freeze
+    /// of the same target was already active.
+    async fn activate_scheduled_freeze(&self, freeze_record: &FreezeRecord) -> anyhow::Result<bool> {
         let conn = self
             .db
             .get_connection()
             .map_err(|e| anyhow::anyhow!("Failed to get database connection: {}", e))?;
 
         // Update the freeze status to active
-        FreezeRecord::update_status(conn, freeze_record.id.clone(), FreezeStatus::Active, None)
+        let activated = FreezeRecord::activate_scheduled(conn, &freeze_record.id)
             .await
             .map_err(|e| anyhow::anyhow!("Failed to update freeze status: {}", e))?;
+        if !activated {
+            warn!(
+                repository = %freeze_record.repository,
+                installation_id = freeze_record.installation_id,
+                freeze_id = %freeze_record.id,
+                "Cancelled scheduled freeze overlapping an active freeze"
+            );
+            return Ok(false);
+        }
+        self.cache.invalidate(
+            freeze_record.installation_id.into(),
+            &freeze_record.repository,
+        );
 
         // Parse repository name
         let parts: Vec<&str> = freeze_record.repository.split('/').collect();
@@ -160,14 +363,29 @@ impl FreezeSchedulerWorker {
         let repository = Repository::new(parts[0], parts[1]);
 
         // Apply the freeze using the freeze manager
-        let freeze_manager = FreezeManager::new(self.db.clone(), self.github.clone());
+        let freeze_manager =
+            FreezeManager::with_config(self.db.clone(), self.github.clone(), self.config.clone())
+                .with_cache(self.cache.clone());
+
+        freeze_manager
+            .notifier
+            .dispatch(
+                FreezeEvent::from_record(FreezeEventKind::Activate, freeze_record).with_watchers(
+                    freeze_manager.watchers_for(
+                        freeze_record.installation_id.into(),
+                        &freeze_record.repository,
+                        &freeze_record.initiated_by,
+                    ),
+                ),
+            )
+            .await;
 
         // We call the internal handle_freeze method directly since we already have the record
         // and don't want to create a duplicate entry
         match self
             .apply_freeze_to_repository(
                 &freeze_manager,
-                freeze_record.installation_id as u64,
+                freeze_record.installation_id.into(),
                 &repository,
                 freeze_record,
             )
@@ -190,14 +408,14 @@ impl FreezeSchedulerWorker {
             }
         }
 
-        Ok(())
+        Ok(true)
     }
 
     /// Apply freeze operations to a repository (like updating PRs, etc.)
     async fn apply_freeze_to_repository(
         &self,
         freeze_manager: &FreezeManager,
-        installation_id: u64,
+        installation_id: InstallationId,
         repository: &Repository,
         freeze_record: &FreezeRecord,
     ) -> anyhow::Result<()> {
@@ -226,3 +444,153 @@ impl FreezeSchedulerWorker {
         Ok(())
     }
 }
+
+/// Decides whether the initiator of an active freeze should be reminded about it.
+///
+/// Only freezes older than `threshold` that have no end time, or whose end time
+/// has already passed, are escalated. Once reminded, the initiator is not
+/// reminded again before `interval` has elapsed.
+fn needs_reminder(
+    record: &FreezeRecord,
+    last_reminded_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    threshold: chrono::Duration,
+    interval: chrono::Duration,
+) -> bool {
+    let long_running = now - record.started_at >= threshold;
+    let open_ended = record.expires_at.is_none_or(|expires_at| expires_at <= now);
+    let reminder_due = last_reminded_at.is_none_or(|reminded_at| now - reminded_at >= interval);
+
+    long_running && open_ended && reminder_due
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record_started(started_at: DateTime<Utc>, expires_at: Option<DateTime<Utc>>) -> FreezeRecord {
+        FreezeRecord::new(
+            "owner/repo".to_string(),
+            12345.into(),
+            started_at,
+            expires_at,
+            None,
+            "alice".to_string(),
+            None,
+        )
+    }
+
+    #[test]
+    fn test_needs_reminder_threshold() {
+        let now = Utc::now();
+        let day = chrono::Duration::hours(24);
+
+        // Open-ended freeze older than the threshold
+        let old = record_started(now - chrono::Duration::hours(25), None);
+        assert!(needs_reminder(&old, None, now, day, day));
+
+        // Open-ended freeze younger than the threshold
+        let recent = record_started(now - chrono::Duration::hours(2), None);
+        assert!(!needs_reminder(&recent, None, now, day, day));
+
+        // Old freeze whose end time has already passed
+        let overdue = record_started(
+            now - chrono::Duration::hours(30),
+            Some(now - chrono::Duration::hours(1)),
+        );
+        assert!(needs_reminder(&overdue, None, now, day, day));
+
+        // Old freeze that still has a future end time
+        let planned = record_started(
+            now - chrono::Duration::hours(30),
+            Some(now + chrono::Duration::hours(1)),
+        );
+        assert!(!needs_reminder(&planned, None, now, day, day));
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_preview() {
+        let db = Database::new_in_memory().await;
+        let pool = db.pool();
+        let now = Utc::now();
+
+        let scheduled = |repository: &str, installation_id: i64, starts_in: chrono::Duration| {
+            FreezeRecord::new_scheduled(
+                repository.to_string(),
+                installation_id.into(),
+                now + starts_in,
+                Some(now + starts_in + chrono::Duration::hours(2)),
+                None,
+                "alice".to_string(),
+                None,
+            )
+        };
+        let active = |repository: &str, ends_in: chrono::Duration| {
+            FreezeRecord::new(
+                repository.to_string(),
+                12345.into(),
+                now - chrono::Duration::hours(3),
+                Some(now + ends_in),
+                None,
+                "alice".to_string(),
+                None,
+            )
+        };
+
+        let due = FreezeRecord::create(pool, &scheduled("owner/due", 12345, chrono::Duration::seconds(30)))
+            .await
+            .unwrap();
+        for record in [
+            // Not due before the next tick
+            scheduled("owner/later", 12345, chrono::Duration::hours(1)),
+            // Another installation's freeze
+            scheduled("other/due", 67890, chrono::Duration::seconds(30)),
+        ] {
+            FreezeRecord::create(pool, &record).await.unwrap();
+        }
+        let cancelled = FreezeRecord::create(pool, &scheduled("owner/cancelled", 12345, chrono::Duration::zero()))
+            .await
+            .unwrap();
+        FreezeRecord::cancel_scheduled(pool, 12345.into(), "owner/cancelled", &cancelled.id)
+            .await
+            .unwrap();
+
+        let elapsed = FreezeRecord::create(pool, &active("owner/elapsed", -chrono::Duration::hours(1)))
+            .await
+            .unwrap();
+        let ending = FreezeRecord::create(pool, &active("owner/ending", chrono::Duration::seconds(30)))
+            .await
+            .unwrap();
+        FreezeRecord::create(pool, &active("owner/running", chrono::Duration::hours(2)))
+            .await
+            .unwrap();
+
+        let at = now + chrono::Duration::from_std(SCHEDULER_INTERVAL).unwrap();
+        let preview = scheduler_preview(pool, 12345.into(), at).await.unwrap();
+        let ids = |records: &[FreezeRecord]| -> Vec<String> {
+            records.iter().map(|r| r.id.clone()).collect()
+        };
+        assert_eq!(ids(&preview.to_activate), vec![due.id.clone()]);
+        assert_eq!(ids(&preview.to_expire), vec![elapsed.id.clone(), ending.id.clone()]);
+
+        // Previewing changes nothing
+        let again = scheduler_preview(pool, 12345.into(), at).await.unwrap();
+        assert_eq!(ids(&again.to_activate), ids(&preview.to_activate));
+        assert_eq!(ids(&again.to_expire), ids(&preview.to_expire));
+    }
+
+    #[test]
+    fn test_needs_reminder_interval() {
+        let now = Utc::now();
+        let day = chrono::Duration::hours(24);
+        let old = record_started(now - chrono::Duration::hours(72), None);
+
+        // Reminded recently: wait for the interval to elapse
+        let recently = Some(now - chrono::Duration::hours(3));
+        assert!(!needs_reminder(&old, recently, now, day, day));
+
+        // Reminded more than an interval ago: remind again
+        let long_ago = Some(now - chrono::Duration::hours(25));
+        assert!(needs_reminder(&old, long_ago, now, day, day));
+    }
+}